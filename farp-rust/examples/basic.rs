@@ -51,6 +51,7 @@ async fn main() -> Result<()> {
 
     let schema_descriptor = SchemaDescriptor {
         schema_type: SchemaType::OpenAPI,
+        schema_id: None,
         spec_version: "3.1.0".to_string(),
         location: SchemaLocation {
             location_type: LocationType::Registry,
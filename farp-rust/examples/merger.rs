@@ -1,7 +1,9 @@
 //! Example demonstrating OpenAPI schema merging with FARP
 
 use farp::manifest::new_manifest;
-use farp::merger::{Merger, MergerConfig, Server, ServiceSchema};
+use farp::merger::{
+    Merger, MergerConfig, MethodConflictStrategy, OperationIdStrategy, Server, ServiceSchema,
+};
 use farp::types::{ConflictStrategy, LocationType, SchemaDescriptor, SchemaType};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -14,6 +16,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         merged_title: "Unified E-Commerce API".to_string(),
         merged_description: "Combined API from User, Product, and Order services".to_string(),
         merged_version: "1.0.0".to_string(),
+        merged_contact: None,
+        merged_license: None,
+        merged_terms_of_service: None,
         include_service_tags: true,
         sort_output: true,
         servers: vec![Server {
@@ -21,6 +26,23 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             description: Some("Production API Gateway".to_string()),
             variables: None,
         }],
+        max_ref_depth: 64,
+        max_schema_depth: 128,
+        prune_unreferenced: false,
+        generate_missing_operation_ids: false,
+        custom_resolver: None,
+        transforms: Vec::new(),
+        operation_id_strategy: OperationIdStrategy::Prefix,
+        gateway_lint: false,
+        strict_path_params: false,
+        case_insensitive_paths: false,
+        normalize_trailing_slash: false,
+        component_separator: "_".to_string(),
+        namespace_separator: ".".to_string(),
+        method_conflict_strategy: MethodConflictStrategy::Overwrite,
+        sanitize_prefixes: true,
+        strip_examples: false,
+        strip_response_schemas: false,
     };
 
     let merger = Merger::new(config);
@@ -33,6 +55,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Add OpenAPI schema descriptor
     user_manifest.add_schema(SchemaDescriptor {
         schema_type: SchemaType::OpenAPI,
+        schema_id: None,
         spec_version: "3.1.0".to_string(),
         location: farp::types::SchemaLocation {
             location_type: LocationType::Inline,
@@ -110,6 +133,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Add OpenAPI schema descriptor
     product_manifest.add_schema(SchemaDescriptor {
         schema_type: SchemaType::OpenAPI,
+        schema_id: None,
         spec_version: "3.1.0".to_string(),
         location: farp::types::SchemaLocation {
             location_type: LocationType::Inline,
@@ -179,6 +203,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Add OpenAPI schema descriptor
     order_manifest.add_schema(SchemaDescriptor {
         schema_type: SchemaType::OpenAPI,
+        schema_id: None,
         spec_version: "3.1.0".to_string(),
         location: farp::types::SchemaLocation {
             location_type: LocationType::Inline,
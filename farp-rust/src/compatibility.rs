@@ -0,0 +1,358 @@
+//! Breaking-change detection between schema versions.
+
+use crate::errors::Result;
+use crate::types::{BreakingChange, ChangeSeverity, ChangeType};
+use serde_json::Value;
+
+/// HTTP methods that carry operations in an OpenAPI path item
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Where a schema sits relative to the client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    /// Sent by the client (parameters, request bodies)
+    Request,
+    /// Returned to the client (responses)
+    Response,
+}
+
+/// Compares two OpenAPI documents and reports changes that can break clients
+///
+/// Detects removed endpoints and methods, and `enum`/`const` changes in
+/// parameter, request-body and response schemas. Enum changes are classified
+/// by position: removing a request value is forward-compatible (`Low`),
+/// adding one is not reported, and removing a response value breaks
+/// consumers (`High`).
+///
+/// Local `$ref`s are resolved before comparing; a document whose references
+/// can't be resolved (missing targets, cycles deeper than the resolver
+/// allows) fails instead of being compared unresolved.
+pub fn detect_openapi_breaking_changes(old: &Value, new: &Value) -> Result<Vec<BreakingChange>> {
+    let old = resolve(old)?;
+    let new = resolve(new)?;
+    let mut changes = Vec::new();
+
+    let empty = serde_json::Map::new();
+    let old_paths = old
+        .get("paths")
+        .and_then(|p| p.as_object())
+        .unwrap_or(&empty);
+    let new_paths = new
+        .get("paths")
+        .and_then(|p| p.as_object())
+        .unwrap_or(&empty);
+
+    let mut paths: Vec<&String> = old_paths.keys().collect();
+    paths.sort();
+
+    for path in paths {
+        let pointer = format!("#/paths/{}", escape(path));
+        let Some(new_item) = new_paths.get(path) else {
+            changes.push(breaking(
+                ChangeType::EndpointRemoved,
+                pointer,
+                format!("endpoint {path} was removed"),
+                ChangeSeverity::Critical,
+            ));
+            continue;
+        };
+        let old_item = &old_paths[path];
+
+        for method in METHODS {
+            let Some(old_op) = old_item.get(method) else {
+                continue;
+            };
+            let pointer = format!("{pointer}/{method}");
+            let Some(new_op) = new_item.get(method) else {
+                changes.push(breaking(
+                    ChangeType::MethodRemoved,
+                    pointer,
+                    format!("{} {path} was removed", method.to_uppercase()),
+                    ChangeSeverity::Critical,
+                ));
+                continue;
+            };
+
+            compare_operation(old_op, new_op, &pointer, &mut changes);
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Resolves local references so schemas can be compared structurally
+fn resolve(doc: &Value) -> Result<Value> {
+    crate::merger::openapi::dereference(doc, 64)
+}
+
+fn compare_operation(old: &Value, new: &Value, pointer: &str, changes: &mut Vec<BreakingChange>) {
+    // Parameters are matched by name and location
+    let params = |op: &Value| -> Vec<Value> {
+        op.get("parameters")
+            .and_then(|p| p.as_array())
+            .cloned()
+            .unwrap_or_default()
+    };
+    let new_params = params(new);
+    for (i, old_param) in params(old).iter().enumerate() {
+        let matching = new_params
+            .iter()
+            .find(|p| p.get("name") == old_param.get("name") && p.get("in") == old_param.get("in"));
+        if let (Some(old_schema), Some(new_schema)) = (
+            old_param.get("schema"),
+            matching.and_then(|p| p.get("schema")),
+        ) {
+            compare_schema(
+                old_schema,
+                new_schema,
+                &format!("{pointer}/parameters/{i}/schema"),
+                Position::Request,
+                changes,
+            );
+        }
+    }
+
+    compare_content(
+        old.get("requestBody"),
+        new.get("requestBody"),
+        &format!("{pointer}/requestBody"),
+        Position::Request,
+        changes,
+    );
+
+    if let (Some(old_responses), Some(new_responses)) = (
+        old.get("responses").and_then(|r| r.as_object()),
+        new.get("responses").and_then(|r| r.as_object()),
+    ) {
+        let mut statuses: Vec<&String> = old_responses.keys().collect();
+        statuses.sort();
+        for status in statuses {
+            compare_content(
+                old_responses.get(status),
+                new_responses.get(status),
+                &format!("{pointer}/responses/{}", escape(status)),
+                Position::Response,
+                changes,
+            );
+        }
+    }
+}
+
+/// Compares the media-type schemas of a request body or response
+fn compare_content(
+    old: Option<&Value>,
+    new: Option<&Value>,
+    pointer: &str,
+    position: Position,
+    changes: &mut Vec<BreakingChange>,
+) {
+    fn content(v: Option<&Value>) -> Option<&serde_json::Map<String, Value>> {
+        v?.get("content")?.as_object()
+    }
+    let (Some(old_content), Some(new_content)) = (content(old), content(new)) else {
+        return;
+    };
+
+    let mut media_types: Vec<&String> = old_content.keys().collect();
+    media_types.sort();
+    for media_type in media_types {
+        let schema = |c: &serde_json::Map<String, Value>| c.get(media_type)?.get("schema").cloned();
+        if let (Some(old_schema), Some(new_schema)) = (schema(old_content), schema(new_content)) {
+            compare_schema(
+                &old_schema,
+                &new_schema,
+                &format!("{pointer}/content/{}/schema", escape(media_type)),
+                position,
+                changes,
+            );
+        }
+    }
+}
+
+/// Recursively compares `enum`/`const` constraints of two JSON schemas
+fn compare_schema(
+    old: &Value,
+    new: &Value,
+    pointer: &str,
+    position: Position,
+    changes: &mut Vec<BreakingChange>,
+) {
+    if let (Some(old_values), Some(new_values)) = (allowed_values(old), allowed_values(new)) {
+        let removed: Vec<&Value> = old_values
+            .iter()
+            .filter(|v| !new_values.contains(v))
+            .collect();
+
+        // Added values only widen what's accepted or returned
+        if !removed.is_empty() {
+            let (side, severity) = match position {
+                Position::Request => ("request", ChangeSeverity::Low),
+                Position::Response => ("response", ChangeSeverity::High),
+            };
+            changes.push(breaking(
+                ChangeType::EnumValueRemoved,
+                pointer.to_string(),
+                format!("{side} enum values removed: {}", list(&removed)),
+                severity,
+            ));
+        }
+    }
+
+    if let (Some(old_props), Some(new_props)) = (
+        old.get("properties").and_then(|p| p.as_object()),
+        new.get("properties").and_then(|p| p.as_object()),
+    ) {
+        let mut names: Vec<&String> = old_props.keys().collect();
+        names.sort();
+        for name in names {
+            if let Some(new_prop) = new_props.get(name) {
+                compare_schema(
+                    &old_props[name],
+                    new_prop,
+                    &format!("{pointer}/properties/{}", escape(name)),
+                    position,
+                    changes,
+                );
+            }
+        }
+    }
+
+    for key in ["items", "additionalProperties"] {
+        if let (Some(old_child), Some(new_child)) = (old.get(key), new.get(key)) {
+            if old_child.is_object() && new_child.is_object() {
+                compare_schema(
+                    old_child,
+                    new_child,
+                    &format!("{pointer}/{key}"),
+                    position,
+                    changes,
+                );
+            }
+        }
+    }
+}
+
+/// The values a schema allows, if restricted by `enum` or `const`
+fn allowed_values(schema: &Value) -> Option<Vec<Value>> {
+    if let Some(values) = schema.get("enum").and_then(|e| e.as_array()) {
+        return Some(values.clone());
+    }
+    schema.get("const").map(|c| vec![c.clone()])
+}
+
+fn list(values: &[&Value]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Escapes a JSON pointer token (RFC 6901)
+fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+fn breaking(
+    change_type: ChangeType,
+    path: String,
+    description: String,
+    severity: ChangeSeverity,
+) -> BreakingChange {
+    BreakingChange {
+        change_type,
+        path,
+        description,
+        severity,
+        migration: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec(request_enum: Value, response_enum: Value) -> Value {
+        json!({
+            "openapi": "3.1.0",
+            "paths": {
+                "/orders": {
+                    "post": {
+                        "requestBody": {"content": {"application/json": {"schema": {
+                            "type": "object",
+                            "properties": {"priority": {"type": "string", "enum": request_enum}}
+                        }}}},
+                        "responses": {"200": {"content": {"application/json": {"schema": {
+                            "$ref": "#/components/schemas/Order"
+                        }}}}}
+                    }
+                }
+            },
+            "components": {"schemas": {"Order": {
+                "type": "object",
+                "properties": {"status": {"type": "string", "enum": response_enum}}
+            }}}
+        })
+    }
+
+    #[test]
+    fn test_request_enum_value_removed() {
+        let old = spec(json!(["low", "high"]), json!(["open"]));
+        let new = spec(json!(["low"]), json!(["open"]));
+
+        let changes = detect_openapi_breaking_changes(&old, &new).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].change_type, ChangeType::EnumValueRemoved);
+        assert_eq!(changes[0].severity, ChangeSeverity::Low);
+        assert_eq!(
+            changes[0].path,
+            "#/paths/~1orders/post/requestBody/content/application~1json/schema/properties/priority"
+        );
+
+        // Adding a request value is non-breaking
+        assert!(detect_openapi_breaking_changes(&new, &old)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_response_enum_value_removed() {
+        let old = spec(json!(["low"]), json!(["open", "closed"]));
+        let new = spec(json!(["low"]), json!(["open"]));
+
+        let changes = detect_openapi_breaking_changes(&old, &new).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].change_type, ChangeType::EnumValueRemoved);
+        assert_eq!(changes[0].severity, ChangeSeverity::High);
+        assert!(changes[0].path.contains("/responses/200/"));
+        assert!(changes[0].description.contains("\"closed\""));
+    }
+
+    #[test]
+    fn test_removed_endpoints_and_methods() {
+        let old = json!({"paths": {"/a": {"get": {}, "post": {}}, "/b": {"get": {}}}});
+        let new = json!({"paths": {"/a": {"get": {}}}});
+
+        let changes = detect_openapi_breaking_changes(&old, &new).unwrap();
+        let types: Vec<ChangeType> = changes.iter().map(|c| c.change_type).collect();
+        assert_eq!(
+            types,
+            vec![ChangeType::MethodRemoved, ChangeType::EndpointRemoved]
+        );
+    }
+
+    #[test]
+    fn test_unresolvable_refs_fail() {
+        let old = spec(json!(["low"]), json!(["open"]));
+        let mut new = old.clone();
+        new["components"]["schemas"]
+            .as_object_mut()
+            .unwrap()
+            .remove("Order");
+
+        assert!(detect_openapi_breaking_changes(&old, &new).is_err());
+    }
+}
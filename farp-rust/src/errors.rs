@@ -53,6 +53,10 @@ pub enum Error {
     #[error("invalid schema location: {0}")]
     InvalidLocation(String),
 
+    /// A concurrent writer changed the record between read and write
+    #[error("concurrent modification: {0}")]
+    ConcurrentModification(String),
+
     /// Schema provider not found
     #[error("schema provider not found for type: {0}")]
     ProviderNotFound(SchemaType),
@@ -87,6 +91,14 @@ pub enum Error {
         source: Box<Error>,
     },
 
+    /// Manifest signature is malformed or does not verify
+    #[error("invalid signature: {0}")]
+    InvalidSignature(String),
+
+    /// An event cursor is older than the retained events or was never issued
+    #[error("event cursor {0} is expired or unknown")]
+    CursorExpired(u64),
+
     /// Validation error
     #[error("validation error: field={field} message={message}")]
     Validation { field: String, message: String },
@@ -107,6 +119,10 @@ pub enum Error {
     #[error("channel receive error")]
     ChannelReceive,
 
+    /// The backend does not support this operation
+    #[error("operation not supported: {operation}")]
+    Unsupported { operation: String },
+
     /// Custom error for extensibility
     #[error("custom error: {0}")]
     Custom(String),
@@ -186,6 +202,18 @@ impl Error {
     pub fn validation_failed(message: impl Into<String>) -> Self {
         Error::ValidationFailed(message.into())
     }
+
+    /// Creates a new invalid signature error
+    pub fn invalid_signature(message: impl Into<String>) -> Self {
+        Error::InvalidSignature(message.into())
+    }
+
+    /// Creates a new unsupported operation error
+    pub fn unsupported(operation: impl Into<String>) -> Self {
+        Error::Unsupported {
+            operation: operation.into(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -217,4 +245,13 @@ mod tests {
         assert!(err.to_string().contains("abc123"));
         assert!(err.to_string().contains("def456"));
     }
+
+    #[test]
+    fn test_unsupported() {
+        let err = Error::unsupported("watch_schemas");
+        assert!(
+            matches!(err, Error::Unsupported { ref operation } if operation == "watch_schemas")
+        );
+        assert_eq!(err.to_string(), "operation not supported: watch_schemas");
+    }
 }
@@ -1,22 +1,43 @@
 //! Gateway client for watching service changes and converting schemas to routes.
 
+use super::diff::{route_diff, RouteDiff};
 use crate::errors::{Error, Result};
+use crate::merger::asyncapi::{
+    channel_protocols, gateway_routable_channels, parse_asyncapi_channels, Protocol,
+};
 use crate::registry::{EventType, ManifestEvent, SchemaRegistry};
-use crate::types::{LocationType, SchemaDescriptor, SchemaManifest, SchemaType};
-use std::collections::HashMap;
+use crate::types::{
+    Address, AuthType, Capability, DataSensitivity, HealthCheckConfig, InstanceStatus,
+    LocationType, MountStrategy, QuotaConfig, ResiliencyConfig, RetryConfig, RoutingConfig,
+    SchemaDescriptor, SchemaManifest, SchemaType, StickyConfig,
+};
+use crate::util::{glob_match, parse_duration};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::{RwLock, Semaphore};
+use tokio::task::JoinSet;
 
 /// Gateway client for API gateway integration
 ///
 /// Watches for service schema changes and provides conversion utilities
-/// to gateway-specific route configurations.
+/// to gateway-specific route configurations. Clones share the registry and
+/// caches.
+#[derive(Clone)]
 pub struct Client {
     registry: Arc<dyn SchemaRegistry>,
     manifest_cache: Arc<RwLock<HashMap<String, SchemaManifest>>>,
     schema_cache: Arc<RwLock<HashMap<String, serde_json::Value>>>,
+    retry: RetryConfig,
+    route_policy: RoutePolicy,
+    idempotency: IdempotencyConfig,
+    fetch_concurrency: usize,
+    max_schema_size: i64,
 }
 
+/// Default number of schemas [`Client::fetch_schemas`] fetches at once
+pub const DEFAULT_FETCH_CONCURRENCY: usize = 8;
+
 impl Client {
     /// Creates a new gateway client
     pub fn new(registry: Arc<dyn SchemaRegistry>) -> Self {
@@ -24,104 +45,554 @@ impl Client {
             registry,
             manifest_cache: Arc::new(RwLock::new(HashMap::new())),
             schema_cache: Arc::new(RwLock::new(HashMap::new())),
+            retry: RetryConfig::default(),
+            route_policy: RoutePolicy::default(),
+            idempotency: IdempotencyConfig::default(),
+            fetch_concurrency: DEFAULT_FETCH_CONCURRENCY,
+            max_schema_size: crate::registry::RegistryConfig::default().max_schema_size,
         }
     }
 
+    /// Sets the retry policy for schema fetches
+    ///
+    /// A manifest's `webhook.retry` takes precedence for its own schemas.
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Sets which instances are eligible for routing
+    pub fn with_route_policy(mut self, policy: RoutePolicy) -> Self {
+        self.route_policy = policy;
+        self
+    }
+
+    /// Sets the idempotency key header and TTL attached to retry-safe routes
+    pub fn with_idempotency(mut self, idempotency: IdempotencyConfig) -> Self {
+        self.idempotency = idempotency;
+        self
+    }
+
+    /// Sets how many distinct schemas are fetched concurrently (at least one)
+    pub fn with_fetch_concurrency(mut self, limit: usize) -> Self {
+        self.fetch_concurrency = limit.max(1);
+        self
+    }
+
+    /// Sets the largest schema size, in bytes, that
+    /// [`warm_cache`](Self::warm_cache) pre-fetches (`0` for no limit)
+    pub fn with_max_schema_size(mut self, bytes: i64) -> Self {
+        self.max_schema_size = bytes;
+        self
+    }
+
+    /// Pre-fetches every schema a service's instances reference
+    ///
+    /// Lists the service's manifests and fetches each schema whose hash isn't
+    /// cached yet, concurrently and with the manifest's retry policy, so the
+    /// first conversion after startup needs no fetches. Schemas whose
+    /// declared `size` exceeds the max schema size are skipped, as are
+    /// schemas that turn out larger than it once fetched (their serialized
+    /// JSON is measured before caching). Fetch failures are left for
+    /// conversion to retry. Returns how many schemas were newly cached.
+    pub async fn warm_cache(&self, service_name: &str) -> Result<usize> {
+        let manifests = self.registry.list_manifests(service_name).await?;
+        let cached: HashSet<String> = self.schema_cache.read().await.keys().cloned().collect();
+        let requests: Vec<_> = manifests
+            .iter()
+            .flat_map(|manifest| {
+                let retry = manifest_retry(manifest, &self.retry);
+                manifest.schemas.iter().map(move |desc| (desc, retry))
+            })
+            .filter(|(desc, _)| !cached.contains(&desc.hash))
+            .filter(|(desc, _)| self.max_schema_size <= 0 || desc.size <= self.max_schema_size)
+            .collect();
+
+        let results = self.fetch_batch(&requests, self.max_schema_size).await;
+        let warmed: HashSet<&str> = requests
+            .iter()
+            .zip(&results)
+            .filter(|(_, result)| result.is_ok())
+            .map(|((desc, _), _)| desc.hash.as_str())
+            .collect();
+        Ok(warmed.len())
+    }
+
     /// Watches for service registrations and schema updates
     ///
-    /// `on_change` is called whenever services are added, updated, or removed
+    /// `on_change` is called with the full route set whenever services are
+    /// added, updated, or removed. Routes are cached per instance and keyed
+    /// by the manifest checksum, so an event only re-converts the instance it
+    /// is about; `on_change` is skipped when the route set comes out unchanged.
+    /// Events are processed one at a time in the order the registry delivers
+    /// them, so route sets are emitted in that order too.
     pub async fn watch_services<F>(&self, service_name: &str, on_change: Arc<F>) -> Result<()>
     where
         F: Fn(Vec<ServiceRoute>) + Send + Sync + 'static,
     {
+        let mut route_cache = RouteCache::default();
+
         // Initial load
         let manifests = self.registry.list_manifests(service_name).await?;
-
-        // Convert initial manifests to routes
-        let routes = self.convert_to_routes(&manifests).await;
+        {
+            let mut cache = self.manifest_cache.write().await;
+            for manifest in &manifests {
+                cache.insert(manifest.instance_id.clone(), manifest.clone());
+            }
+        }
+        let routes = match self
+            .incremental_routes(&mut route_cache, &manifests, None)
+            .await
+        {
+            Some(routes) => routes,
+            None => route_cache.last.clone().unwrap_or_default(),
+        };
         on_change(routes);
 
-        // Create handler for watch events
-        let manifest_cache = self.manifest_cache.clone();
-        let registry = self.registry.clone();
-        let schema_cache = self.schema_cache.clone();
-        let service_name = service_name.to_string();
-        let on_change_ref = on_change.clone();
-
-        let handler = Box::new(move |event: &ManifestEvent| {
-            let manifest_cache = manifest_cache.clone();
-            let schema_cache = schema_cache.clone();
-            let registry = registry.clone();
-            let event = event.clone();
-            let on_change = on_change_ref.clone();
-
-            tokio::spawn(async move {
+        // Events are queued and handled by a single task, in arrival order
+        let (events, mut queue) = tokio::sync::mpsc::unbounded_channel::<ManifestEvent>();
+        let client = self.clone();
+        tokio::spawn(async move {
+            while let Some(event) = queue.recv().await {
                 // Update manifest cache
-                let mut cache = manifest_cache.write().await;
+                let mut cache = client.manifest_cache.write().await;
                 match event.event_type {
-                    EventType::Added | EventType::Updated => {
+                    EventType::Added | EventType::Updated | EventType::Reregistered => {
                         cache.insert(event.manifest.instance_id.clone(), event.manifest.clone());
                     }
                     EventType::Removed => {
                         cache.remove(&event.manifest.instance_id);
                     }
                 }
-
-                // Get all cached manifests
                 let manifests: Vec<SchemaManifest> = cache.values().cloned().collect();
                 drop(cache);
 
-                // Convert to routes
-                let client = Client {
-                    registry: registry.clone(),
-                    manifest_cache: manifest_cache.clone(),
-                    schema_cache: schema_cache.clone(),
-                };
+                let changed = client
+                    .incremental_routes(
+                        &mut route_cache,
+                        &manifests,
+                        Some(&event.manifest.instance_id),
+                    )
+                    .await;
+                if let Some(changed) = changed {
+                    on_change(changed);
+                }
+            }
+        });
 
-                let routes = client.convert_to_routes(&manifests).await;
-                on_change(routes);
-            });
+        let handler = Box::new(move |event: &ManifestEvent| {
+            // The receiver only goes away with the runtime
+            let _ = events.send(event.clone());
         });
 
         // Watch for changes
-        self.registry.watch_manifests(&service_name, handler).await
+        self.registry.watch_manifests(service_name, handler).await
+    }
+
+    /// Watches for service changes, reporting each update as a [`RouteDiff`]
+    ///
+    /// Like [`Client::watch_services`], but `on_diff` receives only what
+    /// changed since the previous route set. The initial load is reported
+    /// with every route as added.
+    pub async fn watch_route_diffs<F>(&self, service_name: &str, on_diff: Arc<F>) -> Result<()>
+    where
+        F: Fn(RouteDiff) + Send + Sync + 'static,
+    {
+        let previous = std::sync::Mutex::new(Vec::new());
+        let on_change = Arc::new(move |routes: Vec<ServiceRoute>| {
+            let mut previous = previous.lock().unwrap_or_else(|e| e.into_inner());
+            let diff = route_diff(&previous, &routes);
+            *previous = routes;
+            drop(previous);
+            if !diff.is_empty() {
+                on_diff(diff);
+            }
+        });
+        self.watch_services(service_name, on_change).await
+    }
+
+    /// Rebuilds the route set, re-converting only instances whose cached
+    /// routes can't be reused
+    ///
+    /// Cached routes are reused when the checksum matches and the instance
+    /// isn't `changed`. Returns `None` when the result equals the last set.
+    async fn incremental_routes(
+        &self,
+        cache: &mut RouteCache,
+        manifests: &[SchemaManifest],
+        changed: Option<&str>,
+    ) -> Option<Vec<ServiceRoute>> {
+        let mut manifests = manifests.to_vec();
+        manifests.sort_by(|a, b| a.instance_id.cmp(&b.instance_id));
+        cache
+            .instances
+            .retain(|id, _| manifests.iter().any(|m| &m.instance_id == id));
+
+        let (eligible, _, _) = self.route_policy.apply(&manifests);
+        let mut routes = Vec::new();
+        for (manifest, weight, prefix) in eligible {
+            let reusable = changed != Some(manifest.instance_id.as_str())
+                && cache
+                    .instances
+                    .get(&manifest.instance_id)
+                    .is_some_and(|cached| cached.checksum == manifest.checksum);
+            let instance_routes = if reusable {
+                cache.instances[&manifest.instance_id].routes.clone()
+            } else {
+                let (instance_routes, complete) =
+                    self.convert_instance(manifest, &mut Vec::new()).await;
+                // Instances with failed fetches are retried on the next event
+                if complete {
+                    cache.instances.insert(
+                        manifest.instance_id.clone(),
+                        CachedRoutes {
+                            checksum: manifest.checksum.clone(),
+                            routes: instance_routes.clone(),
+                        },
+                    );
+                } else {
+                    cache.instances.remove(&manifest.instance_id);
+                }
+                instance_routes
+            };
+            routes.extend(
+                instance_routes
+                    .into_iter()
+                    .map(|route| mount_route(route, weight, &prefix)),
+            );
+        }
+
+        if cache.last.as_ref() == Some(&routes) {
+            return None;
+        }
+        cache.last = Some(routes.clone());
+        Some(routes)
+    }
+
+    /// Converts every schema of one instance, skipping ones that fail to fetch
+    ///
+    /// The flag is `false` when any schema was skipped. Conversion warnings
+    /// are appended to `warnings`.
+    async fn convert_instance(
+        &self,
+        manifest: &SchemaManifest,
+        warnings: &mut Vec<String>,
+    ) -> (Vec<ServiceRoute>, bool) {
+        let mut routes = Vec::new();
+        let mut complete = true;
+        let requests: Vec<_> = manifest
+            .schemas
+            .iter()
+            .map(|desc| (desc, manifest_retry(manifest, &self.retry)))
+            .collect();
+        let schemas = self.fetch_batch(&requests, 0).await;
+        for (schema_desc, schema) in manifest.schemas.iter().zip(schemas) {
+            match schema {
+                Ok(schema) => routes.extend(self.convert_schema_to_routes(
+                    manifest,
+                    schema_desc,
+                    &schema,
+                    warnings,
+                )),
+                Err(_) => complete = false,
+            }
+        }
+        (routes, complete)
     }
 
     /// Converts service manifests to gateway routes
     ///
-    /// This is a reference implementation - actual gateways should customize this
+    /// This is a reference implementation - actual gateways should customize this.
+    /// Schemas that still fail to fetch after retrying are skipped; use
+    /// [`try_convert_to_routes`](Self::try_convert_to_routes) to get the error.
+    /// Instances rejected by the [`RoutePolicy`] are left out; use
+    /// [`convert_to_routes_with_warnings`](Self::convert_to_routes_with_warnings)
+    /// to see which services fell short of it.
     pub async fn convert_to_routes(&self, manifests: &[SchemaManifest]) -> Vec<ServiceRoute> {
+        self.convert_to_routes_with_warnings(manifests).await.routes
+    }
+
+    /// Converts service manifests to gateway routes, reporting policy warnings
+    ///
+    /// Behaves like [`convert_to_routes`](Self::convert_to_routes) and also
+    /// returns a warning for each service with fewer healthy instances than
+    /// the policy's `min_healthy_instances`, or none eligible for routing,
+    /// for each service whose instances declare conflicting mount strategies,
+    /// and for each AsyncAPI channel that is `non-routable` through a gateway.
+    pub async fn convert_to_routes_with_warnings(
+        &self,
+        manifests: &[SchemaManifest],
+    ) -> RouteConversion {
+        let (eligible, mut warnings, _) = self.route_policy.apply(manifests);
+        let schemas = self.fetch_eligible(&eligible).await;
+        let mut routes = Vec::new();
+        for ((manifest, weight, prefix), schemas) in eligible.iter().zip(schemas) {
+            for (schema_desc, schema) in manifest.schemas.iter().zip(schemas) {
+                let Ok(schema) = schema else { continue };
+                routes.extend(
+                    self.convert_schema_to_routes(manifest, schema_desc, &schema, &mut warnings)
+                        .into_iter()
+                        .map(|route| mount_route(route, *weight, prefix)),
+                );
+            }
+        }
+        RouteConversion { routes, warnings }
+    }
+
+    /// Converts service manifests to gateway routes, failing on the first
+    /// schema that can't be fetched after retrying
+    ///
+    /// Instances rejected by the [`RoutePolicy`] are skipped, not errors.
+    /// Conflicting mount strategies fail with `Error::Validation` under
+    /// [`MountConflictStrategy::Error`].
+    pub async fn try_convert_to_routes(
+        &self,
+        manifests: &[SchemaManifest],
+    ) -> Result<Vec<ServiceRoute>> {
+        let (eligible, _, conflicts) = self.route_policy.apply(manifests);
+        if self.route_policy.mount_conflicts == MountConflictStrategy::Error
+            && !conflicts.is_empty()
+        {
+            return Err(Error::validation("routing.strategy", conflicts.join("; ")));
+        }
+        let schemas = self.fetch_eligible(&eligible).await;
         let mut routes = Vec::new();
+        for ((manifest, weight, prefix), schemas) in eligible.iter().zip(schemas) {
+            for (schema_desc, schema) in manifest.schemas.iter().zip(schemas) {
+                let schema = schema.map_err(|e| {
+                    Error::manifest(
+                        manifest.service_name.clone(),
+                        manifest.instance_id.clone(),
+                        e,
+                    )
+                })?;
+                routes.extend(
+                    self.convert_schema_to_routes(manifest, schema_desc, &schema, &mut Vec::new())
+                        .into_iter()
+                        .map(|route| mount_route(route, *weight, prefix)),
+                );
+            }
+        }
+        Ok(routes)
+    }
 
-        for manifest in manifests {
-            for schema_desc in &manifest.schemas {
-                // Fetch schema
-                let schema = match self.fetch_schema(schema_desc).await {
-                    Ok(s) => s,
-                    Err(_) => continue,
-                };
+    /// Fetches a gRPC service's schema, live from the instance when possible
+    ///
+    /// Instances that enable reflection (`endpoints.grpc_reflection` or the
+    /// gRPC metadata's `reflection_enabled`) are asked through
+    /// [`fetch_descriptors`](super::grpc_reflection::fetch_descriptors) at
+    /// their address. When reflection is off or fails, the manifest's gRPC
+    /// schema is fetched and parsed instead.
+    #[cfg(feature = "providers-grpc")]
+    pub async fn fetch_grpc_spec(
+        &self,
+        manifest: &SchemaManifest,
+    ) -> Result<crate::merger::GRPCSpec> {
+        let reflection = manifest.endpoints.grpc_reflection
+            || manifest
+                .grpc_metadata()
+                .is_some_and(|grpc| grpc.reflection_enabled);
+        if let Some(instance) = manifest.instance.as_ref().filter(|_| reflection) {
+            if let Ok(spec) = super::grpc_reflection::fetch_descriptors(&instance.address).await {
+                return Ok(spec);
+            }
+        }
 
-                // Convert schema to routes based on type
-                match schema_desc.schema_type {
-                    SchemaType::OpenAPI => {
-                        routes.extend(self.convert_openapi_to_routes(manifest, &schema));
-                    }
-                    SchemaType::AsyncAPI => {
-                        routes.extend(self.convert_asyncapi_to_routes(manifest, &schema));
-                    }
-                    SchemaType::GraphQL => {
-                        routes.extend(self.convert_graphql_to_routes(manifest, &schema));
-                    }
-                    _ => {}
+        let descriptor = manifest
+            .get_schema(SchemaType::GRPC)
+            .ok_or(Error::SchemaNotFound)?;
+        let schema = self.fetch_manifest_schema(manifest, descriptor).await?;
+        crate::merger::parse_grpc_schema(&schema)
+    }
+
+    /// Fetches one of a manifest's schemas using its retry policy
+    async fn fetch_manifest_schema(
+        &self,
+        manifest: &SchemaManifest,
+        descriptor: &SchemaDescriptor,
+    ) -> Result<serde_json::Value> {
+        self.fetch_schema_with_retry(descriptor, manifest_retry(manifest, &self.retry))
+            .await
+    }
+
+    /// Fetches every schema of the eligible instances in one batch
+    ///
+    /// Results are grouped per instance, in `manifest.schemas` order.
+    async fn fetch_eligible(
+        &self,
+        eligible: &[Eligible<'_>],
+    ) -> Vec<Vec<Result<serde_json::Value>>> {
+        let requests: Vec<_> = eligible
+            .iter()
+            .flat_map(|(manifest, _, _)| {
+                let retry = manifest_retry(manifest, &self.retry);
+                manifest.schemas.iter().map(move |desc| (desc, retry))
+            })
+            .collect();
+        let mut results = self.fetch_batch(&requests, 0).await.into_iter();
+        eligible
+            .iter()
+            .map(|(manifest, _, _)| results.by_ref().take(manifest.schemas.len()).collect())
+            .collect()
+    }
+
+    /// Fetches several schemas, deduplicated by hash and run concurrently
+    ///
+    /// Descriptors sharing a `hash` are fetched once and every one of them
+    /// gets the result. Distinct schemas are fetched at most
+    /// [`with_fetch_concurrency`](Self::with_fetch_concurrency) at a time,
+    /// through the schema cache and with the client's retry policy. Results
+    /// are in `descriptors` order.
+    pub async fn fetch_schemas(
+        &self,
+        descriptors: &[SchemaDescriptor],
+    ) -> Vec<Result<serde_json::Value>> {
+        let requests: Vec<_> = descriptors.iter().map(|d| (d, &self.retry)).collect();
+        self.fetch_batch(&requests, 0).await
+    }
+
+    /// Fetches `(descriptor, retry)` pairs as described in [`fetch_schemas`](Self::fetch_schemas)
+    ///
+    /// A failed fetch is returned as-is to the first descriptor with that
+    /// hash; later duplicates get an `Error::SchemaFetchFailed` carrying its
+    /// message. Descriptors without a hash are never deduplicated. Fetched
+    /// schemas larger than `max_size` bytes (`0` for no limit) fail with
+    /// `Error::SchemaToLarge` and are not cached.
+    async fn fetch_batch(
+        &self,
+        requests: &[(&SchemaDescriptor, &RetryConfig)],
+        max_size: i64,
+    ) -> Vec<Result<serde_json::Value>> {
+        let mut unique = Vec::new();
+        let mut slots = Vec::with_capacity(requests.len());
+        let mut by_hash: HashMap<&str, usize> = HashMap::new();
+        for (descriptor, retry) in requests {
+            let slot = match by_hash.get(descriptor.hash.as_str()) {
+                Some(&slot) if !descriptor.hash.is_empty() => slot,
+                _ => {
+                    by_hash.insert(&descriptor.hash, unique.len());
+                    unique.push(((*descriptor).clone(), (*retry).clone()));
+                    unique.len() - 1
                 }
+            };
+            slots.push(slot);
+        }
+
+        let semaphore = Arc::new(Semaphore::new(self.fetch_concurrency.max(1)));
+        let mut tasks = JoinSet::new();
+        let count = unique.len();
+        for (slot, (descriptor, retry)) in unique.into_iter().enumerate() {
+            let client = self.clone();
+            let semaphore = semaphore.clone();
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                (
+                    slot,
+                    client.fetch_with_retry(&descriptor, &retry, max_size).await,
+                )
+            });
+        }
+
+        let mut fetched: Vec<Option<Result<serde_json::Value>>> =
+            (0..count).map(|_| None).collect();
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((slot, result)) => fetched[slot] = Some(result),
+                Err(e) => std::panic::resume_unwind(e.into_panic()),
             }
         }
+        let mut fetched: Vec<_> = fetched
+            .into_iter()
+            .map(|result| result.expect("every fetch task reports back"))
+            .collect();
+
+        slots
+            .into_iter()
+            .map(|slot| match &mut fetched[slot] {
+                Ok(schema) => Ok(schema.clone()),
+                Err(e) => {
+                    let copy = Error::schema_fetch_failed(e.to_string());
+                    Err(std::mem::replace(e, copy))
+                }
+            })
+            .collect()
+    }
 
+    /// Converts a fetched schema to routes based on its type
+    fn convert_schema_to_routes(
+        &self,
+        manifest: &SchemaManifest,
+        schema_desc: &SchemaDescriptor,
+        schema: &serde_json::Value,
+        warnings: &mut Vec<String>,
+    ) -> Vec<ServiceRoute> {
+        let mut routes = match schema_desc.schema_type {
+            SchemaType::OpenAPI => self.convert_openapi_to_routes(manifest, schema),
+            SchemaType::AsyncAPI => {
+                let (routes, skipped) = self.convert_asyncapi_to_routes(manifest, schema);
+                warnings.extend(skipped);
+                routes
+            }
+            SchemaType::GraphQL => self.convert_graphql_to_routes(manifest, schema),
+            SchemaType::GRPC => self.convert_grpc_to_routes(manifest, schema),
+            _ => Vec::new(),
+        };
+        apply_deprecations(&mut routes, schema_desc, schema);
+        if let Some(instance) = &manifest.instance {
+            for route in &mut routes {
+                route.region.clone_from(&instance.region);
+                route.zone.clone_from(&instance.zone);
+            }
+        }
         routes
     }
 
-    /// Fetches a schema based on its descriptor
-    async fn fetch_schema(&self, descriptor: &SchemaDescriptor) -> Result<serde_json::Value> {
+    /// Fetches a schema, retrying transient failures with exponential backoff
+    ///
+    /// Registry and HTTP fetches are attempted up to `retry.max_attempts` times;
+    /// the last error is returned if every attempt fails. Malformed delays are
+    /// rejected with `Error::Validation` before the first attempt.
+    pub async fn fetch_schema_with_retry(
+        &self,
+        descriptor: &SchemaDescriptor,
+        retry: &RetryConfig,
+    ) -> Result<serde_json::Value> {
+        self.fetch_with_retry(descriptor, retry, 0).await
+    }
+
+    /// [`fetch_schema_with_retry`](Self::fetch_schema_with_retry) with a
+    /// size limit on the fetched schema (`0` for none)
+    async fn fetch_with_retry(
+        &self,
+        descriptor: &SchemaDescriptor,
+        retry: &RetryConfig,
+        max_size: i64,
+    ) -> Result<serde_json::Value> {
+        let max_delay = retry.max_delay_duration()?;
+        let mut delay = retry.initial_delay_duration()?.min(max_delay);
+        let mut attempt = 1;
+
+        loop {
+            match self.fetch_schema(descriptor, max_size).await {
+                Err(e) if attempt < retry.max_attempts && is_transient(&e) => {
+                    tokio::time::sleep(delay).await;
+                    delay = delay.mul_f64(retry.multiplier.max(1.0)).min(max_delay);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Fetches a schema based on its descriptor (single attempt)
+    ///
+    /// Fresh schemas whose serialized JSON exceeds `max_size` bytes (`0` for
+    /// no limit) are rejected before they reach the cache.
+    async fn fetch_schema(
+        &self,
+        descriptor: &SchemaDescriptor,
+        max_size: i64,
+    ) -> Result<serde_json::Value> {
         // Check cache first
         {
             let cache = self.schema_cache.read().await;
@@ -150,6 +621,13 @@ impl Client {
             }
         };
 
+        if max_size > 0 {
+            let size = serde_json::to_vec(&schema)?.len();
+            if size as i64 > max_size {
+                return Err(Error::schema_too_large(size, max_size as usize));
+            }
+        }
+
         // Cache the schema
         {
             let mut cache = self.schema_cache.write().await;
@@ -159,6 +637,21 @@ impl Client {
         Ok(schema)
     }
 
+    /// Fetches only the requested subtrees of a schema
+    ///
+    /// Each entry in `fields` is an RFC 6901 JSON pointer (e.g. `/info`, `/paths`).
+    /// The matching subtrees are assembled into a minimal document that keeps
+    /// their original positions; pointers that don't resolve are left out.
+    /// Sources without partial-read support are fetched in full and then projected.
+    pub async fn fetch_schema_projection(
+        &self,
+        descriptor: &SchemaDescriptor,
+        fields: &[JsonPointer<'_>],
+    ) -> Result<serde_json::Value> {
+        let schema = self.fetch_schema(descriptor, 0).await?;
+        project_schema(&schema, fields)
+    }
+
     /// Converts an OpenAPI schema to gateway routes
     fn convert_openapi_to_routes(
         &self,
@@ -168,30 +661,59 @@ impl Client {
         let mut routes = Vec::new();
 
         if let Some(paths) = schema.get("paths").and_then(|p| p.as_object()) {
-            let base_url = format!("http://{}:8080", manifest.service_name);
+            let base_url = backend_base_url(manifest);
 
             for (path, path_item) in paths {
                 if let Some(path_obj) = path_item.as_object() {
-                    let methods: Vec<String> = path_obj
-                        .keys()
-                        .filter(|k| {
+                    let operations: Vec<(&String, &serde_json::Value)> = path_obj
+                        .iter()
+                        .filter(|(k, _)| {
                             matches!(
                                 k.as_str(),
                                 "get" | "post" | "put" | "delete" | "patch" | "options" | "head"
                             )
                         })
-                        .map(|k| k.to_uppercase())
                         .collect();
+                    let methods: Vec<String> =
+                        operations.iter().map(|(k, _)| k.to_uppercase()).collect();
+                    let operations: Vec<&serde_json::Value> =
+                        operations.into_iter().map(|(_, op)| op).collect();
 
                     if !methods.is_empty() {
+                        let no_retry_methods: Vec<String> = methods
+                            .iter()
+                            .zip(&operations)
+                            .filter(|(method, op)| !is_idempotent(manifest, path, method, op))
+                            .map(|(method, _)| method.clone())
+                            .collect();
+                        let idempotency = (no_retry_methods.len() < methods.len())
+                            .then(|| self.idempotency.clone());
                         routes.push(ServiceRoute {
                             path: path.clone(),
                             methods,
                             target_url: format!("{base_url}{path}"),
                             health_url: format!("{}{}", base_url, manifest.endpoints.health),
+                            health_check: manifest
+                                .endpoints
+                                .health_check
+                                .clone()
+                                .unwrap_or_default(),
                             service_name: manifest.service_name.clone(),
                             service_version: manifest.service_version.clone(),
-                            middleware: Vec::new(),
+                            middleware: auth_middleware(manifest, path, &operations, schema),
+                            sticky: sticky_config(manifest, false),
+                            timeout: route_timeout(manifest, path_item, &operations),
+                            weight: None,
+                            deprecated: false,
+                            sunset: None,
+                            region: None,
+                            zone: None,
+                            idempotency,
+                            no_retry_methods,
+                            quota: manifest.quota.clone(),
+                            resiliency: route_resiliency(manifest),
+                            strip_segments: 0,
+                            sensitivity: route_sensitivity(manifest, &operations),
                             metadata: [("schema_type".to_string(), "openapi".into())]
                                 .iter()
                                 .cloned()
@@ -206,65 +728,225 @@ impl Client {
     }
 
     /// Converts an AsyncAPI schema to gateway routes (WebSocket, SSE)
+    ///
+    /// Only channels listed by [`gateway_routable_channels`] get a route.
+    /// Channels on protocols a gateway can't front (Kafka, AMQP, MQTT, ...)
+    /// are skipped, each with a `non-routable` warning.
     fn convert_asyncapi_to_routes(
         &self,
         manifest: &SchemaManifest,
         schema: &serde_json::Value,
+    ) -> (Vec<ServiceRoute>, Vec<String>) {
+        let spec = parse_asyncapi_channels(schema);
+        let routable = gateway_routable_channels(&spec);
+        let base_url = backend_base_url(manifest);
+
+        let mut routes = Vec::new();
+        for (channel_path, protocol) in &routable {
+            let declared = !channel_protocols(&spec, &spec.channels[channel_path]).is_empty();
+            let capability = channel_capability(manifest, *protocol, declared);
+            let websocket = capability == Capability::WebSocket;
+
+            routes.push(ServiceRoute {
+                path: channel_path.clone(),
+                methods: vec![if websocket { "WEBSOCKET" } else { "SSE" }.to_string()],
+                target_url: format!("{base_url}{channel_path}"),
+                health_url: format!("{}{}", base_url, manifest.endpoints.health),
+                health_check: manifest.endpoints.health_check.clone().unwrap_or_default(),
+                service_name: manifest.service_name.clone(),
+                service_version: manifest.service_version.clone(),
+                middleware: auth_middleware(manifest, channel_path, &[], schema),
+                sticky: sticky_config(manifest, websocket),
+                timeout: route_timeout(manifest, &serde_json::Value::Null, &[]),
+                weight: None,
+                deprecated: false,
+                sunset: None,
+                region: None,
+                zone: None,
+                idempotency: None,
+                no_retry_methods: Vec::new(),
+                quota: manifest.quota.clone(),
+                resiliency: route_resiliency(manifest),
+                strip_segments: 0,
+                sensitivity: route_sensitivity(manifest, &[]),
+                metadata: [
+                    ("schema_type".to_string(), "asyncapi".into()),
+                    ("protocol".to_string(), capability.as_str().into()),
+                ]
+                .iter()
+                .cloned()
+                .collect(),
+            });
+        }
+
+        let mut warnings: Vec<String> = spec
+            .channels
+            .iter()
+            .filter(|(name, _)| !routable.iter().any(|(routed, _)| routed == *name))
+            .map(|(name, channel)| {
+                format!(
+                    "{}: channel {name} is non-routable ({})",
+                    manifest.service_name,
+                    channel_protocols(&spec, channel).join(", ")
+                )
+            })
+            .collect();
+        warnings.sort();
+        (routes, warnings)
+    }
+
+    /// Converts a gRPC schema's HTTP transcoding annotations to gateway routes
+    ///
+    /// Every `google.api.http` binding (see [`GRPCMethod::http_rules`]) becomes
+    /// a route for its HTTP method and path, targeting the gRPC method's
+    /// `/<package>.<Service>/<Method>` path. Field templates such as
+    /// `{name=shelves/*}` are expanded into their literal and wildcard
+    /// segments (see [`transcode_path`]). The `grpc_transcode`
+    /// metadata key carries the `service`, `method` and `body` field the
+    /// gateway needs to transcode the request. Methods without annotations
+    /// get no route.
+    ///
+    /// [`GRPCMethod::http_rules`]: crate::merger::GRPCMethod::http_rules
+    fn convert_grpc_to_routes(
+        &self,
+        manifest: &SchemaManifest,
+        schema: &serde_json::Value,
     ) -> Vec<ServiceRoute> {
+        let Ok(spec) = crate::merger::parse_grpc_schema(schema) else {
+            return Vec::new();
+        };
+        let base_url = backend_base_url(manifest);
+
+        let mut services: Vec<_> = spec.services.values().collect();
+        services.sort_by(|a, b| a.name.cmp(&b.name));
+
         let mut routes = Vec::new();
+        for service in services {
+            let grpc_service = format!("{}.{}", spec.package, service.name);
+            let mut methods: Vec<_> = service.methods.values().collect();
+            methods.sort_by(|a, b| a.name.cmp(&b.name));
 
-        if let Some(channels) = schema.get("channels").and_then(|c| c.as_object()) {
-            let base_url = format!("http://{}:8080", manifest.service_name);
-
-            for channel_path in channels.keys() {
-                routes.push(ServiceRoute {
-                    path: channel_path.clone(),
-                    methods: vec!["WEBSOCKET".to_string()],
-                    target_url: format!("{base_url}{channel_path}"),
-                    health_url: format!("{}{}", base_url, manifest.endpoints.health),
-                    service_name: manifest.service_name.clone(),
-                    service_version: manifest.service_version.clone(),
-                    middleware: Vec::new(),
-                    metadata: [
-                        ("schema_type".to_string(), "asyncapi".into()),
-                        ("protocol".to_string(), "websocket".into()),
-                    ]
-                    .iter()
-                    .cloned()
-                    .collect(),
-                });
+            for method in methods {
+                for rule in method.http_rules() {
+                    let path = transcode_path(&rule.path);
+                    routes.push(ServiceRoute {
+                        methods: vec![rule.method.clone()],
+                        target_url: format!("{base_url}/{grpc_service}/{}", method.name),
+                        health_url: format!("{}{}", base_url, manifest.endpoints.health),
+                        health_check: manifest.endpoints.health_check.clone().unwrap_or_default(),
+                        service_name: manifest.service_name.clone(),
+                        service_version: manifest.service_version.clone(),
+                        middleware: auth_middleware(manifest, &path, &[], schema),
+                        sticky: sticky_config(manifest, false),
+                        timeout: route_timeout(manifest, &serde_json::Value::Null, &[]),
+                        weight: None,
+                        deprecated: false,
+                        sunset: None,
+                        region: None,
+                        zone: None,
+                        idempotency: None,
+                        no_retry_methods: Vec::new(),
+                        quota: manifest.quota.clone(),
+                        resiliency: route_resiliency(manifest),
+                        strip_segments: 0,
+                        sensitivity: route_sensitivity(manifest, &[]),
+                        metadata: [
+                            ("schema_type".to_string(), "grpc".into()),
+                            (
+                                "grpc_transcode".to_string(),
+                                serde_json::json!({
+                                    "service": grpc_service,
+                                    "method": method.name,
+                                    "body": rule.body,
+                                }),
+                            ),
+                        ]
+                        .into_iter()
+                        .collect(),
+                        path,
+                    });
+                }
             }
         }
 
         routes
     }
 
-    /// Converts a GraphQL schema to a gateway route
+    /// Converts a GraphQL schema to gateway routes
+    ///
+    /// Federated subgraphs (descriptor carries `GraphQLFederation` metadata) get a
+    /// subgraph route plus the `_service`/`_entities` SDL route used by Apollo
+    /// Federation routers; plain GraphQL services get a single route.
     fn convert_graphql_to_routes(
         &self,
         manifest: &SchemaManifest,
-        _schema: &serde_json::Value,
+        schema: &serde_json::Value,
     ) -> Vec<ServiceRoute> {
-        let base_url = format!("http://{}:8080", manifest.service_name);
+        let base_url = backend_base_url(manifest);
         let graphql_path = manifest
             .endpoints
             .graphql
             .clone()
             .unwrap_or_else(|| "/graphql".to_string());
 
-        vec![ServiceRoute {
+        let mut route = ServiceRoute {
             path: graphql_path.clone(),
             methods: vec!["POST".to_string(), "GET".to_string()],
             target_url: format!("{base_url}{graphql_path}"),
             health_url: format!("{}{}", base_url, manifest.endpoints.health),
+            health_check: manifest.endpoints.health_check.clone().unwrap_or_default(),
             service_name: manifest.service_name.clone(),
             service_version: manifest.service_version.clone(),
-            middleware: Vec::new(),
+            middleware: auth_middleware(manifest, &graphql_path, &[], schema),
+            sticky: sticky_config(manifest, false),
+            timeout: route_timeout(manifest, &serde_json::Value::Null, &[]),
+            weight: None,
+            deprecated: false,
+            sunset: None,
+            region: None,
+            zone: None,
+            idempotency: None,
+            no_retry_methods: Vec::new(),
+            quota: manifest.quota.clone(),
+            resiliency: route_resiliency(manifest),
+            strip_segments: 0,
+            sensitivity: route_sensitivity(manifest, &[]),
             metadata: [("schema_type".to_string(), "graphql".into())]
                 .iter()
                 .cloned()
                 .collect(),
-        }]
+        };
+
+        let federation = manifest
+            .graphql_metadata()
+            .and_then(|g| g.federation.as_ref());
+
+        let Some(federation) = federation else {
+            return vec![route];
+        };
+
+        route.metadata.insert(
+            "federation_version".to_string(),
+            federation.version.clone().into(),
+        );
+        route.metadata.insert(
+            "subgraph_name".to_string(),
+            federation.subgraph_name.clone().into(),
+        );
+
+        // Routers introspect subgraphs via `_service { sdl }` and resolve entities
+        // via `_entities`, both served by the subgraph's own GraphQL endpoint
+        let service_path = format!("{}/_service", graphql_path.trim_end_matches('/'));
+        let mut sdl_route = route.clone();
+        sdl_route.middleware = auth_middleware(manifest, &service_path, &[], schema);
+        sdl_route.path = service_path;
+        sdl_route.methods = vec!["POST".to_string()];
+        sdl_route.metadata.insert(
+            "federation_operations".to_string(),
+            serde_json::json!(["_service", "_entities"]),
+        );
+
+        vec![route, sdl_route]
     }
 
     /// Clears the schema cache
@@ -280,71 +962,2381 @@ impl Client {
     }
 }
 
-/// Service route configuration for the gateway
-#[derive(Debug, Clone)]
-pub struct ServiceRoute {
-    /// Path pattern for the route
-    pub path: String,
-    /// HTTP methods for this route
-    pub methods: Vec<String>,
-    /// Target backend URL
-    pub target_url: String,
-    /// Health check URL
-    pub health_url: String,
-    /// Backend service name
-    pub service_name: String,
-    /// Backend service version
-    pub service_version: String,
-    /// Middleware names to apply
-    pub middleware: Vec<String>,
-    /// Additional route metadata
-    pub metadata: HashMap<String, serde_json::Value>,
+/// Base URL of an instance's backend
+///
+/// Built from `instance.address` (`host:port`, optionally prefixed with
+/// `http://` or `https://`). Without an explicit scheme, `https` is used when
+/// the manifest declares a `tls` or `https` capability, offers mTLS auth, or
+/// the port is 443. Without a usable address, the backend is assumed at the
+/// service name on port 8080.
+fn backend_base_url(manifest: &SchemaManifest) -> String {
+    let raw = manifest
+        .instance
+        .as_ref()
+        .map(|instance| instance.address.trim().trim_end_matches('/'));
+    let (explicit_scheme, raw) = match raw.map(|a| (a, a.split_once("://"))) {
+        Some((_, Some((scheme @ ("http" | "https"), rest)))) => (Some(scheme), Some(rest)),
+        Some((a, _)) => (None, Some(a)),
+        None => (None, None),
+    };
+    let address = raw.and_then(|a| Address::parse(a).ok());
+
+    let tls = manifest.has_capability("tls")
+        || manifest.has_capability("https")
+        || manifest.auth.as_ref().is_some_and(|auth| {
+            auth.schemes
+                .iter()
+                .any(|scheme| scheme.auth_type == AuthType::MTLS)
+        })
+        || address.as_ref().is_some_and(|a| a.port() == 443);
+    let scheme = explicit_scheme.unwrap_or(if tls { "https" } else { "http" });
+
+    match address {
+        Some(address) => format!("{scheme}://{address}"),
+        None => format!("{scheme}://{}:8080", manifest.service_name),
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::manifest::new_manifest;
-    use crate::registry::memory::MemoryRegistry;
+/// Returns a manifest's own retry policy, falling back to `default`
+fn manifest_retry<'a>(manifest: &'a SchemaManifest, default: &'a RetryConfig) -> &'a RetryConfig {
+    manifest
+        .webhook
+        .as_ref()
+        .and_then(|w| w.retry.as_ref())
+        .unwrap_or(default)
+}
 
-    #[tokio::test]
-    async fn test_gateway_client() {
-        let registry = Arc::new(MemoryRegistry::new());
-        let client = Client::new(registry.clone());
+/// Reports whether a fetch error may succeed on retry
+fn is_transient(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::BackendUnavailable(_) | Error::SchemaFetchFailed(_) | Error::Io(_)
+    )
+}
 
-        let mut manifest = new_manifest("test-service", "v1.0.0", "instance-123");
-        manifest.endpoints.health = "/health".to_string();
-        manifest.update_checksum().unwrap();
+/// RFC 6901 JSON pointer into a schema document (e.g. `/paths`)
+pub type JsonPointer<'a> = &'a str;
 
-        registry.register_manifest(&manifest).await.unwrap();
+/// Builds a minimal document containing only the subtrees addressed by `fields`
+fn project_schema(
+    schema: &serde_json::Value,
+    fields: &[JsonPointer<'_>],
+) -> Result<serde_json::Value> {
+    let mut projected = serde_json::Value::Object(serde_json::Map::new());
 
-        let manifests = vec![manifest];
-        let routes = client.convert_to_routes(&manifests).await;
+    for pointer in fields {
+        if pointer.is_empty() {
+            return Ok(schema.clone());
+        }
+        if !pointer.starts_with('/') {
+            return Err(Error::validation(
+                "fields",
+                format!("invalid JSON pointer: {pointer}"),
+            ));
+        }
 
-        assert!(routes.is_empty()); // No schemas registered yet
+        let Some(value) = schema.pointer(pointer) else {
+            continue;
+        };
+
+        // Walk/create the parent objects, then place the subtree at its leaf
+        let tokens: Vec<String> = pointer[1..]
+            .split('/')
+            .map(|t| t.replace("~1", "/").replace("~0", "~"))
+            .collect();
+        let (leaf, parents) = tokens.split_last().expect("pointer has at least one token");
+
+        let mut target = &mut projected;
+        for token in parents {
+            target = target
+                .as_object_mut()
+                .expect("projection nodes are always objects")
+                .entry(token.clone())
+                .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+            if !target.is_object() {
+                // An earlier, shorter pointer already copied this whole subtree
+                break;
+            }
+        }
+        if let Some(obj) = target.as_object_mut() {
+            obj.insert(leaf.clone(), value.clone());
+        }
     }
 
-    #[tokio::test]
-    async fn test_convert_openapi_to_routes() {
-        let registry = Arc::new(MemoryRegistry::new());
-        let client = Client::new(registry);
+    Ok(projected)
+}
 
-        let mut manifest = new_manifest("user-service", "v1.0.0", "instance-123");
-        manifest.endpoints.health = "/health".to_string();
+/// Resolves the auth middleware identifiers (e.g. `auth:bearer`) for a route
+///
+/// Routes matching one of the manifest's `public_routes` globs get none.
+/// An operation's own OpenAPI `security` requirement overrides the manifest
+/// default; `security: []` marks the operation as public.
+fn auth_middleware(
+    manifest: &SchemaManifest,
+    path: &str,
+    operations: &[&serde_json::Value],
+    schema: &serde_json::Value,
+) -> Vec<String> {
+    if let Some(auth) = &manifest.auth {
+        if auth
+            .public_routes
+            .iter()
+            .any(|pattern| glob_match(pattern, path))
+        {
+            return Vec::new();
+        }
+    }
 
-        let schema = serde_json::json!({
-            "openapi": "3.1.0",
-            "paths": {
-                "/users": {
-                    "get": {},
-                    "post": {}
+    let defaults: Vec<String> = manifest
+        .auth
+        .as_ref()
+        .map(|auth| {
+            auth.schemes
+                .iter()
+                .map(|scheme| format!("auth:{}", scheme.auth_type))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if operations.is_empty() {
+        return dedup(defaults);
+    }
+
+    let mut middleware = Vec::new();
+    for operation in operations {
+        match operation.get("security").and_then(|s| s.as_array()) {
+            Some(requirements) => {
+                for requirement in requirements {
+                    let Some(names) = requirement.as_object() else {
+                        continue;
+                    };
+                    for name in names.keys() {
+                        if let Some(auth_type) = openapi_scheme_type(schema, name) {
+                            middleware.push(format!("auth:{auth_type}"));
+                        }
+                    }
                 }
             }
-        });
+            None => middleware.extend(defaults.iter().cloned()),
+        }
+    }
 
-        let routes = client.convert_openapi_to_routes(&manifest, &schema);
-        assert_eq!(routes.len(), 1);
-        assert_eq!(routes[0].path, "/users");
-        assert_eq!(routes[0].methods, vec!["GET", "POST"]);
+    dedup(middleware)
+}
+
+/// Path prefix mounting `manifest` with `routing` puts before its routes
+///
+/// Normalized to a leading and no trailing `/`; empty for unprefixed mounts.
+fn mount_prefix(manifest: &SchemaManifest, routing: &RoutingConfig) -> String {
+    let prefix = crate::merger::mount_prefix_with(manifest, routing);
+    let segments: Vec<&str> = prefix.split('/').filter(|s| !s.is_empty()).collect();
+    if segments.is_empty() {
+        String::new()
+    } else {
+        format!("/{}", segments.join("/"))
+    }
+}
+
+/// Mounts a converted route under `prefix` with its instance's weight
+///
+/// The gateway path gains the prefix and `strip_segments` its segment
+/// count, so stripping them yields the backend path again.
+fn mount_route(route: ServiceRoute, weight: Option<u32>, prefix: &str) -> ServiceRoute {
+    ServiceRoute {
+        path: format!("{prefix}{}", route.path),
+        weight,
+        strip_segments: prefix.split('/').filter(|s| !s.is_empty()).count(),
+        ..route
+    }
+}
+
+/// Resiliency policy routes inherit from the manifest's service hints
+fn route_resiliency(manifest: &SchemaManifest) -> Option<ResiliencyConfig> {
+    manifest.hints.as_ref()?.resiliency_policy()
+}
+
+/// Data classification for a route
+///
+/// Each operation is classified by its own `x-data-sensitivity`, or else the
+/// service's `hints.sensitivity`, and the most sensitive operation wins.
+/// Routes without operations take the service's level. Unrecognized values
+/// are treated as the most restrictive level (`PCI`) rather than ignored.
+fn route_sensitivity(
+    manifest: &SchemaManifest,
+    operations: &[&serde_json::Value],
+) -> Option<DataSensitivity> {
+    let fallback = manifest.hints.as_ref().and_then(|h| h.sensitivity);
+    if operations.is_empty() {
+        return fallback;
+    }
+    operations
+        .iter()
+        .map(|op| match op.get("x-data-sensitivity") {
+            Some(value) => Some(
+                serde_json::from_value::<DataSensitivity>(value.clone())
+                    .unwrap_or(DataSensitivity::PCI),
+            ),
+            None => fallback,
+        })
+        .max()
+        .flatten()
+}
+
+/// Expands `google.api.http` field templates into gateway path segments
+///
+/// A template's literal segments are kept and each wildcard becomes its own
+/// path variable named after the field: `{name=shelves/*}` becomes
+/// `shelves/{name}`, and templates with several wildcards number them
+/// (`{name=shelves/*/books/*}` becomes `shelves/{name_1}/books/{name_2}`).
+/// A `**` wildcard becomes a catch-all `{name=**}` segment matching the
+/// rest of the path. Plain `{name}` variables are left as they are.
+fn transcode_path(template: &str) -> String {
+    let mut path = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        path.push_str(&rest[..start]);
+        let field = &rest[start + 1..start + end];
+        let (name, pattern) = field.split_once('=').unwrap_or((field, "*"));
+        let segments: Vec<&str> = pattern.split('/').collect();
+        let wildcards = segments
+            .iter()
+            .filter(|s| matches!(**s, "*" | "**"))
+            .count();
+        let mut index = 0;
+        let expanded: Vec<String> = segments
+            .iter()
+            .map(|segment| match *segment {
+                "*" | "**" => {
+                    index += 1;
+                    let var = if wildcards == 1 {
+                        name.to_string()
+                    } else {
+                        format!("{name}_{index}")
+                    };
+                    if *segment == "**" {
+                        format!("{{{var}=**}}")
+                    } else {
+                        format!("{{{var}}}")
+                    }
+                }
+                literal => literal.to_string(),
+            })
+            .collect();
+        path.push_str(&expanded.join("/"));
+        rest = &rest[start + end + 1..];
+    }
+    path.push_str(rest);
+    path
+}
+
+/// Resolves session affinity for a route
+///
+/// An explicit `routing.sticky` applies to every route (and can disable
+/// stickiness); otherwise WebSocket routes of manifests declaring the
+/// `websocket` capability are sticky with default settings.
+fn sticky_config(manifest: &SchemaManifest, websocket: bool) -> Option<StickyConfig> {
+    match &manifest.routing.sticky {
+        Some(sticky) => sticky.enabled.then(|| sticky.clone()),
+        None if websocket
+            && manifest
+                .capabilities
+                .iter()
+                .any(|c| c == Capability::WebSocket.as_str()) =>
+        {
+            Some(StickyConfig::default())
+        }
+        None => None,
+    }
+}
+
+/// Determines whether a routable AsyncAPI channel is served over WebSocket or SSE
+///
+/// Plain `http` channels are SSE for services with the `sse` capability.
+/// Channels declaring no protocol at all default to WebSocket, or SSE for
+/// SSE-only services.
+fn channel_capability(manifest: &SchemaManifest, protocol: Protocol, declared: bool) -> Capability {
+    let has_sse = manifest.has_capability(Capability::SSE.as_str());
+    let has_websocket = manifest.has_capability(Capability::WebSocket.as_str());
+    match protocol {
+        Protocol::WebSocket => Capability::WebSocket,
+        Protocol::SSE => Capability::SSE,
+        Protocol::HTTP if has_sse && (declared || !has_websocket) => Capability::SSE,
+        Protocol::HTTP => Capability::WebSocket,
+    }
+}
+
+/// Resolves the upstream timeout for a route
+///
+/// Uses the longest `x-farp-timeout` among the route's operations, then the
+/// path item's own `x-farp-timeout`, then `hints.recommended_timeout`.
+/// Unparseable values are ignored.
+fn route_timeout(
+    manifest: &SchemaManifest,
+    path_item: &serde_json::Value,
+    operations: &[&serde_json::Value],
+) -> Option<Duration> {
+    let declared = |value: &serde_json::Value| {
+        value
+            .get("x-farp-timeout")
+            .and_then(|t| t.as_str())
+            .and_then(|t| parse_duration(t).ok())
+    };
+
+    operations
+        .iter()
+        .filter_map(|op| declared(op))
+        .max()
+        .or_else(|| declared(path_item))
+        .or_else(|| {
+            let hints = manifest.hints.as_ref()?;
+            hints.recommended_timeout_duration().ok().flatten()
+        })
+}
+
+/// Decides whether an OpenAPI operation may be retried
+///
+/// An operation's `x-farp-idempotent` wins, then a matching service route in
+/// the manifest's `webhook.http_routes`, then HTTP method semantics (`POST`
+/// and `PATCH` are not idempotent).
+fn is_idempotent(
+    manifest: &SchemaManifest,
+    path: &str,
+    method: &str,
+    operation: &serde_json::Value,
+) -> bool {
+    if let Some(declared) = operation.get("x-farp-idempotent").and_then(|v| v.as_bool()) {
+        return declared;
+    }
+
+    let declared = manifest
+        .webhook
+        .as_ref()
+        .and_then(|w| w.http_routes.as_ref())
+        .and_then(|routes| {
+            routes
+                .service_routes
+                .iter()
+                .find(|r| r.path == path && r.method.eq_ignore_ascii_case(method))
+        });
+    match declared {
+        Some(route) => route.idempotent,
+        None => !matches!(method, "POST" | "PATCH"),
+    }
+}
+
+/// Flags deprecated routes and attaches sunset dates
+///
+/// A route is deprecated when all of its methods are, either through an
+/// OpenAPI operation's `deprecated: true` or through a descriptor
+/// `Deprecation` whose path is the route path (`/users`) or a JSON pointer
+/// to it or one of its operations (`#/paths/~1users/get`). The earliest
+/// `removal_date` among matching entries becomes the sunset date of a
+/// deprecated route. Routes with only some methods deprecated list them in
+/// `deprecated_methods` metadata instead and get no sunset date.
+fn apply_deprecations(
+    routes: &mut [ServiceRoute],
+    descriptor: &SchemaDescriptor,
+    schema: &serde_json::Value,
+) {
+    let deprecations = descriptor
+        .compatibility
+        .as_ref()
+        .map(|c| c.deprecations.as_slice())
+        .unwrap_or_default();
+
+    for route in routes {
+        let mut whole_route = false;
+        let mut methods: Vec<String> = Vec::new();
+        let mut sunset: Option<&str> = None;
+
+        if descriptor.schema_type == SchemaType::OpenAPI {
+            let path_item = schema.get("paths").and_then(|p| p.get(&route.path));
+            for method in &route.methods {
+                let flagged = path_item
+                    .and_then(|item| item.get(method.to_ascii_lowercase()))
+                    .and_then(|op| op.get("deprecated"))
+                    .and_then(|d| d.as_bool())
+                    .unwrap_or(false);
+                if flagged {
+                    methods.push(method.clone());
+                }
+            }
+        }
+
+        for deprecation in deprecations {
+            let (path, method) = deprecation_target(&deprecation.path);
+            if path != route.path {
+                continue;
+            }
+            match method {
+                None => whole_route = true,
+                Some(method) => {
+                    match route
+                        .methods
+                        .iter()
+                        .find(|m| m.eq_ignore_ascii_case(&method))
+                    {
+                        Some(m) if !methods.contains(m) => methods.push(m.clone()),
+                        Some(_) => {}
+                        None => continue,
+                    }
+                }
+            }
+            if let Some(date) = deprecation.removal_date.as_deref() {
+                sunset = Some(sunset.map_or(date, |current| current.min(date)));
+            }
+        }
+
+        route.deprecated =
+            whole_route || (!route.methods.is_empty() && methods.len() == route.methods.len());
+        route.sunset = sunset.filter(|_| route.deprecated).map(String::from);
+        if !route.deprecated && !methods.is_empty() {
+            route
+                .metadata
+                .insert("deprecated_methods".to_string(), methods.into());
+        }
+    }
+}
+
+/// Splits a deprecation path into the schema path and optional method
+fn deprecation_target(path: &str) -> (String, Option<String>) {
+    let pointer = path.strip_prefix('#').unwrap_or(path);
+    let Some(rest) = pointer.strip_prefix("/paths/") else {
+        return (path.to_string(), None);
+    };
+
+    let mut segments = rest.split('/');
+    let schema_path = segments
+        .next()
+        .unwrap_or_default()
+        .replace("~1", "/")
+        .replace("~0", "~");
+    (schema_path, segments.next().map(String::from))
+}
+
+/// Maps a named OpenAPI security scheme to its FARP auth type
+fn openapi_scheme_type(schema: &serde_json::Value, name: &str) -> Option<AuthType> {
+    let scheme = schema
+        .get("components")?
+        .get("securitySchemes")?
+        .get(name)?;
+
+    let auth_type = match scheme.get("type")?.as_str()? {
+        "http" => match scheme
+            .get("scheme")
+            .and_then(|s| s.as_str())
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+        {
+            Some("basic") => AuthType::Basic,
+            Some("bearer") => AuthType::Bearer,
+            _ => AuthType::Custom,
+        },
+        "apiKey" => AuthType::APIKey,
+        "oauth2" => AuthType::OAuth2,
+        "openIdConnect" => AuthType::OIDC,
+        "mutualTLS" => AuthType::MTLS,
+        _ => AuthType::Custom,
+    };
+    Some(auth_type)
+}
+
+/// Removes duplicates while keeping first-seen order
+fn dedup(items: Vec<String>) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    items
+        .into_iter()
+        .filter(|item| seen.insert(item.clone()))
+        .collect()
+}
+
+/// Service route configuration for the gateway
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServiceRoute {
+    /// Path pattern for the route
+    pub path: String,
+    /// HTTP methods for this route
+    pub methods: Vec<String>,
+    /// Target backend URL
+    pub target_url: String,
+    /// Health check URL
+    pub health_url: String,
+    /// Health probe configuration
+    pub health_check: HealthCheckConfig,
+    /// Backend service name
+    pub service_name: String,
+    /// Backend service version
+    pub service_version: String,
+    /// Middleware names to apply
+    pub middleware: Vec<String>,
+    /// Session affinity, if the route requires it
+    pub sticky: Option<StickyConfig>,
+    /// Upstream request timeout, if the service declares one
+    pub timeout: Option<Duration>,
+    /// Load-balancing weight (0-100); `None` means the gateway default
+    pub weight: Option<u32>,
+    /// Every method on this route is deprecated
+    pub deprecated: bool,
+    /// Planned removal date (for a `Sunset` header)
+    pub sunset: Option<String>,
+    /// Region of the backend instance, for locality-aware balancing
+    pub region: Option<String>,
+    /// Zone of the backend instance, for locality-aware balancing
+    pub zone: Option<String>,
+    /// Idempotency key handling, set when any method on the route is safe to retry
+    pub idempotency: Option<IdempotencyConfig>,
+    /// Methods the gateway must not retry (non-idempotent writes)
+    pub no_retry_methods: Vec<String>,
+    /// Service-wide rate limit declared in the manifest
+    pub quota: Option<QuotaConfig>,
+    /// Retry and circuit-breaking policy from the service's hints
+    pub resiliency: Option<ResiliencyConfig>,
+    /// Leading segments of `path` added by the manifest's mount strategy
+    /// (e.g. the instance ID), to strip before forwarding to the backend
+    /// (see [`upstream_path`](Self::upstream_path))
+    pub strip_segments: usize,
+    /// Most sensitive data classification of the route's operations
+    pub sensitivity: Option<DataSensitivity>,
+    /// Additional route metadata
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+impl ServiceRoute {
+    /// Returns whether the gateway may retry `method` on this route
+    pub fn retry_safe(&self, method: &str) -> bool {
+        self.idempotency.is_some()
+            && self.methods.iter().any(|m| m.eq_ignore_ascii_case(method))
+            && !self
+                .no_retry_methods
+                .iter()
+                .any(|m| m.eq_ignore_ascii_case(method))
+    }
+
+    /// Returns the backend path for a request path matched by this route
+    ///
+    /// Strips the `strip_segments` leading segments the mount strategy
+    /// added: `/instance-123/users/1` becomes `/users/1` for an
+    /// instance-mounted route.
+    pub fn upstream_path(&self, request_path: &str) -> String {
+        let mut rest = request_path;
+        for _ in 0..self.strip_segments {
+            let segment = rest.strip_prefix('/').unwrap_or(rest);
+            rest = segment.find('/').map_or("", |end| &segment[end..]);
+        }
+        if rest.is_empty() {
+            "/".to_string()
+        } else {
+            rest.to_string()
+        }
+    }
+
+    /// Returns whether requests on this route must be audited (PII, PHI or PCI data)
+    pub fn requires_audit(&self) -> bool {
+        self.sensitivity.is_some_and(|s| s.requires_audit())
+    }
+}
+
+/// Selects the routes carrying regulated data (PII, PHI or PCI), for audit logging
+pub fn routes_requiring_audit(routes: &[ServiceRoute]) -> Vec<&ServiceRoute> {
+    routes.iter().filter(|r| r.requires_audit()).collect()
+}
+
+/// Idempotency key handling for retry-safe routes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdempotencyConfig {
+    /// Request header carrying the client's idempotency key
+    pub header: String,
+    /// How long the gateway should remember a key
+    pub ttl: Duration,
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            header: "Idempotency-Key".to_string(),
+            ttl: Duration::from_secs(24 * 60 * 60),
+        }
+    }
+}
+
+/// Per-instance routes remembered by [`Client::watch_services`]
+#[derive(Default)]
+struct RouteCache {
+    instances: HashMap<String, CachedRoutes>,
+    last: Option<Vec<ServiceRoute>>,
+}
+
+/// Routes converted from one manifest, valid while its checksum holds
+struct CachedRoutes {
+    checksum: String,
+    routes: Vec<ServiceRoute>,
+}
+
+/// How [`Client`] handles instances of one service declaring different mount strategies
+///
+/// Strategies conflict when they differ, or when `Custom` mounts use
+/// different base paths. Each conflict is reported as a warning.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MountConflictStrategy {
+    /// Mount every instance's routes (their paths and `strip_segments`) with
+    /// the highest-priority instance's routing (`routing.priority`, unset
+    /// counts as 0; ties go to the lowest instance ID)
+    #[default]
+    Priority,
+    /// Leave the service unrouted (`try_convert_to_routes` fails)
+    Error,
+}
+
+/// An eligible instance with its route weight and mount prefix
+type Eligible<'a> = (&'a SchemaManifest, Option<u32>, String);
+
+/// Which instances [`Client`] routes to
+///
+/// Manifests without instance metadata are treated as healthy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutePolicy {
+    /// Instance statuses eligible for routing
+    pub allowed_statuses: Vec<InstanceStatus>,
+    /// Also route to draining instances (e.g. for sticky sessions)
+    pub include_draining: bool,
+    /// Warn when a service has fewer healthy instances than this
+    pub min_healthy_instances: usize,
+    /// Percentage of its weight a degraded instance keeps (0-100)
+    pub degraded_weight_percent: u32,
+    /// Resolution for instances of a service with different mount strategies
+    pub mount_conflicts: MountConflictStrategy,
+}
+
+impl Default for RoutePolicy {
+    fn default() -> Self {
+        Self {
+            allowed_statuses: vec![InstanceStatus::Healthy, InstanceStatus::Degraded],
+            include_draining: false,
+            min_healthy_instances: 1,
+            degraded_weight_percent: 50,
+            mount_conflicts: MountConflictStrategy::default(),
+        }
+    }
+}
+
+impl RoutePolicy {
+    /// Policy that routes to every instance regardless of status
+    pub fn allow_all() -> Self {
+        Self {
+            allowed_statuses: vec![
+                InstanceStatus::Starting,
+                InstanceStatus::Healthy,
+                InstanceStatus::Degraded,
+                InstanceStatus::Unhealthy,
+                InstanceStatus::Draining,
+                InstanceStatus::Stopping,
+            ],
+            include_draining: true,
+            min_healthy_instances: 0,
+            degraded_weight_percent: 100,
+            mount_conflicts: MountConflictStrategy::default(),
+        }
+    }
+
+    /// Returns whether an instance with this status may receive traffic
+    pub fn allows(&self, status: InstanceStatus) -> bool {
+        self.allowed_statuses.contains(&status)
+            || (status == InstanceStatus::Draining && self.include_draining)
+    }
+
+    /// Selects eligible manifests with their route weights and mount
+    /// prefixes, plus per-service warnings and mount conflicts
+    ///
+    /// Mount conflicts are also included in the warnings.
+    fn apply<'a>(
+        &self,
+        manifests: &'a [SchemaManifest],
+    ) -> (Vec<Eligible<'a>>, Vec<String>, Vec<String>) {
+        let mut eligible = Vec::new();
+        let mut services: HashMap<&str, (usize, usize)> = HashMap::new();
+
+        for manifest in manifests {
+            let status = manifest
+                .instance
+                .as_ref()
+                .map_or(InstanceStatus::Healthy, |i| i.status);
+            let (healthy, routable) = services.entry(&manifest.service_name).or_default();
+            if status == InstanceStatus::Healthy {
+                *healthy += 1;
+            }
+            if !self.allows(status) {
+                continue;
+            }
+            *routable += 1;
+
+            let base = manifest
+                .instance
+                .as_ref()
+                .and_then(|i| i.weight)
+                .map(|w| w.clamp(0, 100) as u32);
+            let weight = match status {
+                InstanceStatus::Degraded => {
+                    Some(base.unwrap_or(100) * self.degraded_weight_percent.min(100) / 100)
+                }
+                _ => base,
+            };
+            eligible.push((manifest, weight));
+        }
+
+        let mut warnings: Vec<String> = services
+            .into_iter()
+            .filter_map(|(service, (healthy, routable))| {
+                if routable == 0 {
+                    Some(format!(
+                        "service {service} has no instances eligible for routing ({healthy} healthy)"
+                    ))
+                } else if healthy < self.min_healthy_instances {
+                    Some(format!(
+                        "service {service} has {healthy} healthy instances (minimum {})",
+                        self.min_healthy_instances
+                    ))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        warnings.sort();
+
+        let (eligible, conflicts) = self.resolve_mounts(eligible);
+        warnings.extend(conflicts.iter().cloned());
+        (eligible, warnings, conflicts)
+    }
+
+    /// Reconciles the mount strategies of each service's eligible instances
+    fn resolve_mounts<'a>(
+        &self,
+        eligible: Vec<(&'a SchemaManifest, Option<u32>)>,
+    ) -> (Vec<Eligible<'a>>, Vec<String>) {
+        let mount = |m: &SchemaManifest| {
+            let base_path = (m.routing.strategy == MountStrategy::Custom)
+                .then(|| m.routing.base_path.clone().unwrap_or_default());
+            (m.routing.strategy, base_path)
+        };
+
+        let mut services: BTreeMap<&str, Vec<&SchemaManifest>> = BTreeMap::new();
+        for (manifest, _) in &eligible {
+            services
+                .entry(&manifest.service_name)
+                .or_default()
+                .push(manifest);
+        }
+
+        let mut conflicts = Vec::new();
+        let mut winners: HashMap<&str, Option<&SchemaManifest>> = HashMap::new();
+        for (service, instances) in services {
+            let mut mounts: Vec<String> = instances
+                .iter()
+                .map(|m| match mount(m) {
+                    (strategy, Some(base_path)) => format!("{strategy}:{base_path}"),
+                    (strategy, None) => strategy.to_string(),
+                })
+                .collect();
+            mounts.sort();
+            mounts.dedup();
+            if mounts.len() < 2 {
+                continue;
+            }
+
+            let mounts = mounts.join(", ");
+            match self.mount_conflicts {
+                MountConflictStrategy::Priority => {
+                    let winner = instances
+                        .iter()
+                        .copied()
+                        .max_by(|a, b| {
+                            a.routing
+                                .priority
+                                .unwrap_or(0)
+                                .cmp(&b.routing.priority.unwrap_or(0))
+                                .then_with(|| b.instance_id.cmp(&a.instance_id))
+                        })
+                        .expect("conflicting services have instances");
+                    conflicts.push(format!(
+                        "service {service} has conflicting mount strategies ({mounts}); \
+                         mounting every instance like {} ({})",
+                        winner.instance_id, winner.routing.strategy
+                    ));
+                    winners.insert(service, Some(winner));
+                }
+                MountConflictStrategy::Error => {
+                    conflicts.push(format!(
+                        "service {service} has conflicting mount strategies ({mounts}); \
+                         not routed"
+                    ));
+                    winners.insert(service, None);
+                }
+            }
+        }
+
+        let eligible = eligible
+            .into_iter()
+            .filter_map(|(manifest, weight)| {
+                let prefix = match winners.get(manifest.service_name.as_str()) {
+                    None => mount_prefix(manifest, &manifest.routing),
+                    Some(Some(winner)) => mount_prefix(manifest, &winner.routing),
+                    Some(None) => return None,
+                };
+                Some((manifest, weight, prefix))
+            })
+            .collect();
+        (eligible, conflicts)
+    }
+}
+
+/// Routes produced by [`Client::convert_to_routes_with_warnings`]
+#[derive(Debug, Clone, Default)]
+pub struct RouteConversion {
+    /// Routes for eligible instances
+    pub routes: Vec<ServiceRoute>,
+    /// Services that fell short of the [`RoutePolicy`]
+    pub warnings: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::new_manifest;
+    use crate::registry::memory::MemoryRegistry;
+
+    #[tokio::test]
+    async fn test_gateway_client() {
+        let registry = Arc::new(MemoryRegistry::new());
+        let client = Client::new(registry.clone());
+
+        let mut manifest = new_manifest("test-service", "v1.0.0", "instance-123");
+        manifest.endpoints.health = "/health".to_string();
+        manifest.update_checksum().unwrap();
+
+        registry.register_manifest(&manifest).await.unwrap();
+
+        let manifests = vec![manifest];
+        let routes = client.convert_to_routes(&manifests).await;
+
+        assert!(routes.is_empty()); // No schemas registered yet
+    }
+
+    #[tokio::test]
+    async fn test_fetch_schema_projection() {
+        let registry = Arc::new(MemoryRegistry::new());
+        let client = Client::new(registry.clone());
+
+        let schema = serde_json::json!({
+            "openapi": "3.1.0",
+            "info": {"title": "User API", "version": "1.0.0"},
+            "paths": {"/users": {"get": {"operationId": "listUsers"}}},
+            "components": {"schemas": {"User": {"type": "object"}}}
+        });
+        registry
+            .publish_schema("/schemas/user-service/openapi", &schema)
+            .await
+            .unwrap();
+
+        let descriptor = SchemaDescriptor {
+            schema_type: SchemaType::OpenAPI,
+            schema_id: None,
+            spec_version: "3.1.0".to_string(),
+            location: crate::types::SchemaLocation {
+                location_type: LocationType::Registry,
+                url: None,
+                registry_path: Some("/schemas/user-service/openapi".to_string()),
+                headers: None,
+            },
+            content_type: "application/json".to_string(),
+            inline_schema: None,
+            hash: crate::manifest::calculate_schema_checksum(&schema).unwrap(),
+            size: 0,
+            compatibility: None,
+            metadata: None,
+        };
+
+        let projected = client
+            .fetch_schema_projection(&descriptor, &["/info", "/paths", "/missing"])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            projected,
+            serde_json::json!({
+                "info": {"title": "User API", "version": "1.0.0"},
+                "paths": {"/users": {"get": {"operationId": "listUsers"}}}
+            })
+        );
+
+        let nested = client
+            .fetch_schema_projection(&descriptor, &["/paths/~1users/get"])
+            .await
+            .unwrap();
+        assert_eq!(
+            nested,
+            serde_json::json!({"paths": {"/users": {"get": {"operationId": "listUsers"}}}})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_openapi_routes_auth_middleware() {
+        let registry = Arc::new(MemoryRegistry::new());
+        let client = Client::new(registry);
+
+        let mut manifest = new_manifest("user-service", "v1.0.0", "instance-123");
+        manifest.endpoints.health = "/health".to_string();
+        manifest.auth = Some(crate::types::AuthConfig {
+            schemes: vec![crate::types::AuthScheme {
+                auth_type: AuthType::Bearer,
+                config: None,
+            }],
+            required_scopes: Vec::new(),
+            access_control: Vec::new(),
+            token_validation_url: None,
+            public_routes: vec!["/public/**".to_string()],
+        });
+
+        let schema = serde_json::json!({
+            "openapi": "3.1.0",
+            "paths": {
+                "/users": {"get": {}},
+                "/public/docs": {"get": {}},
+                "/status": {"get": {"security": []}},
+                "/keys": {"get": {"security": [{"apiKeyAuth": []}]}}
+            },
+            "components": {
+                "securitySchemes": {
+                    "apiKeyAuth": {"type": "apiKey", "in": "header", "name": "X-API-Key"}
+                }
+            }
+        });
+
+        let routes = client.convert_openapi_to_routes(&manifest, &schema);
+        let middleware = |path: &str| {
+            routes
+                .iter()
+                .find(|r| r.path == path)
+                .map(|r| r.middleware.clone())
+                .unwrap()
+        };
+
+        assert_eq!(middleware("/users"), vec!["auth:bearer"]);
+        assert!(middleware("/public/docs").is_empty());
+        assert!(middleware("/status").is_empty());
+        assert_eq!(middleware("/keys"), vec!["auth:apikey"]);
+    }
+
+    #[tokio::test]
+    async fn test_convert_federated_graphql_to_routes() {
+        let registry = Arc::new(MemoryRegistry::new());
+        let client = Client::new(registry);
+
+        let mut manifest = new_manifest("products", "v1.0.0", "instance-123");
+        manifest.endpoints.health = "/health".to_string();
+
+        let schema = serde_json::json!({});
+        let routes = client.convert_graphql_to_routes(&manifest, &schema);
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].path, "/graphql");
+        assert!(!routes[0].metadata.contains_key("subgraph_name"));
+
+        manifest.add_schema(SchemaDescriptor {
+            schema_type: SchemaType::GraphQL,
+            schema_id: None,
+            spec_version: "2023".to_string(),
+            location: crate::types::SchemaLocation {
+                location_type: LocationType::Inline,
+                url: None,
+                registry_path: None,
+                headers: None,
+            },
+            content_type: "application/graphql".to_string(),
+            inline_schema: Some(schema.clone()),
+            hash: "a".repeat(64),
+            size: 0,
+            compatibility: None,
+            metadata: Some(crate::types::ProtocolMetadata {
+                graphql: Some(crate::types::GraphQLMetadata {
+                    federation: Some(crate::types::GraphQLFederation {
+                        version: "v2".to_string(),
+                        subgraph_name: "products".to_string(),
+                        entities: Vec::new(),
+                        extends: Vec::new(),
+                        provides: Vec::new(),
+                        requires: Vec::new(),
+                    }),
+                    subscriptions_enabled: false,
+                    subscription_protocol: None,
+                    complexity_limit: None,
+                    depth_limit: None,
+                }),
+                grpc: None,
+                openapi: None,
+                asyncapi: None,
+                orpc: None,
+            }),
+        });
+
+        let routes = client.convert_graphql_to_routes(&manifest, &schema);
+        assert_eq!(routes.len(), 2);
+
+        let subgraph = &routes[0];
+        assert_eq!(subgraph.path, "/graphql");
+        assert_eq!(subgraph.metadata["federation_version"], "v2");
+        assert_eq!(subgraph.metadata["subgraph_name"], "products");
+
+        let sdl = &routes[1];
+        assert_eq!(sdl.path, "/graphql/_service");
+        assert_eq!(sdl.methods, vec!["POST"]);
+        assert_eq!(sdl.target_url, "http://products:8080/graphql");
+        assert_eq!(sdl.metadata["subgraph_name"], "products");
+        assert_eq!(
+            sdl.metadata["federation_operations"],
+            serde_json::json!(["_service", "_entities"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_convert_openapi_to_routes() {
+        let registry = Arc::new(MemoryRegistry::new());
+        let client = Client::new(registry);
+
+        let mut manifest = new_manifest("user-service", "v1.0.0", "instance-123");
+        manifest.endpoints.health = "/health".to_string();
+
+        let schema = serde_json::json!({
+            "openapi": "3.1.0",
+            "paths": {
+                "/users": {
+                    "get": {},
+                    "post": {}
+                }
+            }
+        });
+
+        let routes = client.convert_openapi_to_routes(&manifest, &schema);
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].path, "/users");
+        assert_eq!(routes[0].methods, vec!["GET", "POST"]);
+        assert_eq!(routes[0].health_check, HealthCheckConfig::default());
+    }
+
+    #[test]
+    fn test_targets_use_instance_address() {
+        let client = Client::new(Arc::new(MemoryRegistry::new()));
+        let schema = serde_json::json!({"openapi": "3.1.0", "paths": {"/users": {"get": {}}}});
+        let route = |manifest: &SchemaManifest| {
+            client
+                .convert_openapi_to_routes(manifest, &schema)
+                .remove(0)
+        };
+        let with_address = |address: Option<&str>| {
+            let mut manifest = instance_manifest("users-a", InstanceStatus::Healthy, None);
+            manifest.endpoints.health = "/health".to_string();
+            match address {
+                Some(address) => manifest.instance.as_mut().unwrap().address = address.to_string(),
+                None => manifest.instance = None,
+            }
+            manifest
+        };
+
+        let users = route(&with_address(Some("10.0.0.5:9000")));
+        assert_eq!(users.target_url, "http://10.0.0.5:9000/users");
+        assert_eq!(users.health_url, "http://10.0.0.5:9000/health");
+
+        let target = |address| route(&with_address(address)).target_url;
+        assert_eq!(target(Some("[::1]:8080")), "http://[::1]:8080/users");
+        assert_eq!(
+            target(Some("example.com:443")),
+            "https://example.com:443/users"
+        );
+        assert_eq!(
+            target(Some("https://10.0.0.5:9443")),
+            "https://10.0.0.5:9443/users"
+        );
+        // No usable address: the service name on the default port stands in
+        assert_eq!(target(None), "http://user-service:8080/users");
+        assert_eq!(
+            target(Some("not an address")),
+            "http://user-service:8080/users"
+        );
+
+        // TLS capability switches the scheme
+        let mut manifest = with_address(Some("10.0.0.5:9000"));
+        manifest.capabilities.push("tls".to_string());
+        assert_eq!(route(&manifest).target_url, "https://10.0.0.5:9000/users");
+    }
+
+    #[tokio::test]
+    async fn test_route_timeouts() {
+        use crate::types::ServiceHints;
+
+        let client = Client::new(Arc::new(MemoryRegistry::new()));
+        let schema = serde_json::json!({
+            "paths": {
+                "/exports": {
+                    "get": {"x-farp-timeout": "30s"},
+                    "post": {"x-farp-timeout": "1m500ms"}
+                },
+                "/reports": {"x-farp-timeout": "45s", "get": {}},
+                "/users": {"get": {"x-farp-timeout": "soon"}}
+            }
+        });
+
+        let mut manifest = new_manifest("user-service", "v1.0.0", "instance-1");
+        let routes = client.convert_openapi_to_routes(&manifest, &schema);
+        let timeouts: Vec<Option<Duration>> = routes.iter().map(|r| r.timeout).collect();
+        assert_eq!(
+            timeouts,
+            vec![
+                Some(Duration::from_millis(60_500)),
+                Some(Duration::from_secs(45)),
+                None
+            ]
+        );
+
+        // Routes without a valid extension inherit the service hint
+        manifest.hints = Some(ServiceHints {
+            recommended_timeout: Some("10s".to_string()),
+            expected_latency: None,
+            scaling: None,
+            dependencies: Vec::new(),
+            resiliency: None,
+            sensitivity: None,
+        });
+        let routes = client.convert_openapi_to_routes(&manifest, &schema);
+        assert_eq!(routes[0].timeout, Some(Duration::from_millis(60_500)));
+        assert_eq!(routes[2].timeout, Some(Duration::from_secs(10)));
+
+        let asyncapi = serde_json::json!({"channels": {"/ws/cart": {}}});
+        let routes = client.convert_asyncapi_to_routes(&manifest, &asyncapi).0;
+        assert_eq!(routes[0].timeout, Some(Duration::from_secs(10)));
+    }
+
+    #[tokio::test]
+    async fn test_routes_inherit_resiliency() {
+        use crate::types::{CircuitBreakerConfig, LatencyProfile, ResiliencyConfig, ServiceHints};
+
+        let client = Client::new(Arc::new(MemoryRegistry::new()));
+        let schema = serde_json::json!({"paths": {"/users": {"get": {}}}});
+        let mut manifest = new_manifest("user-service", "v1.0.0", "instance-1");
+        assert_eq!(
+            client.convert_openapi_to_routes(&manifest, &schema)[0].resiliency,
+            None
+        );
+
+        manifest.hints = Some(ServiceHints {
+            recommended_timeout: None,
+            expected_latency: Some(LatencyProfile {
+                p50: Some("20ms".to_string()),
+                p95: Some("120ms".to_string()),
+                p99: Some("300ms".to_string()),
+                p999: None,
+            }),
+            scaling: None,
+            dependencies: Vec::new(),
+            resiliency: Some(ResiliencyConfig {
+                retry_attempts: 3,
+                per_try_timeout: None,
+                circuit_breaker: Some(CircuitBreakerConfig::default()),
+            }),
+            sensitivity: None,
+        });
+        let routes = client.convert_openapi_to_routes(&manifest, &schema);
+        let policy = routes[0].resiliency.as_ref().unwrap();
+        assert_eq!(policy.retry_attempts, 3);
+        assert_eq!(policy.per_try_timeout.as_deref(), Some("300ms"));
+        assert_eq!(
+            policy
+                .circuit_breaker
+                .as_ref()
+                .map(|b| b.consecutive_failures),
+            Some(5)
+        );
+
+        let envoy = crate::gateway::export::envoy::to_envoy_config(&routes).unwrap();
+        assert_eq!(
+            envoy["route_config"]["virtual_hosts"][0]["routes"][0]["route"]["retry_policy"]
+                ["per_try_timeout"],
+            "0.300s"
+        );
+        assert_eq!(
+            envoy["clusters"][0]["outlier_detection"]["consecutive_5xx"],
+            5
+        );
+    }
+
+    #[tokio::test]
+    async fn test_asyncapi_channel_protocols() {
+        let client = Client::new(Arc::new(MemoryRegistry::new()));
+        let schema = serde_json::json!({
+            "asyncapi": "3.0.0",
+            "servers": {
+                "broker": {"host": "kafka:9092", "protocol": "kafka"},
+                "push": {"host": "events:8080", "protocol": "http"}
+            },
+            "channels": {
+                "/ws/chat": {"bindings": {"ws": {"method": "GET"}}},
+                "/events/orders": {"servers": [{"$ref": "#/servers/push"}]},
+                "orders.created": {"servers": [{"$ref": "#/servers/broker"}]}
+            }
+        });
+
+        let mut manifest = new_manifest("order-service", "v1.0.0", "instance-1");
+        manifest.capabilities = vec!["websocket".to_string(), "sse".to_string()];
+        let routes = client.convert_asyncapi_to_routes(&manifest, &schema).0;
+
+        let summary: Vec<(&str, &str, &str)> = routes
+            .iter()
+            .map(|r| {
+                (
+                    r.path.as_str(),
+                    r.methods[0].as_str(),
+                    r.metadata["protocol"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        // The Kafka channel isn't routable through the gateway
+        assert_eq!(
+            summary,
+            vec![
+                ("/events/orders", "SSE", "sse"),
+                ("/ws/chat", "WEBSOCKET", "websocket"),
+            ]
+        );
+        assert!(routes[0].sticky.is_none());
+        assert!(routes[1].sticky.is_some());
+
+        // 2.x documents name their servers directly
+        let schema = serde_json::json!({
+            "asyncapi": "2.6.0",
+            "servers": {"stream": {"url": "events:8080", "protocol": "sse"}},
+            "channels": {"/stream": {"servers": ["stream"]}}
+        });
+        let routes = client.convert_asyncapi_to_routes(&manifest, &schema).0;
+        assert_eq!(routes[0].methods, vec!["SSE"]);
+    }
+
+    #[tokio::test]
+    async fn test_asyncapi_non_routable_warnings() {
+        let client = Client::new(Arc::new(MemoryRegistry::new()));
+        let schema = serde_json::json!({
+            "asyncapi": "2.6.0",
+            "servers": {
+                "broker": {"url": "kafka:9092", "protocol": "kafka"},
+                "live": {"url": "orders:8080", "protocol": "ws"}
+            },
+            "channels": {
+                "orders.created": {"servers": ["broker"]},
+                "/ws/orders": {"servers": ["live"]}
+            }
+        });
+        let mut manifest = new_manifest("order-service", "v1.0.0", "instance-1");
+        manifest.capabilities = vec!["websocket".to_string()];
+
+        let (routes, warnings) = client.convert_asyncapi_to_routes(&manifest, &schema);
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].path, "/ws/orders");
+        assert_eq!(
+            warnings,
+            vec!["order-service: channel orders.created is non-routable (kafka)"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sticky_routes() {
+        let registry = Arc::new(MemoryRegistry::new());
+        let client = Client::new(registry);
+
+        let asyncapi = serde_json::json!({"channels": {"/ws/cart": {}}});
+        let openapi = serde_json::json!({"paths": {"/users": {"get": {}}}});
+
+        let mut websocket = new_manifest("cart-service", "v1.0.0", "instance-1");
+        websocket.capabilities = vec!["websocket".to_string()];
+        let routes = client.convert_asyncapi_to_routes(&websocket, &asyncapi).0;
+        assert_eq!(routes[0].sticky, Some(StickyConfig::default()));
+
+        let mut rest = new_manifest("user-service", "v1.0.0", "instance-2");
+        rest.capabilities = vec!["rest".to_string()];
+        let routes = client.convert_openapi_to_routes(&rest, &openapi);
+        assert_eq!(routes[0].sticky, None);
+
+        // An explicit config overrides the WebSocket default
+        websocket.routing.sticky = Some(StickyConfig {
+            enabled: false,
+            ..Default::default()
+        });
+        let routes = client.convert_asyncapi_to_routes(&websocket, &asyncapi).0;
+        assert_eq!(routes[0].sticky, None);
+
+        rest.routing.sticky = Some(StickyConfig {
+            hash_key: Some("X-Session-Id".to_string()),
+            ..Default::default()
+        });
+        let routes = client.convert_openapi_to_routes(&rest, &openapi);
+        assert_eq!(
+            routes[0]
+                .sticky
+                .as_ref()
+                .and_then(|s| s.hash_key.as_deref()),
+            Some("X-Session-Id")
+        );
+    }
+
+    /// Registry whose first `failures` schema fetches fail
+    struct FlakyRegistry {
+        inner: MemoryRegistry,
+        failures: std::sync::atomic::AtomicUsize,
+        fetches: std::sync::atomic::AtomicUsize,
+        delay: Duration,
+        in_flight: std::sync::atomic::AtomicUsize,
+        peak_in_flight: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl SchemaRegistry for FlakyRegistry {
+        async fn register_manifest(&self, manifest: &SchemaManifest) -> Result<()> {
+            self.inner.register_manifest(manifest).await
+        }
+        async fn get_manifest(&self, instance_id: &str) -> Result<SchemaManifest> {
+            self.inner.get_manifest(instance_id).await
+        }
+        async fn update_manifest(&self, manifest: &SchemaManifest) -> Result<()> {
+            self.inner.update_manifest(manifest).await
+        }
+        async fn delete_manifest(&self, instance_id: &str) -> Result<()> {
+            self.inner.delete_manifest(instance_id).await
+        }
+        async fn list_manifests(&self, service_name: &str) -> Result<Vec<SchemaManifest>> {
+            self.inner.list_manifests(service_name).await
+        }
+        async fn publish_schema(&self, path: &str, schema: &serde_json::Value) -> Result<()> {
+            self.inner.publish_schema(path, schema).await
+        }
+        async fn fetch_schema(&self, path: &str) -> Result<serde_json::Value> {
+            use std::sync::atomic::Ordering;
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            let remaining = self.failures.load(Ordering::SeqCst);
+            if remaining > 0 {
+                self.failures.store(remaining - 1, Ordering::SeqCst);
+                return Err(Error::backend_unavailable("connection reset"));
+            }
+            self.inner.fetch_schema(path).await
+        }
+        async fn delete_schema(&self, path: &str) -> Result<()> {
+            self.inner.delete_schema(path).await
+        }
+        async fn watch_manifests(
+            &self,
+            service_name: &str,
+            on_change: Box<dyn crate::registry::ManifestChangeHandler>,
+        ) -> Result<()> {
+            self.inner.watch_manifests(service_name, on_change).await
+        }
+        async fn watch_schemas(
+            &self,
+            path: &str,
+            on_change: Box<dyn crate::registry::SchemaChangeHandler>,
+        ) -> Result<()> {
+            self.inner.watch_schemas(path, on_change).await
+        }
+        async fn close(&self) -> Result<()> {
+            self.inner.close().await
+        }
+        async fn health(&self) -> Result<()> {
+            self.inner.health().await
+        }
+    }
+
+    async fn flaky_manifest(failures: usize) -> (Arc<FlakyRegistry>, SchemaManifest) {
+        let registry = Arc::new(FlakyRegistry {
+            inner: MemoryRegistry::new(),
+            failures: failures.into(),
+            fetches: 0.into(),
+            delay: Duration::ZERO,
+            in_flight: 0.into(),
+            peak_in_flight: 0.into(),
+        });
+        let schema = serde_json::json!({"openapi": "3.1.0", "paths": {"/users": {"get": {}}}});
+        registry
+            .publish_schema("/schemas/user-service/openapi", &schema)
+            .await
+            .unwrap();
+
+        let mut manifest = new_manifest("user-service", "v1.0.0", "instance-123");
+        manifest.add_schema(SchemaDescriptor {
+            schema_type: SchemaType::OpenAPI,
+            schema_id: None,
+            spec_version: "3.1.0".to_string(),
+            location: crate::types::SchemaLocation {
+                location_type: LocationType::Registry,
+                url: None,
+                registry_path: Some("/schemas/user-service/openapi".to_string()),
+                headers: None,
+            },
+            content_type: "application/json".to_string(),
+            inline_schema: None,
+            hash: crate::manifest::calculate_schema_checksum(&schema).unwrap(),
+            size: 0,
+            compatibility: None,
+            metadata: None,
+        });
+        (registry, manifest)
+    }
+
+    #[tokio::test]
+    async fn test_watch_services_reconverts_only_changed_instance() {
+        use std::sync::atomic::Ordering;
+
+        let registry = Arc::new(FlakyRegistry {
+            inner: MemoryRegistry::new(),
+            failures: 0.into(),
+            fetches: 0.into(),
+            delay: Duration::ZERO,
+            in_flight: 0.into(),
+            peak_in_flight: 0.into(),
+        });
+        let instance = |id: &str, schema: &serde_json::Value| {
+            let path = format!("/schemas/user-service/{id}");
+            let mut manifest = new_manifest("user-service", "v1.0.0", id);
+            manifest.endpoints.health = "/health".to_string();
+            manifest.add_schema(SchemaDescriptor {
+                schema_type: SchemaType::OpenAPI,
+                schema_id: None,
+                spec_version: "3.1.0".to_string(),
+                location: crate::types::SchemaLocation {
+                    location_type: LocationType::Registry,
+                    url: None,
+                    registry_path: Some(path),
+                    headers: None,
+                },
+                content_type: "application/json".to_string(),
+                inline_schema: None,
+                hash: crate::manifest::calculate_schema_checksum(schema).unwrap(),
+                size: 0,
+                compatibility: None,
+                metadata: None,
+            });
+            manifest.update_checksum().unwrap();
+            manifest
+        };
+
+        let schema_a = serde_json::json!({"openapi": "3.1.0", "paths": {"/a": {"get": {}}}});
+        let schema_b = serde_json::json!({"openapi": "3.1.0", "paths": {"/b": {"get": {}}}});
+        for (id, schema) in [("a", &schema_a), ("b", &schema_b)] {
+            registry
+                .publish_schema(&format!("/schemas/user-service/{id}"), schema)
+                .await
+                .unwrap();
+            registry
+                .register_manifest(&instance(id, schema))
+                .await
+                .unwrap();
+        }
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let client = Client::new(registry.clone());
+        client
+            .watch_services(
+                "user-service",
+                Arc::new(move |routes: Vec<ServiceRoute>| {
+                    let _ = tx.send(routes);
+                }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(rx.recv().await.unwrap().len(), 2);
+        assert_eq!(registry.fetches.load(Ordering::SeqCst), 2);
+
+        // Changing instance a's schema re-fetches it alone
+        let schema_a = serde_json::json!({
+            "openapi": "3.1.0",
+            "paths": {"/a": {"get": {}}, "/a/{id}": {"get": {}}}
+        });
+        registry
+            .publish_schema("/schemas/user-service/a", &schema_a)
+            .await
+            .unwrap();
+        registry
+            .update_manifest(&instance("a", &schema_a))
+            .await
+            .unwrap();
+        let routes = rx.recv().await.unwrap();
+        let mut paths: Vec<&str> = routes.iter().map(|r| r.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["/a/a", "/a/a/{id}", "/b/b"]);
+        assert_eq!(registry.fetches.load(Ordering::SeqCst), 3);
+
+        // Re-registering b unchanged produces the same routes, so nothing is emitted
+        registry
+            .register_manifest(&instance("b", &schema_b))
+            .await
+            .unwrap();
+        let next = tokio::time::timeout(Duration::from_millis(100), rx.recv()).await;
+        assert!(next.is_err());
+        assert_eq!(registry.fetches.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_schemas_deduplicates_by_hash() {
+        use std::sync::atomic::Ordering;
+
+        let (registry, manifest) = flaky_manifest(0).await;
+        let client = Client::new(registry.clone());
+        let descriptors = vec![manifest.schemas[0].clone(); 3];
+
+        let schemas = client.fetch_schemas(&descriptors).await;
+        assert_eq!(schemas.len(), 3);
+        assert!(schemas
+            .iter()
+            .all(|s| s.as_ref().unwrap()["openapi"] == "3.1.0"));
+        assert_eq!(registry.fetches.load(Ordering::SeqCst), 1);
+
+        // Later batches are served from the cache
+        client.fetch_schemas(&descriptors).await;
+        assert_eq!(registry.fetches.load(Ordering::SeqCst), 1);
+
+        // A failure reaches every descriptor sharing the hash
+        let (registry, manifest) = flaky_manifest(1).await;
+        let client = Client::new(registry.clone()).with_retry(RetryConfig {
+            max_attempts: 1,
+            ..RetryConfig::default()
+        });
+        let schemas = client
+            .fetch_schemas(&[manifest.schemas[0].clone(), manifest.schemas[0].clone()])
+            .await;
+        assert!(schemas.iter().all(Result::is_err));
+        assert_eq!(registry.fetches.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_schemas_bounds_concurrency() {
+        use std::sync::atomic::Ordering;
+
+        let registry = Arc::new(FlakyRegistry {
+            inner: MemoryRegistry::new(),
+            failures: 0.into(),
+            fetches: 0.into(),
+            delay: Duration::from_millis(20),
+            in_flight: 0.into(),
+            peak_in_flight: 0.into(),
+        });
+        let (_, template) = flaky_manifest(0).await;
+        let mut descriptors = Vec::new();
+        for i in 0..6 {
+            let path = format!("/schemas/service-{i}/openapi");
+            let schema = serde_json::json!({"openapi": "3.1.0", "info": {"title": i}});
+            registry.publish_schema(&path, &schema).await.unwrap();
+            let mut descriptor = template.schemas[0].clone();
+            descriptor.location.registry_path = Some(path);
+            descriptor.hash = crate::manifest::calculate_schema_checksum(&schema).unwrap();
+            descriptors.push(descriptor);
+        }
+
+        let client = Client::new(registry.clone()).with_fetch_concurrency(2);
+        let schemas = client.fetch_schemas(&descriptors).await;
+        for (i, schema) in schemas.iter().enumerate() {
+            assert_eq!(schema.as_ref().unwrap()["info"]["title"], i);
+        }
+        assert_eq!(registry.fetches.load(Ordering::SeqCst), 6);
+        assert_eq!(registry.peak_in_flight.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_warm_cache() {
+        use std::sync::atomic::Ordering;
+
+        let (registry, template) = flaky_manifest(0).await;
+        let mut manifests = Vec::new();
+        for i in 0..3 {
+            let path = format!("/schemas/user-service/openapi-{i}");
+            let schema = serde_json::json!({
+                "openapi": "3.1.0",
+                "paths": {format!("/users/{i}"): {"get": {}}}
+            });
+            registry.publish_schema(&path, &schema).await.unwrap();
+
+            let mut manifest = new_manifest("user-service", "v1.0.0", format!("instance-{i}"));
+            manifest.endpoints.health = "/health".to_string();
+            let mut descriptor = template.schemas[0].clone();
+            descriptor.location.registry_path = Some(path);
+            descriptor.hash = crate::manifest::calculate_schema_checksum(&schema).unwrap();
+            descriptor.size = 100;
+            manifest.add_schema(descriptor);
+            manifest.update_checksum().unwrap();
+            registry.register_manifest(&manifest).await.unwrap();
+            manifests.push(manifest);
+        }
+        // Declares a small size but is larger than the limit once fetched
+        let big = serde_json::json!({"openapi": "3.1.0", "info": {"title": "x".repeat(2048)}});
+        registry
+            .publish_schema("/schemas/user-service/openapi-big", &big)
+            .await
+            .unwrap();
+        let mut understated = new_manifest("user-service", "v1.0.0", "instance-lying");
+        understated.endpoints.health = "/health".to_string();
+        let mut descriptor = template.schemas[0].clone();
+        descriptor.location.registry_path = Some("/schemas/user-service/openapi-big".to_string());
+        descriptor.hash = crate::manifest::calculate_schema_checksum(&big).unwrap();
+        descriptor.size = 100;
+        let big_hash = descriptor.hash.clone();
+        understated.add_schema(descriptor);
+        understated.update_checksum().unwrap();
+        registry.register_manifest(&understated).await.unwrap();
+
+        // Declared too large to warm
+        let mut oversized = new_manifest("user-service", "v1.0.0", "instance-big");
+        oversized.endpoints.health = "/health".to_string();
+        let mut descriptor = template.schemas[0].clone();
+        descriptor.size = 10_000;
+        oversized.add_schema(descriptor);
+        oversized.update_checksum().unwrap();
+        registry.register_manifest(&oversized).await.unwrap();
+
+        let client = Client::new(registry.clone()).with_max_schema_size(1024);
+        assert_eq!(client.warm_cache("user-service").await.unwrap(), 3);
+        assert_eq!(registry.fetches.load(Ordering::SeqCst), 4);
+        assert!(!client.schema_cache.read().await.contains_key(&big_hash));
+
+        // Already cached: only the understated schema is fetched again, and
+        // conversion fetches nothing
+        assert_eq!(client.warm_cache("user-service").await.unwrap(), 0);
+        assert_eq!(registry.fetches.load(Ordering::SeqCst), 5);
+        let routes = client.convert_to_routes(&manifests).await;
+        assert_eq!(routes.len(), 3);
+        assert_eq!(registry.fetches.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_schema_retries() {
+        let retry = RetryConfig {
+            max_attempts: 3,
+            initial_delay: "1ms".to_string(),
+            max_delay: "5ms".to_string(),
+            multiplier: 2.0,
+        };
+
+        // Fails twice, succeeds on the third attempt
+        let (registry, manifest) = flaky_manifest(2).await;
+        let client = Client::new(registry.clone()).with_retry(retry.clone());
+        let routes = client.try_convert_to_routes(&[manifest]).await.unwrap();
+        assert_eq!(routes.len(), 1);
+        assert_eq!(
+            registry.fetches.load(std::sync::atomic::Ordering::SeqCst),
+            3
+        );
+
+        // Out of attempts: the error is surfaced, not swallowed
+        let (registry, manifest) = flaky_manifest(3).await;
+        let client = Client::new(registry.clone()).with_retry(retry);
+        let err = client
+            .try_convert_to_routes(std::slice::from_ref(&manifest))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Manifest { .. }));
+        assert_eq!(
+            registry.fetches.load(std::sync::atomic::Ordering::SeqCst),
+            3
+        );
+
+        // A manifest-level retry policy overrides the client's
+        let (registry, mut manifest) = flaky_manifest(3).await;
+        manifest.webhook = Some(crate::types::WebhookConfig {
+            service_webhook: None,
+            gateway_webhook: None,
+            secret: None,
+            subscribe_events: Vec::new(),
+            publish_events: Vec::new(),
+            retry: Some(RetryConfig {
+                max_attempts: 4,
+                initial_delay: "1ms".to_string(),
+                max_delay: "1ms".to_string(),
+                multiplier: 1.0,
+            }),
+            http_routes: None,
+        });
+        let client = Client::new(registry);
+        assert_eq!(client.convert_to_routes(&[manifest]).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_missing_schema_is_not_retried() {
+        let (registry, manifest) = flaky_manifest(0).await;
+        registry
+            .delete_schema("/schemas/user-service/openapi")
+            .await
+            .unwrap();
+        let client = Client::new(registry.clone()).with_retry(RetryConfig {
+            max_attempts: 3,
+            initial_delay: "1ms".to_string(),
+            max_delay: "1ms".to_string(),
+            multiplier: 1.0,
+        });
+
+        let err = client
+            .fetch_schema_with_retry(&manifest.schemas[0], &client.retry)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::SchemaNotFound));
+        assert_eq!(
+            registry.fetches.load(std::sync::atomic::Ordering::SeqCst),
+            1
+        );
+    }
+
+    fn instance_manifest(
+        instance_id: &str,
+        status: InstanceStatus,
+        weight: Option<i32>,
+    ) -> SchemaManifest {
+        let schema = serde_json::json!({"openapi": "3.1.0", "paths": {"/users": {"get": {}}}});
+        let mut manifest = new_manifest("user-service", "v1.0.0", instance_id);
+        manifest.instance = Some(crate::types::InstanceMetadata {
+            address: format!("{instance_id}:8080"),
+            region: None,
+            zone: None,
+            labels: None,
+            weight,
+            status,
+            role: None,
+            deployment: None,
+            started_at: 0,
+            expected_schema_checksum: None,
+        });
+        manifest.add_schema(SchemaDescriptor {
+            schema_type: SchemaType::OpenAPI,
+            schema_id: None,
+            spec_version: "3.1.0".to_string(),
+            location: crate::types::SchemaLocation {
+                location_type: LocationType::Inline,
+                url: None,
+                registry_path: None,
+                headers: None,
+            },
+            content_type: "application/json".to_string(),
+            hash: crate::manifest::calculate_schema_checksum(&schema).unwrap(),
+            inline_schema: Some(schema),
+            size: 0,
+            compatibility: None,
+            metadata: None,
+        });
+        manifest
+    }
+
+    #[tokio::test]
+    async fn test_route_policy_excludes_unhealthy() {
+        let manifests = vec![
+            instance_manifest("healthy", InstanceStatus::Healthy, Some(80)),
+            instance_manifest("degraded", InstanceStatus::Degraded, Some(80)),
+            instance_manifest("unhealthy", InstanceStatus::Unhealthy, None),
+            instance_manifest("draining", InstanceStatus::Draining, None),
+        ];
+
+        let client = Client::new(Arc::new(MemoryRegistry::new()));
+        let conversion = client.convert_to_routes_with_warnings(&manifests).await;
+        assert!(conversion.warnings.is_empty());
+        assert!(conversion.routes.iter().all(|r| r.region.is_none()));
+        let weights: Vec<Option<u32>> = conversion.routes.iter().map(|r| r.weight).collect();
+        assert_eq!(weights, vec![Some(80), Some(40)]);
+
+        let client = Client::new(Arc::new(MemoryRegistry::new())).with_route_policy(RoutePolicy {
+            allowed_statuses: vec![InstanceStatus::Healthy],
+            include_draining: true,
+            ..Default::default()
+        });
+        let routes = client.convert_to_routes(&manifests).await;
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[1].weight, None);
+
+        let client = Client::new(Arc::new(MemoryRegistry::new()))
+            .with_route_policy(RoutePolicy::allow_all());
+        assert_eq!(client.convert_to_routes(&manifests).await.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_mount_strategy_conflicts() {
+        let a = instance_manifest("a", InstanceStatus::Healthy, None);
+        let mut b = instance_manifest("b", InstanceStatus::Healthy, None);
+        b.routing.strategy = MountStrategy::Versioned;
+        let segments = |routes: &[ServiceRoute]| -> Vec<usize> {
+            routes.iter().map(|r| r.strip_segments).collect()
+        };
+        let paths = |routes: &[ServiceRoute]| -> Vec<String> {
+            routes.iter().map(|r| r.path.clone()).collect()
+        };
+
+        // Equal priority: the lowest instance ID's strategy wins
+        let client = Client::new(Arc::new(MemoryRegistry::new()));
+        let manifests = vec![a.clone(), b.clone()];
+        let conversion = client.convert_to_routes_with_warnings(&manifests).await;
+        assert_eq!(segments(&conversion.routes), vec![1, 1]);
+        assert_eq!(paths(&conversion.routes), vec!["/a/users", "/b/users"]);
+        assert_eq!(
+            conversion.warnings,
+            vec![
+                "service user-service has conflicting mount strategies (instance, versioned); \
+                 mounting every instance like a (instance)"
+            ]
+        );
+
+        // A higher-priority instance's strategy wins
+        b.routing.priority = Some(10);
+        let manifests = vec![a.clone(), b.clone()];
+        let routes = client.try_convert_to_routes(&manifests).await.unwrap();
+        assert_eq!(segments(&routes), vec![2, 2]);
+        assert_eq!(
+            paths(&routes),
+            vec!["/user-service/v1.0.0/users", "/user-service/v1.0.0/users"]
+        );
+
+        // Consistent strategies are left alone
+        let mut c = a.clone();
+        c.instance_id = "c".to_string();
+        let conversion = client
+            .convert_to_routes_with_warnings(&[a.clone(), c])
+            .await;
+        assert!(conversion.warnings.is_empty());
+
+        // Error: the service isn't routed and try_convert_to_routes fails
+        let client = Client::new(Arc::new(MemoryRegistry::new())).with_route_policy(RoutePolicy {
+            mount_conflicts: MountConflictStrategy::Error,
+            ..Default::default()
+        });
+        let conversion = client.convert_to_routes_with_warnings(&manifests).await;
+        assert!(conversion.routes.is_empty());
+        assert!(conversion.warnings[0].ends_with("not routed"));
+        let err = client.try_convert_to_routes(&manifests).await.unwrap_err();
+        assert!(matches!(err, Error::Validation { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_routes_are_mounted() {
+        let client = Client::new(Arc::new(MemoryRegistry::new()));
+        let cases = [
+            (MountStrategy::Instance, None, "/a/users", 1),
+            (MountStrategy::Service, None, "/user-service/users", 1),
+            (
+                MountStrategy::Versioned,
+                None,
+                "/user-service/v1.0.0/users",
+                2,
+            ),
+            (MountStrategy::Custom, Some("/api/v1/"), "/api/v1/users", 2),
+            (MountStrategy::Root, None, "/users", 0),
+        ];
+        for (strategy, base_path, path, strip_segments) in cases {
+            let mut manifest = instance_manifest("a", InstanceStatus::Healthy, None);
+            manifest.routing.strategy = strategy;
+            manifest.routing.base_path = base_path.map(String::from);
+            let routes = client.convert_to_routes(&[manifest]).await;
+            assert_eq!(routes[0].path, path);
+            assert_eq!(routes[0].strip_segments, strip_segments);
+
+            // A gateway forwards the request path minus the mount prefix
+            let upstream = format!("http://a:8080{}", routes[0].upstream_path(path));
+            assert_eq!(upstream, routes[0].target_url);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_routes_carry_instance_locality() {
+        let mut manifest = instance_manifest("a", InstanceStatus::Healthy, None);
+        if let Some(instance) = manifest.instance.as_mut() {
+            instance.region = Some("eu-west-1".to_string());
+            instance.zone = Some("eu-west-1a".to_string());
+        }
+
+        let client = Client::new(Arc::new(MemoryRegistry::new()));
+        let routes = client.convert_to_routes(&[manifest]).await;
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].region.as_deref(), Some("eu-west-1"));
+        assert_eq!(routes[0].zone.as_deref(), Some("eu-west-1a"));
+    }
+
+    #[test]
+    fn test_route_sensitivity() {
+        use crate::types::ServiceHints;
+
+        let client = Client::new(Arc::new(MemoryRegistry::new()));
+        let schema = serde_json::json!({"paths": {
+            "/users/{id}": {
+                "get": {"x-data-sensitivity": "pii"},
+                "delete": {"x-data-sensitivity": "internal"}
+            },
+            "/payments": {"post": {"x-data-sensitivity": "pci"}},
+            "/orders": {"get": {"x-data-sensitivity": "public"}, "post": {}},
+            "/vault": {"get": {"x-data-sensitivity": "top-secret"}},
+            "/health": {"get": {}}
+        }});
+        let mut manifest = new_manifest("user-service", "v1.0.0", "instance-1");
+        let routes = client.convert_openapi_to_routes(&manifest, &schema);
+        let sensitivity = |routes: &[ServiceRoute], path: &str| {
+            routes.iter().find(|r| r.path == path).unwrap().sensitivity
+        };
+        assert_eq!(
+            sensitivity(&routes, "/users/{id}"),
+            Some(DataSensitivity::PII)
+        );
+        assert_eq!(
+            sensitivity(&routes, "/payments"),
+            Some(DataSensitivity::PCI)
+        );
+        assert_eq!(
+            sensitivity(&routes, "/orders"),
+            Some(DataSensitivity::Public)
+        );
+        assert_eq!(sensitivity(&routes, "/health"), None);
+        // Unknown levels fail closed
+        assert_eq!(sensitivity(&routes, "/vault"), Some(DataSensitivity::PCI));
+
+        let mut audited: Vec<&str> = routes_requiring_audit(&routes)
+            .iter()
+            .map(|r| r.path.as_str())
+            .collect();
+        audited.sort();
+        assert_eq!(audited, vec!["/payments", "/users/{id}", "/vault"]);
+
+        // Each undeclared operation falls back to the service default
+        manifest.hints = Some(ServiceHints {
+            recommended_timeout: None,
+            expected_latency: None,
+            scaling: None,
+            dependencies: Vec::new(),
+            resiliency: None,
+            sensitivity: Some(DataSensitivity::PHI),
+        });
+        let routes = client.convert_openapi_to_routes(&manifest, &schema);
+        assert_eq!(
+            sensitivity(&routes, "/users/{id}"),
+            Some(DataSensitivity::PII)
+        );
+        assert_eq!(sensitivity(&routes, "/orders"), Some(DataSensitivity::PHI));
+        assert_eq!(
+            sensitivity(&routes, "/payments"),
+            Some(DataSensitivity::PCI)
+        );
+        assert_eq!(sensitivity(&routes, "/health"), Some(DataSensitivity::PHI));
+        assert_eq!(routes_requiring_audit(&routes).len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_watch_route_diffs() {
+        let registry = Arc::new(MemoryRegistry::new());
+        let instance = |id: &str, weight: Option<i32>| {
+            let mut manifest = instance_manifest(id, InstanceStatus::Healthy, weight);
+            manifest.endpoints.health = "/health".to_string();
+            manifest.update_checksum().unwrap();
+            manifest
+        };
+        registry
+            .register_manifest(&instance("a", None))
+            .await
+            .unwrap();
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let client = Client::new(registry.clone());
+        client
+            .watch_route_diffs(
+                "user-service",
+                Arc::new(move |diff: RouteDiff| {
+                    let _ = tx.send(diff);
+                }),
+            )
+            .await
+            .unwrap();
+        let initial = rx.recv().await.unwrap();
+        assert_eq!(initial.added.len(), 1);
+        assert!(initial.removed.is_empty() && initial.changed.is_empty());
+
+        // A new instance only adds its routes
+        registry
+            .register_manifest(&instance("b", None))
+            .await
+            .unwrap();
+        let diff = rx.recv().await.unwrap();
+        assert_eq!(diff.added.len(), 1);
+        assert!(diff.removed.is_empty() && diff.changed.is_empty());
+
+        // Updating an instance only changes its routes
+        registry
+            .update_manifest(&instance("a", Some(10)))
+            .await
+            .unwrap();
+        let diff = rx.recv().await.unwrap();
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert_eq!(diff.changed.len(), 1);
+        let (old, new) = &diff.changed[0];
+        assert_eq!(old.path, "/a/users");
+        assert_eq!((old.weight, new.weight), (None, Some(10)));
+    }
+
+    #[tokio::test]
+    async fn test_route_policy_zero_healthy_warning() {
+        let manifests = vec![
+            instance_manifest("a", InstanceStatus::Unhealthy, None),
+            instance_manifest("b", InstanceStatus::Stopping, None),
+        ];
+
+        let client = Client::new(Arc::new(MemoryRegistry::new()));
+        let conversion = client.convert_to_routes_with_warnings(&manifests).await;
+        assert!(conversion.routes.is_empty());
+        assert_eq!(
+            conversion.warnings,
+            vec!["service user-service has no instances eligible for routing (0 healthy)"]
+        );
+
+        // Degraded instances still serve traffic, but below the healthy minimum
+        let manifests = vec![instance_manifest("c", InstanceStatus::Degraded, None)];
+        let conversion = client.convert_to_routes_with_warnings(&manifests).await;
+        assert_eq!(conversion.routes.len(), 1);
+        assert_eq!(conversion.routes[0].weight, Some(50));
+        assert_eq!(
+            conversion.warnings,
+            vec!["service user-service has 0 healthy instances (minimum 1)"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_and_no_retry() {
+        let schema = serde_json::json!({
+            "openapi": "3.1.0",
+            "paths": {
+                "/users": {"get": {}, "post": {}},
+                "/payments": {"post": {}},
+                "/searches": {"post": {"x-farp-idempotent": true}},
+                "/users/{id}": {"put": {}}
+            }
+        });
+        let mut manifest = new_manifest("user-service", "v1.0.0", "instance-1");
+        manifest.add_schema(SchemaDescriptor {
+            schema_type: SchemaType::OpenAPI,
+            schema_id: None,
+            spec_version: "3.1.0".to_string(),
+            location: crate::types::SchemaLocation {
+                location_type: LocationType::Inline,
+                url: None,
+                registry_path: None,
+                headers: None,
+            },
+            content_type: "application/json".to_string(),
+            hash: crate::manifest::calculate_schema_checksum(&schema).unwrap(),
+            inline_schema: Some(schema),
+            size: 0,
+            compatibility: None,
+            metadata: None,
+        });
+        manifest.webhook = Some(crate::types::WebhookConfig {
+            service_webhook: None,
+            gateway_webhook: None,
+            secret: None,
+            subscribe_events: Vec::new(),
+            publish_events: Vec::new(),
+            retry: None,
+            http_routes: Some(crate::types::HTTPCommunicationRoutes {
+                service_routes: vec![crate::types::CommunicationRoute {
+                    id: "update-user".to_string(),
+                    path: "/users/{id}".to_string(),
+                    method: "PUT".to_string(),
+                    route_type: crate::types::CommunicationRouteType::Control,
+                    description: None,
+                    request_schema: None,
+                    response_schema: None,
+                    auth_required: false,
+                    idempotent: false,
+                    timeout: None,
+                }],
+                gateway_routes: Vec::new(),
+                polling: None,
+            }),
+        });
+
+        let client =
+            Client::new(Arc::new(MemoryRegistry::new())).with_idempotency(IdempotencyConfig {
+                header: "X-Request-Id".to_string(),
+                ttl: Duration::from_secs(60),
+            });
+        let routes = client.convert_to_routes(&[manifest]).await;
+        let route = |path: &str| {
+            routes
+                .iter()
+                .find(|r| r.path == format!("/instance-1{path}"))
+                .unwrap()
+        };
+
+        let users = route("/users");
+        assert!(users.retry_safe("GET"));
+        assert!(!users.retry_safe("POST"));
+        assert_eq!(users.no_retry_methods, vec!["POST"]);
+        assert_eq!(
+            users.idempotency.as_ref().map(|i| i.header.as_str()),
+            Some("X-Request-Id")
+        );
+
+        let payments = route("/payments");
+        assert_eq!(payments.idempotency, None);
+        assert_eq!(payments.no_retry_methods, vec!["POST"]);
+        assert!(!payments.retry_safe("POST"));
+
+        assert!(route("/searches").retry_safe("POST"));
+        assert!(!route("/users/{id}").retry_safe("PUT"));
+    }
+
+    #[cfg(feature = "providers-grpc")]
+    #[tokio::test]
+    async fn test_fetch_grpc_spec_falls_back_to_manifest() {
+        let schema = serde_json::json!({
+            "package": "library.v1",
+            "services": {"LibraryService": {"methods": {}}}
+        });
+        let mut manifest = new_manifest("library", "v1.0.0", "library-1");
+        manifest.endpoints.grpc_reflection = true;
+        // Nothing serves reflection at the instance address
+        let closed = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        manifest.instance = Some(crate::types::InstanceMetadata {
+            address: closed.to_string(),
+            region: None,
+            zone: None,
+            labels: None,
+            weight: None,
+            status: InstanceStatus::Healthy,
+            role: None,
+            deployment: None,
+            started_at: 0,
+            expected_schema_checksum: None,
+        });
+        manifest.add_schema(SchemaDescriptor {
+            schema_type: SchemaType::GRPC,
+            schema_id: None,
+            spec_version: "proto3".to_string(),
+            location: crate::types::SchemaLocation {
+                location_type: LocationType::Inline,
+                url: None,
+                registry_path: None,
+                headers: None,
+            },
+            content_type: "application/json".to_string(),
+            hash: crate::manifest::calculate_schema_checksum(&schema).unwrap(),
+            inline_schema: Some(schema),
+            size: 0,
+            compatibility: None,
+            metadata: None,
+        });
+
+        let client = Client::new(Arc::new(MemoryRegistry::new()));
+        let spec = client.fetch_grpc_spec(&manifest).await.unwrap();
+        assert_eq!(spec.package, "library.v1");
+        assert!(spec.services.contains_key("LibraryService"));
+
+        manifest.schemas.clear();
+        assert!(matches!(
+            client.fetch_grpc_spec(&manifest).await,
+            Err(Error::SchemaNotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_grpc_transcoding_routes() {
+        let schema = serde_json::json!({
+            "package": "library.v1",
+            "services": {
+                "LibraryService": {
+                    "methods": {
+                        "GetBook": {
+                            "input_type": "GetBookRequest",
+                            "output_type": "Book",
+                            "options": {"google.api.http": {"get": "/v1/{name=shelves/*/books/*}"}}
+                        },
+                        "CreateBook": {
+                            "input_type": "CreateBookRequest",
+                            "output_type": "Book",
+                            "options": {"google.api.http": {"post": "/v1/shelves/{shelf}/books", "body": "book"}}
+                        },
+                        "StreamBooks": {"input_type": "Empty", "output_type": "Book"}
+                    }
+                }
+            }
+        });
+        let mut manifest = new_manifest("library", "v1.0.0", "library-1");
+        manifest.add_schema(SchemaDescriptor {
+            schema_type: SchemaType::GRPC,
+            schema_id: None,
+            spec_version: "proto3".to_string(),
+            location: crate::types::SchemaLocation {
+                location_type: LocationType::Inline,
+                url: None,
+                registry_path: None,
+                headers: None,
+            },
+            content_type: "application/json".to_string(),
+            hash: crate::manifest::calculate_schema_checksum(&schema).unwrap(),
+            inline_schema: Some(schema),
+            size: 0,
+            compatibility: None,
+            metadata: None,
+        });
+
+        let client = Client::new(Arc::new(MemoryRegistry::new()));
+        let routes = client.convert_to_routes(&[manifest]).await;
+        assert_eq!(routes.len(), 2);
+
+        let get = routes.iter().find(|r| r.methods == ["GET"]).unwrap();
+        assert_eq!(get.path, "/library-1/v1/shelves/{name_1}/books/{name_2}");
+        assert_eq!(
+            get.target_url,
+            "http://library:8080/library.v1.LibraryService/GetBook"
+        );
+        assert_eq!(
+            get.metadata["grpc_transcode"],
+            serde_json::json!({"service": "library.v1.LibraryService", "method": "GetBook", "body": null})
+        );
+
+        let create = routes.iter().find(|r| r.methods == ["POST"]).unwrap();
+        assert_eq!(create.path, "/library-1/v1/shelves/{shelf}/books");
+        assert_eq!(create.metadata["grpc_transcode"]["body"], "book");
+        assert_eq!(create.metadata["schema_type"], "grpc");
+        // Instance mounts prefix one segment the gateway strips on forward
+        assert_eq!(create.strip_segments, 1);
+    }
+
+    #[test]
+    fn test_transcode_path() {
+        assert_eq!(transcode_path("/v1/{name}"), "/v1/{name}");
+        assert_eq!(transcode_path("/v1/{name=*}"), "/v1/{name}");
+        assert_eq!(
+            transcode_path("/v1/{name=shelves/*}:publish"),
+            "/v1/shelves/{name}:publish"
+        );
+        assert_eq!(
+            transcode_path("/v1/{book.name=shelves/*/books/*}"),
+            "/v1/shelves/{book.name_1}/books/{book.name_2}"
+        );
+        assert_eq!(transcode_path("/v1/{name=files/**}"), "/v1/files/{name=**}");
+    }
+
+    #[tokio::test]
+    async fn test_deprecated_routes() {
+        let schema = serde_json::json!({
+            "openapi": "3.1.0",
+            "paths": {
+                "/v1/users": {"get": {"deprecated": true}},
+                "/v1/orders": {"get": {}, "post": {"deprecated": true}},
+                "/v2/users": {"get": {}}
+            }
+        });
+        let mut manifest = new_manifest("user-service", "v1.0.0", "instance-1");
+        manifest.add_schema(SchemaDescriptor {
+            schema_type: SchemaType::OpenAPI,
+            schema_id: None,
+            spec_version: "3.1.0".to_string(),
+            location: crate::types::SchemaLocation {
+                location_type: LocationType::Inline,
+                url: None,
+                registry_path: None,
+                headers: None,
+            },
+            content_type: "application/json".to_string(),
+            hash: crate::manifest::calculate_schema_checksum(&schema).unwrap(),
+            inline_schema: Some(schema),
+            size: 0,
+            compatibility: Some(crate::types::SchemaCompatibility {
+                mode: crate::types::CompatibilityMode::Backward,
+                previous_versions: vec![],
+                breaking_changes: vec![],
+                deprecations: vec![
+                    crate::types::Deprecation {
+                        path: "#/paths/~1v1~1users/get".to_string(),
+                        deprecated_at: "2026-01-01".to_string(),
+                        removal_date: Some("2026-12-31".to_string()),
+                        replacement: Some("/v2/users".to_string()),
+                        migration: None,
+                        reason: None,
+                    },
+                    crate::types::Deprecation {
+                        path: "/v1/orders".to_string(),
+                        deprecated_at: "2026-01-01".to_string(),
+                        removal_date: None,
+                        replacement: None,
+                        migration: None,
+                        reason: None,
+                    },
+                ],
+            }),
+            metadata: None,
+        });
+
+        let client = Client::new(Arc::new(MemoryRegistry::new()));
+        let routes = client.convert_to_routes(&[manifest]).await;
+        let route = |path: &str| {
+            routes
+                .iter()
+                .find(|r| r.path == format!("/instance-1{path}"))
+                .unwrap()
+        };
+
+        assert!(route("/v1/users").deprecated);
+        assert_eq!(route("/v1/users").sunset.as_deref(), Some("2026-12-31"));
+        assert!(route("/v1/orders").deprecated);
+        assert_eq!(route("/v1/orders").sunset, None);
+        assert!(!route("/v2/users").deprecated);
+        assert!(!route("/v2/users")
+            .metadata
+            .contains_key("deprecated_methods"));
+    }
+
+    #[test]
+    fn test_partially_deprecated_route() {
+        let client = Client::new(Arc::new(MemoryRegistry::new()));
+        let schema = serde_json::json!({
+            "paths": {"/orders": {"get": {}, "post": {"deprecated": true}}}
+        });
+        let manifest = new_manifest("order-service", "v1.0.0", "instance-1");
+        let mut routes = client.convert_openapi_to_routes(&manifest, &schema);
+        let descriptor = SchemaDescriptor {
+            schema_type: SchemaType::OpenAPI,
+            schema_id: None,
+            spec_version: "3.1.0".to_string(),
+            location: crate::types::SchemaLocation {
+                location_type: LocationType::Inline,
+                url: None,
+                registry_path: None,
+                headers: None,
+            },
+            content_type: "application/json".to_string(),
+            inline_schema: None,
+            hash: String::new(),
+            size: 0,
+            compatibility: Some(crate::types::SchemaCompatibility {
+                mode: crate::types::CompatibilityMode::Backward,
+                previous_versions: vec![],
+                breaking_changes: vec![],
+                deprecations: vec![crate::types::Deprecation {
+                    path: "#/paths/~1orders/post".to_string(),
+                    deprecated_at: "2026-01-01".to_string(),
+                    removal_date: Some("2026-12-31".to_string()),
+                    replacement: None,
+                    migration: None,
+                    reason: None,
+                }],
+            }),
+            metadata: None,
+        };
+        apply_deprecations(&mut routes, &descriptor, &schema);
+
+        assert!(!routes[0].deprecated);
+        // The GET half of the route stays up, so it gets no sunset date
+        assert_eq!(routes[0].sunset, None);
+        assert_eq!(
+            routes[0].metadata["deprecated_methods"],
+            serde_json::json!(["POST"])
+        );
     }
 }
@@ -0,0 +1,150 @@
+//! Route set diffing for incremental gateway updates
+
+use super::client::ServiceRoute;
+use std::collections::BTreeMap;
+
+/// Difference between two route sets
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RouteDiff {
+    /// Routes only present in the new set
+    pub added: Vec<ServiceRoute>,
+    /// Routes only present in the old set
+    pub removed: Vec<ServiceRoute>,
+    /// `(old, new)` pairs sharing a path, methods and service but differing otherwise
+    pub changed: Vec<(ServiceRoute, ServiceRoute)>,
+}
+
+impl RouteDiff {
+    /// Returns whether the two route sets were identical
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+type RouteKey<'a> = (&'a str, &'a [String], &'a str);
+
+fn route_key(route: &ServiceRoute) -> RouteKey<'_> {
+    (&route.path, &route.methods, &route.service_name)
+}
+
+fn group(routes: &[ServiceRoute]) -> BTreeMap<RouteKey<'_>, Vec<&ServiceRoute>> {
+    let mut groups: BTreeMap<RouteKey<'_>, Vec<&ServiceRoute>> = BTreeMap::new();
+    for route in routes {
+        groups.entry(route_key(route)).or_default().push(route);
+    }
+    groups
+}
+
+/// Computes the routes added, removed and changed between `old` and `new`
+///
+/// Routes are matched on `(path, methods, service_name)`. Every instance of
+/// a service yields the same key, so within a key identical routes are
+/// matched first, then leftovers are paired by target URL and finally in
+/// order; a pair that still differs is reported as changed and any surplus
+/// as added or removed. Results are ordered by key.
+pub fn route_diff(old: &[ServiceRoute], new: &[ServiceRoute]) -> RouteDiff {
+    let mut old_groups = group(old);
+    let mut new_groups = group(new);
+    let mut diff = RouteDiff::default();
+
+    let keys: Vec<RouteKey<'_>> = {
+        let mut keys: Vec<_> = old_groups
+            .keys()
+            .chain(new_groups.keys())
+            .copied()
+            .collect();
+        keys.sort();
+        keys.dedup();
+        keys
+    };
+
+    for key in keys {
+        let mut olds = old_groups.remove(&key).unwrap_or_default();
+        let mut news = new_groups.remove(&key).unwrap_or_default();
+
+        // Unchanged routes
+        olds.retain(|o| match news.iter().position(|n| n == o) {
+            Some(i) => {
+                news.remove(i);
+                false
+            }
+            None => true,
+        });
+
+        // Same backend, different settings
+        olds.retain(
+            |o| match news.iter().position(|n| n.target_url == o.target_url) {
+                Some(i) => {
+                    diff.changed.push(((*o).clone(), news.remove(i).clone()));
+                    false
+                }
+                None => true,
+            },
+        );
+
+        let paired = olds.len().min(news.len());
+        for (o, n) in olds.drain(..paired).zip(news.drain(..paired)) {
+            diff.changed.push((o.clone(), n.clone()));
+        }
+        diff.removed.extend(olds.into_iter().cloned());
+        diff.added.extend(news.into_iter().cloned());
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(path: &str, target: &str) -> ServiceRoute {
+        ServiceRoute {
+            path: path.to_string(),
+            methods: vec!["GET".to_string()],
+            target_url: target.to_string(),
+            service_name: "user-service".to_string(),
+            service_version: "v1.0.0".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_route_diff_identical() {
+        let routes = vec![route("/users", "http://a"), route("/users", "http://b")];
+        let reordered = vec![routes[1].clone(), routes[0].clone()];
+        assert!(route_diff(&routes, &reordered).is_empty());
+    }
+
+    #[test]
+    fn test_route_diff_added_removed_changed() {
+        let old = vec![
+            route("/users", "http://a"),
+            route("/users", "http://b"),
+            route("/orders", "http://a"),
+        ];
+        let mut updated = route("/users", "http://b");
+        updated.weight = Some(10);
+        let new = vec![
+            route("/users", "http://a"),
+            updated.clone(),
+            route("/users", "http://c"),
+        ];
+
+        let diff = route_diff(&old, &new);
+        assert_eq!(diff.added, vec![route("/users", "http://c")]);
+        assert_eq!(diff.removed, vec![route("/orders", "http://a")]);
+        assert_eq!(diff.changed, vec![(route("/users", "http://b"), updated)]);
+    }
+
+    #[test]
+    fn test_route_diff_pairs_moved_backend() {
+        // The only backend for a key moved: a change, not a remove plus an add
+        let old = vec![route("/users", "http://a")];
+        let new = vec![route("/users", "http://b")];
+
+        let diff = route_diff(&old, &new);
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.changed.len(), 1);
+    }
+}
@@ -0,0 +1,424 @@
+//! Envoy route and cluster configuration export
+
+use super::{is_http_method, mount_prefix, path_regex, regex_escape, replayable, sanitize_name};
+use crate::errors::{Error, Result};
+use crate::gateway::client::ServiceRoute;
+use crate::gateway::openapi::url_origin;
+use crate::types::{QuotaConfig, ResiliencyConfig};
+use crate::util::parse_duration;
+use serde_json::{json, Map, Value};
+use std::time::Duration;
+
+/// Connect timeout of exported clusters
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Shortest token bucket `fill_interval` Envoy accepts
+const MIN_FILL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Renders routes as an Envoy v3 `RouteConfiguration` and cluster list
+///
+/// Returns `{"route_config": ..., "clusters": [...]}` in proto3 JSON, ready
+/// to serve over RDS/CDS or to embed in a static bootstrap. All routes share
+/// one `*` virtual host; the `host` metadata key, if present, becomes an
+/// `:authority` header match. Templated paths (`{id}`) match by
+/// `safe_regex`, other paths exactly, and HTTP methods by a `:method`
+/// header regex. Mounted routes (`strip_segments`) get a `regex_rewrite`
+/// that strips the mount prefix before forwarding.
+///
+/// Each distinct service name becomes a `STRICT_DNS` cluster whose endpoints
+/// are the distinct origins of its routes; `https` origins get an upstream
+/// TLS transport socket.
+///
+/// A route's [`ResiliencyConfig`] becomes its `retry_policy`. Routes whose
+/// methods are all safe to replay retry on `5xx` and resets; others only on
+/// failures where the upstream never saw the request. A circuit breaker
+/// becomes the cluster's `outlier_detection` (`consecutive_5xx`,
+/// `base_ejection_time`) and, with `max_requests`, its `circuit_breakers`
+/// thresholds. An `open_duration` that doesn't parse fails the export.
+///
+/// A [`QuotaConfig`] becomes an `envoy.filters.http.local_ratelimit`
+/// `typed_per_filter_config` on the route: a token bucket refilled with
+/// `requests` tokens every `window`, holding up to `requests + burst`. The
+/// listener's HTTP connection manager must include that filter. Local rate
+/// limits are counted per Envoy instance and shared by all callers, so
+/// per-consumer quotas are not distinguished. A quota whose window doesn't
+/// parse or is shorter than 50ms fails the export.
+pub fn to_envoy_config(routes: &[ServiceRoute]) -> Result<Value> {
+    let mut envoy_routes = Vec::new();
+    let mut clusters: Map<String, Value> = Map::new();
+
+    for (index, route) in routes.iter().enumerate() {
+        let cluster_name = sanitize_name(&route.service_name);
+        let cluster = clusters
+            .entry(cluster_name.clone())
+            .or_insert_with(|| new_cluster(&cluster_name));
+        if let Some((scheme, host, port)) = url_origin(&route.target_url)
+            .as_deref()
+            .and_then(split_origin)
+        {
+            add_endpoint(cluster, &host, port);
+            if scheme == "https" && cluster.get("transport_socket").is_none() {
+                cluster["transport_socket"] = json!({
+                    "name": "envoy.transport_sockets.tls",
+                    "typed_config": {
+                        "@type": "type.googleapis.com/envoy.extensions.transport_sockets.tls.v3.UpstreamTlsContext",
+                        "sni": host,
+                    }
+                });
+            }
+        }
+        if let Some(policy) = &route.resiliency {
+            apply_circuit_breaker(cluster, policy)?;
+        }
+
+        envoy_routes.push(envoy_route(
+            route,
+            &format!("{cluster_name}-{index}"),
+            &cluster_name,
+        )?);
+    }
+
+    Ok(json!({
+        "route_config": {
+            "name": "farp",
+            "virtual_hosts": [{
+                "name": "farp",
+                "domains": ["*"],
+                "routes": envoy_routes,
+            }]
+        },
+        "clusters": clusters.into_iter().map(|(_, c)| c).collect::<Vec<_>>(),
+    }))
+}
+
+fn new_cluster(name: &str) -> Value {
+    json!({
+        "name": name,
+        "type": "STRICT_DNS",
+        "connect_timeout": duration(CONNECT_TIMEOUT),
+        "lb_policy": "ROUND_ROBIN",
+        "load_assignment": {
+            "cluster_name": name,
+            "endpoints": [{ "lb_endpoints": [] }],
+        },
+    })
+}
+
+fn add_endpoint(cluster: &mut Value, host: &str, port: u16) {
+    let endpoint = json!({
+        "endpoint": {
+            "address": { "socket_address": { "address": host, "port_value": port } }
+        }
+    });
+    let endpoints = cluster["load_assignment"]["endpoints"][0]["lb_endpoints"]
+        .as_array_mut()
+        .expect("lb_endpoints is always an array");
+    if !endpoints.contains(&endpoint) {
+        endpoints.push(endpoint);
+    }
+}
+
+/// Builds the Envoy route object for a route
+fn envoy_route(route: &ServiceRoute, name: &str, cluster: &str) -> Result<Value> {
+    let mut route_match = if route.path.contains('{') {
+        json!({ "safe_regex": { "regex": path_regex(&route.path) } })
+    } else {
+        json!({ "path": route.path })
+    };
+
+    let mut headers = Vec::new();
+    let methods: Vec<String> = route
+        .methods
+        .iter()
+        .filter(|m| is_http_method(m))
+        .map(|m| m.to_ascii_uppercase())
+        .collect();
+    if !methods.is_empty() {
+        headers.push(json!({
+            "name": ":method",
+            "string_match": { "safe_regex": { "regex": format!("^({})$", methods.join("|")) } }
+        }));
+    }
+    if let Some(host) = route.metadata.get("host").and_then(|h| h.as_str()) {
+        headers.push(json!({
+            "name": ":authority",
+            "string_match": { "exact": host }
+        }));
+    }
+    if !headers.is_empty() {
+        route_match["headers"] = json!(headers);
+    }
+
+    let mut action = json!({ "cluster": cluster });
+    if let Some(prefix) = mount_prefix(route) {
+        action["regex_rewrite"] = json!({
+            "pattern": { "regex": format!("^{}(/|$)", regex_escape(&prefix)) },
+            "substitution": "/",
+        });
+    }
+    if let Some(timeout) = route.timeout {
+        action["timeout"] = json!(duration(timeout));
+    }
+    if let Some(policy) = route.resiliency.as_ref().filter(|p| p.retry_attempts > 0) {
+        let retry_on = if replayable(route) {
+            "5xx,reset,connect-failure,refused-stream"
+        } else {
+            "connect-failure,refused-stream"
+        };
+        let mut retry = json!({ "retry_on": retry_on, "num_retries": policy.retry_attempts });
+        if let Ok(Some(per_try)) = policy.per_try_timeout_duration() {
+            retry["per_try_timeout"] = json!(duration(per_try));
+        }
+        action["retry_policy"] = retry;
+    }
+
+    let mut envoy_route = json!({ "name": name, "match": route_match, "route": action });
+    if let Some(quota) = &route.quota {
+        envoy_route["typed_per_filter_config"] = json!({
+            "envoy.filters.http.local_ratelimit": local_rate_limit(quota)?
+        });
+    }
+    Ok(envoy_route)
+}
+
+/// Envoy `LocalRateLimit` filter config for a quota
+fn local_rate_limit(quota: &QuotaConfig) -> Result<Value> {
+    let window = quota.window_duration()?;
+    if window < MIN_FILL_INTERVAL {
+        return Err(Error::validation(
+            "quota.window",
+            "Envoy needs a window of at least 50ms",
+        ));
+    }
+    let tokens = quota.requests.max(1);
+    let enabled = json!({
+        "runtime_key": "local_rate_limit_enabled",
+        "default_value": { "numerator": 100, "denominator": "HUNDRED" },
+    });
+    Ok(json!({
+        "@type": "type.googleapis.com/envoy.extensions.filters.http.local_ratelimit.v3.LocalRateLimit",
+        "stat_prefix": "farp_quota",
+        "token_bucket": {
+            "max_tokens": tokens.saturating_add(quota.burst.unwrap_or(0)),
+            "tokens_per_fill": tokens,
+            "fill_interval": duration(window),
+        },
+        "filter_enabled": enabled,
+        "filter_enforced": enabled,
+    }))
+}
+
+/// Adds outlier detection and request thresholds for a circuit breaker
+fn apply_circuit_breaker(cluster: &mut Value, policy: &ResiliencyConfig) -> Result<()> {
+    let Some(breaker) = &policy.circuit_breaker else {
+        return Ok(());
+    };
+    if cluster.get("outlier_detection").is_some() {
+        return Ok(());
+    }
+    let open = parse_duration(&breaker.open_duration)?;
+    cluster["outlier_detection"] = json!({
+        "consecutive_5xx": breaker.consecutive_failures,
+        "base_ejection_time": duration(open),
+    });
+    if let Some(max_requests) = breaker.max_requests {
+        cluster["circuit_breakers"] = json!({
+            "thresholds": [{ "max_requests": max_requests, "max_retries": policy.retry_attempts.max(1) }]
+        });
+    }
+    Ok(())
+}
+
+/// Splits `scheme://host[:port]` into scheme, host and port
+///
+/// IPv6 hosts (`[::1]`) are returned without their brackets.
+fn split_origin(origin: &str) -> Option<(&str, String, u16)> {
+    let (scheme, authority) = origin.split_once("://")?;
+    let default_port = if scheme == "https" { 443 } else { 80 };
+    let (host, port) = match authority.strip_prefix('[') {
+        Some(rest) => {
+            let (host, rest) = rest.split_once(']')?;
+            (host, rest.strip_prefix(':'))
+        }
+        None => match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        },
+    };
+    let port = match port {
+        Some(port) => port.parse().ok()?,
+        None => default_port,
+    };
+    Some((scheme, host.to_string(), port))
+}
+
+/// Formats a duration as a proto3 JSON `Duration` (`"1.500s"`)
+fn duration(d: Duration) -> String {
+    let millis = d.as_millis();
+    if millis % 1000 == 0 {
+        format!("{}s", millis / 1000)
+    } else {
+        format!("{}.{:03}s", millis / 1000, millis % 1000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gateway::export::test_route as route;
+    use crate::types::{CircuitBreakerConfig, QuotaScope};
+
+    #[test]
+    fn test_to_envoy_config() {
+        let policy = ResiliencyConfig {
+            retry_attempts: 3,
+            per_try_timeout: Some("250ms".to_string()),
+            circuit_breaker: Some(CircuitBreakerConfig {
+                consecutive_failures: 7,
+                open_duration: "1m".to_string(),
+                max_requests: Some(100),
+            }),
+        };
+        let mut users = route("/users", &["GET"], "https://users:8443", "user-service");
+        users.resiliency = Some(policy.clone());
+        users.timeout = Some(Duration::from_millis(1500));
+        let mut create = route(
+            "/users/{id}",
+            &["PUT", "POST"],
+            "http://users-b",
+            "user-service",
+        );
+        create.resiliency = Some(policy);
+        create
+            .metadata
+            .insert("host".to_string(), "api.example.com".into());
+
+        let config = to_envoy_config(&[users, create]).unwrap();
+        let routes = &config["route_config"]["virtual_hosts"][0]["routes"];
+        assert_eq!(
+            routes[0],
+            json!({
+                "name": "user-service-0",
+                "match": {
+                    "path": "/users",
+                    "headers": [{
+                        "name": ":method",
+                        "string_match": {"safe_regex": {"regex": "^(GET)$"}}
+                    }]
+                },
+                "route": {
+                    "cluster": "user-service",
+                    "timeout": "1.500s",
+                    "retry_policy": {
+                        "retry_on": "5xx,reset,connect-failure,refused-stream",
+                        "num_retries": 3,
+                        "per_try_timeout": "0.250s"
+                    }
+                }
+            })
+        );
+        assert_eq!(routes[1]["match"]["safe_regex"]["regex"], "^/users/[^/]+$");
+        assert_eq!(routes[1]["match"]["headers"][1]["name"], ":authority");
+        assert_eq!(
+            routes[1]["route"]["retry_policy"]["retry_on"],
+            "connect-failure,refused-stream"
+        );
+
+        let cluster = &config["clusters"][0];
+        assert_eq!(cluster["name"], "user-service");
+        assert_eq!(
+            cluster["outlier_detection"],
+            json!({"consecutive_5xx": 7, "base_ejection_time": "60s"})
+        );
+        assert_eq!(
+            cluster["circuit_breakers"]["thresholds"][0]["max_requests"],
+            100
+        );
+        let endpoints = cluster["load_assignment"]["endpoints"][0]["lb_endpoints"]
+            .as_array()
+            .unwrap();
+        assert_eq!(endpoints.len(), 2);
+        assert_eq!(
+            endpoints[1]["endpoint"]["address"]["socket_address"],
+            json!({"address": "users-b", "port_value": 80})
+        );
+        assert_eq!(cluster["transport_socket"]["typed_config"]["sni"], "users");
+
+        let mut invalid = route("/users", &["GET"], "http://users", "user-service");
+        invalid.resiliency = Some(ResiliencyConfig {
+            retry_attempts: 0,
+            per_try_timeout: None,
+            circuit_breaker: Some(CircuitBreakerConfig {
+                consecutive_failures: 5,
+                open_duration: "a while".to_string(),
+                max_requests: None,
+            }),
+        });
+        assert!(to_envoy_config(&[invalid]).is_err());
+    }
+
+    #[test]
+    fn test_mounted_route_strips_prefix() {
+        let mut user = route("/instance-1/users/{id}", &["GET"], "http://a", "a");
+        user.strip_segments = 1;
+
+        let config = to_envoy_config(&[user]).unwrap();
+        let route = &config["route_config"]["virtual_hosts"][0]["routes"][0];
+        assert_eq!(
+            route["match"]["safe_regex"]["regex"],
+            "^/instance-1/users/[^/]+$"
+        );
+        assert_eq!(
+            route["route"]["regex_rewrite"],
+            json!({"pattern": {"regex": "^/instance-1(/|$)"}, "substitution": "/"})
+        );
+    }
+
+    #[test]
+    fn test_quota_becomes_local_rate_limit() {
+        let mut users = route("/users", &["GET"], "http://users", "user-service");
+        users.quota = Some(QuotaConfig {
+            requests: 100,
+            window: "1m".to_string(),
+            burst: Some(20),
+            scope: QuotaScope::Global,
+        });
+        let unlimited = route("/health", &["GET"], "http://users", "user-service");
+
+        let config = to_envoy_config(&[users.clone(), unlimited]).unwrap();
+        let routes = &config["route_config"]["virtual_hosts"][0]["routes"];
+        let limit = &routes[0]["typed_per_filter_config"]["envoy.filters.http.local_ratelimit"];
+        assert_eq!(
+            limit["token_bucket"],
+            json!({"max_tokens": 120, "tokens_per_fill": 100, "fill_interval": "60s"})
+        );
+        assert_eq!(limit["filter_enforced"]["default_value"]["numerator"], 100);
+        assert!(routes[1].get("typed_per_filter_config").is_none());
+
+        for window in ["soon", "0s", "10ms"] {
+            users.quota.as_mut().unwrap().window = window.to_string();
+            assert!(to_envoy_config(&[users.clone()]).is_err(), "{window}");
+        }
+    }
+
+    #[test]
+    fn test_split_origin() {
+        assert_eq!(
+            split_origin("http://users:8080"),
+            Some(("http", "users".to_string(), 8080))
+        );
+        assert_eq!(
+            split_origin("https://users"),
+            Some(("https", "users".to_string(), 443))
+        );
+        assert_eq!(
+            split_origin("http://[::1]:8080"),
+            Some(("http", "::1".to_string(), 8080))
+        );
+        assert_eq!(
+            split_origin("http://[fd00::2]"),
+            Some(("http", "fd00::2".to_string(), 80))
+        );
+        assert_eq!(split_origin("http://users:port"), None);
+    }
+}
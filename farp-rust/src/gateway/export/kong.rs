@@ -0,0 +1,421 @@
+//! Kong declarative configuration export
+
+use super::{is_http_method, mount_prefix, path_regex, regex_escape, sanitize_name};
+use crate::errors::Result;
+use crate::gateway::client::ServiceRoute;
+use crate::gateway::openapi::url_origin;
+use crate::types::{CircuitBreakerConfig, QuotaConfig, QuotaScope};
+use crate::util::parse_duration;
+use serde_json::{json, Map, Value};
+
+/// Kong rate-limiting windows, shortest first
+const RATE_LIMIT_UNITS: [(&str, u64); 4] = [
+    ("second", 1),
+    ("minute", 60),
+    ("hour", 3_600),
+    ("day", 86_400),
+];
+
+/// Renders routes as a Kong declarative configuration (`_format_version` 3.0)
+///
+/// Each distinct service name becomes one Kong service pointing at a
+/// `<service>.upstream` upstream whose targets are the distinct
+/// `host:port` origins of the service's routes. Routes are named
+/// `<service>-<index>`; templated paths (`{id}`) become `~`-prefixed regex
+/// paths, other paths match by prefix. The `host` metadata key, if present,
+/// restricts the route to that host. Mounted routes (`strip_segments`) match
+/// by a regex capturing the path after the mount prefix, and a
+/// `request-transformer` plugin forwards only that part.
+///
+/// The longest route timeout becomes the service's read and write timeout.
+/// Sticky routes make the upstream hash on their affinity cookie (or
+/// `hash_key` header).
+///
+/// A [`QuotaConfig`] becomes a service-level `rate-limiting` plugin. Kong
+/// counts per fixed second/minute/hour/day, so other windows are rescaled to
+/// the smallest unit covering them (100 per 30s becomes 200 per minute).
+/// Kong has no burst allowance, so `burst` is not exported. Per-consumer
+/// quotas limit by consumer (falling back to client IP for anonymous
+/// callers); global quotas limit by service. A quota whose window doesn't
+/// parse fails the export.
+///
+/// A route's [`ResiliencyConfig`](crate::types::ResiliencyConfig) sets the
+/// service's `retries` (Kong retries connection failures only), and its
+/// circuit breaker sets passive upstream health checks that mark a target
+/// unhealthy after `consecutive_failures` 5xx responses or timeouts. An
+/// unhealthy target gets no traffic for passive checks to see recover, so
+/// the route's health URL and
+/// [`HealthCheckConfig`](crate::types::HealthCheckConfig) also become an active
+/// check that marks it healthy again. A health check `interval` or
+/// `timeout` that doesn't parse fails the export.
+///
+/// Route middleware is not exported, as Kong plugin configuration depends on
+/// the deployment.
+pub fn to_declarative_config(routes: &[ServiceRoute]) -> Result<Value> {
+    let mut services: Map<String, Value> = Map::new();
+    let mut upstreams: Map<String, Value> = Map::new();
+
+    for (index, route) in routes.iter().enumerate() {
+        let service_name = sanitize_name(&route.service_name);
+        let upstream_name = format!("{service_name}.upstream");
+
+        let upstream = upstreams
+            .entry(upstream_name.clone())
+            .or_insert_with(|| json!({ "name": upstream_name, "targets": [] }));
+        let origin = url_origin(&route.target_url);
+        if let Some((_, target)) = origin.as_deref().and_then(split_origin) {
+            let targets = upstream["targets"]
+                .as_array_mut()
+                .expect("targets is always an array");
+            if !targets.iter().any(|t| t["target"] == target.as_str()) {
+                targets.push(json!({ "target": target }));
+            }
+        }
+        if let Some(breaker) = route
+            .resiliency
+            .as_ref()
+            .and_then(|p| p.circuit_breaker.as_ref())
+        {
+            if upstream.get("healthchecks").is_none() {
+                upstream["healthchecks"] = healthchecks(route, breaker)?;
+            }
+        }
+        if let Some(sticky) = &route.sticky {
+            match &sticky.hash_key {
+                Some(header) => {
+                    upstream["hash_on"] = json!("header");
+                    upstream["hash_on_header"] = json!(header);
+                }
+                None => {
+                    upstream["hash_on"] = json!("cookie");
+                    upstream["hash_on_cookie"] = json!(sticky.cookie_name);
+                }
+            }
+        }
+
+        let service = services.entry(service_name.clone()).or_insert_with(|| {
+            let protocol = origin
+                .as_deref()
+                .and_then(split_origin)
+                .map_or("http", |(scheme, _)| scheme);
+            json!({
+                "name": service_name,
+                "protocol": protocol,
+                "host": upstream_name,
+                "routes": [],
+            })
+        });
+        if let Some(timeout) = route.timeout {
+            let millis = timeout.as_millis() as u64;
+            let current = service["read_timeout"].as_u64().unwrap_or(0);
+            if millis > current {
+                service["read_timeout"] = json!(millis);
+                service["write_timeout"] = json!(millis);
+            }
+        }
+        if let Some(policy) = &route.resiliency {
+            if service.get("retries").is_none() {
+                service["retries"] = json!(policy.retry_attempts);
+            }
+        }
+        if let Some(quota) = &route.quota {
+            let plugin = rate_limiting(quota)?;
+            if service.get("plugins").is_none() {
+                service["plugins"] = json!([plugin]);
+            }
+        }
+
+        service["routes"]
+            .as_array_mut()
+            .expect("routes is always an array")
+            .push(kong_route(route, &format!("{service_name}-{index}")));
+    }
+
+    Ok(json!({
+        "_format_version": "3.0",
+        "services": services.into_iter().map(|(_, s)| s).collect::<Vec<_>>(),
+        "upstreams": upstreams.into_iter().map(|(_, u)| u).collect::<Vec<_>>(),
+    }))
+}
+
+/// Builds the Kong route object for a route
+fn kong_route(route: &ServiceRoute, name: &str) -> Value {
+    if let Some(prefix) = mount_prefix(route) {
+        let rest = path_regex(&route.path[prefix.len()..]);
+        let rest = rest.trim_start_matches('^').trim_end_matches('$');
+        let mut kong = plain_kong_route(
+            route,
+            name,
+            format!("~^{}(?<upstream>{rest})$", regex_escape(&prefix)),
+        );
+        kong["plugins"] = json!([{
+            "name": "request-transformer",
+            "config": { "replace": { "uri": "$(uri_captures.upstream)" } },
+        }]);
+        return kong;
+    }
+
+    let path = if route.path.contains('{') {
+        format!("~{}", path_regex(&route.path))
+    } else {
+        route.path.clone()
+    };
+    plain_kong_route(route, name, path)
+}
+
+/// Kong route object matching `path`, without path rewriting
+fn plain_kong_route(route: &ServiceRoute, name: &str, path: String) -> Value {
+    let mut kong = json!({
+        "name": name,
+        "paths": [path],
+        "strip_path": false,
+    });
+
+    let methods: Vec<String> = route
+        .methods
+        .iter()
+        .filter(|m| is_http_method(m))
+        .map(|m| m.to_ascii_uppercase())
+        .collect();
+    if !methods.is_empty() {
+        kong["methods"] = json!(methods);
+    }
+    if let Some(host) = route.metadata.get("host").and_then(|h| h.as_str()) {
+        kong["hosts"] = json!([host]);
+    }
+    kong
+}
+
+/// Upstream health checks for a circuit breaker
+///
+/// Passive checks trip the breaker; active probes of the route's health
+/// URL bring tripped targets back.
+fn healthchecks(route: &ServiceRoute, breaker: &CircuitBreakerConfig) -> Result<Value> {
+    let check = &route.health_check;
+    let seconds = |d: &str| parse_duration(d).map(|d| d.as_secs().max(1));
+    let interval = seconds(&check.interval)?;
+    let timeout = seconds(&check.timeout)?;
+    let (scheme, path) = match route.health_url.split_once("://") {
+        Some((scheme, rest)) => (scheme, rest.find('/').map_or("/", |i| &rest[i..])),
+        None => ("http", "/"),
+    };
+
+    Ok(json!({
+        "active": {
+            "type": scheme,
+            "http_path": path,
+            "timeout": timeout,
+            "healthy": {
+                "interval": interval,
+                "successes": check.healthy_threshold,
+                "http_statuses": check.expected_statuses,
+            },
+            "unhealthy": {
+                "interval": interval,
+                "http_failures": check.unhealthy_threshold,
+                "timeouts": check.unhealthy_threshold,
+            },
+        },
+        "passive": {
+            "healthy": { "successes": check.healthy_threshold },
+            "unhealthy": {
+                "http_failures": breaker.consecutive_failures,
+                "timeouts": breaker.consecutive_failures,
+            },
+        },
+    }))
+}
+
+/// Kong `rate-limiting` plugin for a quota
+fn rate_limiting(quota: &QuotaConfig) -> Result<Value> {
+    let window = quota.window_duration()?.as_millis().max(1);
+    let (unit, seconds) = RATE_LIMIT_UNITS
+        .iter()
+        .find(|(_, seconds)| u128::from(*seconds) * 1000 >= window)
+        .unwrap_or(&RATE_LIMIT_UNITS[RATE_LIMIT_UNITS.len() - 1]);
+    let limit = (u128::from(quota.requests) * u128::from(*seconds) * 1000 / window).max(1);
+
+    let mut config = Map::new();
+    config.insert(unit.to_string(), json!(limit as u64));
+    let limit_by = match quota.scope {
+        QuotaScope::Consumer => "consumer",
+        QuotaScope::Global => "service",
+    };
+    config.insert("limit_by".to_string(), json!(limit_by));
+    config.insert("policy".to_string(), json!("local"));
+
+    Ok(json!({ "name": "rate-limiting", "config": config }))
+}
+
+/// Splits `scheme://host[:port]` into the scheme and a `host:port` target
+fn split_origin(origin: &str) -> Option<(&str, String)> {
+    let (scheme, authority) = origin.split_once("://")?;
+    let has_port = authority
+        .rsplit_once(':')
+        .is_some_and(|(_, port)| !port.is_empty() && port.chars().all(|c| c.is_ascii_digit()));
+    let target = if has_port {
+        authority.to_string()
+    } else {
+        let port = if scheme == "https" { 443 } else { 80 };
+        format!("{authority}:{port}")
+    };
+    Some((scheme, target))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gateway::export::test_route as route;
+    use crate::types::StickyConfig;
+    use std::time::Duration;
+
+    #[test]
+    fn test_to_declarative_config() {
+        let quota = QuotaConfig {
+            requests: 100,
+            window: "30s".to_string(),
+            burst: Some(20),
+            scope: QuotaScope::Consumer,
+        };
+        let mut users = route(
+            "/users",
+            &["GET", "POST"],
+            "http://users-a:8080",
+            "user-service",
+        );
+        users.quota = Some(quota.clone());
+        users.timeout = Some(Duration::from_secs(5));
+        users
+            .metadata
+            .insert("host".to_string(), "api.example.com".into());
+        let mut user = route("/users/{id}", &["GET"], "https://users-b", "user-service");
+        user.quota = Some(quota);
+        let mut events = route(
+            "/events",
+            &["WEBSOCKET"],
+            "http://events:9000",
+            "event-service",
+        );
+        events.sticky = Some(StickyConfig::default());
+        events.quota = Some(QuotaConfig {
+            requests: 10,
+            window: "1s".to_string(),
+            burst: None,
+            scope: QuotaScope::Global,
+        });
+
+        let config = to_declarative_config(&[users, user, events]).unwrap();
+        assert_eq!(config["_format_version"], "3.0");
+
+        let services = config["services"].as_array().unwrap();
+        assert_eq!(services.len(), 2);
+        let users = &services[1];
+        assert_eq!(users["name"], "user-service");
+        assert_eq!(users["host"], "user-service.upstream");
+        assert_eq!(users["read_timeout"], 5000);
+        assert_eq!(
+            users["plugins"],
+            json!([{
+                "name": "rate-limiting",
+                "config": {"minute": 200, "limit_by": "consumer", "policy": "local"}
+            }])
+        );
+        assert_eq!(
+            users["routes"],
+            json!([
+                {
+                    "name": "user-service-0",
+                    "paths": ["/users"],
+                    "methods": ["GET", "POST"],
+                    "hosts": ["api.example.com"],
+                    "strip_path": false
+                },
+                {
+                    "name": "user-service-1",
+                    "paths": ["~^/users/[^/]+$"],
+                    "methods": ["GET"],
+                    "strip_path": false
+                }
+            ])
+        );
+
+        let events = &services[0];
+        assert_eq!(
+            events["plugins"][0]["config"],
+            json!({"second": 10, "limit_by": "service", "policy": "local"})
+        );
+        assert!(events["routes"][0].get("methods").is_none());
+
+        let mut invalid = route("/users", &["GET"], "http://users-a:8080", "user-service");
+        invalid.quota = Some(QuotaConfig {
+            requests: 100,
+            window: "soon".to_string(),
+            burst: None,
+            scope: QuotaScope::Consumer,
+        });
+        assert!(to_declarative_config(&[invalid]).is_err());
+
+        let upstreams = config["upstreams"].as_array().unwrap();
+        assert_eq!(upstreams[0]["hash_on"], "cookie");
+        assert_eq!(
+            upstreams[1]["targets"],
+            json!([{"target": "users-a:8080"}, {"target": "users-b:443"}])
+        );
+    }
+
+    #[test]
+    fn test_mounted_route_strips_prefix() {
+        let mut user = route("/user-service/v1.0.0/users/{id}", &["GET"], "http://a", "a");
+        user.strip_segments = 2;
+
+        let config = to_declarative_config(&[user]).unwrap();
+        let route = &config["services"][0]["routes"][0];
+        assert_eq!(
+            route["paths"],
+            json!(["~^/user-service/v1\\.0\\.0(?<upstream>/users/[^/]+)$"])
+        );
+        assert_eq!(
+            route["plugins"][0]["config"]["replace"]["uri"],
+            "$(uri_captures.upstream)"
+        );
+    }
+
+    #[test]
+    fn test_circuit_breaker_healthchecks() {
+        let mut user = route("/users", &["GET"], "http://users-a:8080", "user-service");
+        user.resiliency = Some(crate::types::ResiliencyConfig {
+            retry_attempts: 2,
+            per_try_timeout: None,
+            circuit_breaker: Some(CircuitBreakerConfig {
+                consecutive_failures: 5,
+                open_duration: "30s".to_string(),
+                max_requests: None,
+            }),
+        });
+
+        let config = to_declarative_config(&[user.clone()]).unwrap();
+        assert_eq!(
+            config["upstreams"][0]["healthchecks"],
+            json!({
+                "active": {
+                    "type": "http",
+                    "http_path": "/health",
+                    "timeout": 2,
+                    "healthy": {"interval": 10, "successes": 2, "http_statuses": [200]},
+                    "unhealthy": {"interval": 10, "http_failures": 3, "timeouts": 3}
+                },
+                "passive": {
+                    "healthy": {"successes": 2},
+                    "unhealthy": {"http_failures": 5, "timeouts": 5}
+                }
+            })
+        );
+
+        let mut invalid = user.clone();
+        invalid.health_check.interval = "often".to_string();
+        assert!(to_declarative_config(&[invalid]).is_err());
+        let mut invalid = user;
+        invalid.health_check.timeout = "soon".to_string();
+        assert!(to_declarative_config(&[invalid]).is_err());
+    }
+}
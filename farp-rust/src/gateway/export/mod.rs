@@ -0,0 +1,130 @@
+//! Gateway configuration exporters
+//!
+//! Each submodule renders [`ServiceRoute`](super::ServiceRoute)s into the
+//! native configuration format of a specific gateway.
+
+pub mod envoy;
+pub mod kong;
+pub mod traefik;
+
+use super::ServiceRoute;
+
+/// Converts an OpenAPI path template into an anchored regular expression
+///
+/// A catch-all `{name=**}` segment matches the rest of the path.
+fn path_regex(path: &str) -> String {
+    let segments: Vec<String> = path
+        .split('/')
+        .map(|segment| {
+            if segment.starts_with('{') && segment.ends_with("=**}") {
+                ".+".to_string()
+            } else if segment.starts_with('{') && segment.ends_with('}') {
+                "[^/]+".to_string()
+            } else {
+                regex_escape(segment)
+            }
+        })
+        .collect();
+    format!("^{}$", segments.join("/"))
+}
+
+/// The leading path segments a gateway strips before forwarding the route
+///
+/// `None` for routes without a mount prefix (`strip_segments` of 0).
+fn mount_prefix(route: &ServiceRoute) -> Option<String> {
+    if route.strip_segments == 0 {
+        return None;
+    }
+    let segments: Vec<&str> = route
+        .path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .take(route.strip_segments)
+        .collect();
+    Some(format!("/{}", segments.join("/")))
+}
+
+fn regex_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if "\\.+*?()|[]{}^$".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn is_http_method(method: &str) -> bool {
+    matches!(
+        method.to_ascii_uppercase().as_str(),
+        "GET" | "POST" | "PUT" | "DELETE" | "PATCH" | "OPTIONS" | "HEAD" | "TRACE"
+    )
+}
+
+/// Whether every method of the route may be replayed after the upstream saw it
+///
+/// Methods are replayable when HTTP defines them as idempotent or the route
+/// is [`retry_safe`](ServiceRoute::retry_safe) for them, unless listed in
+/// `no_retry_methods`.
+fn replayable(route: &ServiceRoute) -> bool {
+    route.methods.iter().all(|method| {
+        let idempotent = matches!(
+            method.to_ascii_uppercase().as_str(),
+            "GET" | "HEAD" | "OPTIONS" | "PUT" | "DELETE" | "TRACE"
+        ) && !route
+            .no_retry_methods
+            .iter()
+            .any(|m| m.eq_ignore_ascii_case(method));
+        idempotent || route.retry_safe(method)
+    })
+}
+
+/// Restricts a name to the characters gateways accept in object names
+fn sanitize_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Route fixture shared by the exporter tests
+#[cfg(test)]
+fn test_route(path: &str, methods: &[&str], target: &str, service: &str) -> ServiceRoute {
+    ServiceRoute {
+        path: path.to_string(),
+        methods: methods.iter().map(|m| m.to_string()).collect(),
+        target_url: format!("{target}{path}"),
+        health_url: format!("{target}/health"),
+        service_name: service.to_string(),
+        service_version: "v1.0.0".to_string(),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mount_prefix() {
+        let mut route = test_route("/instance-1/users/{id}", &["GET"], "http://a", "a");
+        assert_eq!(mount_prefix(&route), None);
+        route.strip_segments = 1;
+        assert_eq!(mount_prefix(&route).as_deref(), Some("/instance-1"));
+        route.strip_segments = 2;
+        assert_eq!(mount_prefix(&route).as_deref(), Some("/instance-1/users"));
+    }
+
+    #[test]
+    fn test_path_regex() {
+        assert_eq!(path_regex("/users/{id}"), "^/users/[^/]+$");
+        assert_eq!(path_regex("/v1/files/{name=**}"), "^/v1/files/.+$");
+        assert_eq!(path_regex("/v1.0/users"), "^/v1\\.0/users$");
+    }
+}
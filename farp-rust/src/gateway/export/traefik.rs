@@ -0,0 +1,285 @@
+//! Traefik dynamic configuration export
+
+use super::{is_http_method, mount_prefix, path_regex, sanitize_name};
+use crate::errors::Result;
+use crate::gateway::client::ServiceRoute;
+use crate::gateway::openapi::url_origin;
+use crate::types::{QuotaConfig, QuotaScope, StickyConfig};
+use crate::util::parse_duration;
+use serde_json::{json, Map, Value};
+
+/// Renders routes as a Traefik dynamic configuration (file provider format)
+///
+/// Produces one `http.routers` entry per route, named
+/// `<service>-<index>`, with a rule combining the `host` metadata key (if
+/// any), the route path and its HTTP methods. Templated path segments
+/// (`{id}`) become `PathRegexp` matches; other paths use `PathPrefix`.
+///
+/// Routes of the same service share one `http.services` load balancer whose
+/// servers are the distinct origins of their target URLs. Sticky routes get
+/// a separate `<service>-sticky` load balancer with cookie affinity, since
+/// Traefik configures session affinity on the service rather than the
+/// router. Header-hash affinity (`hash_key`) has no Traefik equivalent and
+/// falls back to the cookie. A sticky `ttl` that doesn't parse fails the
+/// export.
+///
+/// Route middleware names are sanitized (`auth:jwt` becomes `auth-jwt`) and
+/// referenced from the router. Their definitions depend on the deployment
+/// and must come from another provider file; Traefik rejects middlewares
+/// without a configuration, so `http.middlewares` only holds the ones
+/// generated below.
+///
+/// Mounted routes (`strip_segments`) get a `stripPrefix` middleware per
+/// mount prefix, named `<service>-strip-<prefix>`, so the backend sees its
+/// own paths.
+///
+/// A route's [`QuotaConfig`] becomes a `<service>-quota` `rateLimit`
+/// middleware. Traefik counts per client IP by default, which stands in for
+/// per-consumer quotas; global quotas share one bucket per request host. A
+/// quota whose window doesn't parse fails the export.
+///
+/// A route's [`ResiliencyConfig`](crate::types::ResiliencyConfig) with
+/// retries becomes a `<service>-retry` middleware. Traefik only retries
+/// requests the upstream never answered, so this is safe for any method.
+/// Its circuit breaker works on error ratios rather than consecutive
+/// failures and is not exported.
+pub fn to_dynamic_config(routes: &[ServiceRoute]) -> Result<Value> {
+    let mut routers = Map::new();
+    let mut services: Map<String, Value> = Map::new();
+    let mut middlewares = Map::new();
+
+    for (index, route) in routes.iter().enumerate() {
+        let service_base = sanitize_name(&route.service_name);
+        let service_name = match route.sticky {
+            Some(_) => format!("{service_base}-sticky"),
+            None => service_base.clone(),
+        };
+
+        let sticky = route.sticky.as_ref().map(sticky_cookie).transpose()?;
+        let service = services.entry(service_name.clone()).or_insert_with(|| {
+            let mut load_balancer = json!({ "servers": [] });
+            if let Some(sticky) = sticky {
+                load_balancer["sticky"] = sticky;
+            }
+            json!({ "loadBalancer": load_balancer })
+        });
+        if let Some(origin) = url_origin(&route.target_url) {
+            let servers = service["loadBalancer"]["servers"]
+                .as_array_mut()
+                .expect("servers is always an array");
+            if !servers.iter().any(|s| s["url"] == origin.as_str()) {
+                servers.push(json!({ "url": origin }));
+            }
+        }
+
+        let mut names: Vec<String> = route.middleware.iter().map(|m| sanitize_name(m)).collect();
+        if let Some(prefix) = mount_prefix(route) {
+            let name = format!("{service_base}-strip{}", sanitize_name(&prefix));
+            middlewares
+                .entry(name.clone())
+                .or_insert_with(|| json!({ "stripPrefix": { "prefixes": [prefix] } }));
+            names.push(name);
+        }
+        if let Some(quota) = &route.quota {
+            let name = format!("{service_base}-quota");
+            let limit = rate_limit(quota)?;
+            middlewares.entry(name.clone()).or_insert(limit);
+            names.push(name);
+        }
+        if let Some(policy) = route.resiliency.as_ref().filter(|p| p.retry_attempts > 0) {
+            let name = format!("{service_base}-retry");
+            middlewares
+                .entry(name.clone())
+                .or_insert_with(|| json!({ "retry": { "attempts": policy.retry_attempts + 1 } }));
+            names.push(name);
+        }
+
+        let mut router = json!({
+            "rule": router_rule(route),
+            "service": service_name,
+        });
+        if !names.is_empty() {
+            router["middlewares"] = json!(names);
+        }
+        routers.insert(format!("{service_base}-{index}"), router);
+    }
+
+    Ok(json!({
+        "http": {
+            "routers": routers,
+            "services": services,
+            "middlewares": middlewares,
+        }
+    }))
+}
+
+/// Builds the router rule for a route
+fn router_rule(route: &ServiceRoute) -> String {
+    let mut matchers = Vec::new();
+
+    if let Some(host) = route.metadata.get("host").and_then(|h| h.as_str()) {
+        matchers.push(format!("Host(`{host}`)"));
+    }
+
+    if route.path.contains('{') {
+        matchers.push(format!("PathRegexp(`{}`)", path_regex(&route.path)));
+    } else {
+        matchers.push(format!("PathPrefix(`{}`)", route.path));
+    }
+
+    let methods: Vec<String> = route
+        .methods
+        .iter()
+        .filter(|m| is_http_method(m))
+        .map(|m| format!("Method(`{}`)", m.to_ascii_uppercase()))
+        .collect();
+    match methods.len() {
+        0 => {}
+        1 => matchers.extend(methods),
+        _ => matchers.push(format!("({})", methods.join(" || "))),
+    }
+
+    matchers.join(" && ")
+}
+
+/// Traefik cookie affinity for a sticky config
+fn sticky_cookie(sticky: &StickyConfig) -> Result<Value> {
+    let ttl = parse_duration(&sticky.ttl)?;
+    Ok(json!({
+        "cookie": { "name": sticky.cookie_name, "httpOnly": true, "maxAge": ttl.as_secs() }
+    }))
+}
+
+/// Traefik rate limit middleware for a quota
+///
+/// The period is written in Go duration syntax, which Traefik parses.
+fn rate_limit(quota: &QuotaConfig) -> Result<Value> {
+    let window = quota.window_duration()?;
+    let period = if window.subsec_millis() == 0 {
+        format!("{}s", window.as_secs())
+    } else {
+        format!("{}ms", window.as_millis().max(1))
+    };
+    let mut limit = json!({ "average": quota.requests, "period": period });
+    if let Some(burst) = quota.burst {
+        limit["burst"] = json!(burst);
+    }
+    if quota.scope == QuotaScope::Global {
+        limit["sourceCriterion"] = json!({ "requestHost": true });
+    }
+    Ok(json!({ "rateLimit": limit }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gateway::export::test_route as route;
+
+    #[test]
+    fn test_to_dynamic_config() {
+        let mut users = route(
+            "/users",
+            &["GET", "POST"],
+            "http://users-a:8080",
+            "user-service",
+        );
+        users.middleware = vec!["auth:jwt".to_string()];
+        users.quota = Some(QuotaConfig {
+            requests: 100,
+            window: "1m".to_string(),
+            burst: Some(20),
+            scope: QuotaScope::Consumer,
+        });
+        users
+            .metadata
+            .insert("host".to_string(), "api.example.com".into());
+        let user = route(
+            "/users/{id}",
+            &["GET"],
+            "http://users-b:8080",
+            "user-service",
+        );
+        let mut events = route(
+            "/events",
+            &["WEBSOCKET"],
+            "http://events:9000",
+            "event-service",
+        );
+        events.sticky = Some(StickyConfig::default());
+
+        let config = to_dynamic_config(&[users, user, events]).unwrap();
+        let http = &config["http"];
+
+        let routers = http["routers"].as_object().unwrap();
+        assert_eq!(routers.len(), 3);
+        assert_eq!(
+            routers["user-service-0"]["rule"],
+            "Host(`api.example.com`) && PathPrefix(`/users`) && (Method(`GET`) || Method(`POST`))"
+        );
+        assert_eq!(
+            routers["user-service-0"]["middlewares"],
+            json!(["auth-jwt", "user-service-quota"])
+        );
+        assert_eq!(
+            routers["user-service-1"]["rule"],
+            "PathRegexp(`^/users/[^/]+$`) && Method(`GET`)"
+        );
+        assert_eq!(routers["event-service-2"]["rule"], "PathPrefix(`/events`)");
+        assert_eq!(
+            routers["event-service-2"]["service"],
+            "event-service-sticky"
+        );
+
+        let services = &http["services"];
+        assert_eq!(
+            services["user-service"]["loadBalancer"]["servers"],
+            json!([{"url": "http://users-a:8080"}, {"url": "http://users-b:8080"}])
+        );
+        let events_lb = &services["event-service-sticky"]["loadBalancer"];
+        assert_eq!(events_lb["servers"], json!([{"url": "http://events:9000"}]));
+        assert_eq!(
+            events_lb["sticky"]["cookie"]["name"],
+            StickyConfig::default().cookie_name.as_str()
+        );
+
+        // Referenced, but defined by the operator
+        assert!(http["middlewares"].get("auth-jwt").is_none());
+        assert_eq!(
+            http["middlewares"]["user-service-quota"],
+            json!({"rateLimit": {"average": 100, "period": "60s", "burst": 20}})
+        );
+
+        let mut invalid = route("/users", &["GET"], "http://users-a:8080", "user-service");
+        invalid.quota = Some(QuotaConfig {
+            requests: 100,
+            window: "soon".to_string(),
+            burst: None,
+            scope: QuotaScope::Consumer,
+        });
+        assert!(to_dynamic_config(&[invalid]).is_err());
+
+        let mut invalid = route("/events", &["GET"], "http://events:9000", "event-service");
+        invalid.sticky = Some(StickyConfig {
+            ttl: "forever".to_string(),
+            ..Default::default()
+        });
+        assert!(to_dynamic_config(&[invalid]).is_err());
+    }
+
+    #[test]
+    fn test_mounted_route_strips_prefix() {
+        let mut user = route("/instance-1/users/{id}", &["GET"], "http://a", "a");
+        user.strip_segments = 1;
+
+        let config = to_dynamic_config(&[user]).unwrap();
+        let http = &config["http"];
+        assert_eq!(
+            http["routers"]["a-0"]["middlewares"],
+            json!(["a-strip-instance-1"])
+        );
+        assert_eq!(
+            http["middlewares"]["a-strip-instance-1"],
+            json!({"stripPrefix": {"prefixes": ["/instance-1"]}})
+        );
+    }
+}
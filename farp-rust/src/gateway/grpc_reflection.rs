@@ -0,0 +1,778 @@
+//! Live gRPC schema discovery through server reflection
+//!
+//! Services that set `reflection_enabled` serve their descriptors over the
+//! standard `grpc.reflection` API, so a gateway can read the schema straight
+//! from an instance instead of the registry. `v1` is tried first, then
+//! `v1alpha` for older servers.
+
+use crate::errors::{Error, Result};
+use crate::merger::grpc::{GRPCEnum, GRPCField, GRPCMessage, GRPCMethod, GRPCService, GRPCSpec};
+use prost::Message;
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{DescriptorProto, EnumDescriptorProto, FileDescriptorProto};
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+use std::time::Duration;
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::codegen::tokio_stream;
+use tonic::transport::{Channel, Endpoint};
+use tonic::{Code, Status};
+
+/// Reflection RPCs, newest first
+const REFLECTION_PATHS: [&str; 2] = [
+    "/grpc.reflection.v1.ServerReflection/ServerReflectionInfo",
+    "/grpc.reflection.v1alpha.ServerReflection/ServerReflectionInfo",
+];
+
+/// How long to wait for the reflection endpoint to accept a connection
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long each reflection RPC may take, including reading its responses
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Fetches a service's gRPC schema from its reflection endpoint
+///
+/// `endpoint` is `host:port` or a full `http(s)://` URI. Every service the
+/// server lists (except the reflection service itself) is resolved to its
+/// file descriptors, and the result is built like a parsed gRPC schema:
+/// the package is the one declaring the first service by name, services
+/// and types of that package are keyed by their short names, and types
+/// from other packages (`google.protobuf.Empty`) by their full names.
+/// Services of other packages are left out. Method options, including
+/// `google.api.http` annotations, are not recovered.
+///
+/// Returns `Error::Unsupported` when the server doesn't implement
+/// reflection, and `Error::SchemaFetchFailed` when it can't be reached,
+/// fails a request or doesn't answer one within 10 seconds.
+pub async fn fetch_descriptors(endpoint: &str) -> Result<GRPCSpec> {
+    fetch_with_timeout(endpoint, REQUEST_TIMEOUT).await
+}
+
+async fn fetch_with_timeout(endpoint: &str, timeout: Duration) -> Result<GRPCSpec> {
+    let uri = if endpoint.contains("://") {
+        endpoint.to_string()
+    } else {
+        format!("http://{endpoint}")
+    };
+    let channel = Endpoint::from_shared(uri)
+        .map_err(|e| Error::invalid_location(format!("{endpoint}: {e}")))?
+        .connect_timeout(CONNECT_TIMEOUT)
+        .timeout(timeout)
+        .connect()
+        .await
+        .map_err(|e| Error::schema_fetch_failed(format!("{endpoint}: {e}")))?;
+    let mut grpc = tonic::client::Grpc::new(channel);
+
+    for path in REFLECTION_PATHS {
+        match reflect(&mut grpc, path, endpoint, timeout).await {
+            Err(Error::Unsupported { .. }) => continue,
+            Err(e) => return Err(e),
+            Ok((services, files)) => return build_spec(&services, &files),
+        }
+    }
+    Err(Error::unsupported(format!(
+        "gRPC server reflection on {endpoint}"
+    )))
+}
+
+/// Lists the server's services and fetches the files that define them
+async fn reflect(
+    grpc: &mut tonic::client::Grpc<Channel>,
+    path: &'static str,
+    endpoint: &str,
+    timeout: Duration,
+) -> Result<(Vec<String>, Vec<FileDescriptorProto>)> {
+    let listing = exchange(
+        grpc,
+        path,
+        endpoint,
+        vec![ReflectionQuery::ListServices(String::new())],
+        timeout,
+    )
+    .await?;
+    let mut services: Vec<String> = listing
+        .into_iter()
+        .filter_map(|response| match response.answer {
+            Some(ReflectionAnswer::Services(list)) => Some(list.service),
+            _ => None,
+        })
+        .flatten()
+        .map(|service| service.name)
+        .filter(|name| !name.starts_with("grpc.reflection."))
+        .collect();
+    services.sort();
+    if services.is_empty() {
+        return Ok((services, Vec::new()));
+    }
+
+    let queries = services
+        .iter()
+        .map(|name| ReflectionQuery::FileContainingSymbol(name.clone()))
+        .collect();
+    let mut files = Vec::new();
+    let mut seen = HashSet::new();
+    for response in exchange(grpc, path, endpoint, queries, timeout).await? {
+        match response.answer {
+            Some(ReflectionAnswer::FileDescriptors(found)) => {
+                for bytes in found.file_descriptor_proto {
+                    let file = FileDescriptorProto::decode(bytes.as_slice()).map_err(|e| {
+                        Error::schema_fetch_failed(format!("{endpoint}: bad descriptor: {e}"))
+                    })?;
+                    if seen.insert(file.name().to_string()) {
+                        files.push(file);
+                    }
+                }
+            }
+            Some(ReflectionAnswer::Error(error)) => {
+                return Err(Error::schema_fetch_failed(format!(
+                    "{endpoint}: reflection error {}: {}",
+                    error.error_code, error.error_message
+                )))
+            }
+            _ => {}
+        }
+    }
+    Ok((services, files))
+}
+
+/// Sends `queries` on one reflection stream and collects every response
+async fn exchange(
+    grpc: &mut tonic::client::Grpc<Channel>,
+    path: &'static str,
+    endpoint: &str,
+    queries: Vec<ReflectionQuery>,
+    timeout: Duration,
+) -> Result<Vec<ReflectionResponse>> {
+    // A server can accept the stream and then never answer or finish it
+    tokio::time::timeout(timeout, exchange_messages(grpc, path, endpoint, queries))
+        .await
+        .map_err(|_| {
+            Error::schema_fetch_failed(format!(
+                "{endpoint}: reflection request timed out after {timeout:?}"
+            ))
+        })?
+}
+
+async fn exchange_messages(
+    grpc: &mut tonic::client::Grpc<Channel>,
+    path: &'static str,
+    endpoint: &str,
+    queries: Vec<ReflectionQuery>,
+) -> Result<Vec<ReflectionResponse>> {
+    let status_error = |status: Status| {
+        if status.code() == Code::Unimplemented {
+            Error::unsupported(format!("{path} on {endpoint}"))
+        } else {
+            Error::schema_fetch_failed(format!("{endpoint}: {}", status.message()))
+        }
+    };
+
+    grpc.ready()
+        .await
+        .map_err(|e| Error::schema_fetch_failed(format!("{endpoint}: {e}")))?;
+    let requests: Vec<ReflectionRequest> = queries
+        .into_iter()
+        .map(|query| ReflectionRequest {
+            host: String::new(),
+            query: Some(query),
+        })
+        .collect();
+    let mut stream = grpc
+        .streaming(
+            tonic::Request::new(tokio_stream::iter(requests)),
+            PathAndQuery::from_static(path),
+            ProstCodec::default(),
+        )
+        .await
+        .map_err(status_error)?
+        .into_inner();
+
+    let mut responses = Vec::new();
+    while let Some(response) = stream.message().await.map_err(status_error)? {
+        responses.push(response);
+    }
+    Ok(responses)
+}
+
+/// Builds a [`GRPCSpec`] from the files defining `services`
+fn build_spec(services: &[String], files: &[FileDescriptorProto]) -> Result<GRPCSpec> {
+    let declares = |file: &FileDescriptorProto, service: &str| {
+        file.service
+            .iter()
+            .any(|s| qualify(file.package(), s.name()) == service)
+    };
+    let primary = services
+        .first()
+        .and_then(|service| files.iter().find(|file| declares(file, service)))
+        .ok_or_else(|| Error::invalid_schema("reflection lists no services"))?;
+    let package = primary.package();
+
+    let mut spec = GRPCSpec {
+        syntax: match primary.syntax() {
+            "" => "proto2".to_string(),
+            syntax => syntax.to_string(),
+        },
+        package: package.to_string(),
+        services: HashMap::new(),
+        messages: HashMap::new(),
+        enums: HashMap::new(),
+        security_schemes: HashMap::new(),
+        imports: Vec::new(),
+    };
+
+    for file in files {
+        let proto3 = file.syntax() == "proto3";
+        for message in &file.message_type {
+            add_message(&mut spec, file.package(), "", message, proto3);
+        }
+        for enumeration in &file.enum_type {
+            add_enum(&mut spec, file.package(), "", enumeration);
+        }
+        if file.package() != package {
+            continue;
+        }
+        for import in &file.dependency {
+            if !spec.imports.contains(import) {
+                spec.imports.push(import.clone());
+            }
+        }
+        for service in &file.service {
+            if !services.contains(&qualify(package, service.name())) {
+                continue;
+            }
+            let methods = service
+                .method
+                .iter()
+                .map(|method| {
+                    let grpc_method = GRPCMethod {
+                        name: method.name().to_string(),
+                        description: None,
+                        input_type: relative_type(package, method.input_type()),
+                        output_type: relative_type(package, method.output_type()),
+                        client_streaming: method.client_streaming(),
+                        server_streaming: method.server_streaming(),
+                        options: None,
+                    };
+                    (grpc_method.name.clone(), grpc_method)
+                })
+                .collect();
+            spec.services.insert(
+                service.name().to_string(),
+                GRPCService {
+                    name: service.name().to_string(),
+                    description: None,
+                    methods,
+                    options: None,
+                },
+            );
+        }
+    }
+
+    Ok(spec)
+}
+
+/// Adds a message and its nested types, named relative to the spec's package
+fn add_message(
+    spec: &mut GRPCSpec,
+    file_package: &str,
+    parent: &str,
+    message: &DescriptorProto,
+    proto3: bool,
+) {
+    let scope = qualify(parent, message.name());
+    let name = relative_type(&spec.package, &qualify(file_package, &scope));
+    let fields = message
+        .field
+        .iter()
+        .map(|field| {
+            let field_type = match field.r#type() {
+                Type::Message | Type::Enum | Type::Group => {
+                    relative_type(&spec.package, field.type_name())
+                }
+                scalar => scalar
+                    .as_str_name()
+                    .trim_start_matches("TYPE_")
+                    .to_ascii_lowercase(),
+            };
+            let grpc_field = GRPCField {
+                name: field.name().to_string(),
+                field_type,
+                number: field.number(),
+                repeated: field.label() == Label::Repeated,
+                optional: field.proto3_optional() || (!proto3 && field.label() == Label::Optional),
+            };
+            (grpc_field.name.clone(), grpc_field)
+        })
+        .collect();
+    spec.messages.insert(
+        name.clone(),
+        GRPCMessage {
+            name,
+            description: None,
+            fields,
+            options: None,
+        },
+    );
+
+    for nested in &message.nested_type {
+        add_message(spec, file_package, &scope, nested, proto3);
+    }
+    for enumeration in &message.enum_type {
+        add_enum(spec, file_package, &scope, enumeration);
+    }
+}
+
+fn add_enum(
+    spec: &mut GRPCSpec,
+    file_package: &str,
+    parent: &str,
+    enumeration: &EnumDescriptorProto,
+) {
+    let name = relative_type(
+        &spec.package,
+        &qualify(file_package, &qualify(parent, enumeration.name())),
+    );
+    let values = enumeration
+        .value
+        .iter()
+        .map(|value| (value.name().to_string(), value.number()))
+        .collect();
+    spec.enums.insert(
+        name.clone(),
+        GRPCEnum {
+            name,
+            description: None,
+            values,
+        },
+    );
+}
+
+fn qualify(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_string()
+    } else {
+        format!("{scope}.{name}")
+    }
+}
+
+/// Strips the leading dot and, for types in `package`, the package itself
+fn relative_type(package: &str, type_name: &str) -> String {
+    let type_name = type_name.trim_start_matches('.');
+    type_name
+        .strip_prefix(package)
+        .and_then(|rest| rest.strip_prefix('.'))
+        .filter(|_| !package.is_empty())
+        .unwrap_or(type_name)
+        .to_string()
+}
+
+/// `ServerReflectionRequest`, limited to the queries sent here
+#[derive(Clone, PartialEq, prost::Message)]
+struct ReflectionRequest {
+    #[prost(string, tag = "1")]
+    host: String,
+    #[prost(oneof = "ReflectionQuery", tags = "4, 7")]
+    query: Option<ReflectionQuery>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+enum ReflectionQuery {
+    #[prost(string, tag = "4")]
+    FileContainingSymbol(String),
+    #[prost(string, tag = "7")]
+    ListServices(String),
+}
+
+/// `ServerReflectionResponse`, limited to the answers read here
+#[derive(Clone, PartialEq, prost::Message)]
+struct ReflectionResponse {
+    #[prost(oneof = "ReflectionAnswer", tags = "4, 6, 7")]
+    answer: Option<ReflectionAnswer>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+enum ReflectionAnswer {
+    #[prost(message, tag = "4")]
+    FileDescriptors(FileDescriptorResponse),
+    #[prost(message, tag = "6")]
+    Services(ListServiceResponse),
+    #[prost(message, tag = "7")]
+    Error(ErrorResponse),
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct FileDescriptorResponse {
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    file_descriptor_proto: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ListServiceResponse {
+    #[prost(message, repeated, tag = "1")]
+    service: Vec<ServiceResponse>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ServiceResponse {
+    #[prost(string, tag = "1")]
+    name: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ErrorResponse {
+    #[prost(int32, tag = "1")]
+    error_code: i32,
+    #[prost(string, tag = "2")]
+    error_message: String,
+}
+
+/// Protobuf codec for the reflection messages
+struct ProstCodec<E, D>(PhantomData<(E, D)>);
+
+impl<E, D> Default for ProstCodec<E, D> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<E, D> Codec for ProstCodec<E, D>
+where
+    E: Message + Send + 'static,
+    D: Message + Default + Send + 'static,
+{
+    type Encode = E;
+    type Decode = D;
+    type Encoder = ProstEncoder<E>;
+    type Decoder = ProstDecoder<D>;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        ProstEncoder(PhantomData)
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        ProstDecoder(PhantomData)
+    }
+}
+
+struct ProstEncoder<T>(PhantomData<T>);
+
+impl<T: Message> Encoder for ProstEncoder<T> {
+    type Item = T;
+    type Error = Status;
+
+    fn encode(&mut self, item: T, dst: &mut EncodeBuf<'_>) -> std::result::Result<(), Status> {
+        item.encode(dst)
+            .map_err(|e| Status::internal(e.to_string()))
+    }
+}
+
+struct ProstDecoder<T>(PhantomData<T>);
+
+impl<T: Message + Default> Decoder for ProstDecoder<T> {
+    type Item = T;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> std::result::Result<Option<T>, Status> {
+        T::decode(src)
+            .map(Some)
+            .map_err(|e| Status::internal(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost_types::{FieldDescriptorProto, MethodDescriptorProto, ServiceDescriptorProto};
+    use std::convert::Infallible;
+    use std::sync::Arc;
+    use tonic::codec::Streaming;
+    use tonic::codegen::{http, BoxFuture, BoxStream, Context, Poll, Service};
+    use tonic::server::NamedService;
+    use tonic::transport::server::TcpIncoming;
+    use tonic::transport::Server;
+
+    /// Serves `v1alpha` reflection over a fixed set of files
+    #[derive(Clone)]
+    struct ReflectionServer {
+        files: Arc<Vec<FileDescriptorProto>>,
+    }
+
+    impl NamedService for ReflectionServer {
+        const NAME: &'static str = "grpc.reflection.v1alpha.ServerReflection";
+    }
+
+    impl Service<http::Request<tonic::body::Body>> for ReflectionServer {
+        type Response = http::Response<tonic::body::Body>;
+        type Error = Infallible;
+        type Future = BoxFuture<Self::Response, Infallible>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<std::result::Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: http::Request<tonic::body::Body>) -> Self::Future {
+            let handler = ReflectionHandler {
+                files: self.files.clone(),
+            };
+            Box::pin(async move {
+                let mut grpc = tonic::server::Grpc::new(ProstCodec::<
+                    ReflectionResponse,
+                    ReflectionRequest,
+                >::default());
+                Ok(grpc.streaming(handler, request).await)
+            })
+        }
+    }
+
+    struct ReflectionHandler {
+        files: Arc<Vec<FileDescriptorProto>>,
+    }
+
+    impl Service<tonic::Request<Streaming<ReflectionRequest>>> for ReflectionHandler {
+        type Response = tonic::Response<BoxStream<ReflectionResponse>>;
+        type Error = Status;
+        type Future = BoxFuture<Self::Response, Status>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<std::result::Result<(), Status>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: tonic::Request<Streaming<ReflectionRequest>>) -> Self::Future {
+            let files = self.files.clone();
+            Box::pin(async move {
+                let mut incoming = request.into_inner();
+                let mut answers = Vec::new();
+                while let Some(request) = incoming.message().await? {
+                    answers.push(Ok(ReflectionResponse {
+                        answer: Some(answer(&files, request.query)),
+                    }));
+                }
+                let stream: BoxStream<ReflectionResponse> = Box::pin(tokio_stream::iter(answers));
+                Ok(tonic::Response::new(stream))
+            })
+        }
+    }
+
+    fn answer(files: &[FileDescriptorProto], query: Option<ReflectionQuery>) -> ReflectionAnswer {
+        match query {
+            Some(ReflectionQuery::ListServices(_)) => {
+                let mut service: Vec<ServiceResponse> = files
+                    .iter()
+                    .flat_map(|file| {
+                        file.service.iter().map(|s| ServiceResponse {
+                            name: qualify(file.package(), s.name()),
+                        })
+                    })
+                    .collect();
+                service.push(ServiceResponse {
+                    name: "grpc.reflection.v1alpha.ServerReflection".to_string(),
+                });
+                ReflectionAnswer::Services(ListServiceResponse { service })
+            }
+            Some(ReflectionQuery::FileContainingSymbol(symbol)) => {
+                // The defining file comes first, followed by its dependencies
+                let file = files
+                    .iter()
+                    .find(|file| {
+                        file.service
+                            .iter()
+                            .any(|s| qualify(file.package(), s.name()) == symbol)
+                    })
+                    .unwrap();
+                let file_descriptor_proto = std::iter::once(file)
+                    .chain(
+                        files
+                            .iter()
+                            .filter(|f| file.dependency.contains(&f.name().to_string())),
+                    )
+                    .map(|f| f.encode_to_vec())
+                    .collect();
+                ReflectionAnswer::FileDescriptors(FileDescriptorResponse {
+                    file_descriptor_proto,
+                })
+            }
+            None => ReflectionAnswer::Error(ErrorResponse {
+                error_code: Code::InvalidArgument as i32,
+                error_message: "empty request".to_string(),
+            }),
+        }
+    }
+
+    fn field(
+        name: &str,
+        number: i32,
+        kind: Type,
+        type_name: Option<&str>,
+        label: Label,
+    ) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            label: Some(label as i32),
+            r#type: Some(kind as i32),
+            type_name: type_name.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    fn method(
+        name: &str,
+        input: &str,
+        output: &str,
+        server_streaming: bool,
+    ) -> MethodDescriptorProto {
+        MethodDescriptorProto {
+            name: Some(name.to_string()),
+            input_type: Some(input.to_string()),
+            output_type: Some(output.to_string()),
+            server_streaming: Some(server_streaming),
+            ..Default::default()
+        }
+    }
+
+    fn library_files() -> Vec<FileDescriptorProto> {
+        let empty = FileDescriptorProto {
+            name: Some("google/protobuf/empty.proto".to_string()),
+            package: Some("google.protobuf".to_string()),
+            message_type: vec![DescriptorProto {
+                name: Some("Empty".to_string()),
+                ..Default::default()
+            }],
+            syntax: Some("proto3".to_string()),
+            ..Default::default()
+        };
+        let library = FileDescriptorProto {
+            name: Some("library/v1/library.proto".to_string()),
+            package: Some("library.v1".to_string()),
+            dependency: vec!["google/protobuf/empty.proto".to_string()],
+            message_type: vec![
+                DescriptorProto {
+                    name: Some("Book".to_string()),
+                    field: vec![
+                        field("name", 1, Type::String, None, Label::Optional),
+                        field("tags", 2, Type::String, None, Label::Repeated),
+                        field(
+                            "format",
+                            3,
+                            Type::Enum,
+                            Some(".library.v1.Book.Format"),
+                            Label::Optional,
+                        ),
+                    ],
+                    enum_type: vec![EnumDescriptorProto {
+                        name: Some("Format".to_string()),
+                        value: vec![
+                            prost_types::EnumValueDescriptorProto {
+                                name: Some("FORMAT_UNSPECIFIED".to_string()),
+                                number: Some(0),
+                                options: None,
+                            },
+                            prost_types::EnumValueDescriptorProto {
+                                name: Some("FORMAT_EBOOK".to_string()),
+                                number: Some(1),
+                                options: None,
+                            },
+                        ],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                DescriptorProto {
+                    name: Some("GetBookRequest".to_string()),
+                    field: vec![field("name", 1, Type::String, None, Label::Optional)],
+                    ..Default::default()
+                },
+            ],
+            service: vec![ServiceDescriptorProto {
+                name: Some("LibraryService".to_string()),
+                method: vec![
+                    method(
+                        "GetBook",
+                        ".library.v1.GetBookRequest",
+                        ".library.v1.Book",
+                        false,
+                    ),
+                    method(
+                        "ListBooks",
+                        ".google.protobuf.Empty",
+                        ".library.v1.Book",
+                        true,
+                    ),
+                ],
+                options: None,
+            }],
+            syntax: Some("proto3".to_string()),
+            ..Default::default()
+        };
+        vec![library, empty]
+    }
+
+    #[tokio::test]
+    async fn test_fetch_descriptors() {
+        let incoming = TcpIncoming::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        let addr = incoming.local_addr().unwrap();
+        tokio::spawn(
+            Server::builder()
+                .add_service(ReflectionServer {
+                    files: Arc::new(library_files()),
+                })
+                .serve_with_incoming(incoming),
+        );
+
+        // The server only speaks v1alpha, so the v1 attempt falls through
+        let spec = fetch_descriptors(&addr.to_string()).await.unwrap();
+        assert_eq!(spec.package, "library.v1");
+        assert_eq!(spec.syntax, "proto3");
+        assert_eq!(spec.imports, vec!["google/protobuf/empty.proto"]);
+
+        let service = &spec.services["LibraryService"];
+        let get = &service.methods["GetBook"];
+        assert_eq!(
+            (get.input_type.as_str(), get.output_type.as_str()),
+            ("GetBookRequest", "Book")
+        );
+        let list = &service.methods["ListBooks"];
+        assert_eq!(list.input_type, "google.protobuf.Empty");
+        assert!(list.server_streaming && !list.client_streaming);
+
+        let book = &spec.messages["Book"];
+        assert!(book.fields["tags"].repeated);
+        assert_eq!(book.fields["format"].field_type, "Book.Format");
+        assert_eq!(book.fields["name"].field_type, "string");
+        assert!(!book.fields["name"].optional);
+        assert!(spec.messages.contains_key("google.protobuf.Empty"));
+        assert_eq!(spec.enums["Book.Format"].values["FORMAT_EBOOK"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_descriptors_times_out() {
+        // Accepts connections but never speaks HTTP/2
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                connections.push(socket);
+            }
+        });
+
+        let started = std::time::Instant::now();
+        let result = fetch_with_timeout(&addr.to_string(), Duration::from_millis(200)).await;
+        assert!(matches!(result, Err(Error::SchemaFetchFailed(_))));
+        assert!(started.elapsed() < Duration::from_secs(4));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_descriptors_unavailable() {
+        // Nothing listens on a port we just released
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        assert!(matches!(
+            fetch_descriptors(&addr.to_string()).await,
+            Err(Error::SchemaFetchFailed(_))
+        ));
+    }
+}
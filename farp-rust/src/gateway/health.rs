@@ -0,0 +1,397 @@
+//! Active health probing of service instances
+//!
+//! Instances report their own status in the manifest, but a gateway may
+//! want to check for itself. [`HealthProber`] calls an instance's health
+//! endpoint over plain HTTP and derives an [`InstanceStatus`] from the
+//! response with [`HealthReport::parse`].
+
+use crate::errors::{Error, Result};
+use crate::types::{Address, HealthCheckConfig, InstanceStatus, SchemaManifest};
+use crate::util::parse_duration;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// Largest health response read from an instance
+pub const MAX_HEALTH_RESPONSE: u64 = 64 * 1024;
+
+/// A parsed health endpoint response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthReport {
+    /// HTTP status code of the response
+    pub status_code: u16,
+    /// Status derived from the code, the body's `status` and its checks
+    pub status: InstanceStatus,
+    /// Status of each named dependency check in the body's `checks`
+    pub checks: BTreeMap<String, InstanceStatus>,
+}
+
+impl HealthReport {
+    /// Derives an instance's status from its health endpoint response
+    ///
+    /// A status code outside `expected_statuses` is unhealthy. Otherwise a
+    /// JSON body's `status` is read (`healthy`/`ok`/`up`/`pass` and
+    /// `unhealthy`/`down`/`fail`/`error` are understood, as is every
+    /// [`InstanceStatus`] name); a missing or unrecognized status counts as
+    /// healthy. `checks` may map names to a status string or an object with
+    /// a `status`; a healthy instance with a failing check is degraded.
+    pub fn parse(status_code: u16, body: &[u8], expected_statuses: &[u16]) -> Self {
+        let json: Option<serde_json::Value> = serde_json::from_slice(body).ok();
+
+        let checks: BTreeMap<String, InstanceStatus> = json
+            .as_ref()
+            .and_then(|b| b.get("checks")?.as_object())
+            .map(|checks| {
+                checks
+                    .iter()
+                    .filter_map(|(name, check)| {
+                        let status = check.as_str().or_else(|| check.get("status")?.as_str())?;
+                        Some((name.clone(), parse_status(status)?))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let status = if !expected_statuses.contains(&status_code) {
+            InstanceStatus::Unhealthy
+        } else {
+            let declared = json
+                .as_ref()
+                .and_then(|b| parse_status(b.get("status")?.as_str()?))
+                .unwrap_or(InstanceStatus::Healthy);
+            if declared == InstanceStatus::Healthy
+                && checks.values().any(|s| *s != InstanceStatus::Healthy)
+            {
+                InstanceStatus::Degraded
+            } else {
+                declared
+            }
+        };
+
+        Self {
+            status_code,
+            status,
+            checks,
+        }
+    }
+}
+
+fn parse_status(status: &str) -> Option<InstanceStatus> {
+    match status.to_ascii_lowercase().as_str() {
+        "healthy" | "ok" | "up" | "pass" | "passing" => Some(InstanceStatus::Healthy),
+        "degraded" | "warn" | "warning" => Some(InstanceStatus::Degraded),
+        "unhealthy" | "down" | "fail" | "failing" | "error" => Some(InstanceStatus::Unhealthy),
+        "starting" => Some(InstanceStatus::Starting),
+        "draining" => Some(InstanceStatus::Draining),
+        "stopping" => Some(InstanceStatus::Stopping),
+        _ => None,
+    }
+}
+
+/// Probes instance health endpoints over HTTP
+///
+/// Defaults to the gateway defaults of [`HealthCheckConfig`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthProber {
+    timeout: Duration,
+    expected_statuses: Vec<u16>,
+}
+
+impl Default for HealthProber {
+    fn default() -> Self {
+        Self::from_config(&HealthCheckConfig::default())
+            .expect("default health check config is valid")
+    }
+}
+
+impl HealthProber {
+    /// Creates a prober with the default timeout and expected statuses
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a prober from a manifest's probe configuration
+    ///
+    /// Fails with `Error::Validation` when `timeout` isn't a valid duration.
+    pub fn from_config(config: &HealthCheckConfig) -> Result<Self> {
+        Ok(Self {
+            timeout: parse_duration(&config.timeout)?,
+            expected_statuses: config.expected_statuses.clone(),
+        })
+    }
+
+    /// Sets how long a probe may take, connection included
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the HTTP status codes treated as healthy
+    pub fn with_expected_statuses(mut self, statuses: Vec<u16>) -> Self {
+        self.expected_statuses = statuses;
+        self
+    }
+
+    /// Probes an instance and returns its derived status
+    ///
+    /// Instances that can't be reached, time out or send a malformed
+    /// response are unhealthy. See [`probe_report`](Self::probe_report).
+    pub async fn probe(&self, manifest: &SchemaManifest) -> Result<InstanceStatus> {
+        Ok(self.probe_report(manifest).await?.status)
+    }
+
+    /// Probes an instance with a `GET` to its `instance.address` plus
+    /// `endpoints.health`
+    ///
+    /// The address is `host:port`, optionally prefixed with `http://`.
+    /// Fails with `Error::Validation` when the manifest has no valid instance
+    /// address (see [`Address::parse`]) or its health path doesn't start
+    /// with `/` or contains whitespace or control characters, and
+    /// `Error::Unsupported` for `https://` addresses; transport failures
+    /// yield an unhealthy report with status code 0.
+    pub async fn probe_report(&self, manifest: &SchemaManifest) -> Result<HealthReport> {
+        let address = manifest
+            .instance
+            .as_ref()
+            .map(|i| i.address.as_str())
+            .filter(|a| !a.is_empty())
+            .ok_or_else(|| Error::validation("instance.address", "required for probing"))?;
+        if address.starts_with("https://") {
+            return Err(Error::unsupported("probing https health endpoints"));
+        }
+        let address = address.strip_prefix("http://").unwrap_or(address);
+        let address = Address::parse(address.trim_end_matches('/'))?;
+        let path = match manifest.endpoints.health.as_str() {
+            "" => "/",
+            path => path,
+        };
+        // The path goes into the request line verbatim
+        if !path.starts_with('/') || path.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(Error::validation(
+                "endpoints.health",
+                format!("{path:?} is not a valid request path"),
+            ));
+        }
+
+        let response = tokio::time::timeout(self.timeout, get(&address, path)).await;
+        Ok(match response {
+            Ok(Ok((status_code, body))) => {
+                HealthReport::parse(status_code, &body, &self.expected_statuses)
+            }
+            _ => HealthReport {
+                status_code: 0,
+                status: InstanceStatus::Unhealthy,
+                checks: BTreeMap::new(),
+            },
+        })
+    }
+}
+
+/// Sends an HTTP/1.0 `GET` and returns the status code and body
+///
+/// HTTP/1.0 keeps the response unchunked and delimited by the connection close.
+async fn get(address: &Address, path: &str) -> Result<(u16, Vec<u8>)> {
+    let failed = |e: std::io::Error| Error::backend_unavailable(format!("{address}: {e}"));
+
+    let mut stream = TcpStream::connect((address.host(), address.port()))
+        .await
+        .map_err(failed)?;
+    let request = format!(
+        "GET {path} HTTP/1.0\r\nHost: {address}\r\nAccept: application/json\r\nUser-Agent: farp-health-prober\r\n\r\n"
+    );
+    stream.write_all(request.as_bytes()).await.map_err(failed)?;
+
+    let mut response = Vec::new();
+    stream
+        .take(MAX_HEALTH_RESPONSE)
+        .read_to_end(&mut response)
+        .await
+        .map_err(failed)?;
+
+    let malformed = || Error::backend_unavailable(format!("{address}: malformed HTTP response"));
+    let header_end = response
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or_else(malformed)?;
+    let status_line = response[..header_end]
+        .split(|b| *b == b'\n')
+        .next()
+        .and_then(|line| std::str::from_utf8(line).ok())
+        .ok_or_else(malformed)?;
+    let status_code = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse::<u16>().ok())
+        .filter(|_| status_line.starts_with("HTTP/"))
+        .ok_or_else(malformed)?;
+
+    Ok((status_code, response[header_end + 4..].to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::new_manifest;
+    use crate::types::InstanceMetadata;
+    use tokio::net::TcpListener;
+
+    /// Serves `response` to every connection, returning the server's address
+    async fn serve(response: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = [0u8; 1024];
+                let _ = socket.read(&mut request).await;
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        address
+    }
+
+    fn manifest(address: &str) -> SchemaManifest {
+        let mut manifest = new_manifest("user-service", "v1.0.0", "instance-1");
+        manifest.endpoints.health = "/health".to_string();
+        manifest.instance = Some(InstanceMetadata {
+            address: address.to_string(),
+            region: None,
+            zone: None,
+            labels: None,
+            weight: None,
+            // Self-reported status is ignored by the prober
+            status: InstanceStatus::Healthy,
+            role: None,
+            deployment: None,
+            started_at: 0,
+            expected_schema_checksum: None,
+        });
+        manifest
+    }
+
+    #[test]
+    fn test_parse_health_report() {
+        let ok = [200];
+        assert_eq!(
+            HealthReport::parse(200, br#"{"status": "UP"}"#, &ok).status,
+            InstanceStatus::Healthy
+        );
+        assert_eq!(
+            HealthReport::parse(200, b"OK", &ok).status,
+            InstanceStatus::Healthy
+        );
+        assert_eq!(
+            HealthReport::parse(200, br#"{"status": "draining"}"#, &ok).status,
+            InstanceStatus::Draining
+        );
+        assert_eq!(
+            HealthReport::parse(503, br#"{"status": "ok"}"#, &ok).status,
+            InstanceStatus::Unhealthy
+        );
+
+        let report = HealthReport::parse(
+            200,
+            br#"{"status": "pass", "checks": {"db": {"status": "fail"}, "cache": "ok"}}"#,
+            &ok,
+        );
+        assert_eq!(report.status, InstanceStatus::Degraded);
+        assert_eq!(report.checks["db"], InstanceStatus::Unhealthy);
+        assert_eq!(report.checks["cache"], InstanceStatus::Healthy);
+    }
+
+    #[tokio::test]
+    async fn test_probe_instances() {
+        let prober = HealthProber::new();
+
+        let healthy = serve(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n{\"status\":\"healthy\"}",
+        )
+        .await;
+        assert_eq!(
+            prober.probe(&manifest(&healthy)).await.unwrap(),
+            InstanceStatus::Healthy
+        );
+
+        let failing = serve(
+            "HTTP/1.1 503 Service Unavailable\r\n\r\n{\"status\":\"down\",\"checks\":{\"db\":\"down\"}}",
+        )
+        .await;
+        let report = prober.probe_report(&manifest(&failing)).await.unwrap();
+        assert_eq!(report.status_code, 503);
+        assert_eq!(report.status, InstanceStatus::Unhealthy);
+        assert_eq!(report.checks["db"], InstanceStatus::Unhealthy);
+
+        // Expected statuses are configurable
+        let prober = prober.with_expected_statuses(vec![200, 503]);
+        assert_eq!(
+            prober
+                .probe(&manifest(&format!("http://{failing}")))
+                .await
+                .unwrap(),
+            InstanceStatus::Unhealthy
+        );
+        let degraded = serve("HTTP/1.1 503 OK\r\n\r\n{\"status\":\"degraded\"}").await;
+        assert_eq!(
+            prober.probe(&manifest(&degraded)).await.unwrap(),
+            InstanceStatus::Degraded
+        );
+    }
+
+    #[tokio::test]
+    async fn test_probe_unreachable_and_slow_instances() {
+        let closed = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .to_string();
+        let prober = HealthProber::new().with_timeout(Duration::from_millis(200));
+        assert_eq!(
+            prober.probe(&manifest(&closed)).await.unwrap(),
+            InstanceStatus::Unhealthy
+        );
+
+        // Accepts but never answers
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let slow = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            let _socket = listener.accept().await;
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        });
+        let report = prober.probe_report(&manifest(&slow)).await.unwrap();
+        assert_eq!(report.status_code, 0);
+        assert_eq!(report.status, InstanceStatus::Unhealthy);
+
+        let mut missing = manifest(&slow);
+        missing.instance = None;
+        assert!(matches!(
+            prober.probe(&missing).await,
+            Err(Error::Validation { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_probe_rejects_invalid_targets() {
+        let prober = HealthProber::new();
+        let healthy = serve("HTTP/1.1 200 OK\r\n\r\n").await;
+
+        for path in [
+            "/health\r\nX-Injected: 1",
+            "/health HTTP/1.1\r\n\r\nGET /admin",
+            "health",
+        ] {
+            let mut injected = manifest(&healthy);
+            injected.endpoints.health = path.to_string();
+            assert!(matches!(
+                prober.probe(&injected).await,
+                Err(Error::Validation { field, .. }) if field == "endpoints.health"
+            ));
+        }
+
+        for address in ["localhost", "evil\r\nHost: x:80", "::1:8080"] {
+            assert!(matches!(
+                prober.probe(&manifest(address)).await,
+                Err(Error::Validation { field, .. }) if field == "instance.address"
+            ));
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! Load-balancing helpers for gateway routes
+
+use super::client::ServiceRoute;
+
+/// Weight given to routes that don't declare one
+pub const DEFAULT_WEIGHT: u32 = 100;
+
+/// Multiplier applied to backends in the caller's zone
+pub const SAME_ZONE_BOOST: u32 = 4;
+
+/// Multiplier applied to backends in the caller's region but another zone
+pub const SAME_REGION_BOOST: u32 = 2;
+
+/// How close a backend is to the gateway
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Locality {
+    /// Same zone (and region, when both are known)
+    Zone,
+    /// Same region, different or unknown zone
+    Region,
+    /// Different region, or no locality information
+    Remote,
+}
+
+/// A route backend with its locality-adjusted weight
+#[derive(Debug, Clone)]
+pub struct WeightedBackend {
+    /// The backend's route
+    pub route: ServiceRoute,
+    /// Locality relative to the gateway
+    pub locality: Locality,
+    /// Route weight after applying the locality boost
+    pub effective_weight: u32,
+}
+
+/// Ranks backends by locality, boosting same-zone then same-region ones
+///
+/// Each backend's weight (or [`DEFAULT_WEIGHT`]) is multiplied by
+/// [`SAME_ZONE_BOOST`] or [`SAME_REGION_BOOST`] depending on how close it
+/// is to `local_zone`/`local_region`. A zone only matches when the regions
+/// don't contradict each other. The result is ordered by locality, then by
+/// effective weight, keeping the input order for ties.
+pub fn prefer_local(
+    backends: &[ServiceRoute],
+    local_zone: Option<&str>,
+    local_region: Option<&str>,
+) -> Vec<WeightedBackend> {
+    let mut ranked: Vec<WeightedBackend> = backends
+        .iter()
+        .map(|route| {
+            let locality = locality(route, local_zone, local_region);
+            let boost = match locality {
+                Locality::Zone => SAME_ZONE_BOOST,
+                Locality::Region => SAME_REGION_BOOST,
+                Locality::Remote => 1,
+            };
+            WeightedBackend {
+                route: route.clone(),
+                locality,
+                effective_weight: route.weight.unwrap_or(DEFAULT_WEIGHT).saturating_mul(boost),
+            }
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| {
+        a.locality
+            .cmp(&b.locality)
+            .then(b.effective_weight.cmp(&a.effective_weight))
+    });
+    ranked
+}
+
+/// Classifies a route's instance relative to the gateway's zone and region
+fn locality(
+    route: &ServiceRoute,
+    local_zone: Option<&str>,
+    local_region: Option<&str>,
+) -> Locality {
+    let region = route.region.as_deref();
+    let same_region = region.is_some() && region == local_region;
+    let regions_conflict = matches!((region, local_region), (Some(a), Some(b)) if a != b);
+
+    if !regions_conflict && route.zone.is_some() && route.zone.as_deref() == local_zone {
+        Locality::Zone
+    } else if same_region {
+        Locality::Region
+    } else {
+        Locality::Remote
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(
+        target: &str,
+        region: Option<&str>,
+        zone: Option<&str>,
+        weight: Option<u32>,
+    ) -> ServiceRoute {
+        ServiceRoute {
+            path: "/users".to_string(),
+            methods: vec!["GET".to_string()],
+            target_url: target.to_string(),
+            service_name: "user-service".to_string(),
+            service_version: "v1.0.0".to_string(),
+            weight,
+            region: region.map(str::to_string),
+            zone: zone.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn targets(ranked: &[WeightedBackend]) -> Vec<&str> {
+        ranked.iter().map(|b| b.route.target_url.as_str()).collect()
+    }
+
+    #[test]
+    fn test_prefer_local_orders_by_locality() {
+        let backends = vec![
+            route(
+                "http://remote",
+                Some("us-east-1"),
+                Some("us-east-1a"),
+                Some(100),
+            ),
+            route("http://region", Some("eu-west-1"), Some("eu-west-1b"), None),
+            route(
+                "http://zone",
+                Some("eu-west-1"),
+                Some("eu-west-1a"),
+                Some(50),
+            ),
+            route("http://unknown", None, None, None),
+        ];
+
+        let ranked = prefer_local(&backends, Some("eu-west-1a"), Some("eu-west-1"));
+        assert_eq!(
+            targets(&ranked),
+            vec![
+                "http://zone",
+                "http://region",
+                "http://remote",
+                "http://unknown"
+            ]
+        );
+        let weights: Vec<u32> = ranked.iter().map(|b| b.effective_weight).collect();
+        assert_eq!(weights, vec![200, 200, 100, 100]);
+        assert_eq!(ranked[0].locality, Locality::Zone);
+        assert_eq!(ranked[2].locality, Locality::Remote);
+    }
+
+    #[test]
+    fn test_prefer_local_ignores_zone_in_other_region() {
+        let backends = vec![
+            route("http://a", Some("us-east-1"), Some("a"), Some(10)),
+            route("http://b", Some("eu-west-1"), Some("b"), Some(10)),
+        ];
+
+        // Zone "a" exists in both regions; only the local region's counts
+        let ranked = prefer_local(&backends, Some("a"), Some("eu-west-1"));
+        assert_eq!(targets(&ranked), vec!["http://b", "http://a"]);
+        assert_eq!(ranked[1].locality, Locality::Remote);
+        assert_eq!(ranked[1].effective_weight, 10);
+    }
+
+    #[test]
+    fn test_prefer_local_without_locality_keeps_weights() {
+        let backends = vec![
+            route("http://light", Some("eu-west-1"), None, Some(20)),
+            route("http://heavy", Some("eu-west-1"), None, Some(80)),
+        ];
+
+        let ranked = prefer_local(&backends, None, None);
+        assert_eq!(targets(&ranked), vec!["http://heavy", "http://light"]);
+        assert!(ranked.iter().all(|b| b.locality == Locality::Remote));
+    }
+}
@@ -1,5 +1,19 @@
 //! Gateway client implementation
 
 pub mod client;
+pub mod diff;
+pub mod export;
+#[cfg(feature = "providers-grpc")]
+pub mod grpc_reflection;
+pub mod health;
+pub mod lb;
+pub mod openapi;
 
-pub use client::{Client, ServiceRoute};
+pub use client::{
+    routes_requiring_audit, Client, IdempotencyConfig, JsonPointer, MountConflictStrategy,
+    RouteConversion, RoutePolicy, ServiceRoute,
+};
+pub use diff::{route_diff, RouteDiff};
+pub use health::{HealthProber, HealthReport};
+pub use lb::{prefer_local, Locality, WeightedBackend};
+pub use openapi::routes_to_openapi;
@@ -0,0 +1,190 @@
+//! OpenAPI generation from gateway routes
+
+use super::client::ServiceRoute;
+use crate::merger::types::{Info, OpenAPISpec, Operation, PathItem, Server, Tag};
+use std::collections::HashMap;
+
+/// Synthesizes an OpenAPI document describing everything the gateway proxies
+///
+/// Each route path becomes a path item with one operation per HTTP method,
+/// tagged with the backend service name. Servers are the distinct
+/// `scheme://host[:port]` origins of the routes' target URLs. Non-HTTP
+/// methods (e.g. `WEBSOCKET`) are left out.
+pub fn routes_to_openapi(routes: &[ServiceRoute], info: Info) -> OpenAPISpec {
+    let mut paths: HashMap<String, PathItem> = HashMap::new();
+    let mut servers: Vec<Server> = Vec::new();
+    let mut tags: Vec<Tag> = Vec::new();
+
+    for route in routes {
+        let mut item = paths.remove(&route.path).unwrap_or_else(empty_path_item);
+        let mut added = false;
+
+        for method in &route.methods {
+            let slot = match method.to_ascii_lowercase().as_str() {
+                "get" => &mut item.get,
+                "put" => &mut item.put,
+                "post" => &mut item.post,
+                "delete" => &mut item.delete,
+                "options" => &mut item.options,
+                "head" => &mut item.head,
+                "patch" => &mut item.patch,
+                "trace" => &mut item.trace,
+                _ => continue,
+            };
+            *slot = Some(route_operation(route));
+            added = true;
+        }
+
+        if !added && item == empty_path_item() {
+            continue;
+        }
+        paths.insert(route.path.clone(), item);
+
+        if added {
+            if let Some(origin) = url_origin(&route.target_url) {
+                if !servers.iter().any(|s| s.url == origin) {
+                    servers.push(Server {
+                        url: origin,
+                        description: Some(route.service_name.clone()),
+                        variables: None,
+                    });
+                }
+            }
+            if !tags.iter().any(|t| t.name == route.service_name) {
+                tags.push(Tag {
+                    name: route.service_name.clone(),
+                    description: None,
+                    external_docs: None,
+                    extensions: HashMap::new(),
+                });
+            }
+        }
+    }
+
+    tags.sort_by(|a, b| a.name.cmp(&b.name));
+
+    OpenAPISpec {
+        openapi: "3.1.0".to_string(),
+        info,
+        servers,
+        paths,
+        webhooks: HashMap::new(),
+        components: None,
+        security: Vec::new(),
+        tags,
+        extensions: HashMap::new(),
+    }
+}
+
+fn route_operation(route: &ServiceRoute) -> Operation {
+    Operation {
+        operation_id: None,
+        summary: None,
+        description: None,
+        tags: vec![route.service_name.clone()],
+        parameters: Vec::new(),
+        request_body: None,
+        responses: None,
+        security: Vec::new(),
+        deprecated: route.deprecated.then_some(true),
+        callbacks: HashMap::new(),
+        extensions: HashMap::new(),
+    }
+}
+
+fn empty_path_item() -> PathItem {
+    PathItem {
+        summary: None,
+        description: None,
+        get: None,
+        put: None,
+        post: None,
+        delete: None,
+        options: None,
+        head: None,
+        patch: None,
+        trace: None,
+        parameters: Vec::new(),
+        extensions: HashMap::new(),
+    }
+}
+
+/// Returns `scheme://host[:port]` of an absolute URL
+pub(crate) fn url_origin(url: &str) -> Option<String> {
+    let (scheme, rest) = url.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    if authority.is_empty() {
+        return None;
+    }
+    Some(format!("{scheme}://{authority}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(path: &str, methods: &[&str], service: &str) -> ServiceRoute {
+        ServiceRoute {
+            path: path.to_string(),
+            methods: methods.iter().map(|m| m.to_string()).collect(),
+            target_url: format!("http://{service}:8080{path}"),
+            health_url: format!("http://{service}:8080/health"),
+            service_name: service.to_string(),
+            service_version: "v1.0.0".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_routes_to_openapi() {
+        let routes = vec![
+            route("/users", &["GET", "POST"], "user-service"),
+            route("/users/{id}", &["GET", "DELETE"], "user-service"),
+            route("/orders", &["GET"], "order-service"),
+            route("/events", &["WEBSOCKET"], "order-service"),
+        ];
+
+        let info = Info {
+            title: "Gateway".to_string(),
+            description: None,
+            version: "1.0.0".to_string(),
+            terms_of_service: None,
+            contact: None,
+            license: None,
+            extensions: HashMap::new(),
+        };
+
+        let spec = routes_to_openapi(&routes, info);
+
+        assert_eq!(spec.paths.len(), 3);
+        let users = &spec.paths["/users"];
+        assert!(users.get.is_some());
+        assert!(users.post.is_some());
+        assert!(users.put.is_none());
+
+        let operation_count: usize = spec
+            .paths
+            .values()
+            .map(|item| {
+                [&item.get, &item.post, &item.put, &item.delete]
+                    .iter()
+                    .filter(|op| op.is_some())
+                    .count()
+            })
+            .sum();
+        assert_eq!(operation_count, 5);
+
+        let tags: Vec<&str> = spec.tags.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(tags, vec!["order-service", "user-service"]);
+        assert_eq!(
+            spec.paths["/orders"].get.as_ref().unwrap().tags,
+            vec!["order-service"]
+        );
+
+        let servers: Vec<&str> = spec.servers.iter().map(|s| s.url.as_str()).collect();
+        assert_eq!(
+            servers,
+            vec!["http://user-service:8080", "http://order-service:8080"]
+        );
+    }
+}
@@ -30,6 +30,7 @@
 //! # Feature Flags
 //!
 //! - `default`: Core types + memory registry
+//! - `dynamo-registry`: DynamoDB registry backend
 //! - `providers-openapi`: OpenAPI provider
 //! - `providers-asyncapi`: AsyncAPI provider
 //! - `providers-grpc`: gRPC provider
@@ -39,22 +40,31 @@
 //! - `providers-thrift`: Thrift provider
 //! - `providers-all`: All providers
 //! - `gateway`: Gateway client implementation
+//! - `signing`: Ed25519 manifest signing and verification
+//! - `server`: HTTP pull server for manifests and schemas
 //! - `full`: Everything enabled
 
+pub mod compatibility;
 pub mod errors;
 pub mod manifest;
+mod msgpack;
+pub mod patch;
 pub mod provider;
 pub mod storage;
 pub mod types;
+pub mod util;
 pub mod version;
 
 // Registry module
 pub mod registry {
-    use crate::errors::Result;
-    use crate::types::SchemaManifest;
+    use crate::errors::{Error, Result};
+    use crate::types::{
+        InstanceStatus, LocationType, SchemaDescriptor, SchemaManifest, SchemaType,
+    };
     use async_trait::async_trait;
     use serde::{Deserialize, Serialize};
     use std::collections::HashMap;
+    use tokio::io::{AsyncRead, AsyncReadExt};
 
     /// Schema registry trait for managing manifests and schemas
     #[async_trait]
@@ -64,9 +74,169 @@ pub mod registry {
         async fn update_manifest(&self, manifest: &SchemaManifest) -> Result<()>;
         async fn delete_manifest(&self, instance_id: &str) -> Result<()>;
         async fn list_manifests(&self, service_name: &str) -> Result<Vec<SchemaManifest>>;
+        /// Lists manifests matching a selector
+        ///
+        /// The default implementation lists every manifest and filters locally.
+        async fn list_manifests_filtered(
+            &self,
+            selector: &ManifestSelector,
+        ) -> Result<Vec<SchemaManifest>> {
+            Ok(self
+                .list_manifests("")
+                .await?
+                .into_iter()
+                .filter(|m| selector.matches(m))
+                .collect())
+        }
         async fn publish_schema(&self, path: &str, schema: &serde_json::Value) -> Result<()>;
         async fn fetch_schema(&self, path: &str) -> Result<serde_json::Value>;
         async fn delete_schema(&self, path: &str) -> Result<()>;
+        /// Publishes a schema read from `reader`, declared to be `total_size` bytes
+        ///
+        /// The declared size is checked before reading and the stream must
+        /// yield exactly that many bytes. The default implementation buffers
+        /// the body, up to the default `max_schema_size`, and hands the
+        /// parsed schema to [`publish_schema`](Self::publish_schema), which
+        /// applies the backend's own size limit; KV backends should override
+        /// it to store the body in chunks (see
+        /// [`crate::storage::StorageHelper::put_chunked`]).
+        async fn publish_schema_chunked(
+            &self,
+            path: &str,
+            reader: &mut (dyn AsyncRead + Send + Unpin),
+            total_size: u64,
+        ) -> Result<()> {
+            let body = read_schema_body(reader, total_size, 0).await?;
+            let schema = serde_json::from_slice(&body)?;
+            self.publish_schema(path, &schema).await
+        }
+        /// Opens a reader over the serialized schema at `path`
+        ///
+        /// The default implementation serializes the result of
+        /// [`fetch_schema`](Self::fetch_schema) into memory.
+        async fn fetch_schema_streaming(
+            &self,
+            path: &str,
+        ) -> Result<Box<dyn AsyncRead + Send + Unpin>> {
+            let schema = self.fetch_schema(path).await?;
+            Ok(Box::new(std::io::Cursor::new(serde_json::to_vec(&schema)?)))
+        }
+        /// Lists published schema paths starting with `prefix` (empty for all)
+        async fn list_schemas(&self, _prefix: &str) -> Result<Vec<String>> {
+            Err(Error::unsupported("list_schemas"))
+        }
+        /// Publishes a schema as a JSON Patch against the schema currently at `path`
+        ///
+        /// The stored schema must still hash to `base_hash`, otherwise
+        /// `Error::ChecksumMismatch` is returned. The patched schema replaces it
+        /// and the patch is appended to the audit chain returned by
+        /// [`schema_patches`](Self::schema_patches). Returns the new schema hash.
+        ///
+        /// The default implementation is not atomic; backends should override it
+        /// when concurrent publishers are expected.
+        async fn publish_schema_delta(
+            &self,
+            path: &str,
+            base_hash: &str,
+            patch: &[serde_json::Value],
+        ) -> Result<String> {
+            let base = self.fetch_schema(path).await?;
+            let (schema, entry) = SchemaPatch::apply(&base, base_hash, patch)?;
+
+            let mut chain = self.schema_patches(path).await?;
+            chain.push(entry.clone());
+            self.publish_schema(path, &schema).await?;
+            self.publish_schema(
+                &SchemaPatch::chain_path(path),
+                &serde_json::to_value(&chain)?,
+            )
+            .await?;
+
+            Ok(entry.result_hash)
+        }
+        /// Returns the patches applied to `path` via `publish_schema_delta`, oldest first
+        async fn schema_patches(&self, path: &str) -> Result<Vec<SchemaPatch>> {
+            match self.fetch_schema(&SchemaPatch::chain_path(path)).await {
+                Ok(chain) => Ok(serde_json::from_value(chain)?),
+                Err(Error::SchemaNotFound) => Ok(Vec::new()),
+                Err(e) => Err(e),
+            }
+        }
+        /// Captures every manifest and published schema for backup or migration
+        ///
+        /// The default implementation lists and fetches item by item, so it is
+        /// not a consistent point-in-time view under concurrent writes.
+        async fn export_snapshot(&self) -> Result<RegistrySnapshot> {
+            let manifests = self.list_manifests("").await?;
+            let mut schemas = HashMap::new();
+            for path in self.list_schemas("").await? {
+                match self.fetch_schema(&path).await {
+                    Ok(schema) => {
+                        schemas.insert(path, schema);
+                    }
+                    Err(Error::SchemaNotFound) => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            Ok(RegistrySnapshot::new(manifests, schemas))
+        }
+        /// Loads a snapshot produced by [`export_snapshot`](Self::export_snapshot)
+        ///
+        /// Each manifest is validated before registration; failures are
+        /// reported per item rather than aborting the import. Fails outright
+        /// only when the snapshot format is unsupported.
+        async fn import_snapshot(
+            &self,
+            snapshot: &RegistrySnapshot,
+            options: ImportOptions,
+        ) -> Result<ImportReport> {
+            import_snapshot_items(self, snapshot, options).await
+        }
+        /// Returns the schema of `schema_type` from the service's newest instance
+        ///
+        /// Picks, among healthy instances (or ones without instance
+        /// metadata) that publish such a schema, the one with the highest
+        /// semver `service_version`; a leading `v` is ignored and unparseable
+        /// versions are skipped. Fails with `Error::ManifestNotFound` when
+        /// the service has no instances and `Error::SchemaNotFound` when none
+        /// qualifies.
+        async fn resolve_latest_schema(
+            &self,
+            service_name: &str,
+            schema_type: SchemaType,
+        ) -> Result<(SchemaDescriptor, serde_json::Value)> {
+            resolve_schema_by_version(self, service_name, schema_type, None).await
+        }
+        /// Like [`resolve_latest_schema`](Self::resolve_latest_schema), limited
+        /// to service versions matching a Cargo-style range (e.g. `^1.2`)
+        async fn resolve_schema_version(
+            &self,
+            service_name: &str,
+            schema_type: SchemaType,
+            version_range: &str,
+        ) -> Result<(SchemaDescriptor, serde_json::Value)> {
+            let range = semver::VersionReq::parse(version_range)
+                .map_err(|e| Error::validation("version_range", e.to_string()))?;
+            resolve_schema_by_version(self, service_name, schema_type, Some(&range)).await
+        }
+        /// Reports what registering `manifest` would change, without writing
+        ///
+        /// Runs the same admission checks as
+        /// [`register_manifest`](Self::register_manifest) and diffs the
+        /// manifest against the current manifest of the same `instance_id`.
+        /// For a new instance the baseline has no schemas, capabilities or
+        /// instance metadata, so everything shows as added. The default
+        /// implementation only checks the protocol version and validates the
+        /// manifest; registries with a [`RegistryConfig`] also apply its
+        /// checks.
+        async fn register_dry_run(
+            &self,
+            manifest: &SchemaManifest,
+        ) -> Result<crate::manifest::ManifestDiff> {
+            check_protocol_version(manifest)?;
+            manifest.validate()?;
+            diff_with_current(self, manifest).await
+        }
         async fn watch_manifests(
             &self,
             service_name: &str,
@@ -81,6 +251,263 @@ pub mod registry {
         async fn health(&self) -> Result<()>;
     }
 
+    /// Current [`RegistrySnapshot`] format version
+    pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+    /// Serialized contents of a registry
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct RegistrySnapshot {
+        /// Snapshot format version
+        pub format_version: u32,
+        /// When the snapshot was taken (Unix timestamp)
+        pub created_at: i64,
+        /// Every registered manifest
+        pub manifests: Vec<SchemaManifest>,
+        /// Published schemas keyed by registry path
+        pub schemas: HashMap<String, serde_json::Value>,
+    }
+
+    impl RegistrySnapshot {
+        /// Creates a snapshot in the current format
+        pub fn new(
+            manifests: Vec<SchemaManifest>,
+            schemas: HashMap<String, serde_json::Value>,
+        ) -> Self {
+            Self {
+                format_version: SNAPSHOT_FORMAT_VERSION,
+                created_at: chrono::Utc::now().timestamp(),
+                manifests,
+                schemas,
+            }
+        }
+    }
+
+    /// Options for [`SchemaRegistry::import_snapshot`]
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct ImportOptions {
+        /// Leave manifests and schemas that already exist untouched
+        pub skip_existing: bool,
+    }
+
+    /// Per-item outcome of a snapshot import
+    ///
+    /// Items are named `manifest:<instance_id>` or `schema:<path>`.
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct ImportReport {
+        /// Items written to the registry
+        pub imported: Vec<String>,
+        /// Items left alone because they already existed
+        pub skipped: Vec<String>,
+        /// Items that could not be imported, with the reason
+        pub failed: Vec<(String, String)>,
+    }
+
+    impl ImportReport {
+        /// Reports whether every item was imported or skipped
+        pub fn is_success(&self) -> bool {
+            self.failed.is_empty()
+        }
+    }
+
+    /// Rejects manifests written for a protocol version this build can't process
+    ///
+    /// Registries call this before accepting a manifest, so a fleet never
+    /// stores versions its gateways can't read. Fails with
+    /// `Error::IncompatibleVersion`.
+    pub fn check_protocol_version(manifest: &SchemaManifest) -> Result<()> {
+        if crate::version::is_compatible(&manifest.version) {
+            Ok(())
+        } else {
+            Err(Error::incompatible_version(
+                manifest.version.clone(),
+                crate::version::PROTOCOL_VERSION.to_string(),
+            ))
+        }
+    }
+
+    /// Counts the instances of `service_name` (empty for all services) per
+    /// manifest protocol version
+    pub async fn protocol_versions<R: SchemaRegistry + ?Sized>(
+        registry: &R,
+        service_name: &str,
+    ) -> Result<HashMap<String, usize>> {
+        let mut versions = HashMap::new();
+        for manifest in registry.list_manifests(service_name).await? {
+            *versions.entry(manifest.version).or_default() += 1;
+        }
+        Ok(versions)
+    }
+
+    /// Reads a schema body of exactly `total_size` bytes from `reader`
+    ///
+    /// Fails with `Error::SchemaToLarge` when `total_size` exceeds `max_size`
+    /// (the default `max_schema_size` when not positive, since the body is
+    /// buffered) and with `Error::Validation` when the stream is shorter or
+    /// longer than declared. Memory is reserved as bytes arrive rather than
+    /// up front from the declared size.
+    pub async fn read_schema_body(
+        reader: &mut (dyn AsyncRead + Send + Unpin),
+        total_size: u64,
+        max_size: i64,
+    ) -> Result<Vec<u8>> {
+        let max_size = match max_size {
+            size if size > 0 => size as u64,
+            _ => RegistryConfig::default().max_schema_size as u64,
+        };
+        if total_size > max_size {
+            return Err(Error::schema_too_large(
+                usize::try_from(total_size).unwrap_or(usize::MAX),
+                max_size as usize,
+            ));
+        }
+
+        let mut body = Vec::new();
+        reader.take(total_size + 1).read_to_end(&mut body).await?;
+        if body.len() as u64 > total_size {
+            return Err(Error::validation(
+                "total_size",
+                format!("stream is longer than the declared {total_size} bytes"),
+            ));
+        }
+        if (body.len() as u64) < total_size {
+            return Err(Error::validation(
+                "total_size",
+                format!("stream ended after {} of {total_size} bytes", body.len()),
+            ));
+        }
+        Ok(body)
+    }
+
+    /// Loads the schema a descriptor points at: inline, or from `registry`
+    ///
+    /// HTTP locations are not fetched and yield `Error::Unsupported`.
+    pub(crate) async fn descriptor_schema<R: SchemaRegistry + ?Sized>(
+        registry: &R,
+        descriptor: &SchemaDescriptor,
+    ) -> Result<serde_json::Value> {
+        match descriptor.location.location_type {
+            LocationType::Inline => descriptor
+                .inline_schema
+                .clone()
+                .ok_or_else(|| Error::invalid_location("inline schema is missing")),
+            LocationType::Registry => {
+                let path = descriptor
+                    .location
+                    .registry_path
+                    .as_deref()
+                    .ok_or_else(|| Error::invalid_location("registry path is missing"))?;
+                registry.fetch_schema(path).await
+            }
+            LocationType::HTTP => Err(Error::unsupported("fetching HTTP schema locations")),
+        }
+    }
+
+    /// Shared logic behind [`SchemaRegistry::resolve_latest_schema`] and
+    /// [`SchemaRegistry::resolve_schema_version`]
+    async fn resolve_schema_by_version<R: SchemaRegistry + ?Sized>(
+        registry: &R,
+        service_name: &str,
+        schema_type: SchemaType,
+        range: Option<&semver::VersionReq>,
+    ) -> Result<(SchemaDescriptor, serde_json::Value)> {
+        let manifests = registry.list_manifests(service_name).await?;
+        if manifests.is_empty() {
+            return Err(Error::ManifestNotFound);
+        }
+
+        let descriptor = manifests
+            .iter()
+            .filter(|m| {
+                m.instance
+                    .as_ref()
+                    .map_or(true, |i| i.status == InstanceStatus::Healthy)
+            })
+            .filter_map(|m| {
+                let version = m
+                    .service_version
+                    .strip_prefix('v')
+                    .unwrap_or(&m.service_version);
+                let version = semver::Version::parse(version).ok()?;
+                if range.is_some_and(|r| !r.matches(&version)) {
+                    return None;
+                }
+                Some((version, m.get_schema(schema_type)?))
+            })
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, descriptor)| descriptor.clone())
+            .ok_or(Error::SchemaNotFound)?;
+
+        let schema = descriptor_schema(registry, &descriptor).await?;
+        Ok((descriptor, schema))
+    }
+
+    /// Shared diff logic behind [`SchemaRegistry::register_dry_run`], run
+    /// after the registry's admission checks
+    async fn diff_with_current<R: SchemaRegistry + ?Sized>(
+        registry: &R,
+        manifest: &SchemaManifest,
+    ) -> Result<crate::manifest::ManifestDiff> {
+        let current = match registry.get_manifest(&manifest.instance_id).await {
+            Ok(current) => current,
+            Err(Error::ManifestNotFound) => SchemaManifest {
+                schemas: Vec::new(),
+                capabilities: Vec::new(),
+                instance: None,
+                ..manifest.clone()
+            },
+            Err(e) => return Err(e),
+        };
+        Ok(crate::manifest::diff_manifests(&current, manifest))
+    }
+
+    /// Shared import logic behind [`SchemaRegistry::import_snapshot`]
+    async fn import_snapshot_items<R: SchemaRegistry + ?Sized>(
+        registry: &R,
+        snapshot: &RegistrySnapshot,
+        options: ImportOptions,
+    ) -> Result<ImportReport> {
+        if snapshot.format_version != SNAPSHOT_FORMAT_VERSION {
+            return Err(Error::incompatible_version(
+                snapshot.format_version.to_string(),
+                SNAPSHOT_FORMAT_VERSION.to_string(),
+            ));
+        }
+
+        let mut report = ImportReport::default();
+
+        for manifest in &snapshot.manifests {
+            let item = format!("manifest:{}", manifest.instance_id);
+            if let Err(e) = manifest.validate() {
+                report.failed.push((item, e.to_string()));
+                continue;
+            }
+            if options.skip_existing && registry.get_manifest(&manifest.instance_id).await.is_ok() {
+                report.skipped.push(item);
+                continue;
+            }
+            match registry.register_manifest(manifest).await {
+                Ok(()) => report.imported.push(item),
+                Err(e) => report.failed.push((item, e.to_string())),
+            }
+        }
+
+        let mut paths: Vec<&String> = snapshot.schemas.keys().collect();
+        paths.sort();
+        for path in paths {
+            let item = format!("schema:{path}");
+            if options.skip_existing && registry.fetch_schema(path).await.is_ok() {
+                report.skipped.push(item);
+                continue;
+            }
+            match registry.publish_schema(path, &snapshot.schemas[path]).await {
+                Ok(()) => report.imported.push(item),
+                Err(e) => report.failed.push((item, e.to_string())),
+            }
+        }
+
+        Ok(report)
+    }
+
     pub trait ManifestChangeHandler: Send + Sync {
         fn on_change(&self, event: &ManifestEvent);
     }
@@ -107,11 +534,44 @@ pub mod registry {
         }
     }
 
+    /// Receives webhook events raised by a registry (e.g. traffic shifts)
+    pub trait WebhookDispatcher: Send + Sync {
+        fn dispatch(&self, event: &crate::types::WebhookEvent);
+    }
+
+    impl<F> WebhookDispatcher for F
+    where
+        F: Fn(&crate::types::WebhookEvent) + Send + Sync,
+    {
+        fn dispatch(&self, event: &crate::types::WebhookEvent) {
+            self(event)
+        }
+    }
+
     #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
     pub struct ManifestEvent {
         pub event_type: EventType,
         pub manifest: SchemaManifest,
         pub timestamp: i64,
+        /// Position in the event log that recorded the event, usable as a
+        /// resume cursor with that log; 0 until recorded
+        #[serde(default)]
+        pub sequence: u64,
+    }
+
+    impl ManifestEvent {
+        /// Creates an unrecorded event stamped with the current time
+        ///
+        /// The sequence is assigned by whichever event log records the
+        /// event, in the order it records them.
+        pub fn new(event_type: EventType, manifest: SchemaManifest) -> Self {
+            Self {
+                event_type,
+                manifest,
+                timestamp: chrono::Utc::now().timestamp(),
+                sequence: 0,
+            }
+        }
     }
 
     #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -122,15 +582,26 @@ pub mod registry {
         pub timestamp: i64,
     }
 
+    /// Kind of change carried by a registry event
+    ///
+    /// Registration and update are reported separately so consumers can tell a
+    /// brand-new instance apart from one coming back after a restart.
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
     #[serde(rename_all = "lowercase")]
     pub enum EventType {
+        /// An instance was registered for the first time
         #[serde(rename = "added")]
         Added,
+        /// An existing instance was changed via `update_manifest`
         #[serde(rename = "updated")]
         Updated,
+        /// An instance was deleted
         #[serde(rename = "removed")]
         Removed,
+        /// `register_manifest` was called for an `instance_id` that already
+        /// existed, e.g. an instance re-registering after a restart
+        #[serde(rename = "reregistered")]
+        Reregistered,
     }
 
     impl std::fmt::Display for EventType {
@@ -139,6 +610,7 @@ pub mod registry {
                 EventType::Added => "added",
                 EventType::Updated => "updated",
                 EventType::Removed => "removed",
+                EventType::Reregistered => "reregistered",
             };
             write!(f, "{s}")
         }
@@ -152,6 +624,18 @@ pub mod registry {
         pub max_schema_size: i64,
         pub compression_threshold: i64,
         pub ttl: i64,
+        /// Reject manifests whose checksum is empty or doesn't match their schemas
+        #[serde(default)]
+        pub require_checksum: bool,
+        /// Reject manifests whose registry-located schemas aren't published
+        #[serde(default)]
+        pub verify_registry_schemas: bool,
+        /// Reject manifests whose schemas declare a `content_type` their
+        /// schema type doesn't allow (see [`SchemaType::allowed_content_types`])
+        ///
+        /// [`SchemaType::allowed_content_types`]: crate::types::SchemaType::allowed_content_types
+        #[serde(default)]
+        pub strict_content_types: bool,
     }
 
     impl Default for RegistryConfig {
@@ -163,7 +647,161 @@ pub mod registry {
                 max_schema_size: 1024 * 1024,
                 compression_threshold: 100 * 1024,
                 ttl: 0,
+                require_checksum: false,
+                verify_registry_schemas: false,
+                strict_content_types: false,
+            }
+        }
+    }
+
+    impl RegistryConfig {
+        /// Parses `backend_config` as a typed backend configuration
+        ///
+        /// ```
+        /// use farp::registry::backend::RedisConfig;
+        /// use farp::registry::RegistryConfig;
+        ///
+        /// let mut config = RegistryConfig::default();
+        /// config
+        ///     .backend_config
+        ///     .insert("url".to_string(), "redis://localhost:6379".into());
+        /// let redis: RedisConfig = config.typed_backend_config().unwrap();
+        /// assert_eq!(redis.pool_size, 10);
+        /// ```
+        pub fn typed_backend_config<T: backend::BackendConfig>(&self) -> Result<T> {
+            T::from_map(&self.backend_config)
+        }
+
+        /// Enforces `require_checksum` for a manifest entering the registry
+        pub fn check_manifest_checksum(&self, manifest: &SchemaManifest) -> Result<()> {
+            if !self.require_checksum {
+                return Ok(());
+            }
+            if manifest.checksum.is_empty() {
+                return Err(Error::validation("checksum", "checksum is required"));
+            }
+
+            let expected = crate::manifest::calculate_manifest_checksum(manifest)?;
+            if manifest.checksum != expected {
+                return Err(Error::validation(
+                    "checksum",
+                    format!(
+                        "checksum mismatch: expected {expected}, got {}",
+                        manifest.checksum
+                    ),
+                ));
+            }
+            Ok(())
+        }
+
+        /// Enforces `strict_content_types` for a manifest entering the registry
+        ///
+        /// Every mismatching schema is reported in one `Error::Validation`.
+        pub fn check_content_types(&self, manifest: &SchemaManifest) -> Result<()> {
+            if !self.strict_content_types {
+                return Ok(());
+            }
+            let problems: Vec<String> = manifest
+                .schemas
+                .iter()
+                .filter(|d| !d.schema_type.accepts_content_type(&d.content_type))
+                .map(|d| format!("{} for {}", d.content_type, d.schema_type))
+                .collect();
+            if problems.is_empty() {
+                return Ok(());
+            }
+            Err(Error::validation(
+                "content_type",
+                format!("unexpected content types: {}", problems.join(", ")),
+            ))
+        }
+
+        /// Enforces `verify_registry_schemas` for a manifest entering `registry`
+        ///
+        /// Every schema with `LocationType::Registry` must be published at its
+        /// `registry_path`, and when the descriptor carries a hash the published
+        /// schema must match it. All offending paths are reported together as
+        /// `Error::SchemaFetchFailed`.
+        pub async fn check_registry_schemas<R: SchemaRegistry + ?Sized>(
+            &self,
+            registry: &R,
+            manifest: &SchemaManifest,
+        ) -> Result<()> {
+            if !self.verify_registry_schemas {
+                return Ok(());
+            }
+
+            let mut problems = Vec::new();
+            for descriptor in &manifest.schemas {
+                if descriptor.location.location_type != crate::types::LocationType::Registry {
+                    continue;
+                }
+                let Some(path) = descriptor.location.registry_path.as_deref() else {
+                    continue; // manifest validation reports the missing path
+                };
+                match registry.fetch_schema(path).await {
+                    Ok(schema) if !descriptor.hash.is_empty() => {
+                        let hash = crate::manifest::calculate_schema_checksum(&schema)?;
+                        if hash != descriptor.hash {
+                            problems.push(format!("{path} (hash mismatch)"));
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(Error::SchemaNotFound) => problems.push(format!("{path} (missing)")),
+                    Err(e) => return Err(e),
+                }
+            }
+
+            if problems.is_empty() {
+                Ok(())
+            } else {
+                Err(Error::schema_fetch_failed(format!(
+                    "registry schemas not published: {}",
+                    problems.join(", ")
+                )))
+            }
+        }
+    }
+
+    /// Query for [`SchemaRegistry::list_manifests_filtered`]
+    ///
+    /// Unset fields match everything; set fields must all match.
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct ManifestSelector {
+        /// Service name glob (e.g. `payment-*`)
+        pub service_name: Option<String>,
+        /// Labels the instance must carry with exactly these values
+        pub labels: HashMap<String, String>,
+        /// Required instance status
+        pub status: Option<crate::types::InstanceStatus>,
+        /// Required instance role
+        pub role: Option<crate::types::InstanceRole>,
+    }
+
+    impl ManifestSelector {
+        /// Reports whether a manifest satisfies every set criterion
+        ///
+        /// Label, status and role criteria never match manifests without
+        /// instance metadata.
+        pub fn matches(&self, manifest: &SchemaManifest) -> bool {
+            if let Some(pattern) = &self.service_name {
+                if !crate::util::glob_match(pattern, &manifest.service_name) {
+                    return false;
+                }
+            }
+            if self.labels.is_empty() && self.status.is_none() && self.role.is_none() {
+                return true;
             }
+
+            let Some(instance) = &manifest.instance else {
+                return false;
+            };
+            let labels_match = self.labels.iter().all(|(key, value)| {
+                instance.labels.as_ref().and_then(|labels| labels.get(key)) == Some(value)
+            });
+            labels_match
+                && self.status.map_or(true, |status| instance.status == status)
+                && self.role.map_or(true, |role| instance.role == Some(role))
         }
     }
 
@@ -175,6 +813,53 @@ pub mod registry {
         fn size(&self) -> usize;
     }
 
+    /// A JSON Patch applied to a published schema, kept for audit
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct SchemaPatch {
+        /// Hash of the schema the patch was applied to
+        pub base_hash: String,
+        /// Hash of the resulting schema
+        pub result_hash: String,
+        /// RFC 6902 operations
+        pub patch: Vec<serde_json::Value>,
+        pub timestamp: i64,
+    }
+
+    impl SchemaPatch {
+        /// Path under which backends without native support store the patch chain
+        pub fn chain_path(path: &str) -> String {
+            format!("{path}#patches")
+        }
+
+        /// Verifies `base` against `base_hash` and applies `patch` to it
+        ///
+        /// Returns the patched schema and the audit entry describing the change.
+        pub fn apply(
+            base: &serde_json::Value,
+            base_hash: &str,
+            patch: &[serde_json::Value],
+        ) -> Result<(serde_json::Value, Self)> {
+            let actual = crate::manifest::calculate_schema_checksum(base)?;
+            if actual != base_hash {
+                return Err(Error::checksum_mismatch(base_hash.to_string(), actual));
+            }
+
+            let mut schema = base.clone();
+            crate::patch::apply_patch(&mut schema, patch)?;
+            let result_hash = crate::manifest::calculate_schema_checksum(&schema)?;
+
+            Ok((
+                schema,
+                Self {
+                    base_hash: actual,
+                    result_hash,
+                    patch: patch.to_vec(),
+                    timestamp: chrono::Utc::now().timestamp(),
+                },
+            ))
+        }
+    }
+
     #[derive(Debug, Clone)]
     pub struct FetchOptions {
         pub use_cache: bool,
@@ -211,8 +896,70 @@ pub mod registry {
         }
     }
 
+    /// Reconnection behavior for long-lived manifest watches
+    #[derive(Debug, Clone)]
+    pub struct WatchConfig {
+        /// Delay before the first resubscribe attempt
+        pub initial_backoff: std::time::Duration,
+        /// Upper bound for the delay between attempts
+        pub max_backoff: std::time::Duration,
+        /// Factor applied to the delay after each failed attempt
+        pub multiplier: f64,
+    }
+
+    impl Default for WatchConfig {
+        fn default() -> Self {
+            Self {
+                initial_backoff: std::time::Duration::from_millis(100),
+                max_backoff: std::time::Duration::from_secs(30),
+                multiplier: 2.0,
+            }
+        }
+    }
+
+    impl WatchConfig {
+        /// Returns the delay to use after `current`
+        pub fn next_backoff(&self, current: std::time::Duration) -> std::time::Duration {
+            current.mul_f64(self.multiplier).min(self.max_backoff)
+        }
+    }
+
+    /// Handle to a running watch
+    ///
+    /// The watch keeps running until `stop` is called or the registry is closed;
+    /// dropping the handle detaches it without stopping.
+    #[derive(Debug)]
+    pub struct WatchHandle {
+        stop: tokio::sync::watch::Sender<bool>,
+    }
+
+    impl WatchHandle {
+        /// Creates a handle and the receiver the watch task listens on
+        pub fn new() -> (Self, tokio::sync::watch::Receiver<bool>) {
+            let (stop, stopped) = tokio::sync::watch::channel(false);
+            (Self { stop }, stopped)
+        }
+
+        /// Stops the watch
+        pub fn stop(&self) {
+            let _ = self.stop.send(true);
+        }
+
+        /// Reports whether `stop` has been called
+        pub fn is_stopped(&self) -> bool {
+            *self.stop.borrow()
+        }
+    }
+
+    pub mod backend;
+    pub mod cached;
+    pub mod gc;
+
     #[cfg(feature = "memory-registry")]
     pub mod memory;
+
+    #[cfg(feature = "dynamo-registry")]
+    pub mod dynamo;
 }
 
 // Providers
@@ -225,6 +972,10 @@ pub mod gateway;
 // Merger for OpenAPI composition
 pub mod merger;
 
+// HTTP pull server
+#[cfg(feature = "server")]
+pub mod server;
+
 // Re-exports for convenience
 pub use errors::{Error, Result};
 pub use version::{get_version, is_compatible, PROTOCOL_VERSION};
@@ -3,8 +3,9 @@
 use crate::errors::{Error, Result};
 use crate::types::*;
 use crate::version::{is_compatible, PROTOCOL_VERSION};
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 /// Creates a new schema manifest with default values
 ///
@@ -40,8 +41,11 @@ pub fn new_manifest(
         auth: None,
         webhook: None,
         hints: None,
+        quota: None,
         updated_at: chrono::Utc::now().timestamp(),
         checksum: String::new(),
+        signature: None,
+        checksum_memo: ChecksumMemo::default(),
     }
 }
 
@@ -52,6 +56,9 @@ impl SchemaManifest {
     }
 
     /// Adds a capability to the manifest
+    ///
+    /// Capabilities keep their insertion order; adding one that is already
+    /// present is a no-op.
     pub fn add_capability(&mut self, capability: impl Into<String>) {
         let cap = capability.into();
         if !self.capabilities.contains(&cap) {
@@ -59,6 +66,114 @@ impl SchemaManifest {
         }
     }
 
+    /// Removes duplicate capabilities, keeping the first occurrence of each
+    ///
+    /// Use this after assigning `capabilities` directly; the remaining
+    /// entries keep their relative order.
+    pub fn dedup_capabilities(&mut self) {
+        let mut seen = HashSet::new();
+        self.capabilities.retain(|cap| seen.insert(cap.clone()));
+    }
+
+    /// Drops empty optional structures so the manifest serializes minimally
+    ///
+    /// Optional fields holding only empty or default values are reset to
+    /// `None` (where the gateway applies the same defaults), duplicate
+    /// capabilities, blank and duplicate routing tags and rewrite rules with
+    /// neither pattern nor replacement are removed. Meaning, the schema set
+    /// and the checksum are unchanged.
+    pub fn compact(&mut self) {
+        self.dedup_capabilities();
+
+        if let Some(instance) = self.instance.as_mut() {
+            if instance.labels.as_ref().is_some_and(HashMap::is_empty) {
+                instance.labels = None;
+            }
+        }
+
+        if self.endpoints.health_check == Some(HealthCheckConfig::default()) {
+            self.endpoints.health_check = None;
+        }
+
+        let routing = &mut self.routing;
+        if routing.base_path.as_deref() == Some("") {
+            routing.base_path = None;
+        }
+        if routing.subdomain.as_deref() == Some("") {
+            routing.subdomain = None;
+        }
+        routing
+            .rewrite
+            .retain(|rule| !(rule.pattern.is_empty() && rule.replacement.is_empty()));
+        let mut seen = HashSet::new();
+        routing
+            .tags
+            .retain(|tag| !tag.is_empty() && seen.insert(tag.clone()));
+
+        if self.hints.as_ref().is_some_and(|hints| {
+            hints.recommended_timeout.is_none()
+                && hints.expected_latency.is_none()
+                && hints.scaling.is_none()
+                && hints.dependencies.is_empty()
+                && hints.resiliency.is_none()
+                && hints.sensitivity.is_none()
+        }) {
+            self.hints = None;
+        }
+
+        if self.webhook.as_ref().is_some_and(|webhook| {
+            webhook.service_webhook.is_none()
+                && webhook.gateway_webhook.is_none()
+                && webhook.secret.is_none()
+                && webhook.subscribe_events.is_empty()
+                && webhook.publish_events.is_empty()
+                && webhook.retry.is_none()
+                && webhook.http_routes.is_none()
+        }) {
+            self.webhook = None;
+        }
+
+        for schema in &mut self.schemas {
+            if schema
+                .location
+                .headers
+                .as_ref()
+                .is_some_and(HashMap::is_empty)
+            {
+                schema.location.headers = None;
+            }
+            if schema.metadata.as_ref().is_some_and(|m| {
+                m.graphql.is_none()
+                    && m.grpc.is_none()
+                    && m.openapi.is_none()
+                    && m.asyncapi.is_none()
+                    && m.orpc.is_none()
+            }) {
+                schema.metadata = None;
+            }
+        }
+    }
+
+    /// Replaces the schema of the given type (or adds it) and refreshes the checksum
+    ///
+    /// Produces the same checksum as `add_schema` followed by `update_checksum`,
+    /// but keeps the schema's position so the other entries are untouched.
+    pub fn update_schema(
+        &mut self,
+        schema_type: SchemaType,
+        descriptor: SchemaDescriptor,
+    ) -> Result<()> {
+        match self
+            .schemas
+            .iter_mut()
+            .find(|s| s.schema_type == schema_type && s.schema_id == descriptor.schema_id)
+        {
+            Some(existing) => *existing = descriptor,
+            None => self.schemas.push(descriptor),
+        }
+        self.update_checksum()
+    }
+
     /// Updates the checksum based on all schema hashes
     pub fn update_checksum(&mut self) -> Result<()> {
         let checksum = calculate_manifest_checksum(self)?;
@@ -68,10 +183,21 @@ impl SchemaManifest {
     }
 
     /// Validates the manifest for correctness
+    ///
+    /// Fails fast with the first problem found; see [`validate_all`](Self::validate_all)
+    /// to collect every problem at once.
     pub fn validate(&self) -> Result<()> {
+        self.validate_all()
+            .map_err(|mut errors| errors.swap_remove(0))
+    }
+
+    /// Validates the manifest, collecting every problem instead of stopping at the first
+    pub fn validate_all(&self) -> std::result::Result<(), Vec<Error>> {
+        let mut errors = Vec::new();
+
         // Check protocol version compatibility
         if !is_compatible(&self.version) {
-            return Err(Error::incompatible_version(
+            errors.push(Error::incompatible_version(
                 self.version.clone(),
                 PROTOCOL_VERSION.to_string(),
             ));
@@ -79,19 +205,19 @@ impl SchemaManifest {
 
         // Check required fields
         if self.service_name.is_empty() {
-            return Err(Error::validation(
+            errors.push(Error::validation(
                 "service_name",
                 "service name is required",
             ));
         }
 
         if self.instance_id.is_empty() {
-            return Err(Error::validation("instance_id", "instance ID is required"));
+            errors.push(Error::validation("instance_id", "instance ID is required"));
         }
 
         // Validate health endpoint
         if self.endpoints.health.is_empty() {
-            return Err(Error::validation(
+            errors.push(Error::validation(
                 "endpoints.health",
                 "health endpoint is required",
             ));
@@ -99,25 +225,201 @@ impl SchemaManifest {
 
         // Validate each schema descriptor
         for (i, schema) in self.schemas.iter().enumerate() {
-            validate_schema_descriptor(schema).map_err(|e| {
-                Error::invalid_manifest(format!("invalid schema at index {i}: {e}"))
-            })?;
+            if let Err(e) = validate_schema_descriptor(schema) {
+                errors.push(Error::invalid_manifest(format!(
+                    "invalid schema at index {i}: {e}"
+                )));
+            }
+        }
+
+        // Validate capabilities
+        let mut seen = HashSet::new();
+        for capability in &self.capabilities {
+            if capability.is_empty() {
+                errors.push(Error::validation(
+                    "capabilities",
+                    "capability must not be empty",
+                ));
+            } else if !seen.insert(capability.as_str()) {
+                errors.push(Error::validation(
+                    "capabilities",
+                    format!("duplicate capability: {capability}"),
+                ));
+            }
         }
 
         // Verify checksum if present
         if !self.checksum.is_empty() {
-            let expected = calculate_manifest_checksum(self)?;
-            if self.checksum != expected {
-                return Err(Error::checksum_mismatch(expected, self.checksum.clone()));
+            match calculate_manifest_checksum(self) {
+                Ok(expected) if self.checksum != expected => {
+                    errors.push(Error::checksum_mismatch(expected, self.checksum.clone()));
+                }
+                Ok(_) => {}
+                Err(e) => errors.push(e),
             }
         }
 
-        Ok(())
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
     }
 
     /// Retrieves a schema descriptor by type
+    ///
+    /// When several schemas share the type, the one without a `schema_id` is
+    /// preferred, then the first declared.
     pub fn get_schema(&self, schema_type: SchemaType) -> Option<&SchemaDescriptor> {
-        self.schemas.iter().find(|s| s.schema_type == schema_type)
+        self.schemas
+            .iter()
+            .find(|s| s.schema_type == schema_type && s.schema_id.is_none())
+            .or_else(|| self.schemas.iter().find(|s| s.schema_type == schema_type))
+    }
+
+    /// Retrieves a schema descriptor by type and `schema_id`
+    pub fn get_schema_by_id(
+        &self,
+        schema_type: SchemaType,
+        schema_id: Option<&str>,
+    ) -> Option<&SchemaDescriptor> {
+        self.schemas
+            .iter()
+            .find(|s| s.schema_type == schema_type && s.schema_id.as_deref() == schema_id)
+    }
+
+    /// Retrieves every schema descriptor of a type, in declaration order
+    pub fn get_schemas(&self, schema_type: SchemaType) -> Vec<&SchemaDescriptor> {
+        self.schemas
+            .iter()
+            .filter(|s| s.schema_type == schema_type)
+            .collect()
+    }
+
+    /// GraphQL metadata of the first GraphQL schema that declares any
+    pub fn graphql_metadata(&self) -> Option<&GraphQLMetadata> {
+        self.protocol_metadata(SchemaType::GraphQL, |m| m.graphql.as_ref())
+    }
+
+    /// gRPC metadata of the first gRPC schema that declares any
+    pub fn grpc_metadata(&self) -> Option<&GRPCMetadata> {
+        self.protocol_metadata(SchemaType::GRPC, |m| m.grpc.as_ref())
+    }
+
+    /// OpenAPI metadata of the first OpenAPI schema that declares any
+    pub fn openapi_metadata(&self) -> Option<&OpenAPIMetadata> {
+        self.protocol_metadata(SchemaType::OpenAPI, |m| m.openapi.as_ref())
+    }
+
+    /// AsyncAPI metadata of the first AsyncAPI schema that declares any
+    pub fn asyncapi_metadata(&self) -> Option<&AsyncAPIMetadata> {
+        self.protocol_metadata(SchemaType::AsyncAPI, |m| m.asyncapi.as_ref())
+    }
+
+    /// oRPC metadata of the first oRPC schema that declares any
+    pub fn orpc_metadata(&self) -> Option<&ORPCMetadata> {
+        self.protocol_metadata(SchemaType::ORPC, |m| m.orpc.as_ref())
+    }
+
+    /// Composition settings of the first OpenAPI schema that declares any
+    pub fn openapi_composition(&self) -> Option<&CompositionConfig> {
+        self.protocol_metadata(SchemaType::OpenAPI, |m| {
+            m.openapi.as_ref().and_then(|o| o.composition.as_ref())
+        })
+    }
+
+    /// Derives the capabilities implied by the published schemas
+    ///
+    /// OpenAPI implies REST, gRPC implies gRPC and GraphQL implies GraphQL.
+    /// AsyncAPI schemas contribute WebSocket, MQTT or AMQP according to the
+    /// protocol in their metadata and the servers of their inline spec.
+    /// Capabilities are returned in first-seen order without duplicates.
+    pub fn infer_capabilities(&self) -> Vec<Capability> {
+        let mut capabilities = Vec::new();
+        let mut add = |capability: Capability| {
+            if !capabilities.contains(&capability) {
+                capabilities.push(capability);
+            }
+        };
+        for schema in &self.schemas {
+            match schema.schema_type {
+                SchemaType::OpenAPI => add(Capability::REST),
+                SchemaType::GRPC => add(Capability::GRPC),
+                SchemaType::GraphQL => add(Capability::GraphQL),
+                SchemaType::AsyncAPI => {
+                    let declared = schema
+                        .metadata
+                        .as_ref()
+                        .and_then(|m| m.asyncapi.as_ref())
+                        .map(|m| m.protocol.as_str());
+                    let servers = schema
+                        .inline_schema
+                        .as_ref()
+                        .and_then(|s| s.get("servers"))
+                        .and_then(|s| s.as_object())
+                        .into_iter()
+                        .flat_map(|servers| servers.values())
+                        .filter_map(|server| server.get("protocol")?.as_str());
+                    for protocol in declared.into_iter().chain(servers) {
+                        if let Some(capability) = Capability::from_protocol(protocol) {
+                            add(capability);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        capabilities
+    }
+
+    /// Finds the first schema of a type whose metadata yields a value
+    fn protocol_metadata<'a, T>(
+        &'a self,
+        schema_type: SchemaType,
+        select: impl Fn(&'a ProtocolMetadata) -> Option<&'a T>,
+    ) -> Option<&'a T> {
+        self.schemas
+            .iter()
+            .filter(|s| s.schema_type == schema_type)
+            .find_map(|s| s.metadata.as_ref().and_then(&select))
+    }
+
+    /// Returns a copy safe to export to less-trusted systems
+    ///
+    /// Uses the default [`RedactOptions`]; see [`redacted_with`](Self::redacted_with).
+    pub fn redacted(&self) -> SchemaManifest {
+        self.redacted_with(&RedactOptions::default())
+    }
+
+    /// Returns a copy with secrets and internal hints removed
+    ///
+    /// Clears `webhook.secret` and `auth.token_validation_url`, drops instance
+    /// labels whose key starts with one of `options.internal_label_prefixes`
+    /// and removes `hints.dependencies`. The signature no longer matches the
+    /// redacted content, so it is dropped too.
+    pub fn redacted_with(&self, options: &RedactOptions) -> SchemaManifest {
+        let mut manifest = self.clone();
+
+        if let Some(webhook) = manifest.webhook.as_mut() {
+            webhook.secret = None;
+        }
+        if let Some(auth) = manifest.auth.as_mut() {
+            auth.token_validation_url = None;
+        }
+        if let Some(labels) = manifest.instance.as_mut().and_then(|i| i.labels.as_mut()) {
+            labels.retain(|key, _| {
+                !options
+                    .internal_label_prefixes
+                    .iter()
+                    .any(|prefix| key.starts_with(prefix.as_str()))
+            });
+        }
+        if let Some(hints) = manifest.hints.as_mut() {
+            hints.dependencies.clear();
+        }
+        manifest.signature = None;
+
+        manifest
     }
 
     /// Checks if the manifest includes a specific capability
@@ -136,9 +438,151 @@ impl SchemaManifest {
     }
 
     /// Deserializes a manifest from JSON
+    ///
+    /// Use [`SchemaManifest::from_json_limited`] for manifests from
+    /// untrusted sources.
     pub fn from_json(data: &[u8]) -> Result<Self> {
         serde_json::from_slice(data).map_err(|e| Error::invalid_manifest(e.to_string()))
     }
+
+    /// Deserializes a manifest from JSON, rejecting payloads beyond `limits`
+    ///
+    /// This is the recommended entry point for manifests received from
+    /// untrusted peers: the payload's size, nesting depth and container
+    /// lengths are checked in a single pass before anything is allocated,
+    /// so hostile input fails fast with `Error::InvalidManifest`.
+    pub fn from_json_limited(data: &[u8], limits: &DeserializeLimits) -> Result<Self> {
+        check_json_limits(data, limits)?;
+        let mut deserializer = serde_json::Deserializer::from_slice(data);
+        let manifest = Self::deserialize(&mut deserializer)
+            .map_err(|e| Error::invalid_manifest(e.to_string()))?;
+        deserializer
+            .end()
+            .map_err(|e| Error::invalid_manifest(e.to_string()))?;
+        Ok(manifest)
+    }
+
+    /// Serializes the manifest to the compact binary format
+    ///
+    /// Layout: [`BINARY_MAGIC`], one [`BINARY_FORMAT_VERSION`] byte, then the
+    /// manifest as MessagePack.
+    pub fn to_binary(&self) -> Vec<u8> {
+        let value = serde_json::to_value(self).expect("manifest serializes to JSON");
+        let mut out = Vec::with_capacity(1024);
+        out.extend_from_slice(&BINARY_MAGIC);
+        out.push(BINARY_FORMAT_VERSION);
+        crate::msgpack::encode(&value, &mut out);
+        out
+    }
+
+    /// Deserializes a manifest from the compact binary format
+    ///
+    /// Returns `Error::IncompatibleVersion` for a format version this build
+    /// doesn't know, and `Error::InvalidManifest` for anything malformed.
+    pub fn from_binary(data: &[u8]) -> Result<Self> {
+        let header_len = BINARY_MAGIC.len() + 1;
+        if data.len() < header_len {
+            return Err(Error::invalid_manifest("binary manifest is truncated"));
+        }
+        if data[..BINARY_MAGIC.len()] != BINARY_MAGIC {
+            return Err(Error::invalid_manifest("not a binary FARP manifest"));
+        }
+        let version = data[BINARY_MAGIC.len()];
+        if version != BINARY_FORMAT_VERSION {
+            return Err(Error::incompatible_version(
+                version.to_string(),
+                BINARY_FORMAT_VERSION.to_string(),
+            ));
+        }
+
+        let value = crate::msgpack::decode(&data[header_len..])?;
+        serde_json::from_value(value).map_err(|e| Error::invalid_manifest(e.to_string()))
+    }
+}
+
+/// Leading bytes of a binary manifest
+pub const BINARY_MAGIC: [u8; 4] = *b"FARP";
+
+/// Version of the binary manifest layout written by [`SchemaManifest::to_binary`]
+pub const BINARY_FORMAT_VERSION: u8 = 1;
+
+/// Bounds enforced by [`SchemaManifest::from_json_limited`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeserializeLimits {
+    /// Maximum payload size in bytes
+    pub max_size: usize,
+    /// Maximum nesting depth of arrays and objects
+    pub max_depth: usize,
+    /// Maximum number of elements in any single array or object
+    pub max_elements: usize,
+}
+
+impl Default for DeserializeLimits {
+    fn default() -> Self {
+        Self {
+            max_size: 4 * 1024 * 1024,
+            max_depth: 32,
+            max_elements: 10_000,
+        }
+    }
+}
+
+/// Scans JSON structure without parsing values, enforcing `limits`
+///
+/// Malformed JSON that stays within the limits is left for the
+/// deserializer to reject.
+fn check_json_limits(data: &[u8], limits: &DeserializeLimits) -> Result<()> {
+    if data.len() > limits.max_size {
+        return Err(Error::invalid_manifest(format!(
+            "manifest is {} bytes, limit is {}",
+            data.len(),
+            limits.max_size
+        )));
+    }
+
+    // Element count of each open container, innermost last
+    let mut open: Vec<usize> = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    for &byte in data {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'[' | b'{' => {
+                if open.len() == limits.max_depth {
+                    return Err(Error::invalid_manifest(format!(
+                        "manifest nesting exceeds depth {}",
+                        limits.max_depth
+                    )));
+                }
+                open.push(1);
+            }
+            b']' | b'}' => {
+                open.pop();
+            }
+            b',' => {
+                if let Some(count) = open.last_mut() {
+                    *count += 1;
+                    if *count > limits.max_elements {
+                        return Err(Error::invalid_manifest(format!(
+                            "manifest container exceeds {} elements",
+                            limits.max_elements
+                        )));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
 }
 
 /// Validates a schema descriptor
@@ -191,6 +635,94 @@ pub fn validate_schema_descriptor(sd: &SchemaDescriptor) -> Result<()> {
     Ok(())
 }
 
+/// Guesses the schema type of an unlabeled schema document
+///
+/// Looks for discriminating keys: `openapi`/`swagger`, `asyncapi` and `orpc`
+/// version fields, GraphQL SDL or introspection results, protobuf `syntax`
+/// markers, Avro named types and Thrift IDL summaries. Bare strings are
+/// treated as IDL source. Returns `None` when nothing matches.
+///
+/// # Examples
+///
+/// ```
+/// use farp::manifest::detect_schema_type;
+/// use farp::types::SchemaType;
+///
+/// let doc = serde_json::json!({"openapi": "3.1.0", "paths": {}});
+/// assert_eq!(detect_schema_type(&doc), Some(SchemaType::OpenAPI));
+/// ```
+pub fn detect_schema_type(value: &serde_json::Value) -> Option<SchemaType> {
+    if let Some(source) = value.as_str() {
+        return detect_idl_type(source);
+    }
+    let obj = value.as_object()?;
+    let has = |key: &str| obj.contains_key(key);
+
+    if has("openapi") || has("swagger") {
+        return Some(SchemaType::OpenAPI);
+    }
+    if has("asyncapi") {
+        return Some(SchemaType::AsyncAPI);
+    }
+    if has("orpc") {
+        return Some(SchemaType::ORPC);
+    }
+    if has("__schema") || value.pointer("/data/__schema").is_some() {
+        return Some(SchemaType::GraphQL);
+    }
+    if let Some(sdl) = obj.get("sdl").and_then(|v| v.as_str()) {
+        return detect_idl_type(sdl).or(Some(SchemaType::GraphQL));
+    }
+    if obj
+        .get("syntax")
+        .and_then(|v| v.as_str())
+        .is_some_and(|s| s.starts_with("proto"))
+        || (has("file") && value.pointer("/file/0/package").is_some())
+        || (has("services") && has("messages"))
+    {
+        return Some(SchemaType::GRPC);
+    }
+    if matches!(
+        obj.get("type").and_then(|v| v.as_str()),
+        Some("record" | "enum" | "fixed")
+    ) && has("name")
+    {
+        return Some(SchemaType::Avro);
+    }
+    if has("services") && (has("structs") || has("namespace")) {
+        return Some(SchemaType::Thrift);
+    }
+
+    None
+}
+
+/// Guesses the type of raw IDL source
+fn detect_idl_type(source: &str) -> Option<SchemaType> {
+    let source = source.trim_start();
+    if source.starts_with("syntax") && source.contains("proto") {
+        return Some(SchemaType::GRPC);
+    }
+    if [
+        "type Query",
+        "type Mutation",
+        "type Subscription",
+        "schema {",
+    ]
+    .iter()
+    .any(|marker| source.contains(marker))
+    {
+        return Some(SchemaType::GraphQL);
+    }
+    if source.contains("service ") && (source.contains("rpc ") || source.contains("message ")) {
+        return Some(SchemaType::GRPC);
+    }
+    if source.contains("struct ") && (source.contains("namespace ") || source.contains("service "))
+    {
+        return Some(SchemaType::Thrift);
+    }
+    None
+}
+
 /// Validates a schema location
 fn validate_schema_location(sl: &SchemaLocation) -> Result<()> {
     if !sl.location_type.is_valid() {
@@ -222,29 +754,161 @@ fn validate_schema_location(sl: &SchemaLocation) -> Result<()> {
 }
 
 /// Calculates the SHA256 checksum of a manifest by combining all schema hashes
+///
+/// Hashes are combined in `(type, spec_version, location, hash)` order, so
+/// the checksum doesn't depend on the order of `schemas`.
 pub fn calculate_manifest_checksum(manifest: &SchemaManifest) -> Result<String> {
     if manifest.schemas.is_empty() {
         return Ok(String::new());
     }
 
-    // Sort schemas by type for deterministic hashing
-    let mut sorted_schemas = manifest.schemas.clone();
-    sorted_schemas.sort_by(|a, b| a.schema_type.as_str().cmp(b.schema_type.as_str()));
+    if let Some(checksum) = manifest.checksum_memo.get(&manifest.schemas) {
+        return Ok(checksum);
+    }
 
-    // Concatenate all schema hashes
-    let combined: String = sorted_schemas.iter().map(|s| s.hash.as_str()).collect();
+    // Sort schemas by type for deterministic hashing, then by spec version,
+    // location and hash so several schemas of one type hash the same in any
+    // order (a single schema per type orders exactly as the Go implementation)
+    let mut sorted_schemas: Vec<&SchemaDescriptor> = manifest.schemas.iter().collect();
+    sorted_schemas.sort_by_cached_key(|schema| schema.checksum_key());
 
-    // Calculate SHA256 of combined hashes
+    // Calculate SHA256 of the concatenated schema hashes
     let mut hasher = Sha256::new();
-    hasher.update(combined.as_bytes());
-    let result = hasher.finalize();
-    Ok(hex::encode(result))
+    for schema in &sorted_schemas {
+        hasher.update(schema.hash.as_bytes());
+    }
+    let checksum = hex::encode(hasher.finalize());
+
+    manifest.checksum_memo.set(&manifest.schemas, &checksum);
+    Ok(checksum)
+}
+
+/// Options controlling [`SchemaManifest::redacted_with`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedactOptions {
+    /// Instance label key prefixes treated as internal and removed
+    pub internal_label_prefixes: Vec<String>,
+}
+
+impl Default for RedactOptions {
+    fn default() -> Self {
+        Self {
+            internal_label_prefixes: vec!["internal.".to_string(), "internal/".to_string()],
+        }
+    }
+}
+
+/// Options controlling [`normalize_schema_with`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NormalizeOptions {
+    /// Drop object members whose value is `null`
+    ///
+    /// Off by default: `"default": null` and `"const": null` are meaningful.
+    pub drop_nulls: bool,
+    /// Drop `x-*` extensions whose value is `null`, `{}` or `[]`
+    pub drop_empty_extensions: bool,
+}
+
+impl Default for NormalizeOptions {
+    fn default() -> Self {
+        Self {
+            drop_nulls: false,
+            drop_empty_extensions: true,
+        }
+    }
+}
+
+/// Normalizes a schema with the default [`NormalizeOptions`]
+///
+/// Object keys are sorted recursively and empty extension artifacts left by
+/// generators are removed, so semantically identical documents serialize to
+/// the same bytes. Hash it with [`SchemaChecksumVersion::Normalized`] when
+/// every consumer of the hash opts into that version too.
+pub fn normalize_schema(value: &serde_json::Value) -> serde_json::Value {
+    normalize_schema_with(value, &NormalizeOptions::default())
+}
+
+/// Normalizes a schema with explicit options
+pub fn normalize_schema_with(
+    value: &serde_json::Value,
+    options: &NormalizeOptions,
+) -> serde_json::Value {
+    use serde_json::Value;
+
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+
+            let mut normalized = serde_json::Map::new();
+            for key in keys {
+                let child = &map[key];
+                if options.drop_nulls && child.is_null() {
+                    continue;
+                }
+                let child = normalize_schema_with(child, options);
+                if options.drop_empty_extensions && key.starts_with("x-") && is_empty(&child) {
+                    continue;
+                }
+                normalized.insert(key.clone(), child);
+            }
+            Value::Object(normalized)
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| normalize_schema_with(item, options))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+fn is_empty(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Null => true,
+        serde_json::Value::Object(map) => map.is_empty(),
+        serde_json::Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+/// Algorithm behind a schema checksum
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SchemaChecksumVersion {
+    /// SHA256 of the canonical (sorted-key, compact) JSON
+    ///
+    /// Byte-compatible with the Go implementation's `CalculateSchemaChecksum`,
+    /// and what registries use to verify `SchemaDescriptor::hash`.
+    #[default]
+    Canonical,
+    /// SHA256 of the [`normalize_schema`] form, which also ignores empty
+    /// extension artifacts
+    ///
+    /// Opt-in: peers hashing with [`Canonical`](Self::Canonical) (including
+    /// Go services) compute different hashes for the same schema.
+    Normalized,
 }
 
 /// Calculates the SHA256 checksum of a schema
+///
+/// Hashes the canonical JSON serialization, matching the Go implementation
+/// byte for byte. Use [`calculate_schema_checksum_with`] to opt into
+/// [`SchemaChecksumVersion::Normalized`].
 pub fn calculate_schema_checksum(schema: &serde_json::Value) -> Result<String> {
-    // Serialize to canonical JSON (map keys are sorted by serde_json)
-    let data = serde_json::to_vec(schema)?;
+    calculate_schema_checksum_with(schema, SchemaChecksumVersion::Canonical)
+}
+
+/// Calculates the SHA256 checksum of a schema with the given algorithm
+pub fn calculate_schema_checksum_with(
+    schema: &serde_json::Value,
+    version: SchemaChecksumVersion,
+) -> Result<String> {
+    // Canonical JSON: map keys are sorted by serde_json
+    let data = match version {
+        SchemaChecksumVersion::Canonical => serde_json::to_vec(schema)?,
+        SchemaChecksumVersion::Normalized => serde_json::to_vec(&normalize_schema(schema))?,
+    };
 
     // Calculate SHA256
     let mut hasher = Sha256::new();
@@ -253,6 +917,92 @@ pub fn calculate_schema_checksum(schema: &serde_json::Value) -> Result<String> {
     Ok(hex::encode(result))
 }
 
+/// Moves inline schemas larger than `threshold` bytes into the registry
+///
+/// Each oversized schema is published under
+/// `/schemas/<service>/<type>-<hash>`, so re-running this on an unchanged
+/// manifest publishes to the same paths. The descriptor is switched to a
+/// [`LocationType::Registry`] location with its `hash` and `size` filled in.
+/// The schema content is unchanged, so the manifest checksum stays the same.
+pub async fn externalize_large_schemas(
+    manifest: &mut SchemaManifest,
+    registry: &dyn crate::registry::SchemaRegistry,
+    threshold: usize,
+) -> Result<()> {
+    for descriptor in &mut manifest.schemas {
+        if descriptor.location.location_type != LocationType::Inline {
+            continue;
+        }
+        let Some(schema) = descriptor.inline_schema.as_ref() else {
+            continue;
+        };
+        let size = serde_json::to_vec(schema)?.len();
+        if size <= threshold {
+            continue;
+        }
+
+        let hash = calculate_schema_checksum(schema)?;
+        let path = format!(
+            "/schemas/{}/{}-{hash}",
+            manifest.service_name, descriptor.schema_type
+        );
+        registry.publish_schema(&path, schema).await?;
+
+        descriptor.location = SchemaLocation {
+            location_type: LocationType::Registry,
+            url: None,
+            registry_path: Some(path),
+            headers: None,
+        };
+        descriptor.inline_schema = None;
+        descriptor.hash = hash;
+        descriptor.size = size as i64;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "signing")]
+pub use ed25519_dalek::{SigningKey, VerifyingKey};
+
+/// Returns the canonical JSON bytes covered by a manifest signature
+///
+/// The `signature` field itself is excluded, and object keys are emitted in
+/// sorted order so the payload is stable regardless of map iteration order.
+#[cfg(feature = "signing")]
+fn signing_payload(manifest: &SchemaManifest) -> Result<Vec<u8>> {
+    let mut unsigned = manifest.clone();
+    unsigned.signature = None;
+    let value = serde_json::to_value(&unsigned)?;
+    serde_json::to_vec(&value).map_err(Error::from)
+}
+
+/// Signs a manifest with an Ed25519 key, returning a hex-encoded detached signature
+///
+/// Store the result in `manifest.signature` before publishing.
+#[cfg(feature = "signing")]
+pub fn sign(manifest: &SchemaManifest, key: &SigningKey) -> String {
+    use ed25519_dalek::Signer;
+
+    let payload = signing_payload(manifest).expect("manifest is always serializable to JSON");
+    hex::encode(key.sign(&payload).to_bytes())
+}
+
+/// Verifies a hex-encoded Ed25519 signature produced by [`sign`]
+#[cfg(feature = "signing")]
+pub fn verify(manifest: &SchemaManifest, signature: &str, public_key: &VerifyingKey) -> Result<()> {
+    use ed25519_dalek::{Signature, Verifier};
+
+    let bytes = hex::decode(signature)
+        .map_err(|e| Error::invalid_signature(format!("signature is not valid hex: {e}")))?;
+    let signature = Signature::from_slice(&bytes)
+        .map_err(|e| Error::invalid_signature(format!("malformed signature: {e}")))?;
+
+    let payload = signing_payload(manifest)?;
+    public_key
+        .verify(&payload, &signature)
+        .map_err(|_| Error::invalid_signature("signature does not match manifest"))
+}
+
 /// Represents the difference between two manifests
 #[derive(Debug, Clone, PartialEq)]
 pub struct ManifestDiff {
@@ -262,18 +1012,25 @@ pub struct ManifestDiff {
     pub schemas_removed: Vec<SchemaDescriptor>,
     /// Schemas present in both but with different hashes
     pub schemas_changed: Vec<SchemaChangeDiff>,
-    /// New capabilities
+    /// New capabilities, sorted
     pub capabilities_added: Vec<String>,
-    /// Removed capabilities
+    /// Removed capabilities, sorted
     pub capabilities_removed: Vec<String>,
     /// Whether endpoints changed
     pub endpoints_changed: bool,
+    /// Instance status transition (old, new), when both manifests carry
+    /// instance metadata and the status differs
+    pub status_change: Option<(InstanceStatus, InstanceStatus)>,
+    /// Instance role transition (old, new); a missing role or missing
+    /// instance metadata counts as `None`
+    pub role_change: Option<(Option<InstanceRole>, Option<InstanceRole>)>,
 }
 
 /// Represents a changed schema
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SchemaChangeDiff {
     pub schema_type: SchemaType,
+    pub schema_id: Option<String>,
     pub old_hash: String,
     pub new_hash: String,
 }
@@ -287,6 +1044,8 @@ impl ManifestDiff {
             || !self.capabilities_added.is_empty()
             || !self.capabilities_removed.is_empty()
             || self.endpoints_changed
+            || self.status_change.is_some()
+            || self.role_change.is_some()
     }
 }
 
@@ -299,116 +1058,529 @@ pub fn diff_manifests(old: &SchemaManifest, new: &SchemaManifest) -> ManifestDif
         capabilities_added: Vec::new(),
         capabilities_removed: Vec::new(),
         endpoints_changed: false,
+        status_change: None,
+        role_change: None,
     };
 
-    // Build maps for easier comparison
-    let old_schemas: HashMap<SchemaType, &SchemaDescriptor> =
-        old.schemas.iter().map(|s| (s.schema_type, s)).collect();
-    let new_schemas: HashMap<SchemaType, &SchemaDescriptor> =
-        new.schemas.iter().map(|s| (s.schema_type, s)).collect();
+    // Schemas are identified by type plus optional schema ID
+    let key = |s: &SchemaDescriptor| (s.schema_type, s.schema_id.clone());
+    let old_schemas: HashMap<(SchemaType, Option<String>), &SchemaDescriptor> =
+        old.schemas.iter().map(|s| (key(s), s)).collect();
+    let new_schemas: HashMap<(SchemaType, Option<String>), &SchemaDescriptor> =
+        new.schemas.iter().map(|s| (key(s), s)).collect();
 
     // Find added and changed schemas
-    for (schema_type, new_schema) in &new_schemas {
-        if let Some(old_schema) = old_schemas.get(schema_type) {
+    for new_schema in &new.schemas {
+        if let Some(old_schema) = old_schemas.get(&key(new_schema)) {
             // Schema exists in both, check if changed
             if old_schema.hash != new_schema.hash {
                 diff.schemas_changed.push(SchemaChangeDiff {
-                    schema_type: *schema_type,
+                    schema_type: new_schema.schema_type,
+                    schema_id: new_schema.schema_id.clone(),
                     old_hash: old_schema.hash.clone(),
                     new_hash: new_schema.hash.clone(),
                 });
             }
         } else {
             // Schema is new
-            diff.schemas_added.push((*new_schema).clone());
+            diff.schemas_added.push(new_schema.clone());
         }
     }
 
     // Find removed schemas
-    for (schema_type, old_schema) in &old_schemas {
-        if !new_schemas.contains_key(schema_type) {
-            diff.schemas_removed.push((*old_schema).clone());
-        }
-    }
-
-    // Compare capabilities
-    let old_caps: HashSet<&String> = old.capabilities.iter().collect();
-    let new_caps: HashSet<&String> = new.capabilities.iter().collect();
-
-    for cap in &new_caps {
-        if !old_caps.contains(cap) {
-            diff.capabilities_added.push((*cap).clone());
+    for old_schema in &old.schemas {
+        if !new_schemas.contains_key(&key(old_schema)) {
+            diff.schemas_removed.push(old_schema.clone());
         }
     }
 
-    for cap in &old_caps {
-        if !new_caps.contains(cap) {
-            diff.capabilities_removed.push((*cap).clone());
-        }
-    }
+    // Compare capabilities; sorted sets keep the diff reproducible
+    let old_caps: BTreeSet<&String> = old.capabilities.iter().collect();
+    let new_caps: BTreeSet<&String> = new.capabilities.iter().collect();
+    diff.capabilities_added = new_caps
+        .difference(&old_caps)
+        .map(|c| (*c).clone())
+        .collect();
+    diff.capabilities_removed = old_caps
+        .difference(&new_caps)
+        .map(|c| (*c).clone())
+        .collect();
 
     // Compare endpoints (simple comparison)
     if old.endpoints != new.endpoints {
         diff.endpoints_changed = true;
     }
 
+    // Compare instance status and role
+    if old.instance != new.instance {
+        if let (Some(old_instance), Some(new_instance)) = (&old.instance, &new.instance) {
+            if old_instance.status != new_instance.status {
+                diff.status_change = Some((old_instance.status, new_instance.status));
+            }
+        }
+        let old_role = old.instance.as_ref().and_then(|i| i.role);
+        let new_role = new.instance.as_ref().and_then(|i| i.role);
+        if old_role != new_role {
+            diff.role_change = Some((old_role, new_role));
+        }
+    }
+
     diff
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_new_manifest() {
-        let manifest = new_manifest("test-service", "v1.0.0", "instance-123");
-        assert_eq!(manifest.service_name, "test-service");
-        assert_eq!(manifest.service_version, "v1.0.0");
-        assert_eq!(manifest.instance_id, "instance-123");
-        assert_eq!(manifest.version, PROTOCOL_VERSION);
-    }
+/// Manifest fields whose strings [`ManifestTemplate::render`] substitutes
+pub const TEMPLATED_FIELDS: [&str; 6] = [
+    "service_name",
+    "service_version",
+    "instance_id",
+    "instance",
+    "endpoints",
+    "routing",
+];
+
+/// Manifest template with inheritance and `{{placeholder}}` substitution
+///
+/// Rendering deep-merges `overrides` onto `base` (objects merge key by key;
+/// arrays and scalars replace), then substitutes `{{name}}` placeholders in
+/// the strings of the deployment fields listed in [`TEMPLATED_FIELDS`] and
+/// of each schema's `location`. Schema content (`inline_schema`) is left
+/// as-is, so a literal `{{...}}` in it survives. Any placeholder without a
+/// variable is an error.
+///
+/// # Examples
+///
+/// ```
+/// use farp::manifest::{new_manifest, ManifestTemplate};
+/// use std::collections::HashMap;
+///
+/// let template = ManifestTemplate {
+///     base: Some(new_manifest("user-service", "v1.0.0", "")),
+///     overrides: serde_json::json!({"instance_id": "user-{{region}}-1"}),
+/// };
+/// let vars = HashMap::from([("region".to_string(), "eu".to_string())]);
+/// assert_eq!(template.render(&vars).unwrap().instance_id, "user-eu-1");
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ManifestTemplate {
+    /// Manifest the template inherits from
+    pub base: Option<SchemaManifest>,
+    /// Partial manifest (as JSON) applied on top of `base`
+    pub overrides: serde_json::Value,
+}
 
-    #[test]
-    fn test_add_schema() {
-        let mut manifest = new_manifest("test", "v1", "id1");
-        let schema = SchemaDescriptor {
-            schema_type: SchemaType::OpenAPI,
-            spec_version: "3.1.0".to_string(),
-            location: SchemaLocation {
-                location_type: LocationType::HTTP,
-                url: Some("http://example.com/openapi.json".to_string()),
-                registry_path: None,
-                headers: None,
-            },
-            content_type: "application/json".to_string(),
-            inline_schema: None,
-            hash: "a".repeat(64),
-            size: 1024,
-            compatibility: None,
-            metadata: None,
+impl ManifestTemplate {
+    /// Renders a manifest, substituting `vars` into placeholders
+    ///
+    /// Without a `base`, `overrides` must describe a complete manifest.
+    /// The checksum is recomputed from the rendered schemas.
+    pub fn render(&self, vars: &HashMap<String, String>) -> Result<SchemaManifest> {
+        let mut value = match &self.base {
+            Some(base) => serde_json::to_value(base)?,
+            None => serde_json::Value::Object(serde_json::Map::new()),
         };
+        merge_json(&mut value, &self.overrides);
+        if let serde_json::Value::Object(fields) = &mut value {
+            for (key, field) in fields.iter_mut() {
+                if TEMPLATED_FIELDS.contains(&key.as_str()) {
+                    substitute_placeholders(field, vars, &format!("/{key}"))?;
+                }
+            }
+            if let Some(serde_json::Value::Array(schemas)) = fields.get_mut("schemas") {
+                for (i, schema) in schemas.iter_mut().enumerate() {
+                    if let Some(location) = schema.get_mut("location") {
+                        substitute_placeholders(location, vars, &format!("/schemas/{i}/location"))?;
+                    }
+                }
+            }
+        }
 
-        manifest.add_schema(schema);
-        assert_eq!(manifest.schemas.len(), 1);
+        let mut manifest: SchemaManifest =
+            serde_json::from_value(value).map_err(|e| Error::invalid_manifest(e.to_string()))?;
+        manifest.update_checksum()?;
+        Ok(manifest)
     }
+}
 
-    #[test]
-    fn test_add_capability() {
-        let mut manifest = new_manifest("test", "v1", "id1");
-        manifest.add_capability("rest");
-        manifest.add_capability("grpc");
-        manifest.add_capability("rest"); // Duplicate should be ignored
-
-        assert_eq!(manifest.capabilities.len(), 2);
-        assert!(manifest.has_capability("rest"));
-        assert!(manifest.has_capability("grpc"));
-        assert!(!manifest.has_capability("websocket"));
+/// Deep-merges `overrides` into `target`
+fn merge_json(target: &mut serde_json::Value, overrides: &serde_json::Value) {
+    match (target, overrides) {
+        (serde_json::Value::Object(target), serde_json::Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                match target.get_mut(key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        target.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (target, overrides) => *target = overrides.clone(),
     }
+}
 
-    #[test]
-    fn test_validate_schema_descriptor() {
-        let valid = SchemaDescriptor {
-            schema_type: SchemaType::OpenAPI,
+/// Replaces `{{name}}` in every string, reporting the first unresolved one
+fn substitute_placeholders(
+    value: &mut serde_json::Value,
+    vars: &HashMap<String, String>,
+    pointer: &str,
+) -> Result<()> {
+    match value {
+        serde_json::Value::String(s) => {
+            let mut rendered = String::with_capacity(s.len());
+            let mut rest = s.as_str();
+            while let Some(start) = rest.find("{{") {
+                let end = rest[start..].find("}}").ok_or_else(|| {
+                    Error::validation(pointer, format!("unterminated placeholder in {s:?}"))
+                })? + start;
+                let name = rest[start + 2..end].trim();
+                let replacement = vars.get(name).ok_or_else(|| {
+                    Error::validation(pointer, format!("unresolved placeholder {{{{{name}}}}}"))
+                })?;
+                rendered.push_str(&rest[..start]);
+                rendered.push_str(replacement);
+                rest = &rest[end + 2..];
+            }
+            rendered.push_str(rest);
+            *s = rendered;
+        }
+        serde_json::Value::Array(items) => {
+            for (i, item) in items.iter_mut().enumerate() {
+                substitute_placeholders(item, vars, &format!("{pointer}/{i}"))?;
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                substitute_placeholders(item, vars, &format!("{pointer}/{key}"))?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Severity of a [`LintFinding`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// Informational note
+    Info,
+    /// Likely problem that does not block registration
+    Warning,
+    /// Hard validation failure
+    Error,
+}
+
+/// A single issue reported by [`lint`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintFinding {
+    /// Severity
+    pub level: LintLevel,
+    /// Stable identifier (e.g. `FARP001`)
+    pub code: String,
+    /// Manifest field the finding refers to (e.g. `schemas[0].hash`)
+    pub path: String,
+    /// Human-readable description
+    pub message: String,
+}
+
+/// Structured output of [`lint`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintReport {
+    /// All findings, in manifest field order
+    pub findings: Vec<LintFinding>,
+}
+
+impl LintReport {
+    /// Returns true if any finding is an error
+    pub fn has_errors(&self) -> bool {
+        self.findings.iter().any(|f| f.level == LintLevel::Error)
+    }
+
+    /// Returns the codes of all findings
+    pub fn codes(&self) -> Vec<&str> {
+        self.findings.iter().map(|f| f.code.as_str()).collect()
+    }
+
+    /// Returns findings at the given level
+    pub fn at_level(&self, level: LintLevel) -> impl Iterator<Item = &LintFinding> {
+        self.findings.iter().filter(move |f| f.level == level)
+    }
+
+    fn push(
+        &mut self,
+        level: LintLevel,
+        code: &str,
+        path: impl Into<String>,
+        message: impl Into<String>,
+    ) {
+        self.findings.push(LintFinding {
+            level,
+            code: code.to_string(),
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+}
+
+/// Lints a manifest, reporting hard validation failures and soft issues
+///
+/// Errors are the failures of [`SchemaManifest::validate_all`], so a
+/// manifest lints clean of errors exactly when it validates.
+///
+/// Codes are stable across releases:
+///
+/// | Code      | Level   | Meaning                                              |
+/// |-----------|---------|------------------------------------------------------|
+/// | `FARP000` | error   | other validation failure                             |
+/// | `FARP001` | error   | incompatible protocol version                        |
+/// | `FARP002` | error   | missing service name                                 |
+/// | `FARP003` | error   | missing instance ID                                  |
+/// | `FARP004` | error   | missing health endpoint                              |
+/// | `FARP005` | error   | invalid schema descriptor                            |
+/// | `FARP006` | error   | empty or duplicate capability                        |
+/// | `FARP007` | error   | checksum mismatch                                    |
+/// | `FARP101` | warning | no metrics endpoint                                  |
+/// | `FARP102` | warning | schema has no compatibility mode                     |
+/// | `FARP103` | warning | OpenAPI components merged with an empty prefix       |
+/// | `FARP104` | warning | inline schema content looks like another schema type |
+/// | `FARP105` | warning | declared capabilities disagree with the schemas      |
+/// | `FARP106` | warning | content type not expected for the schema type        |
+/// | `FARP201` | info    | manifest has no checksum                             |
+///
+/// # Examples
+///
+/// ```
+/// use farp::manifest::{lint, new_manifest};
+///
+/// let manifest = new_manifest("user-service", "v1.0.0", "instance-1");
+/// let report = lint(&manifest);
+/// assert!(report.codes().contains(&"FARP004"));
+/// ```
+pub fn lint(manifest: &SchemaManifest) -> LintReport {
+    let mut report = LintReport::default();
+
+    // Hard failures are exactly what validation rejects
+    for error in manifest.validate_all().err().unwrap_or_default() {
+        let (code, path) = match &error {
+            Error::IncompatibleVersion { .. } => ("FARP001", "version".to_string()),
+            Error::Validation { field, .. } => {
+                let code = match field.as_str() {
+                    "service_name" => "FARP002",
+                    "instance_id" => "FARP003",
+                    "endpoints.health" => "FARP004",
+                    "capabilities" => "FARP006",
+                    _ => "FARP000",
+                };
+                (code, field.clone())
+            }
+            Error::InvalidManifest(_) => ("FARP005", "schemas".to_string()),
+            _ => ("FARP007", "checksum".to_string()),
+        };
+        let message = match error {
+            Error::Validation { message, .. } => message,
+            other => other.to_string(),
+        };
+        report.push(LintLevel::Error, code, path, message);
+    }
+
+    if manifest
+        .endpoints
+        .metrics
+        .as_deref()
+        .map_or(true, str::is_empty)
+    {
+        report.push(
+            LintLevel::Warning,
+            "FARP101",
+            "endpoints.metrics",
+            "no metrics endpoint; gateways cannot scrape this instance",
+        );
+    }
+
+    for (i, schema) in manifest.schemas.iter().enumerate() {
+        if schema.compatibility.is_none() {
+            report.push(
+                LintLevel::Warning,
+                "FARP102",
+                format!("schemas[{i}].compatibility"),
+                format!(
+                    "{} schema has no compatibility mode; breaking changes cannot be checked",
+                    schema.schema_type
+                ),
+            );
+        }
+        if let Some(detected) = schema.inline_schema.as_ref().and_then(detect_schema_type) {
+            if schema.schema_type != SchemaType::Custom && detected != schema.schema_type {
+                report.push(
+                    LintLevel::Warning,
+                    "FARP104",
+                    format!("schemas[{i}].type"),
+                    format!(
+                        "declared as {} but inline content looks like {detected}",
+                        schema.schema_type
+                    ),
+                );
+            }
+        }
+        if !schema.content_type.is_empty()
+            && !schema
+                .schema_type
+                .accepts_content_type(&schema.content_type)
+        {
+            report.push(
+                LintLevel::Warning,
+                "FARP106",
+                format!("schemas[{i}].content_type"),
+                format!(
+                    "content type {} is not expected for {} schemas",
+                    schema.content_type, schema.schema_type
+                ),
+            );
+        }
+        if let Some(names) = unprefixed_components(schema) {
+            report.push(
+                LintLevel::Warning,
+                "FARP103",
+                format!("schemas[{i}].metadata.openapi.composition.component_prefix"),
+                format!(
+                    "components {} have no component prefix and may collide when merged",
+                    names.join(", ")
+                ),
+            );
+        }
+    }
+
+    let declared = |capability: Capability| {
+        manifest
+            .capabilities
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(capability.as_str()))
+    };
+    let inferred = manifest.infer_capabilities();
+    for capability in &inferred {
+        if !declared(*capability) {
+            report.push(
+                LintLevel::Warning,
+                "FARP105",
+                "capabilities",
+                format!("schemas imply capability {capability}, which is not declared"),
+            );
+        }
+    }
+    for capability in [Capability::REST, Capability::GRPC, Capability::GraphQL] {
+        if declared(capability) && !inferred.contains(&capability) {
+            report.push(
+                LintLevel::Warning,
+                "FARP105",
+                "capabilities",
+                format!("capability {capability} is declared but no schema provides it"),
+            );
+        }
+    }
+
+    if manifest.checksum.is_empty() {
+        report.push(
+            LintLevel::Info,
+            "FARP201",
+            "checksum",
+            "manifest has no checksum; integrity cannot be verified",
+        );
+    }
+
+    report
+}
+
+/// Returns the inline OpenAPI component names that would be merged unprefixed
+///
+/// Without an explicit prefix the merger prefixes components with the
+/// service name, so only an explicitly empty prefix merges them as-is.
+fn unprefixed_components(schema: &SchemaDescriptor) -> Option<Vec<String>> {
+    if schema.schema_type != SchemaType::OpenAPI {
+        return None;
+    }
+    let composition = schema
+        .metadata
+        .as_ref()
+        .and_then(|m| m.openapi.as_ref())
+        .and_then(|o| o.composition.as_ref())?;
+    if !composition.include_in_merged || composition.component_prefix.as_deref() != Some("") {
+        return None;
+    }
+
+    let components = schema
+        .inline_schema
+        .as_ref()?
+        .get("components")?
+        .as_object()?;
+    let mut names: Vec<String> = components
+        .values()
+        .filter_map(|section| section.as_object())
+        .flat_map(|section| section.keys().cloned())
+        .collect();
+    if names.is_empty() {
+        return None;
+    }
+    names.sort();
+    names.dedup();
+    Some(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_manifest() {
+        let manifest = new_manifest("test-service", "v1.0.0", "instance-123");
+        assert_eq!(manifest.service_name, "test-service");
+        assert_eq!(manifest.service_version, "v1.0.0");
+        assert_eq!(manifest.instance_id, "instance-123");
+        assert_eq!(manifest.version, PROTOCOL_VERSION);
+    }
+
+    #[test]
+    fn test_add_schema() {
+        let mut manifest = new_manifest("test", "v1", "id1");
+        let schema = SchemaDescriptor {
+            schema_type: SchemaType::OpenAPI,
+            schema_id: None,
+            spec_version: "3.1.0".to_string(),
+            location: SchemaLocation {
+                location_type: LocationType::HTTP,
+                url: Some("http://example.com/openapi.json".to_string()),
+                registry_path: None,
+                headers: None,
+            },
+            content_type: "application/json".to_string(),
+            inline_schema: None,
+            hash: "a".repeat(64),
+            size: 1024,
+            compatibility: None,
+            metadata: None,
+        };
+
+        manifest.add_schema(schema);
+        assert_eq!(manifest.schemas.len(), 1);
+    }
+
+    #[test]
+    fn test_add_capability() {
+        let mut manifest = new_manifest("test", "v1", "id1");
+        manifest.add_capability("rest");
+        manifest.add_capability("grpc");
+        manifest.add_capability("rest"); // Duplicate should be ignored
+
+        assert_eq!(manifest.capabilities.len(), 2);
+        assert!(manifest.has_capability("rest"));
+        assert!(manifest.has_capability("grpc"));
+        assert!(!manifest.has_capability("websocket"));
+    }
+
+    #[test]
+    fn test_validate_schema_descriptor() {
+        let valid = SchemaDescriptor {
+            schema_type: SchemaType::OpenAPI,
+            schema_id: None,
             spec_version: "3.1.0".to_string(),
             location: SchemaLocation {
                 location_type: LocationType::HTTP,
@@ -431,6 +1603,7 @@ mod tests {
     fn test_validate_schema_descriptor_invalid_hash() {
         let invalid = SchemaDescriptor {
             schema_type: SchemaType::OpenAPI,
+            schema_id: None,
             spec_version: "3.1.0".to_string(),
             location: SchemaLocation {
                 location_type: LocationType::HTTP,
@@ -454,6 +1627,7 @@ mod tests {
         let mut manifest = new_manifest("test", "v1", "id1");
         manifest.add_schema(SchemaDescriptor {
             schema_type: SchemaType::OpenAPI,
+            schema_id: None,
             spec_version: "3.1.0".to_string(),
             location: SchemaLocation {
                 location_type: LocationType::HTTP,
@@ -473,6 +1647,303 @@ mod tests {
         assert_eq!(checksum.len(), 64); // SHA256 produces 64 hex characters
     }
 
+    fn descriptor(schema_type: SchemaType, hash: String) -> SchemaDescriptor {
+        SchemaDescriptor {
+            schema_type,
+            schema_id: None,
+            spec_version: "1.0.0".to_string(),
+            location: SchemaLocation {
+                location_type: LocationType::HTTP,
+                url: Some("http://example.com".to_string()),
+                registry_path: None,
+                headers: None,
+            },
+            content_type: "application/json".to_string(),
+            inline_schema: None,
+            hash,
+            size: 1024,
+            compatibility: None,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_infer_capabilities() {
+        let mut manifest = new_manifest("test", "v1", "id1");
+        manifest.add_schema(descriptor(SchemaType::OpenAPI, "a".repeat(64)));
+        manifest.add_schema(descriptor(SchemaType::GRPC, "b".repeat(64)));
+        let mut events = descriptor(SchemaType::AsyncAPI, "c".repeat(64));
+        events.inline_schema = Some(serde_json::json!({
+            "asyncapi": "2.6.0",
+            "servers": {"broker": {"url": "mqtt://broker", "protocol": "mqtt"}}
+        }));
+        manifest.add_schema(events);
+        assert_eq!(
+            manifest.infer_capabilities(),
+            vec![Capability::REST, Capability::GRPC, Capability::MQTT]
+        );
+
+        manifest.capabilities = vec!["rest".to_string(), "mqtt".to_string()];
+        manifest.add_capability("graphql");
+        let report = lint(&manifest);
+        let mismatches: Vec<&str> = report
+            .findings
+            .iter()
+            .filter(|f| f.code == "FARP105")
+            .map(|f| f.message.as_str())
+            .collect();
+        assert_eq!(
+            mismatches,
+            vec![
+                "schemas imply capability grpc, which is not declared",
+                "capability graphql is declared but no schema provides it",
+            ]
+        );
+
+        manifest.capabilities = vec!["REST".to_string(), "grpc".to_string(), "mqtt".to_string()];
+        assert!(!lint(&manifest).codes().contains(&"FARP105"));
+    }
+
+    #[test]
+    fn test_update_schema_matches_full_recompute() {
+        let mut manifest = new_manifest("test", "v1", "id1");
+        manifest.add_schema(descriptor(SchemaType::OpenAPI, "a".repeat(64)));
+        manifest.add_schema(descriptor(SchemaType::AsyncAPI, "b".repeat(64)));
+        manifest.add_schema(descriptor(SchemaType::GRPC, "c".repeat(64)));
+        manifest.update_checksum().unwrap();
+
+        manifest
+            .update_schema(
+                SchemaType::AsyncAPI,
+                descriptor(SchemaType::AsyncAPI, "d".repeat(64)),
+            )
+            .unwrap();
+
+        let mut fresh = new_manifest("test", "v1", "id1");
+        fresh.add_schema(descriptor(SchemaType::GRPC, "c".repeat(64)));
+        fresh.add_schema(descriptor(SchemaType::OpenAPI, "a".repeat(64)));
+        fresh.add_schema(descriptor(SchemaType::AsyncAPI, "d".repeat(64)));
+        assert_eq!(
+            manifest.checksum,
+            calculate_manifest_checksum(&fresh).unwrap()
+        );
+        assert_eq!(manifest.schemas.len(), 3);
+
+        // Adding a new type via update_schema also matches
+        manifest
+            .update_schema(
+                SchemaType::GraphQL,
+                descriptor(SchemaType::GraphQL, "e".repeat(64)),
+            )
+            .unwrap();
+        fresh.add_schema(descriptor(SchemaType::GraphQL, "e".repeat(64)));
+        assert_eq!(
+            manifest.checksum,
+            calculate_manifest_checksum(&fresh).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_checksum_memo_invalidated_on_hash_change() {
+        let mut manifest = new_manifest("test", "v1", "id1");
+        manifest.add_schema(descriptor(SchemaType::OpenAPI, "a".repeat(64)));
+        let first = calculate_manifest_checksum(&manifest).unwrap();
+
+        // Mutating the hash directly (bypassing update_schema) must not hit the memo
+        manifest.schemas[0].hash = "f".repeat(64);
+        let second = calculate_manifest_checksum(&manifest).unwrap();
+        assert_ne!(first, second);
+
+        // Matches the original algorithm: SHA256 of the concatenated hashes
+        let expected = hex::encode(Sha256::digest("f".repeat(64).as_bytes()));
+        assert_eq!(second, expected);
+    }
+
+    #[test]
+    fn test_manifest_checksum_ignores_schema_order() {
+        let mut public = descriptor(SchemaType::OpenAPI, "b".repeat(64));
+        public.location.registry_path = Some("/schemas/test/openapi-public".to_string());
+        let mut internal = descriptor(SchemaType::OpenAPI, "a".repeat(64));
+        internal.location.registry_path = Some("/schemas/test/openapi-internal".to_string());
+        let graphql = descriptor(SchemaType::GraphQL, "c".repeat(64));
+
+        let mut forward = new_manifest("test", "v1", "id1");
+        forward.schemas = vec![public.clone(), graphql.clone(), internal.clone()];
+        let mut reverse = new_manifest("test", "v1", "id1");
+        reverse.schemas = vec![internal.clone(), graphql.clone(), public.clone()];
+        let checksum = calculate_manifest_checksum(&forward).unwrap();
+        assert_eq!(checksum, calculate_manifest_checksum(&reverse).unwrap());
+
+        // Moving the locations reorders the hashes, and the memo notices
+        forward.schemas[0].location.registry_path = internal.location.registry_path.clone();
+        forward.schemas[2].location.registry_path = public.location.registry_path.clone();
+        assert_ne!(calculate_manifest_checksum(&forward).unwrap(), checksum);
+    }
+
+    #[test]
+    fn test_validate_all_collects_every_error() {
+        let mut manifest = new_manifest("", "v1", "id1");
+        manifest.endpoints.health = "/health".to_string();
+        manifest.add_schema(descriptor(SchemaType::OpenAPI, "not-a-hash".to_string()));
+        manifest.add_capability("rest");
+        manifest.capabilities.push("rest".to_string());
+
+        let errors = manifest.validate_all().unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(&errors[0], Error::Validation { field, .. } if field == "service_name"));
+        assert!(matches!(&errors[1], Error::InvalidManifest(msg) if msg.contains("index 0")));
+        assert!(matches!(&errors[2], Error::Validation { field, .. } if field == "capabilities"));
+
+        // Fail-fast path reports the first of them
+        assert!(matches!(
+            manifest.validate(),
+            Err(Error::Validation { field, .. }) if field == "service_name"
+        ));
+    }
+
+    #[test]
+    fn test_normalized_schema_checksum() {
+        let a: serde_json::Value = serde_json::from_str(
+            r#"{"openapi": "3.1.0", "info": {"title": "Users", "version": "1.0"}, "paths": {}}"#,
+        )
+        .unwrap();
+        let b: serde_json::Value = serde_json::from_str(
+            r#"{
+                "paths": {},
+                "x-generator": {},
+                "info": {"version": "1.0", "title": "Users", "x-logo": null},
+                "openapi": "3.1.0"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(normalize_schema(&a), normalize_schema(&b));
+        let normalized = |schema| {
+            calculate_schema_checksum_with(schema, SchemaChecksumVersion::Normalized).unwrap()
+        };
+        assert_eq!(normalized(&a), normalized(&b));
+        // The default checksum stays canonical, so the documents differ
+        assert_ne!(
+            calculate_schema_checksum(&a).unwrap(),
+            calculate_schema_checksum(&b).unwrap()
+        );
+
+        // Nulls are only dropped when asked to
+        let with_default = serde_json::json!({"default": null, "type": "string"});
+        assert_eq!(normalize_schema(&with_default), with_default);
+        let options = NormalizeOptions {
+            drop_nulls: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            normalize_schema_with(&with_default, &options),
+            serde_json::json!({"type": "string"})
+        );
+    }
+
+    #[test]
+    fn test_schema_checksum_matches_go() {
+        // sha256 of {"info":{"title":"Users"},"openapi":"3.1.0","x-generator":{}},
+        // as Go's json.Marshal writes it
+        let schema = serde_json::json!({
+            "x-generator": {},
+            "openapi": "3.1.0",
+            "info": {"title": "Users"}
+        });
+        assert_eq!(
+            calculate_schema_checksum(&schema).unwrap(),
+            "4b6f80e9612912805252374e461eb99a2f1e6f29c991aa5c9a7f01c3bd241903"
+        );
+    }
+
+    #[test]
+    fn test_manifest_template() {
+        let mut base = new_manifest("user-service", "v1.0.0", "placeholder");
+        base.endpoints.health = "/health".to_string();
+        base.add_capability("rest");
+        base.add_schema(SchemaDescriptor {
+            schema_type: SchemaType::OpenAPI,
+            schema_id: None,
+            spec_version: "3.1.0".to_string(),
+            location: SchemaLocation {
+                location_type: LocationType::HTTP,
+                url: Some("http://user.{{region}}.internal/openapi.json".to_string()),
+                registry_path: None,
+                headers: None,
+            },
+            content_type: "application/json".to_string(),
+            inline_schema: None,
+            hash: "a".repeat(64),
+            size: 64,
+            compatibility: None,
+            metadata: None,
+        });
+        base.add_schema(SchemaDescriptor {
+            schema_type: SchemaType::OpenAPI,
+            schema_id: Some("inline".to_string()),
+            spec_version: "3.1.0".to_string(),
+            location: SchemaLocation {
+                location_type: LocationType::Inline,
+                url: None,
+                registry_path: None,
+                headers: None,
+            },
+            content_type: "application/json".to_string(),
+            inline_schema: Some(serde_json::json!({
+                "info": {"description": "Mustache: {{user}}"}
+            })),
+            hash: "a".repeat(64),
+            size: 64,
+            compatibility: None,
+            metadata: None,
+        });
+
+        let template = ManifestTemplate {
+            base: Some(base),
+            overrides: serde_json::json!({
+                "instance_id": "user-{{region}}-{{ ordinal }}",
+                "endpoints": {"metrics": "/metrics"},
+                "instance": {
+                    "address": "user.{{region}}.internal:8080",
+                    "region": "{{region}}",
+                    "status": "healthy",
+                    "started_at": 0
+                }
+            }),
+        };
+
+        for region in ["eu-west-1", "us-east-1"] {
+            let vars = HashMap::from([
+                ("region".to_string(), region.to_string()),
+                ("ordinal".to_string(), "1".to_string()),
+            ]);
+            let manifest = template.render(&vars).unwrap();
+
+            assert_eq!(manifest.instance_id, format!("user-{region}-1"));
+            let instance = manifest.instance.as_ref().unwrap();
+            assert_eq!(instance.region.as_deref(), Some(region));
+            assert_eq!(instance.address, format!("user.{region}.internal:8080"));
+            // Overrides merge into inherited objects instead of replacing them
+            assert_eq!(manifest.endpoints.health, "/health");
+            assert_eq!(manifest.endpoints.metrics.as_deref(), Some("/metrics"));
+            assert_eq!(manifest.capabilities, vec!["rest"]);
+            assert!(manifest.validate().is_ok());
+            // Locations are templated, schema content is not
+            assert_eq!(
+                manifest.schemas[0].location.url,
+                Some(format!("http://user.{region}.internal/openapi.json"))
+            );
+            assert_eq!(
+                manifest.schemas[1].inline_schema.as_ref().unwrap()["info"]["description"],
+                "Mustache: {{user}}"
+            );
+        }
+
+        let vars = HashMap::from([("region".to_string(), "eu-west-1".to_string())]);
+        let err = template.render(&vars).unwrap_err();
+        assert!(matches!(err, Error::Validation { ref field, .. } if field == "/instance_id"));
+    }
+
     #[test]
     fn test_diff_manifests() {
         let mut old = new_manifest("test", "v1", "id1");
@@ -488,6 +1959,146 @@ mod tests {
         assert!(diff.has_changes());
     }
 
+    fn with_instance(status: InstanceStatus, role: Option<InstanceRole>) -> SchemaManifest {
+        let mut manifest = new_manifest("test", "v1", "id1");
+        manifest.instance = Some(InstanceMetadata {
+            address: "10.0.0.1:8080".to_string(),
+            region: None,
+            zone: None,
+            labels: None,
+            weight: None,
+            status,
+            role,
+            deployment: None,
+            started_at: 0,
+            expected_schema_checksum: None,
+        });
+        manifest
+    }
+
+    #[test]
+    fn test_diff_status_transition() {
+        let old = with_instance(InstanceStatus::Healthy, Some(InstanceRole::Primary));
+        let new = with_instance(InstanceStatus::Unhealthy, Some(InstanceRole::Primary));
+
+        let diff = diff_manifests(&old, &new);
+        assert_eq!(
+            diff.status_change,
+            Some((InstanceStatus::Healthy, InstanceStatus::Unhealthy))
+        );
+        assert_eq!(diff.role_change, None);
+        assert!(diff.has_changes());
+
+        assert!(!diff_manifests(&old, &old).has_changes());
+    }
+
+    #[test]
+    fn test_diff_role_transition() {
+        let old = with_instance(InstanceStatus::Healthy, Some(InstanceRole::Primary));
+        let new = with_instance(InstanceStatus::Healthy, Some(InstanceRole::Canary));
+
+        let diff = diff_manifests(&old, &new);
+        assert_eq!(
+            diff.role_change,
+            Some((Some(InstanceRole::Primary), Some(InstanceRole::Canary)))
+        );
+        assert_eq!(diff.status_change, None);
+        assert!(diff.has_changes());
+
+        // Dropping instance metadata clears the role but has no status to compare
+        let diff = diff_manifests(&old, &new_manifest("test", "v1", "id1"));
+        assert_eq!(diff.role_change, Some((Some(InstanceRole::Primary), None)));
+        assert_eq!(diff.status_change, None);
+    }
+
+    #[test]
+    fn test_diff_capabilities_sorted() {
+        let caps = |names: &[&str]| names.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        let mut old = new_manifest("test", "v1", "id1");
+        old.capabilities = caps(&["websocket", "rest", "mqtt", "amqp", "grpc"]);
+        let mut new = new_manifest("test", "v1", "id1");
+        new.capabilities = caps(&["sse", "rest", "graphql", "webhook", "grpc", "orpc"]);
+
+        for _ in 0..10 {
+            let diff = diff_manifests(&old, &new);
+            assert_eq!(
+                diff.capabilities_added,
+                caps(&["graphql", "orpc", "sse", "webhook"])
+            );
+            assert_eq!(
+                diff.capabilities_removed,
+                caps(&["amqp", "mqtt", "websocket"])
+            );
+        }
+    }
+
+    #[test]
+    fn test_dedup_capabilities() {
+        let mut manifest = new_manifest("test", "v1", "id1");
+        manifest.capabilities = ["rest", "grpc", "rest", "sse", "grpc"]
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        manifest.dedup_capabilities();
+        assert_eq!(manifest.capabilities, vec!["rest", "grpc", "sse"]);
+
+        manifest.add_capability("grpc");
+        manifest.add_capability("mqtt");
+        assert_eq!(manifest.capabilities, vec!["rest", "grpc", "sse", "mqtt"]);
+    }
+
+    #[test]
+    fn test_deserialized_capabilities_are_deduplicated() {
+        let mut manifest = new_manifest("test", "v1", "id1");
+        manifest.capabilities = ["rest", "grpc", "rest", "sse", "grpc"]
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        let json = manifest.to_json().unwrap();
+
+        let parsed = SchemaManifest::from_json(&json).unwrap();
+        assert_eq!(parsed.capabilities, vec!["rest", "grpc", "sse"]);
+        let parsed = SchemaManifest::from_binary(&manifest.to_binary()).unwrap();
+        assert_eq!(parsed.capabilities, vec!["rest", "grpc", "sse"]);
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_sign_and_verify() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut manifest = new_manifest("test-service", "v1.0.0", "instance-123");
+        manifest.endpoints.health = "/health".to_string();
+
+        let signature = sign(&manifest, &key);
+        manifest.signature = Some(signature.clone());
+
+        assert!(verify(&manifest, &signature, &key.verifying_key()).is_ok());
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_verify_rejects_tampered_manifest() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut manifest = new_manifest("test-service", "v1.0.0", "instance-123");
+        let signature = sign(&manifest, &key);
+
+        manifest.service_version = "v6.6.6".to_string();
+        let err = verify(&manifest, &signature, &key.verifying_key()).unwrap_err();
+        assert!(matches!(err, Error::InvalidSignature(_)));
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_verify_rejects_wrong_key() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let other = SigningKey::from_bytes(&[9u8; 32]);
+        let manifest = new_manifest("test-service", "v1.0.0", "instance-123");
+        let signature = sign(&manifest, &key);
+
+        let err = verify(&manifest, &signature, &other.verifying_key()).unwrap_err();
+        assert!(matches!(err, Error::InvalidSignature(_)));
+    }
+
     #[test]
     fn test_manifest_serialization() {
         let manifest = new_manifest("test-service", "v1.0.0", "instance-123");
@@ -498,4 +2109,703 @@ mod tests {
         assert_eq!(deserialized.service_name, "test-service");
         assert_eq!(deserialized.instance_id, "instance-123");
     }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let mut manifest = new_manifest("test-service", "v1.0.0", "instance-123");
+        manifest.endpoints.health = "/health".to_string();
+        manifest.capabilities.push("rest".to_string());
+        manifest.add_schema(descriptor(SchemaType::OpenAPI, "a".repeat(64)));
+        manifest.update_checksum().unwrap();
+
+        let binary = manifest.to_binary();
+        assert_eq!(&binary[..4], b"FARP");
+        assert_eq!(binary[4], BINARY_FORMAT_VERSION);
+        assert!(binary.len() < manifest.to_json().unwrap().len());
+
+        assert_eq!(SchemaManifest::from_binary(&binary).unwrap(), manifest);
+    }
+
+    #[test]
+    fn test_binary_rejects_malformed() {
+        let binary = new_manifest("test-service", "v1.0.0", "instance-123").to_binary();
+
+        let truncated = SchemaManifest::from_binary(&binary[..binary.len() - 3]).unwrap_err();
+        assert!(matches!(truncated, Error::InvalidManifest(_)));
+        assert!(SchemaManifest::from_binary(&binary[..3]).is_err());
+
+        let mut bad_magic = binary.clone();
+        bad_magic[0] = b'X';
+        assert!(matches!(
+            SchemaManifest::from_binary(&bad_magic).unwrap_err(),
+            Error::InvalidManifest(_)
+        ));
+
+        let mut future = binary;
+        future[4] = BINARY_FORMAT_VERSION + 1;
+        assert!(matches!(
+            SchemaManifest::from_binary(&future).unwrap_err(),
+            Error::IncompatibleVersion { .. }
+        ));
+    }
+
+    #[test]
+    fn test_from_json_limited() {
+        let manifest = new_manifest("test-service", "v1.0.0", "instance-123");
+        let json = manifest.to_json().unwrap();
+        let limits = DeserializeLimits::default();
+        assert_eq!(
+            SchemaManifest::from_json_limited(&json, &limits).unwrap(),
+            manifest
+        );
+
+        // Brackets inside strings don't count towards nesting
+        let mut quoted = manifest.clone();
+        quoted.service_name = "[[[[{{{{\\\"".repeat(20);
+        let json = quoted.to_json().unwrap();
+        assert!(SchemaManifest::from_json_limited(&json, &limits).is_ok());
+
+        let nested = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+        let err = SchemaManifest::from_json_limited(nested.as_bytes(), &limits).unwrap_err();
+        assert!(matches!(err, Error::InvalidManifest(ref m) if m.contains("depth")));
+
+        // The capabilities array is the widest container
+        let mut wide = manifest.clone();
+        wide.capabilities = (0..64).map(|i| format!("capability-{i}")).collect();
+        let json = wide.to_json().unwrap();
+        let at_limit = DeserializeLimits {
+            max_elements: 64,
+            ..limits
+        };
+        assert!(SchemaManifest::from_json_limited(&json, &at_limit).is_ok());
+        let below_limit = DeserializeLimits {
+            max_elements: 63,
+            ..limits
+        };
+        let err = SchemaManifest::from_json_limited(&json, &below_limit).unwrap_err();
+        assert!(matches!(err, Error::InvalidManifest(ref m) if m.contains("elements")));
+
+        let small = DeserializeLimits {
+            max_size: 16,
+            ..limits
+        };
+        let err = SchemaManifest::from_json_limited(&json, &small).unwrap_err();
+        assert!(matches!(err, Error::InvalidManifest(ref m) if m.contains("bytes")));
+    }
+
+    #[test]
+    fn test_lint() {
+        let mut manifest = new_manifest("svc", "v1", "");
+        manifest.capabilities = vec!["rest".to_string(), "rest".to_string()];
+        manifest.add_schema(SchemaDescriptor {
+            schema_type: SchemaType::OpenAPI,
+            schema_id: None,
+            spec_version: "3.1.0".to_string(),
+            location: SchemaLocation {
+                location_type: LocationType::Inline,
+                url: None,
+                registry_path: None,
+                headers: None,
+            },
+            content_type: "application/json".to_string(),
+            inline_schema: Some(serde_json::json!({
+                "components": {"schemas": {"User": {"type": "object"}}}
+            })),
+            hash: "short".to_string(),
+            size: 64,
+            compatibility: None,
+            metadata: Some(ProtocolMetadata {
+                graphql: None,
+                grpc: None,
+                openapi: Some(OpenAPIMetadata {
+                    extensions: None,
+                    server_variables: None,
+                    default_security: vec![],
+                    composition: Some(CompositionConfig {
+                        include_in_merged: true,
+                        component_prefix: Some(String::new()),
+                        tag_prefix: None,
+                        operation_id_prefix: None,
+                        conflict_strategy: ConflictStrategy::Prefix,
+                        preserve_extensions: false,
+                        custom_servers: vec![],
+                    }),
+                }),
+                asyncapi: None,
+                orpc: None,
+            }),
+        });
+
+        let report = lint(&manifest);
+        let codes = report.codes();
+        for code in [
+            "FARP003", "FARP004", "FARP005", "FARP006", "FARP101", "FARP102", "FARP103", "FARP201",
+        ] {
+            assert!(codes.contains(&code), "missing {code} in {codes:?}");
+        }
+        assert!(!codes.contains(&"FARP001"));
+        assert!(!codes.contains(&"FARP002"));
+        assert!(report.has_errors());
+
+        let duplicate = report
+            .findings
+            .iter()
+            .find(|f| f.code == "FARP006")
+            .unwrap();
+        assert_eq!(duplicate.path, "capabilities");
+        let collision = report
+            .findings
+            .iter()
+            .find(|f| f.code == "FARP103")
+            .unwrap();
+        assert_eq!(collision.level, LintLevel::Warning);
+        assert!(collision.message.contains("User"));
+
+        // Fixing the issues leaves no findings
+        manifest.instance_id = "instance-1".to_string();
+        manifest.endpoints.health = "/health".to_string();
+        manifest.endpoints.metrics = Some("/metrics".to_string());
+        manifest.capabilities.pop();
+        let schema = &mut manifest.schemas[0];
+        schema.hash = "a".repeat(64);
+        schema.compatibility = Some(SchemaCompatibility {
+            mode: CompatibilityMode::Backward,
+            previous_versions: vec![],
+            breaking_changes: vec![],
+            deprecations: vec![],
+        });
+        schema.metadata = Some(ProtocolMetadata {
+            graphql: None,
+            grpc: None,
+            openapi: Some(OpenAPIMetadata {
+                extensions: None,
+                server_variables: None,
+                default_security: vec![],
+                composition: Some(CompositionConfig {
+                    include_in_merged: true,
+                    component_prefix: Some("Svc".to_string()),
+                    tag_prefix: None,
+                    operation_id_prefix: None,
+                    conflict_strategy: ConflictStrategy::Prefix,
+                    preserve_extensions: false,
+                    custom_servers: vec![],
+                }),
+            }),
+            asyncapi: None,
+            orpc: None,
+        });
+        manifest.update_checksum().unwrap();
+        assert!(lint(&manifest).findings.is_empty());
+
+        // Without a prefix components merge under the service name
+        manifest.schemas[0].metadata = None;
+        manifest.update_checksum().unwrap();
+        assert!(lint(&manifest).findings.is_empty());
+
+        manifest.checksum = "0".repeat(64);
+        assert_eq!(lint(&manifest).codes(), vec!["FARP007"]);
+    }
+
+    #[test]
+    fn test_detect_schema_type() {
+        use serde_json::json;
+
+        let cases = [
+            (
+                json!({"openapi": "3.1.0", "info": {}}),
+                Some(SchemaType::OpenAPI),
+            ),
+            (json!({"swagger": "2.0"}), Some(SchemaType::OpenAPI)),
+            (
+                json!({"asyncapi": "2.6.0", "channels": {}}),
+                Some(SchemaType::AsyncAPI),
+            ),
+            (
+                json!({"orpc": "1.0.0", "procedures": []}),
+                Some(SchemaType::ORPC),
+            ),
+            (
+                json!({"sdl": "type Query { me: User }"}),
+                Some(SchemaType::GraphQL),
+            ),
+            (json!({"data": {"__schema": {}}}), Some(SchemaType::GraphQL)),
+            (
+                json!({"syntax": "proto3", "package": "users"}),
+                Some(SchemaType::GRPC),
+            ),
+            (
+                json!("syntax = \"proto3\";\nservice Users {}"),
+                Some(SchemaType::GRPC),
+            ),
+            (
+                json!({"type": "record", "name": "User", "fields": []}),
+                Some(SchemaType::Avro),
+            ),
+            (
+                json!({"namespace": "com.users", "services": [], "structs": []}),
+                Some(SchemaType::Thrift),
+            ),
+            (json!({"title": "unknown"}), None),
+            (json!(42), None),
+        ];
+        for (doc, expected) in cases {
+            assert_eq!(detect_schema_type(&doc), expected, "{doc}");
+        }
+    }
+
+    #[test]
+    fn test_lint_schema_type_mismatch() {
+        let mut manifest = new_manifest("svc", "v1", "instance-1");
+        manifest.add_schema(SchemaDescriptor {
+            schema_type: SchemaType::OpenAPI,
+            schema_id: None,
+            spec_version: "2.6.0".to_string(),
+            location: SchemaLocation {
+                location_type: LocationType::Inline,
+                url: None,
+                registry_path: None,
+                headers: None,
+            },
+            content_type: "application/json".to_string(),
+            inline_schema: Some(serde_json::json!({"asyncapi": "2.6.0", "channels": {}})),
+            hash: "a".repeat(64),
+            size: 64,
+            compatibility: None,
+            metadata: None,
+        });
+
+        let report = lint(&manifest);
+        let finding = report
+            .findings
+            .iter()
+            .find(|f| f.code == "FARP104")
+            .unwrap();
+        assert_eq!(finding.path, "schemas[0].type");
+        assert!(finding.message.contains("asyncapi"));
+
+        manifest.schemas[0].schema_type = SchemaType::AsyncAPI;
+        assert!(!lint(&manifest).codes().contains(&"FARP104"));
+    }
+
+    #[test]
+    fn test_lint_content_type_mismatch() {
+        let mut manifest = new_manifest("svc", "v1", "instance-1");
+        manifest.add_schema(SchemaDescriptor {
+            schema_type: SchemaType::OpenAPI,
+            schema_id: None,
+            spec_version: "3.1.0".to_string(),
+            location: SchemaLocation {
+                location_type: LocationType::Inline,
+                url: None,
+                registry_path: None,
+                headers: None,
+            },
+            content_type: "text/csv".to_string(),
+            inline_schema: Some(serde_json::json!({"openapi": "3.1.0", "paths": {}})),
+            hash: "a".repeat(64),
+            size: 64,
+            compatibility: None,
+            metadata: None,
+        });
+
+        let report = lint(&manifest);
+        let finding = report
+            .findings
+            .iter()
+            .find(|f| f.code == "FARP106")
+            .unwrap();
+        assert_eq!(finding.level, LintLevel::Warning);
+        assert_eq!(finding.path, "schemas[0].content_type");
+
+        manifest.schemas[0].content_type = "application/yaml".to_string();
+        assert!(!lint(&manifest).codes().contains(&"FARP106"));
+    }
+
+    #[test]
+    fn test_multiple_schemas_of_same_type() {
+        let with_id = |id: &str, hash: String| SchemaDescriptor {
+            schema_id: Some(id.to_string()),
+            ..descriptor(SchemaType::OpenAPI, hash)
+        };
+
+        let mut old = new_manifest("svc", "v1", "id1");
+        old.add_schema(with_id("public", "a".repeat(64)));
+        old.add_schema(with_id("internal", "b".repeat(64)));
+
+        assert_eq!(old.get_schemas(SchemaType::OpenAPI).len(), 2);
+        assert_eq!(
+            old.get_schema_by_id(SchemaType::OpenAPI, Some("internal"))
+                .unwrap()
+                .hash,
+            "b".repeat(64)
+        );
+        assert!(old.get_schema_by_id(SchemaType::OpenAPI, None).is_none());
+
+        // Only the internal schema changes
+        let mut new = old.clone();
+        new.update_schema(SchemaType::OpenAPI, with_id("internal", "c".repeat(64)))
+            .unwrap();
+        assert_eq!(new.schemas.len(), 2);
+
+        let diff = diff_manifests(&old, &new);
+        assert!(diff.schemas_added.is_empty());
+        assert!(diff.schemas_removed.is_empty());
+        assert_eq!(
+            diff.schemas_changed,
+            vec![SchemaChangeDiff {
+                schema_type: SchemaType::OpenAPI,
+                schema_id: Some("internal".to_string()),
+                old_hash: "b".repeat(64),
+                new_hash: "c".repeat(64),
+            }]
+        );
+
+        // Removing one keeps the other
+        new.schemas
+            .retain(|s| s.schema_id.as_deref() == Some("public"));
+        let diff = diff_manifests(&old, &new);
+        assert_eq!(diff.schemas_removed.len(), 1);
+        assert_eq!(
+            diff.schemas_removed[0].schema_id.as_deref(),
+            Some("internal")
+        );
+        assert!(diff.schemas_changed.is_empty());
+    }
+
+    #[test]
+    fn test_protocol_metadata_accessors() {
+        let mut manifest = new_manifest("test-service", "v1.0.0", "instance-123");
+        assert!(manifest.graphql_metadata().is_none());
+        assert!(manifest.openapi_composition().is_none());
+
+        let descriptor =
+            |schema_type, schema_id: Option<&str>, metadata: serde_json::Value| SchemaDescriptor {
+                schema_type,
+                schema_id: schema_id.map(str::to_string),
+                spec_version: String::new(),
+                location: SchemaLocation {
+                    location_type: LocationType::Inline,
+                    url: None,
+                    registry_path: None,
+                    headers: None,
+                },
+                content_type: "application/json".to_string(),
+                inline_schema: None,
+                hash: String::new(),
+                size: 0,
+                compatibility: None,
+                metadata: serde_json::from_value(metadata).unwrap(),
+            };
+        manifest.add_schema(descriptor(
+            SchemaType::OpenAPI,
+            None,
+            serde_json::Value::Null,
+        ));
+        manifest.add_schema(descriptor(
+            SchemaType::OpenAPI,
+            Some("admin"),
+            serde_json::json!({
+                "openapi": {"composition": {
+                    "include_in_merged": false,
+                    "component_prefix": "admin",
+                    "tag_prefix": null,
+                    "operation_id_prefix": null,
+                    "conflict_strategy": "prefix",
+                    "preserve_extensions": true
+                }}
+            }),
+        ));
+        manifest.add_schema(descriptor(
+            SchemaType::GraphQL,
+            None,
+            serde_json::json!({
+                "graphql": {"complexity_limit": 500}
+            }),
+        ));
+        manifest.add_schema(descriptor(
+            SchemaType::GRPC,
+            None,
+            serde_json::json!({
+                "grpc": {"reflection_enabled": true, "packages": ["users.v1"], "services": []}
+            }),
+        ));
+
+        // The first schema declaring metadata wins, even if another comes first
+        let composition = manifest.openapi_composition().unwrap();
+        assert_eq!(composition.component_prefix.as_deref(), Some("admin"));
+        assert!(manifest.openapi_metadata().is_some());
+        assert_eq!(
+            manifest.graphql_metadata().and_then(|g| g.complexity_limit),
+            Some(500)
+        );
+        assert_eq!(
+            manifest.grpc_metadata().map(|g| g.packages.clone()),
+            Some(vec!["users.v1".to_string()])
+        );
+        assert!(manifest.asyncapi_metadata().is_none());
+        assert!(manifest.orpc_metadata().is_none());
+    }
+
+    #[test]
+    fn test_redacted() {
+        let mut manifest = new_manifest("user-service", "v1.0.0", "instance-1");
+        manifest.endpoints.health = "/health".to_string();
+        manifest.signature = Some("sig".to_string());
+        manifest.instance = Some(InstanceMetadata {
+            address: "10.0.0.1:8080".to_string(),
+            region: Some("eu-west-1".to_string()),
+            zone: None,
+            labels: Some(HashMap::from([
+                ("team".to_string(), "identity".to_string()),
+                ("internal.owner".to_string(), "alice".to_string()),
+            ])),
+            weight: None,
+            status: InstanceStatus::Healthy,
+            role: None,
+            deployment: None,
+            started_at: 0,
+            expected_schema_checksum: None,
+        });
+        manifest.auth = Some(AuthConfig {
+            schemes: Vec::new(),
+            required_scopes: vec!["users:read".to_string()],
+            access_control: Vec::new(),
+            token_validation_url: Some("http://auth.internal/validate".to_string()),
+            public_routes: vec!["/health".to_string()],
+        });
+        manifest.webhook = Some(WebhookConfig {
+            service_webhook: Some("/webhooks".to_string()),
+            gateway_webhook: None,
+            secret: Some("hmac-secret".to_string()),
+            subscribe_events: Vec::new(),
+            publish_events: Vec::new(),
+            retry: None,
+            http_routes: None,
+        });
+        manifest.hints = Some(ServiceHints {
+            recommended_timeout: Some("5s".to_string()),
+            expected_latency: None,
+            scaling: None,
+            dependencies: vec![ServiceDependency {
+                service_name: "billing-service".to_string(),
+                schema_type: SchemaType::GRPC,
+                version_range: None,
+                critical: true,
+                used_operations: Vec::new(),
+            }],
+            resiliency: None,
+            sensitivity: None,
+        });
+
+        let redacted = manifest.redacted();
+        let webhook = redacted.webhook.as_ref().unwrap();
+        assert_eq!(webhook.secret, None);
+        assert_eq!(webhook.service_webhook.as_deref(), Some("/webhooks"));
+        let auth = redacted.auth.as_ref().unwrap();
+        assert_eq!(auth.token_validation_url, None);
+        assert_eq!(auth.public_routes, vec!["/health"]);
+        let instance = redacted.instance.as_ref().unwrap();
+        assert_eq!(
+            instance.labels,
+            Some(HashMap::from([(
+                "team".to_string(),
+                "identity".to_string()
+            )]))
+        );
+        assert_eq!(instance.region.as_deref(), Some("eu-west-1"));
+        let hints = redacted.hints.as_ref().unwrap();
+        assert!(hints.dependencies.is_empty());
+        assert_eq!(hints.recommended_timeout.as_deref(), Some("5s"));
+        assert_eq!(redacted.signature, None);
+        assert_eq!(redacted.service_name, "user-service");
+        assert_eq!(redacted.checksum, manifest.checksum);
+
+        // The original keeps everything
+        assert_eq!(
+            manifest.webhook.as_ref().unwrap().secret.as_deref(),
+            Some("hmac-secret")
+        );
+        assert_eq!(manifest.hints.as_ref().unwrap().dependencies.len(), 1);
+
+        let custom = manifest.redacted_with(&RedactOptions {
+            internal_label_prefixes: vec!["team".to_string()],
+        });
+        let labels = custom.instance.unwrap().labels.unwrap();
+        assert!(labels.contains_key("internal.owner"));
+        assert!(!labels.contains_key("team"));
+    }
+
+    #[tokio::test]
+    async fn test_externalize_large_schemas() {
+        use crate::registry::memory::MemoryRegistry;
+        use crate::registry::SchemaRegistry;
+
+        let inline = |schema_type: SchemaType, schema: serde_json::Value| SchemaDescriptor {
+            schema_type,
+            schema_id: None,
+            spec_version: "3.1.0".to_string(),
+            location: SchemaLocation {
+                location_type: LocationType::Inline,
+                url: None,
+                registry_path: None,
+                headers: None,
+            },
+            content_type: "application/json".to_string(),
+            hash: calculate_schema_checksum(&schema).unwrap(),
+            inline_schema: Some(schema),
+            size: 0,
+            compatibility: None,
+            metadata: None,
+        };
+        let paths: serde_json::Map<String, serde_json::Value> = (0..50)
+            .map(|i| (format!("/items/{i}"), serde_json::json!({"get": {}})))
+            .collect();
+        let large = serde_json::json!({"openapi": "3.1.0", "paths": paths});
+        let small = serde_json::json!({"asyncapi": "3.0.0", "channels": {}});
+
+        let mut manifest = new_manifest("user-service", "v1.0.0", "instance-1");
+        manifest.add_schema(inline(SchemaType::OpenAPI, large.clone()));
+        manifest.add_schema(inline(SchemaType::AsyncAPI, small.clone()));
+        manifest.update_checksum().unwrap();
+        let before = manifest.checksum.clone();
+
+        let registry = MemoryRegistry::new();
+        externalize_large_schemas(&mut manifest, &registry, 256)
+            .await
+            .unwrap();
+
+        let openapi = &manifest.schemas[0];
+        let hash = calculate_schema_checksum(&large).unwrap();
+        let path = format!("/schemas/user-service/openapi-{hash}");
+        assert_eq!(openapi.location.location_type, LocationType::Registry);
+        assert_eq!(
+            openapi.location.registry_path.as_deref(),
+            Some(path.as_str())
+        );
+        assert!(openapi.inline_schema.is_none());
+        assert_eq!(openapi.hash, hash);
+        assert!(openapi.size > 256);
+        assert_eq!(registry.fetch_schema(&path).await.unwrap(), large);
+        assert!(validate_schema_descriptor(openapi).is_ok());
+
+        // Schemas under the threshold stay inline
+        let asyncapi = &manifest.schemas[1];
+        assert_eq!(asyncapi.location.location_type, LocationType::Inline);
+        assert_eq!(asyncapi.inline_schema.as_ref(), Some(&small));
+
+        // Only the location moved, so the content checksum is unchanged
+        assert_eq!(manifest.checksum, before);
+
+        // Re-running is a no-op
+        let after = manifest.clone();
+        externalize_large_schemas(&mut manifest, &registry, 256)
+            .await
+            .unwrap();
+        assert_eq!(manifest.schemas, after.schemas);
+    }
+
+    #[test]
+    fn test_compact() {
+        let mut manifest = new_manifest("user-service", "v1.0.0", "instance-1");
+        manifest.endpoints.health = "/health".to_string();
+        manifest.capabilities = vec!["rest".to_string(), "rest".to_string()];
+        manifest.instance = Some(InstanceMetadata {
+            address: "10.0.0.1:8080".to_string(),
+            region: None,
+            zone: None,
+            labels: Some(HashMap::new()),
+            weight: None,
+            status: InstanceStatus::Healthy,
+            role: None,
+            deployment: None,
+            started_at: 0,
+            expected_schema_checksum: None,
+        });
+        manifest.endpoints.health_check = Some(HealthCheckConfig::default());
+        manifest.routing.tags = vec!["public".to_string(), String::new(), "public".to_string()];
+        manifest.routing.base_path = Some(String::new());
+        manifest.routing.subdomain = Some(String::new());
+        let strip_v1 = PathRewrite {
+            pattern: "^/v1".to_string(),
+            replacement: String::new(),
+        };
+        manifest.routing.rewrite = vec![
+            PathRewrite {
+                pattern: String::new(),
+                replacement: String::new(),
+            },
+            strip_v1.clone(),
+        ];
+        manifest.hints = Some(ServiceHints {
+            recommended_timeout: None,
+            expected_latency: None,
+            scaling: None,
+            dependencies: Vec::new(),
+            resiliency: None,
+            sensitivity: None,
+        });
+        manifest.webhook = Some(WebhookConfig {
+            service_webhook: None,
+            gateway_webhook: None,
+            secret: None,
+            subscribe_events: Vec::new(),
+            publish_events: Vec::new(),
+            retry: None,
+            http_routes: None,
+        });
+        let schema = serde_json::json!({"openapi": "3.1.0", "paths": {}});
+        manifest.add_schema(SchemaDescriptor {
+            schema_type: SchemaType::OpenAPI,
+            schema_id: None,
+            spec_version: "3.1.0".to_string(),
+            location: SchemaLocation {
+                location_type: LocationType::Inline,
+                url: None,
+                registry_path: None,
+                headers: Some(HashMap::new()),
+            },
+            content_type: "application/json".to_string(),
+            hash: calculate_schema_checksum(&schema).unwrap(),
+            inline_schema: Some(schema),
+            size: 0,
+            compatibility: None,
+            metadata: Some(ProtocolMetadata {
+                graphql: None,
+                grpc: None,
+                openapi: None,
+                asyncapi: None,
+                orpc: None,
+            }),
+        });
+        manifest.update_checksum().unwrap();
+
+        let mut compacted = manifest.clone();
+        compacted.compact();
+        let before = serde_json::to_vec(&manifest).unwrap();
+        let after = serde_json::to_vec(&compacted).unwrap();
+        assert!(after.len() < before.len());
+
+        assert_eq!(compacted.capabilities, vec!["rest"]);
+        assert_eq!(compacted.routing.tags, vec!["public"]);
+        assert_eq!(compacted.routing.base_path, None);
+        assert_eq!(compacted.routing.subdomain, None);
+        assert_eq!(compacted.routing.rewrite, vec![strip_v1]);
+        assert_eq!(compacted.instance.as_ref().unwrap().labels, None);
+        assert_eq!(compacted.endpoints.health_check, None);
+        assert_eq!(compacted.hints, None);
+        assert_eq!(compacted.webhook, None);
+        assert_eq!(compacted.schemas[0].location.headers, None);
+        assert_eq!(compacted.schemas[0].metadata, None);
+        assert_eq!(compacted.checksum, manifest.checksum);
+
+        // The compact form round-trips unchanged
+        let decoded: SchemaManifest = serde_json::from_slice(&after).unwrap();
+        assert_eq!(decoded, compacted);
+        assert!(decoded.validate().is_ok());
+
+        // Already-compact manifests are left alone
+        let mut again = compacted.clone();
+        again.compact();
+        assert_eq!(again, compacted);
+    }
 }
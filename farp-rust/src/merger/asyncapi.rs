@@ -3,7 +3,7 @@
 use super::types::*;
 use super::*;
 use crate::errors::Result;
-use crate::types::{SchemaManifest, SchemaType};
+use crate::types::{Capability, SchemaManifest, SchemaType};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -49,6 +49,9 @@ pub struct Channel {
     pub parameters: Option<HashMap<String, serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bindings: Option<HashMap<String, serde_json::Value>>,
+    /// Names of the servers the channel is available on; empty means all
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub servers: Vec<String>,
     #[serde(flatten)]
     pub extensions: HashMap<String, serde_json::Value>,
 }
@@ -118,6 +121,10 @@ impl AsyncAPIMerger {
     }
 
     pub fn merge(&self, schemas: Vec<AsyncAPIServiceSchema>) -> Result<AsyncAPIMergeResult> {
+        self.config.validate_separators(false)?;
+        let separator = self.config.component_separator.as_str();
+        let namespace = self.config.namespace_separator.as_str();
+
         let mut result = AsyncAPIMergeResult {
             spec: AsyncAPISpec {
                 asyncapi: "2.6.0".to_string(),
@@ -176,12 +183,22 @@ impl AsyncAPIMerger {
             let parsed = schema.parsed.as_ref().unwrap();
             let strategy = self.config.default_conflict_strategy;
 
-            let channel_prefix = &schema.manifest.service_name;
-            let message_prefix = &schema.manifest.service_name;
+            result
+                .warnings
+                .extend(validate_asyncapi_capabilities(parsed, &schema.manifest));
+
+            let prefix = self.config.prefix(&service_name);
+            let channel_prefix = &prefix;
+            let message_prefix = &prefix;
 
             // Merge channels
             for (channel_name, channel) in &parsed.channels {
-                let mut prefixed_name = format!("{channel_prefix}.{channel_name}");
+                let mut channel = prefix_channel_refs(channel.clone(), message_prefix, separator);
+                for server in &mut channel.servers {
+                    *server = format!("{prefix}{separator}{server}");
+                }
+                let channel = &channel;
+                let mut prefixed_name = format!("{channel_prefix}{namespace}{channel_name}");
 
                 if let Some(existing_service) = seen_channels.get(&prefixed_name) {
                     let conflict = Conflict {
@@ -210,7 +227,7 @@ impl AsyncAPIMerger {
                             result.conflicts.push(c);
                         }
                         ConflictStrategy::Prefix => {
-                            prefixed_name = format!("{service_name}.{channel_name}");
+                            prefixed_name = format!("{prefix}{namespace}{channel_name}");
                             let mut c = conflict;
                             c.resolution = format!("Prefixed to {prefixed_name}");
                             result.conflicts.push(c);
@@ -240,7 +257,7 @@ impl AsyncAPIMerger {
             if let Some(components) = &parsed.components {
                 // Merge messages
                 for (name, message) in &components.messages {
-                    let prefixed_name = format!("{message_prefix}_{name}");
+                    let prefixed_name = format!("{message_prefix}{separator}{name}");
                     if let Some(existing_service) = seen_messages.get(&prefixed_name) {
                         if strategy == ConflictStrategy::Skip {
                             result.conflicts.push(Conflict {
@@ -254,21 +271,23 @@ impl AsyncAPIMerger {
                         }
                     }
 
+                    let mut message = message.clone();
+                    rewrite_asyncapi_refs(&mut message, message_prefix, separator);
                     if let Some(spec_components) = result.spec.components.as_mut() {
                         spec_components
                             .messages
-                            .insert(prefixed_name.clone(), message.clone());
+                            .insert(prefixed_name.clone(), message);
                     }
                     seen_messages.insert(prefixed_name, service_name.clone());
                 }
 
                 // Merge schemas
                 for (name, schema_obj) in &components.schemas {
-                    let prefixed_name = format!("{message_prefix}_{name}");
+                    let prefixed_name = format!("{message_prefix}{separator}{name}");
+                    let mut schema_obj = schema_obj.clone();
+                    rewrite_asyncapi_refs(&mut schema_obj, message_prefix, separator);
                     if let Some(spec_components) = result.spec.components.as_mut() {
-                        spec_components
-                            .schemas
-                            .insert(prefixed_name, schema_obj.clone());
+                        spec_components.schemas.insert(prefixed_name, schema_obj);
                     }
                 }
 
@@ -302,7 +321,7 @@ impl AsyncAPIMerger {
                                 result.conflicts.push(c);
                             }
                             ConflictStrategy::Prefix => {
-                                let prefixed_name = format!("{service_name}_{name}");
+                                let prefixed_name = format!("{prefix}{separator}{name}");
                                 let mut c = conflict;
                                 c.resolution = format!("Prefixed to {prefixed_name}");
                                 result.conflicts.push(c);
@@ -334,7 +353,7 @@ impl AsyncAPIMerger {
 
             // Merge servers
             for (server_name, server) in &parsed.servers {
-                let prefixed_name = format!("{service_name}_{server_name}");
+                let prefixed_name = format!("{prefix}{separator}{server_name}");
                 if let Some(existing_service) = seen_servers.get(&prefixed_name) {
                     result.warnings.push(format!(
                         "Server {server_name} from {service_name} overwrites {existing_service}"
@@ -352,6 +371,197 @@ impl AsyncAPIMerger {
     }
 }
 
+/// Rewrites local message and schema `$ref`s to their prefixed component names
+///
+/// The merger renames `components.messages.X` and `components.schemas.X` to
+/// `{prefix}{separator}X`; this updates `#/components/messages/X` and
+/// `#/components/schemas/X` pointers anywhere in `value` to match, including
+/// those nested in inline `payload` schemas. Other references are untouched.
+pub fn rewrite_asyncapi_refs(value: &mut serde_json::Value, prefix: &str, separator: &str) {
+    match value {
+        serde_json::Value::Object(obj) => {
+            for (key, child) in obj.iter_mut() {
+                if key == "$ref" {
+                    if let Some(reference) = child.as_str() {
+                        *child = prefix_asyncapi_ref(reference, prefix, separator).into();
+                    }
+                } else {
+                    rewrite_asyncapi_refs(child, prefix, separator);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for child in items {
+                rewrite_asyncapi_refs(child, prefix, separator);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn prefix_asyncapi_ref(reference: &str, prefix: &str, separator: &str) -> String {
+    for kind in ["messages", "schemas"] {
+        let base = format!("#/components/{kind}/");
+        if let Some(name) = reference.strip_prefix(&base) {
+            return format!("{base}{prefix}{separator}{name}");
+        }
+    }
+    reference.to_string()
+}
+
+/// Round-trips a channel through JSON to rewrite its operations' message refs
+fn prefix_channel_refs(channel: Channel, prefix: &str, separator: &str) -> Channel {
+    let Ok(mut value) = serde_json::to_value(&channel) else {
+        return channel;
+    };
+    rewrite_asyncapi_refs(&mut value, prefix, separator);
+    serde_json::from_value(value).unwrap_or(channel)
+}
+
+/// Capabilities that AsyncAPI server protocols can back
+const PROTOCOL_CAPABILITIES: [Capability; 3] =
+    [Capability::MQTT, Capability::AMQP, Capability::WebSocket];
+
+/// Checks the manifest's capabilities against the spec's server protocols
+///
+/// Returns a warning for every messaging capability (`mqtt`, `amqp`,
+/// `websocket`) without a matching server, and for every server whose
+/// protocol implies a capability the manifest doesn't declare.
+pub fn validate_asyncapi_capabilities(
+    spec: &AsyncAPISpec,
+    manifest: &SchemaManifest,
+) -> Vec<String> {
+    let service_name = &manifest.service_name;
+    let declared = |capability: Capability| {
+        manifest
+            .capabilities
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(capability.as_str()))
+    };
+
+    let mut servers: Vec<(&String, &AsyncServer)> = spec.servers.iter().collect();
+    servers.sort_by_key(|(name, _)| *name);
+
+    let mut warnings = Vec::new();
+    for capability in PROTOCOL_CAPABILITIES {
+        let served = servers
+            .iter()
+            .any(|(_, server)| Capability::from_protocol(&server.protocol) == Some(capability));
+        if declared(capability) && !served {
+            warnings.push(format!(
+                "{service_name} declares capability {capability} but has no {capability} server"
+            ));
+        }
+    }
+    for (name, server) in servers {
+        if let Some(capability) = Capability::from_protocol(&server.protocol) {
+            if !declared(capability) {
+                warnings.push(format!(
+                    "{service_name} server {name} uses protocol {} but capability {capability} is not declared",
+                    server.protocol
+                ));
+            }
+        }
+    }
+
+    warnings
+}
+
+/// Transport through which a gateway can expose an AsyncAPI channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Protocol {
+    /// WebSocket (`ws`, `wss`)
+    WebSocket,
+    /// Server-Sent Events (`sse`)
+    SSE,
+    /// Plain HTTP (`http`, `https`); streaming style is up to the service
+    HTTP,
+}
+
+impl Protocol {
+    /// Maps an AsyncAPI server or binding protocol to a gateway transport
+    ///
+    /// Returns `None` for brokers a gateway can't front (Kafka, AMQP, MQTT, ...).
+    pub fn from_server_protocol(protocol: &str) -> Option<Protocol> {
+        match protocol.to_ascii_lowercase().as_str() {
+            "ws" | "wss" => Some(Protocol::WebSocket),
+            "sse" => Some(Protocol::SSE),
+            "http" | "https" => Some(Protocol::HTTP),
+            _ => None,
+        }
+    }
+
+    /// Get string representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Protocol::WebSocket => "websocket",
+            Protocol::SSE => "sse",
+            Protocol::HTTP => "http",
+        }
+    }
+}
+
+impl std::fmt::Display for Protocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Lists the channels a gateway can route to, with their transport
+///
+/// A channel's protocols come from its bindings, then the servers it is
+/// bound to, then every server in the document; the first one a gateway can
+/// front wins. Channels without any declared protocol are assumed to be
+/// [`Protocol::HTTP`]. Channels only reachable over brokers (Kafka, AMQP,
+/// MQTT, ...) are left out. The result is sorted by channel name.
+pub fn gateway_routable_channels(spec: &AsyncAPISpec) -> Vec<(String, Protocol)> {
+    let mut channels: Vec<(String, Protocol)> = spec
+        .channels
+        .iter()
+        .filter_map(|(name, channel)| {
+            let protocols = channel_protocols(spec, channel);
+            let protocol = if protocols.is_empty() {
+                Protocol::HTTP
+            } else {
+                protocols
+                    .iter()
+                    .find_map(|p| Protocol::from_server_protocol(p))?
+            };
+            Some((name.clone(), protocol))
+        })
+        .collect();
+    channels.sort_by(|a, b| a.0.cmp(&b.0));
+    channels
+}
+
+/// Protocols a channel is declared on, in the order described by
+/// [`gateway_routable_channels`]
+pub(crate) fn channel_protocols(spec: &AsyncAPISpec, channel: &Channel) -> Vec<String> {
+    let mut protocols: Vec<String> = channel
+        .bindings
+        .iter()
+        .flat_map(|bindings| bindings.keys().cloned())
+        .collect();
+    protocols.sort();
+    if protocols.is_empty() {
+        protocols = channel
+            .servers
+            .iter()
+            .filter_map(|name| spec.servers.get(name))
+            .map(|server| server.protocol.clone())
+            .collect();
+    }
+    if protocols.is_empty() {
+        let mut servers: Vec<(&String, &AsyncServer)> = spec.servers.iter().collect();
+        servers.sort_by_key(|(name, _)| *name);
+        protocols = servers
+            .into_iter()
+            .map(|(_, server)| server.protocol.clone())
+            .collect();
+    }
+    protocols
+}
+
 /// Parse AsyncAPI schema from JSON
 pub fn parse_asyncapi_schema(raw: &serde_json::Value) -> Result<AsyncAPISpec> {
     let schema_map = raw
@@ -394,6 +604,37 @@ pub fn parse_asyncapi_schema(raw: &serde_json::Value) -> Result<AsyncAPISpec> {
     })
 }
 
+/// Parses just the servers and channels of an AsyncAPI document
+///
+/// Unlike [`parse_asyncapi_schema`] this accepts documents without a
+/// version or `info`; it's enough for [`gateway_routable_channels`].
+pub fn parse_asyncapi_channels(raw: &serde_json::Value) -> AsyncAPISpec {
+    let section = |key: &str| raw.get(key).and_then(|v| v.as_object());
+    AsyncAPISpec {
+        asyncapi: raw
+            .get("asyncapi")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        info: Info {
+            title: String::new(),
+            description: None,
+            version: String::new(),
+            terms_of_service: None,
+            contact: None,
+            license: None,
+            extensions: HashMap::new(),
+        },
+        servers: section("servers")
+            .map(parse_async_servers)
+            .unwrap_or_default(),
+        channels: section("channels").map(parse_channels).unwrap_or_default(),
+        components: None,
+        security: Vec::new(),
+        extensions: HashMap::new(),
+    }
+}
+
 fn parse_async_servers(
     obj: &serde_json::Map<String, serde_json::Value>,
 ) -> HashMap<String, AsyncServer> {
@@ -403,7 +644,12 @@ fn parse_async_servers(
                 Some((
                     name.clone(),
                     AsyncServer {
-                        url: s.get("url")?.as_str()?.to_string(),
+                        // 3.x splits the URL into `host` and `pathname`
+                        url: s
+                            .get("url")
+                            .or_else(|| s.get("host"))?
+                            .as_str()?
+                            .to_string(),
                         protocol: s.get("protocol")?.as_str()?.to_string(),
                         description: s
                             .get("description")
@@ -432,13 +678,17 @@ fn parse_channels(obj: &serde_json::Map<String, serde_json::Value>) -> HashMap<S
                         subscribe: c
                             .get("subscribe")
                             .and_then(|v| v.as_object())
-                            .map(super::openapi::parse_operation_public),
+                            .map(parse_async_operation),
                         publish: c
                             .get("publish")
                             .and_then(|v| v.as_object())
-                            .map(super::openapi::parse_operation_public),
+                            .map(parse_async_operation),
                         parameters: None,
-                        bindings: None,
+                        bindings: c
+                            .get("bindings")
+                            .and_then(|v| v.as_object())
+                            .map(|b| b.iter().map(|(k, v)| (k.clone(), v.clone())).collect()),
+                        servers: parse_channel_servers(c.get("servers")),
                         extensions: HashMap::new(),
                     },
                 )
@@ -447,6 +697,32 @@ fn parse_channels(obj: &serde_json::Map<String, serde_json::Value>) -> HashMap<S
         .collect()
 }
 
+/// Reads a channel's server list: names in 2.x, `#/servers/<name>` refs in 3.x
+fn parse_channel_servers(value: Option<&serde_json::Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|server| {
+            server
+                .as_str()
+                .or_else(|| server.get("$ref")?.as_str()?.strip_prefix("#/servers/"))
+                .map(String::from)
+        })
+        .collect()
+}
+
+/// Parses a 2.x publish/subscribe operation, keeping its `message`
+fn parse_async_operation(obj: &serde_json::Map<String, serde_json::Value>) -> Operation {
+    let mut operation = super::openapi::parse_operation_public(obj);
+    if let Some(message) = obj.get("message") {
+        operation
+            .extensions
+            .insert("message".to_string(), message.clone());
+    }
+    operation
+}
+
 fn parse_async_components(obj: &serde_json::Map<String, serde_json::Value>) -> AsyncComponents {
     AsyncComponents {
         messages: obj
@@ -470,6 +746,15 @@ fn merge_channels(existing: Channel, new: Channel) -> Channel {
         publish: new.publish.or(existing.publish),
         parameters: new.parameters.or(existing.parameters),
         bindings: new.bindings.or(existing.bindings),
+        servers: {
+            let mut servers = existing.servers;
+            for server in new.servers {
+                if !servers.contains(&server) {
+                    servers.push(server);
+                }
+            }
+            servers
+        },
         extensions: {
             let mut ext = existing.extensions;
             ext.extend(new.extensions);
@@ -485,3 +770,172 @@ fn should_include_asyncapi(schema: &AsyncAPIServiceSchema) -> bool {
         .iter()
         .any(|s| s.schema_type == SchemaType::AsyncAPI)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::new_manifest;
+
+    fn spec_with_servers(protocols: &[(&str, &str)]) -> AsyncAPISpec {
+        parse_asyncapi_schema(&serde_json::json!({
+            "asyncapi": "2.6.0",
+            "info": {"title": "Events", "version": "1.0.0"},
+            "servers": protocols
+                .iter()
+                .map(|(name, protocol)| {
+                    (name.to_string(), serde_json::json!({"url": "broker:1234", "protocol": protocol}))
+                })
+                .collect::<serde_json::Map<_, _>>(),
+            "channels": {}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate_asyncapi_capabilities_matching() {
+        let mut manifest = new_manifest("events", "v1.0.0", "instance-1");
+        manifest.capabilities = vec!["mqtt".to_string(), "websocket".to_string()];
+
+        let spec = spec_with_servers(&[("broker", "mqtt"), ("live", "wss"), ("log", "kafka")]);
+        assert!(validate_asyncapi_capabilities(&spec, &manifest).is_empty());
+    }
+
+    #[test]
+    fn test_validate_asyncapi_capabilities_mismatched() {
+        let mut manifest = new_manifest("events", "v1.0.0", "instance-1");
+        manifest.capabilities = vec!["amqp".to_string()];
+
+        let spec = spec_with_servers(&[("broker", "kafka"), ("devices", "mqtt")]);
+        let warnings = validate_asyncapi_capabilities(&spec, &manifest);
+        assert_eq!(
+            warnings,
+            vec![
+                "events declares capability amqp but has no amqp server",
+                "events server devices uses protocol mqtt but capability mqtt is not declared",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_gateway_routable_channels() {
+        let spec = parse_asyncapi_schema(&serde_json::json!({
+            "asyncapi": "2.6.0",
+            "info": {"title": "Orders", "version": "1.0.0"},
+            "servers": {
+                "broker": {"url": "kafka:9092", "protocol": "kafka"},
+                "live": {"url": "orders:8080", "protocol": "wss"}
+            },
+            "channels": {
+                "orders.created": {"servers": ["broker"]},
+                "/ws/orders": {"servers": ["live"]},
+                "/ws/cart": {"bindings": {"ws": {"method": "GET"}}},
+                "orders.audit": {"bindings": {"kafka": {"topic": "audit"}}},
+                "/anywhere": {}
+            }
+        }))
+        .unwrap();
+
+        // Unbound channels take the first gateway-reachable document server
+        assert_eq!(
+            gateway_routable_channels(&spec),
+            vec![
+                ("/anywhere".to_string(), Protocol::WebSocket),
+                ("/ws/cart".to_string(), Protocol::WebSocket),
+                ("/ws/orders".to_string(), Protocol::WebSocket),
+            ]
+        );
+
+        let kafka_only = parse_asyncapi_channels(&serde_json::json!({
+            "servers": {"broker": {"host": "kafka:9092", "protocol": "kafka"}},
+            "channels": {"orders.created": {"servers": [{"$ref": "#/servers/broker"}]}}
+        }));
+        assert!(gateway_routable_channels(&kafka_only).is_empty());
+
+        let undeclared = parse_asyncapi_channels(&serde_json::json!({"channels": {"/events": {}}}));
+        assert_eq!(
+            gateway_routable_channels(&undeclared),
+            vec![("/events".to_string(), Protocol::HTTP)]
+        );
+    }
+
+    #[test]
+    fn test_merge_rewrites_message_refs() {
+        let mut manifest = new_manifest("orders", "v1.0.0", "instance-1");
+        manifest.schemas.push(crate::types::SchemaDescriptor {
+            schema_type: SchemaType::AsyncAPI,
+            schema_id: None,
+            spec_version: "2.6.0".to_string(),
+            location: crate::types::SchemaLocation {
+                location_type: crate::types::LocationType::Inline,
+                url: None,
+                registry_path: None,
+                headers: None,
+            },
+            content_type: "application/json".to_string(),
+            inline_schema: None,
+            hash: String::new(),
+            size: 0,
+            compatibility: None,
+            metadata: None,
+        });
+
+        let schema = serde_json::json!({
+            "asyncapi": "2.6.0",
+            "info": {"title": "Orders", "version": "1.0.0"},
+            "channels": {
+                "order/created": {
+                    "subscribe": {"message": {"$ref": "#/components/messages/OrderCreated"}}
+                },
+                "order/cancelled": {
+                    "publish": {
+                        "message": {
+                            "payload": {
+                                "type": "object",
+                                "properties": {"order": {"$ref": "#/components/schemas/Order"}}
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "messages": {
+                    "OrderCreated": {"payload": {"$ref": "#/components/schemas/Order"}}
+                },
+                "schemas": {
+                    "Order": {
+                        "type": "object",
+                        "properties": {"lines": {"items": {"$ref": "#/components/schemas/Line"}}}
+                    },
+                    "Line": {"type": "object"}
+                }
+            }
+        });
+
+        let result = AsyncAPIMerger::new(MergerConfig::default())
+            .merge(vec![AsyncAPIServiceSchema {
+                manifest,
+                schema,
+                parsed: None,
+            }])
+            .unwrap();
+        let spec = serde_json::to_value(&result.spec).unwrap();
+
+        assert_eq!(
+            spec["channels"]["orders.order/created"]["subscribe"]["message"]["$ref"],
+            "#/components/messages/orders_OrderCreated"
+        );
+        assert_eq!(
+            spec["channels"]["orders.order/cancelled"]["publish"]["message"]["payload"]
+                ["properties"]["order"]["$ref"],
+            "#/components/schemas/orders_Order"
+        );
+        assert_eq!(
+            spec["components"]["messages"]["orders_OrderCreated"]["payload"]["$ref"],
+            "#/components/schemas/orders_Order"
+        );
+        assert_eq!(
+            spec["components"]["schemas"]["orders_Order"]["properties"]["lines"]["items"]["$ref"],
+            "#/components/schemas/orders_Line"
+        );
+    }
+}
@@ -58,6 +58,7 @@ pub struct GRPCTLSConfig {
 /// gRPC service definition
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GRPCService {
+    #[serde(default)]
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -69,12 +70,15 @@ pub struct GRPCService {
 /// gRPC method (RPC)
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GRPCMethod {
+    #[serde(default)]
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     pub input_type: String,
     pub output_type: String,
+    #[serde(default)]
     pub client_streaming: bool,
+    #[serde(default)]
     pub server_streaming: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub options: Option<HashMap<String, serde_json::Value>>,
@@ -140,6 +144,9 @@ impl GRPCMerger {
     }
 
     pub fn merge(&self, schemas: Vec<GRPCServiceSchema>) -> Result<GRPCMergeResult> {
+        self.config.validate_separators(true)?;
+        let separator = self.config.component_separator.as_str();
+
         let mut result = GRPCMergeResult {
             spec: GRPCSpec {
                 syntax: "proto3".to_string(),
@@ -186,12 +193,13 @@ impl GRPCMerger {
             let parsed = schema.parsed.as_ref().unwrap();
             let strategy = self.config.default_conflict_strategy;
 
-            let service_prefix = &schema.manifest.service_name;
-            let message_prefix = &schema.manifest.service_name;
+            let prefix = self.config.prefix(&service_name);
+            let service_prefix = &prefix;
+            let message_prefix = &prefix;
 
             // Merge services
             for (svc_name, service) in &parsed.services {
-                let mut prefixed_name = format!("{service_prefix}_{svc_name}");
+                let mut prefixed_name = format!("{service_prefix}{separator}{svc_name}");
 
                 if let Some(existing_service) = seen_services.get(&prefixed_name) {
                     let conflict = Conflict {
@@ -220,7 +228,7 @@ impl GRPCMerger {
                             result.conflicts.push(c);
                         }
                         ConflictStrategy::Prefix => {
-                            prefixed_name = format!("{service_name}_{svc_name}");
+                            prefixed_name = format!("{prefix}{separator}{svc_name}");
                             let mut c = conflict;
                             c.resolution = format!("Prefixed to {prefixed_name}");
                             result.conflicts.push(c);
@@ -242,7 +250,7 @@ impl GRPCMerger {
 
             // Merge messages
             for (msg_name, message) in &parsed.messages {
-                let prefixed_name = format!("{message_prefix}_{msg_name}");
+                let prefixed_name = format!("{message_prefix}{separator}{msg_name}");
                 if let Some(existing_service) = seen_messages.get(&prefixed_name) {
                     if strategy == ConflictStrategy::Skip {
                         result.conflicts.push(Conflict {
@@ -265,7 +273,7 @@ impl GRPCMerger {
 
             // Merge enums
             for (enum_name, enum_def) in &parsed.enums {
-                let prefixed_name = format!("{message_prefix}_{enum_name}");
+                let prefixed_name = format!("{message_prefix}{separator}{enum_name}");
                 if let Some(existing_service) = seen_enums.get(&prefixed_name) {
                     result.warnings.push(format!(
                         "Enum {enum_name} from {service_name} overwrites {existing_service}"
@@ -307,7 +315,7 @@ impl GRPCMerger {
                             result.conflicts.push(c);
                         }
                         ConflictStrategy::Prefix => {
-                            let prefixed_name = format!("{service_name}_{name}");
+                            let prefixed_name = format!("{prefix}{separator}{name}");
                             let mut c = conflict;
                             c.resolution = format!("Prefixed to {prefixed_name}");
                             result.conflicts.push(c);
@@ -340,11 +348,26 @@ impl GRPCMerger {
 }
 
 /// Parse gRPC schema from JSON
+///
+/// Reads `package`, `services`, `messages` and `enums` in [`GRPCSpec`]'s
+/// JSON layout. Service and method names default to their map keys.
 pub fn parse_grpc_schema(raw: &serde_json::Value) -> Result<GRPCSpec> {
     let schema_map = raw
         .as_object()
         .ok_or_else(|| crate::errors::Error::invalid_schema("schema must be an object"))?;
 
+    let mut services: HashMap<String, GRPCService> = parse_grpc_map(raw, "services")?;
+    for (name, service) in &mut services {
+        if service.name.is_empty() {
+            service.name.clone_from(name);
+        }
+        for (method_name, method) in &mut service.methods {
+            if method.name.is_empty() {
+                method.name.clone_from(method_name);
+            }
+        }
+    }
+
     let spec = GRPCSpec {
         syntax: "proto3".to_string(),
         package: schema_map
@@ -352,9 +375,9 @@ pub fn parse_grpc_schema(raw: &serde_json::Value) -> Result<GRPCSpec> {
             .and_then(|v| v.as_str())
             .unwrap_or("default")
             .to_string(),
-        services: HashMap::new(),
-        messages: HashMap::new(),
-        enums: HashMap::new(),
+        services,
+        messages: parse_grpc_map(raw, "messages")?,
+        enums: parse_grpc_map(raw, "enums")?,
         security_schemes: HashMap::new(),
         imports: Vec::new(),
     };
@@ -362,6 +385,79 @@ pub fn parse_grpc_schema(raw: &serde_json::Value) -> Result<GRPCSpec> {
     Ok(spec)
 }
 
+fn parse_grpc_map<T: serde::de::DeserializeOwned>(
+    raw: &serde_json::Value,
+    key: &str,
+) -> Result<HashMap<String, T>> {
+    match raw.get(key) {
+        Some(value) => serde_json::from_value(value.clone())
+            .map_err(|e| crate::errors::Error::invalid_schema(format!("{key}: {e}"))),
+        None => Ok(HashMap::new()),
+    }
+}
+
+/// HTTP binding from a method's `google.api.http` option
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRule {
+    /// HTTP method (upper case)
+    pub method: String,
+    /// URL path template, e.g. `/v1/users/{id}`
+    pub path: String,
+    /// Request field the HTTP body maps to (`*` for the whole message)
+    pub body: Option<String>,
+}
+
+impl GRPCMethod {
+    /// Returns the method's HTTP transcoding rules
+    ///
+    /// Reads the `google.api.http` option (also accepted as
+    /// `(google.api.http)`) and its `additional_bindings`. Each binding uses
+    /// one of `get`, `put`, `post`, `delete` or `patch`, or a `custom`
+    /// `{kind, path}` pair.
+    pub fn http_rules(&self) -> Vec<HttpRule> {
+        let Some(options) = &self.options else {
+            return Vec::new();
+        };
+        let Some(http) = options
+            .get("google.api.http")
+            .or_else(|| options.get("(google.api.http)"))
+        else {
+            return Vec::new();
+        };
+
+        let mut rules: Vec<HttpRule> = http_rule(http).into_iter().collect();
+        if let Some(bindings) = http.get("additional_bindings").and_then(|b| b.as_array()) {
+            rules.extend(bindings.iter().filter_map(http_rule));
+        }
+        rules
+    }
+}
+
+fn http_rule(binding: &serde_json::Value) -> Option<HttpRule> {
+    let (method, path) = ["get", "put", "post", "delete", "patch"]
+        .iter()
+        .find_map(|method| {
+            let path = binding.get(*method)?.as_str()?;
+            Some((method.to_ascii_uppercase(), path.to_string()))
+        })
+        .or_else(|| {
+            let custom = binding.get("custom")?;
+            Some((
+                custom.get("kind")?.as_str()?.to_ascii_uppercase(),
+                custom.get("path")?.as_str()?.to_string(),
+            ))
+        })?;
+    Some(HttpRule {
+        method,
+        path,
+        body: binding
+            .get("body")
+            .and_then(|b| b.as_str())
+            .filter(|b| !b.is_empty())
+            .map(String::from),
+    })
+}
+
 fn should_include_grpc(schema: &GRPCServiceSchema) -> bool {
     schema
         .manifest
@@ -369,3 +465,67 @@ fn should_include_grpc(schema: &GRPCServiceSchema) -> bool {
         .iter()
         .any(|s| s.schema_type == SchemaType::GRPC)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_rules() {
+        let spec = parse_grpc_schema(&serde_json::json!({
+            "package": "users.v1",
+            "services": {
+                "UserService": {
+                    "methods": {
+                        "GetUser": {
+                            "input_type": "GetUserRequest",
+                            "output_type": "User",
+                            "options": {
+                                "google.api.http": {
+                                    "get": "/v1/users/{id}",
+                                    "additional_bindings": [
+                                        {"custom": {"kind": "head", "path": "/v1/users/{id}"}}
+                                    ]
+                                }
+                            }
+                        },
+                        "CreateUser": {
+                            "input_type": "CreateUserRequest",
+                            "output_type": "User",
+                            "options": {"(google.api.http)": {"post": "/v1/users", "body": "user"}}
+                        },
+                        "Ping": {"input_type": "Empty", "output_type": "Empty"}
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        let service = &spec.services["UserService"];
+        assert_eq!(service.name, "UserService");
+        let get = &service.methods["GetUser"];
+        assert_eq!(get.name, "GetUser");
+        assert_eq!(
+            get.http_rules(),
+            vec![
+                HttpRule {
+                    method: "GET".to_string(),
+                    path: "/v1/users/{id}".to_string(),
+                    body: None,
+                },
+                HttpRule {
+                    method: "HEAD".to_string(),
+                    path: "/v1/users/{id}".to_string(),
+                    body: None,
+                },
+            ]
+        );
+        assert_eq!(
+            service.methods["CreateUser"].http_rules()[0]
+                .body
+                .as_deref(),
+            Some("user")
+        );
+        assert!(service.methods["Ping"].http_rules().is_empty());
+    }
+}
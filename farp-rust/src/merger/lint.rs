@@ -0,0 +1,261 @@
+//! Lint rules for OpenAPI patterns that route poorly through gateways
+
+use super::types::{OpenAPISpec, Operation, Parameter, PathItem};
+use crate::manifest::{LintFinding, LintLevel};
+use std::collections::HashMap;
+
+/// Checks a spec for patterns gateways handle badly
+///
+/// Findings carry the offending API path and one of these codes:
+///
+/// | Code | Level | Check |
+/// |------|-------|-------|
+/// | FARP301 | warning | Wildcard or greedy path segment (`*`, `{rest+}`, `{rest:.*}`) |
+/// | FARP302 | warning | Path overlaps another, e.g. `/users/me` and `/users/{id}` |
+/// | FARP303 | warning | Operation without an `operationId` |
+/// | FARP304 | info | String path parameter with no `pattern`, `maxLength`, `enum` or `format` |
+///
+/// Findings are ordered by path, then code.
+pub fn lint_for_gateway(spec: &OpenAPISpec) -> Vec<LintFinding> {
+    let mut paths: Vec<&String> = spec.paths.keys().collect();
+    paths.sort();
+
+    let mut findings = Vec::new();
+    for (i, path) in paths.iter().enumerate() {
+        let item = &spec.paths[*path];
+
+        if let Some(segment) = path.split('/').find(|s| is_wildcard(s)) {
+            findings.push(finding(
+                LintLevel::Warning,
+                "FARP301",
+                path,
+                format!("wildcard segment {segment} matches unbounded paths"),
+            ));
+        }
+
+        for other in &paths[i + 1..] {
+            if overlaps(path, other) {
+                findings.push(finding(
+                    LintLevel::Warning,
+                    "FARP302",
+                    path,
+                    format!("ambiguous with {other}; routing depends on gateway match order"),
+                ));
+            }
+        }
+
+        for (method, operation) in operations(item) {
+            if operation
+                .operation_id
+                .as_deref()
+                .map_or(true, str::is_empty)
+            {
+                findings.push(finding(
+                    LintLevel::Warning,
+                    "FARP303",
+                    path,
+                    format!("{method} has no operationId"),
+                ));
+            }
+        }
+
+        for name in template_params(path) {
+            let declared = operations(item)
+                .flat_map(|(_, op)| op.parameters.iter())
+                .chain(&item.parameters)
+                .find(|p| p.in_ == "path" && p.name == name);
+            if declared.is_some_and(is_unbounded) {
+                findings.push(finding(
+                    LintLevel::Info,
+                    "FARP304",
+                    path,
+                    format!("path parameter {name} accepts any string"),
+                ));
+            }
+        }
+    }
+    findings
+}
+
+/// Lists mismatches between a path's `{placeholders}` and its path parameters
+///
+/// For every operation, each placeholder must be declared as an `in: path`
+/// parameter (on the operation or the path item) and every declared path
+/// parameter must appear in the template. Operations that declare no path
+/// parameters at all are skipped, as are operations with `$ref` parameters
+/// that don't resolve against `component_params`. Messages look like
+/// `GET /users/{userId}: ...`.
+pub fn path_param_mismatches(
+    path: &str,
+    item: &PathItem,
+    component_params: &HashMap<String, Parameter>,
+) -> Vec<String> {
+    let placeholders: Vec<&str> = template_params(path).collect();
+    let resolve = |p: &'_ Parameter| -> Option<Parameter> {
+        match &p.reference {
+            Some(reference) => reference
+                .strip_prefix("#/components/parameters/")
+                .and_then(|name| component_params.get(name))
+                .cloned(),
+            None => Some(p.clone()),
+        }
+    };
+
+    let mut mismatches = Vec::new();
+    for (method, operation) in operations(item) {
+        let Some(params) = item
+            .parameters
+            .iter()
+            .chain(&operation.parameters)
+            .map(resolve)
+            .collect::<Option<Vec<Parameter>>>()
+        else {
+            continue;
+        };
+        let declared: Vec<&str> = params
+            .iter()
+            .filter(|p| p.in_ == "path")
+            .map(|p| p.name.as_str())
+            .collect();
+        if declared.is_empty() {
+            continue;
+        }
+
+        for name in &placeholders {
+            if !declared.contains(name) {
+                mismatches.push(format!(
+                    "{method} {path}: placeholder {{{name}}} has no path parameter"
+                ));
+            }
+        }
+        for name in &declared {
+            if !placeholders.contains(name) {
+                mismatches.push(format!(
+                    "{method} {path}: path parameter {name} is not in the template"
+                ));
+            }
+        }
+    }
+    mismatches.dedup();
+    mismatches
+}
+
+fn finding(level: LintLevel, code: &str, path: &str, message: String) -> LintFinding {
+    LintFinding {
+        level,
+        code: code.to_string(),
+        path: path.to_string(),
+        message,
+    }
+}
+
+fn operations(item: &PathItem) -> impl Iterator<Item = (&'static str, &Operation)> {
+    [
+        ("GET", &item.get),
+        ("PUT", &item.put),
+        ("POST", &item.post),
+        ("DELETE", &item.delete),
+        ("OPTIONS", &item.options),
+        ("HEAD", &item.head),
+        ("PATCH", &item.patch),
+        ("TRACE", &item.trace),
+    ]
+    .into_iter()
+    .filter_map(|(method, op)| op.as_ref().map(|op| (method, op)))
+}
+
+fn template_name(segment: &str) -> Option<&str> {
+    segment.strip_prefix('{')?.strip_suffix('}')
+}
+
+fn template_params(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter_map(template_name)
+}
+
+fn is_wildcard(segment: &str) -> bool {
+    segment.contains('*')
+        || template_name(segment).is_some_and(|name| name.ends_with('+') || name.contains(':'))
+}
+
+/// Two paths overlap when some request path could match both
+fn overlaps(a: &str, b: &str) -> bool {
+    let a: Vec<&str> = a.split('/').collect();
+    let b: Vec<&str> = b.split('/').collect();
+    a.len() == b.len()
+        && a.iter()
+            .zip(&b)
+            .all(|(x, y)| x == y || template_name(x).is_some() || template_name(y).is_some())
+}
+
+fn is_unbounded(param: &Parameter) -> bool {
+    let Some(schema) = param.schema.as_ref().and_then(|s| s.as_object()) else {
+        return false;
+    };
+    schema.get("type").and_then(|t| t.as_str()) == Some("string")
+        && !["pattern", "maxLength", "enum", "format"]
+            .iter()
+            .any(|key| schema.contains_key(*key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merger::parse_openapi_schema;
+
+    fn lint(paths: serde_json::Value) -> Vec<LintFinding> {
+        let spec = parse_openapi_schema(&serde_json::json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Users", "version": "1.0.0"},
+            "paths": paths
+        }))
+        .unwrap();
+        lint_for_gateway(&spec)
+    }
+
+    fn codes(findings: &[LintFinding]) -> Vec<(&str, &str)> {
+        findings
+            .iter()
+            .map(|f| (f.code.as_str(), f.path.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_overlapping_paths() {
+        let findings = lint(serde_json::json!({
+            "/users/me": {"get": {"operationId": "me"}},
+            "/users/{id}": {"get": {"operationId": "getUser"}},
+            "/users/{id}/orders": {"get": {"operationId": "orders"}}
+        }));
+        assert_eq!(codes(&findings), vec![("FARP302", "/users/me")]);
+        assert!(findings[0].message.contains("/users/{id}"));
+        assert_eq!(findings[0].level, LintLevel::Warning);
+    }
+
+    #[test]
+    fn test_missing_operation_id() {
+        let findings = lint(serde_json::json!({
+            "/users": {"get": {"operationId": "listUsers"}, "post": {}}
+        }));
+        assert_eq!(codes(&findings), vec![("FARP303", "/users")]);
+        assert!(findings[0].message.starts_with("POST"));
+    }
+
+    #[test]
+    fn test_wildcards_and_unbounded_params() {
+        let findings = lint(serde_json::json!({
+            "/files/{path+}": {"get": {"operationId": "file"}},
+            "/orders/{id}": {"get": {
+                "operationId": "order",
+                "parameters": [{"name": "id", "in": "path", "required": true, "schema": {"type": "string"}}]
+            }},
+            "/items/{id}": {"get": {
+                "operationId": "item",
+                "parameters": [{"name": "id", "in": "path", "required": true, "schema": {"type": "string", "format": "uuid"}}]
+            }}
+        }));
+        assert_eq!(
+            codes(&findings),
+            vec![("FARP301", "/files/{path+}"), ("FARP304", "/orders/{id}")]
+        );
+    }
+}
@@ -2,19 +2,26 @@
 
 pub mod asyncapi;
 pub mod grpc;
+pub mod lint;
 pub mod openapi;
 pub mod orpc;
+pub mod transform;
 pub mod types;
 
+pub use crate::util::sanitize_identifier;
 pub use asyncapi::*;
 pub use grpc::*;
+pub use lint::*;
 pub use openapi::*;
 pub use orpc::*;
+pub use transform::*;
 pub use types::*;
 
 use crate::errors::Result;
-use crate::types::{ConflictStrategy, SchemaManifest, SchemaType};
+use crate::types::{ChangeSeverity, ConflictStrategy, SchemaManifest, SchemaType};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// OpenAPI schema merger
 pub struct Merger {
@@ -22,7 +29,7 @@ pub struct Merger {
 }
 
 /// Merger configuration
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct MergerConfig {
     /// Default conflict strategy if not specified in metadata
     pub default_conflict_strategy: ConflictStrategy,
@@ -32,12 +39,105 @@ pub struct MergerConfig {
     pub merged_description: String,
     /// Version for the merged OpenAPI spec
     pub merged_version: String,
+    /// Contact for the merged OpenAPI spec
+    pub merged_contact: Option<Contact>,
+    /// License for the merged OpenAPI spec
+    pub merged_license: Option<License>,
+    /// Terms of service URL for the merged OpenAPI spec
+    pub merged_terms_of_service: Option<String>,
     /// Whether to include service tags in operations
     pub include_service_tags: bool,
-    /// Whether to sort merged content alphabetically
+    /// Whether to sort merged tags alphabetically
+    ///
+    /// Paths, components and other maps always serialize in key order, so
+    /// merged JSON is byte-stable either way.
     pub sort_output: bool,
     /// Custom server URLs for the merged spec
     pub servers: Vec<Server>,
+    /// Maximum number of `$ref` hops a schema walk may follow before giving up
+    pub max_ref_depth: usize,
+    /// Maximum nesting of objects and arrays in an input document
+    ///
+    /// The merge's reference rewriting walks recurse once per level, so
+    /// deeper documents are rejected up front; see
+    /// [`check_depth`](openapi::check_depth).
+    pub max_schema_depth: usize,
+    /// Whether to drop component schemas nothing references from the output
+    pub prune_unreferenced: bool,
+    /// Whether to synthesize `operationId`s (e.g. `get_users_by_id`) where missing
+    pub generate_missing_operation_ids: bool,
+    /// Resolver consulted before the conflict strategy
+    pub custom_resolver: Option<Arc<dyn ConflictResolver>>,
+    /// Transforms applied, in order, to each parsed spec before merging
+    pub transforms: Vec<Arc<dyn SchemaTransform>>,
+    /// How merged `operationId`s are derived, regardless of conflict strategy
+    pub operation_id_strategy: OperationIdStrategy,
+    /// Whether to run [`lint_for_gateway`] on the merged spec and report
+    /// findings as warnings
+    pub gateway_lint: bool,
+    /// Fail the merge, instead of warning, when a path's `{placeholders}`
+    /// don't match its declared path parameters (see [`path_param_mismatches`])
+    pub strict_path_params: bool,
+    /// Treat paths differing only in case (`/Users`, `/users`) as conflicting,
+    /// as case-insensitive gateways route them to the same place; output
+    /// keeps each path's original casing
+    pub case_insensitive_paths: bool,
+    /// Treat paths differing only in a trailing slash (`/users/`, `/users`)
+    /// as conflicting
+    pub normalize_trailing_slash: bool,
+    /// Joins a prefix to a component, message or server name (`{prefix}_{name}`)
+    pub component_separator: String,
+    /// Joins a prefix to an AsyncAPI channel or oRPC procedure (`{prefix}.{name}`)
+    pub namespace_separator: String,
+    /// Which operation wins when the `Merge` strategy combines two path items
+    /// that both define the same HTTP method
+    pub method_conflict_strategy: MethodConflictStrategy,
+    /// Whether to pass prefixes through [`sanitize_identifier`] so names like
+    /// `order.service v2` yield valid identifiers (`order_service_v2`)
+    pub sanitize_prefixes: bool,
+    /// Whether to drop `example`/`examples` from the merged spec
+    /// (see [`strip_examples`])
+    pub strip_examples: bool,
+    /// Whether to drop response body schemas from the merged spec, keeping
+    /// status codes and media types (see [`strip_response_schemas`])
+    pub strip_response_schemas: bool,
+}
+
+impl std::fmt::Debug for MergerConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MergerConfig")
+            .field("default_conflict_strategy", &self.default_conflict_strategy)
+            .field("merged_title", &self.merged_title)
+            .field("merged_description", &self.merged_description)
+            .field("merged_version", &self.merged_version)
+            .field("merged_contact", &self.merged_contact)
+            .field("merged_license", &self.merged_license)
+            .field("merged_terms_of_service", &self.merged_terms_of_service)
+            .field("include_service_tags", &self.include_service_tags)
+            .field("sort_output", &self.sort_output)
+            .field("servers", &self.servers)
+            .field("max_ref_depth", &self.max_ref_depth)
+            .field("max_schema_depth", &self.max_schema_depth)
+            .field("prune_unreferenced", &self.prune_unreferenced)
+            .field(
+                "generate_missing_operation_ids",
+                &self.generate_missing_operation_ids,
+            )
+            .field("custom_resolver", &self.custom_resolver.is_some())
+            .field("transforms", &self.transforms.len())
+            .field("operation_id_strategy", &self.operation_id_strategy)
+            .field("gateway_lint", &self.gateway_lint)
+            .field("strict_path_params", &self.strict_path_params)
+            .field("case_insensitive_paths", &self.case_insensitive_paths)
+            .field("normalize_trailing_slash", &self.normalize_trailing_slash)
+            .field("component_separator", &self.component_separator)
+            .field("namespace_separator", &self.namespace_separator)
+            .field("method_conflict_strategy", &self.method_conflict_strategy)
+            .field("sanitize_prefixes", &self.sanitize_prefixes)
+            .field("strip_examples", &self.strip_examples)
+            .field("strip_response_schemas", &self.strip_response_schemas)
+            .finish()
+    }
 }
 
 impl Default for MergerConfig {
@@ -47,9 +147,138 @@ impl Default for MergerConfig {
             merged_title: "Federated API".to_string(),
             merged_description: "Merged API specification from multiple services".to_string(),
             merged_version: "1.0.0".to_string(),
+            merged_contact: None,
+            merged_license: None,
+            merged_terms_of_service: None,
             include_service_tags: true,
             sort_output: true,
             servers: Vec::new(),
+            max_ref_depth: 64,
+            max_schema_depth: 128,
+            prune_unreferenced: false,
+            generate_missing_operation_ids: false,
+            custom_resolver: None,
+            transforms: Vec::new(),
+            operation_id_strategy: OperationIdStrategy::default(),
+            gateway_lint: false,
+            strict_path_params: false,
+            case_insensitive_paths: false,
+            normalize_trailing_slash: false,
+            component_separator: "_".to_string(),
+            namespace_separator: ".".to_string(),
+            method_conflict_strategy: MethodConflictStrategy::default(),
+            sanitize_prefixes: true,
+            strip_examples: false,
+            strip_response_schemas: false,
+        }
+    }
+}
+
+impl MergerConfig {
+    /// Returns `prefix` as used in merged names, sanitized when
+    /// `sanitize_prefixes` is set
+    pub fn prefix(&self, prefix: &str) -> String {
+        if self.sanitize_prefixes {
+            sanitize_identifier(prefix)
+        } else {
+            prefix.to_string()
+        }
+    }
+
+    /// Checks the separators yield valid names in the target format
+    ///
+    /// Component separators may only use `A-Z a-z 0-9 . - _` (the OpenAPI
+    /// component key alphabet), or `A-Z a-z 0-9 _` for `protobuf` (gRPC)
+    /// identifiers. Namespace separators must be non-empty and free of
+    /// whitespace, `/`, `#`, `{` and `}`.
+    pub fn validate_separators(&self, protobuf: bool) -> Result<()> {
+        let component_char = |c: char| {
+            c.is_ascii_alphanumeric() || c == '_' || (!protobuf && (c == '.' || c == '-'))
+        };
+        if self.component_separator.is_empty()
+            || !self.component_separator.chars().all(component_char)
+        {
+            return Err(crate::errors::Error::validation(
+                "component_separator",
+                format!(
+                    "{:?} is not valid in {} names",
+                    self.component_separator,
+                    if protobuf { "protobuf" } else { "component" }
+                ),
+            ));
+        }
+        if self.namespace_separator.is_empty()
+            || self
+                .namespace_separator
+                .chars()
+                .any(|c| c.is_whitespace() || "/#{}".contains(c))
+        {
+            return Err(crate::errors::Error::validation(
+                "namespace_separator",
+                format!("{:?} is not a valid separator", self.namespace_separator),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// How the `Merge` strategy resolves a method both services define on a path
+///
+/// Each collision is recorded as a [`ConflictType::Path`] conflict whose
+/// item is `"{METHOD} {path}"`. A service with a higher routing priority
+/// keeps its operation under `Overwrite` too.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MethodConflictStrategy {
+    /// The incoming service's operation replaces the existing one
+    #[default]
+    Overwrite,
+    /// The operation already in the merged spec is kept
+    Keep,
+    /// Fail the merge
+    Error,
+}
+
+/// How [`Merger`] rewrites `operationId`s
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OperationIdStrategy {
+    /// `{prefix}_{id}`, using the composition's `operation_id_prefix`
+    /// (or the service name)
+    #[default]
+    Prefix,
+    /// `{prefix}_{id}_{hash}`, where `hash` is the first 8 hex digits of the
+    /// SHA256 of the service name and version, so services (or versions of
+    /// one service) sharing a prefix can't collide
+    ServiceHash,
+    /// Leave IDs untouched
+    Keep,
+}
+
+impl OperationIdStrategy {
+    /// Rewrites `id` for the given prefix and service
+    pub fn apply(
+        &self,
+        id: &str,
+        prefix: &str,
+        service_name: &str,
+        service_version: &str,
+    ) -> String {
+        let prefixed = if prefix.is_empty() {
+            id.to_string()
+        } else {
+            format!("{prefix}_{id}")
+        };
+        match self {
+            OperationIdStrategy::Prefix => prefixed,
+            OperationIdStrategy::ServiceHash => {
+                use sha2::{Digest, Sha256};
+                let mut hasher = Sha256::new();
+                hasher.update(service_name.as_bytes());
+                hasher.update([0]);
+                hasher.update(service_version.as_bytes());
+                let hash = hex::encode(hasher.finalize());
+                format!("{prefixed}_{}", &hash[..8])
+            }
+            OperationIdStrategy::Keep => id.to_string(),
         }
     }
 }
@@ -81,7 +310,7 @@ pub struct MergeResult {
 }
 
 /// Conflict encountered during merging
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Conflict {
     /// Type of conflict
     pub conflict_type: ConflictType,
@@ -96,7 +325,8 @@ pub struct Conflict {
 }
 
 /// Type of conflict
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ConflictType {
     /// Path conflict
     Path,
@@ -108,6 +338,213 @@ pub enum ConflictType {
     OperationID,
     /// Security scheme conflict
     SecurityScheme,
+    /// Webhook name conflict
+    Webhook,
+}
+
+impl ConflictType {
+    /// All conflict types, in report order
+    pub const ALL: [ConflictType; 6] = [
+        ConflictType::Path,
+        ConflictType::Webhook,
+        ConflictType::Component,
+        ConflictType::SecurityScheme,
+        ConflictType::OperationID,
+        ConflictType::Tag,
+    ];
+}
+
+impl std::fmt::Display for ConflictType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ConflictType::Path => "path",
+            ConflictType::Component => "component",
+            ConflictType::Tag => "tag",
+            ConflictType::OperationID => "operation_id",
+            ConflictType::SecurityScheme => "security_scheme",
+            ConflictType::Webhook => "webhook",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// Output format for [`MergeResult::conflict_report`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// Pretty-printed JSON array of conflicts
+    Json,
+    /// Markdown tables, one section per conflict type
+    Markdown,
+}
+
+/// Outcome chosen by a [`ConflictResolver`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+    /// Keep the item already merged and drop the incoming one
+    KeepExisting,
+    /// Replace the merged item with the incoming one
+    TakeNew,
+    /// Merge the incoming item under a different name
+    RenameTo(String),
+    /// Abort the merge with this message
+    Error(String),
+}
+
+/// Domain-specific conflict resolution
+///
+/// Consulted for path, webhook, component and security scheme conflicts
+/// before the configured [`ConflictStrategy`]. Returning `None` falls back to
+/// the strategy.
+pub trait ConflictResolver: Send + Sync {
+    /// Resolves `conflict` between the service that contributed the item
+    /// first and the service being merged
+    fn resolve(
+        &self,
+        conflict: &Conflict,
+        existing: &ServiceSchema,
+        incoming: &ServiceSchema,
+    ) -> Option<Resolution>;
+}
+
+impl<F> ConflictResolver for F
+where
+    F: Fn(&Conflict, &ServiceSchema, &ServiceSchema) -> Option<Resolution> + Send + Sync,
+{
+    fn resolve(
+        &self,
+        conflict: &Conflict,
+        existing: &ServiceSchema,
+        incoming: &ServiceSchema,
+    ) -> Option<Resolution> {
+        self(conflict, existing, incoming)
+    }
+}
+
+impl Conflict {
+    /// Classifies how much attention this conflict needs
+    ///
+    /// Anything under the `Error` strategy and silently replaced paths or
+    /// security schemes are high; prefixed items and tags are low.
+    pub fn severity(&self) -> ChangeSeverity {
+        if self.strategy == ConflictStrategy::Error {
+            return ChangeSeverity::High;
+        }
+
+        match (self.conflict_type, self.strategy) {
+            (ConflictType::Tag, _) | (_, ConflictStrategy::Prefix) => ChangeSeverity::Low,
+            (
+                ConflictType::Path | ConflictType::Webhook | ConflictType::SecurityScheme,
+                ConflictStrategy::Overwrite | ConflictStrategy::Merge,
+            ) => ChangeSeverity::High,
+            _ => ChangeSeverity::Medium,
+        }
+    }
+}
+
+impl MergeResult {
+    /// Returns the component schemas no operation can reach, sorted by name
+    ///
+    /// Reachability starts at the `$ref`s in paths, webhooks and the
+    /// non-schema components, and follows references between schemas, so a
+    /// schema used only by another orphan is reported too.
+    pub fn unreferenced_components(&self) -> Vec<String> {
+        let Some(components) = &self.spec.components else {
+            return Vec::new();
+        };
+
+        let mut pending = Vec::new();
+        let roots = [
+            serde_json::to_value(&self.spec.paths),
+            serde_json::to_value(&self.spec.webhooks),
+            serde_json::to_value(&components.responses),
+            serde_json::to_value(&components.parameters),
+            serde_json::to_value(&components.request_bodies),
+            serde_json::to_value(&components.headers),
+        ];
+        for root in roots.iter().flatten() {
+            collect_schema_refs(root, &mut pending);
+        }
+
+        let mut reachable = std::collections::HashSet::new();
+        while let Some(name) = pending.pop() {
+            if reachable.insert(name.clone()) {
+                if let Some(schema) = components.schemas.get(&name) {
+                    collect_schema_refs(schema, &mut pending);
+                }
+            }
+        }
+
+        let mut unreferenced: Vec<String> = components
+            .schemas
+            .keys()
+            .filter(|name| !reachable.contains(*name))
+            .cloned()
+            .collect();
+        unreferenced.sort();
+        unreferenced
+    }
+
+    /// Counts conflicts by severity
+    pub fn conflict_summary(&self) -> HashMap<ChangeSeverity, usize> {
+        let mut summary = HashMap::new();
+        for conflict in &self.conflicts {
+            *summary.entry(conflict.severity()).or_insert(0) += 1;
+        }
+        summary
+    }
+
+    /// Renders the conflicts for CI output or dashboards
+    ///
+    /// JSON is the conflict list as-is and deserializes back into
+    /// `Vec<Conflict>`. Markdown groups conflicts by type, each group as a
+    /// table with type, item, services, strategy and resolution columns.
+    pub fn conflict_report(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Json => {
+                serde_json::to_string_pretty(&self.conflicts).unwrap_or_else(|_| "[]".to_string())
+            }
+            ReportFormat::Markdown => {
+                if self.conflicts.is_empty() {
+                    return "No merge conflicts.\n".to_string();
+                }
+
+                let mut report = String::new();
+                for conflict_type in ConflictType::ALL {
+                    let group: Vec<&Conflict> = self
+                        .conflicts
+                        .iter()
+                        .filter(|c| c.conflict_type == conflict_type)
+                        .collect();
+                    if group.is_empty() {
+                        continue;
+                    }
+
+                    if !report.is_empty() {
+                        report.push('\n');
+                    }
+                    report.push_str(&format!("### {conflict_type} ({})\n\n", group.len()));
+                    report.push_str("| Type | Item | Services | Strategy | Resolution |\n");
+                    report.push_str("|------|------|----------|----------|------------|\n");
+                    for conflict in group {
+                        report.push_str(&format!(
+                            "| {} | {} | {} | {} | {} |\n",
+                            conflict.conflict_type,
+                            markdown_cell(&conflict.item),
+                            markdown_cell(&conflict.services.join(", ")),
+                            conflict.strategy,
+                            markdown_cell(&conflict.resolution),
+                        ));
+                    }
+                }
+                report
+            }
+        }
+    }
+}
+
+/// Escapes text for use inside a Markdown table cell
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
 }
 
 impl Merger {
@@ -121,8 +558,50 @@ impl Merger {
         Self::new(MergerConfig::default())
     }
 
+    /// Inlines the local `$ref`s of a raw OpenAPI document
+    ///
+    /// Fails with `Error::InvalidSchema` when resolution needs more than
+    /// `max_ref_depth` hops, e.g. on a self-referential schema.
+    pub fn dereference(&self, raw: &serde_json::Value) -> Result<serde_json::Value> {
+        openapi::dereference(raw, self.config.max_ref_depth)
+    }
+
     /// Merges multiple OpenAPI schemas from service manifests
-    pub fn merge(&self, schemas: Vec<ServiceSchema>) -> Result<MergeResult> {
+    ///
+    /// When any manifest sets `routing.priority`, services are merged in
+    /// descending priority (unset counts as 0), ties ordered by service name,
+    /// so a higher-priority service's paths win `Overwrite` and `Merge`
+    /// conflicts. Otherwise services are merged in the given order.
+    pub fn merge(&self, mut schemas: Vec<ServiceSchema>) -> Result<MergeResult> {
+        if schemas
+            .iter()
+            .any(|s| s.manifest.routing.priority.is_some())
+        {
+            schemas.sort_by(|a, b| {
+                service_priority(b)
+                    .cmp(&service_priority(a))
+                    .then_with(|| a.manifest.service_name.cmp(&b.manifest.service_name))
+            });
+        }
+        self.merge_stream(schemas.into_iter().map(Ok))
+    }
+
+    /// Merges OpenAPI schemas one at a time as `schemas` yields them
+    ///
+    /// Each service's spec is dropped once merged, so only the merged result
+    /// and conflict-tracking state stay in memory (plus earlier services when
+    /// a custom resolver needs them). The first `Err` from `schemas` aborts
+    /// the merge.
+    ///
+    /// Schemas are merged in the order yielded. A path already contributed by
+    /// a service of higher `routing.priority` is kept on `Overwrite` and wins
+    /// operation clashes on `Merge`.
+    pub fn merge_stream(
+        &self,
+        schemas: impl Iterator<Item = Result<ServiceSchema>>,
+    ) -> Result<MergeResult> {
+        self.config.validate_separators(false)?;
+        let separator = self.config.component_separator.as_str();
         let mut result = MergeResult {
             spec: OpenAPISpec {
                 openapi: "3.1.0".to_string(),
@@ -130,13 +609,14 @@ impl Merger {
                     title: self.config.merged_title.clone(),
                     description: Some(self.config.merged_description.clone()),
                     version: self.config.merged_version.clone(),
-                    terms_of_service: None,
-                    contact: None,
-                    license: None,
+                    terms_of_service: self.config.merged_terms_of_service.clone(),
+                    contact: self.config.merged_contact.clone(),
+                    license: self.config.merged_license.clone(),
                     extensions: HashMap::new(),
                 },
                 servers: self.config.servers.clone(),
                 paths: HashMap::new(),
+                webhooks: HashMap::new(),
                 components: Some(Components {
                     schemas: HashMap::new(),
                     responses: HashMap::new(),
@@ -144,6 +624,8 @@ impl Merger {
                     request_bodies: HashMap::new(),
                     headers: HashMap::new(),
                     security_schemes: HashMap::new(),
+                    examples: HashMap::new(),
+                    links: HashMap::new(),
                 }),
                 security: Vec::new(),
                 tags: Vec::new(),
@@ -155,15 +637,29 @@ impl Merger {
             warnings: Vec::new(),
         };
 
+        for server in &self.config.servers {
+            if let Err(e) = server.validate_variables() {
+                result.warnings.push(format!("Server {}: {e}", server.url));
+            }
+        }
+
         // Track what we've seen for conflict detection
-        let mut seen_paths: HashMap<String, String> = HashMap::new();
-        let mut seen_components: HashMap<String, String> = HashMap::new();
+        // Normalized path -> (contributing service, path as merged)
+        let mut seen_paths: HashMap<String, (String, String)> = HashMap::new();
+        let mut seen_webhooks: HashMap<String, String> = HashMap::new();
+        // Component kind -> name -> contributing service
+        let mut seen_components: HashMap<&str, HashMap<String, String>> = HashMap::new();
         let mut seen_operation_ids: HashMap<String, String> = HashMap::new();
-        let mut seen_tags: HashMap<String, Tag> = HashMap::new();
+        let mut seen_tags: HashMap<String, usize> = HashMap::new();
         let mut seen_security_schemes: HashMap<String, String> = HashMap::new();
+        // Explicit routing priorities of merged services
+        let mut priorities: HashMap<String, i32> = HashMap::new();
+        // Earlier services, kept only for the custom resolver
+        let mut contributed: HashMap<String, ServiceSchema> = HashMap::new();
 
         // Process each schema
-        for mut schema in schemas {
+        for schema in schemas {
+            let mut schema = schema?;
             let service_name = schema.manifest.service_name.clone();
 
             // Check if this schema should be included
@@ -173,9 +669,33 @@ impl Merger {
             }
 
             result.included_services.push(service_name.clone());
+            let priority = schema.manifest.routing.priority;
+            if let Some(priority) = priority {
+                priorities.insert(service_name.clone(), priority);
+            }
+
+            // Bound the recursion of every walk below
+            check_depth(&schema.schema, self.config.max_schema_depth).map_err(|_| {
+                crate::errors::Error::invalid_schema(format!(
+                    "{service_name}: max schema depth exceeded"
+                ))
+            })?;
 
-            // Parse the schema if not already parsed
+            // Parse the schema if not already parsed, upgrading 3.0 inputs to 3.1
             if schema.parsed.is_none() {
+                let version = schema
+                    .schema
+                    .get("openapi")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string();
+                if version.starts_with("3.0") {
+                    normalize_to_3_1(&mut schema.schema);
+                    result.warnings.push(format!(
+                        "Upgraded {service_name} from OpenAPI {version} to 3.1.0"
+                    ));
+                }
+
                 match parse_openapi_schema(&schema.schema) {
                     Ok(parsed) => schema.parsed = Some(parsed),
                     Err(e) => {
@@ -187,6 +707,11 @@ impl Merger {
                 }
             }
 
+            if let Some(parsed) = schema.parsed.as_mut() {
+                for transform in &self.config.transforms {
+                    transform.transform(parsed);
+                }
+            }
             let parsed = schema.parsed.as_ref().unwrap();
 
             // Get composition config
@@ -194,16 +719,68 @@ impl Merger {
             let strategy = self.get_conflict_strategy(comp_config.as_ref());
 
             // Determine prefixes
-            let component_prefix = get_component_prefix(&schema.manifest, comp_config.as_ref());
-            let tag_prefix = get_tag_prefix(&schema.manifest, comp_config.as_ref());
-            let operation_id_prefix =
-                get_operation_id_prefix(&schema.manifest, comp_config.as_ref());
+            let component_prefix = self.config.prefix(&get_component_prefix(
+                &schema.manifest,
+                comp_config.as_ref(),
+            ));
+            let tag_prefix = self
+                .config
+                .prefix(&get_tag_prefix(&schema.manifest, comp_config.as_ref()));
+            let operation_id_prefix = self.config.prefix(&get_operation_id_prefix(
+                &schema.manifest,
+                comp_config.as_ref(),
+            ));
 
             // Merge paths
-            let paths = apply_routing(&parsed.paths, &schema.manifest);
+            // IDs are generated from the service's own paths, before mounting
+            // and prefixing, so they don't depend on the routing strategy
+            let mut source_paths = parsed.paths.clone();
+            let mut source_webhooks = parsed.webhooks.clone();
+            if self.config.generate_missing_operation_ids {
+                for (path, item) in source_paths.iter_mut() {
+                    fill_missing_operation_ids(item, path);
+                }
+                for (name, item) in source_webhooks.iter_mut() {
+                    fill_missing_operation_ids(item, name);
+                }
+            }
+
+            let no_params = HashMap::new();
+            let component_params = parsed
+                .components
+                .as_ref()
+                .map_or(&no_params, |c| &c.parameters);
+            let mut source_path_names: Vec<&String> = source_paths.keys().collect();
+            source_path_names.sort();
+            for path in source_path_names {
+                let mismatches = path_param_mismatches(path, &source_paths[path], component_params);
+                if mismatches.is_empty() {
+                    continue;
+                }
+                if self.config.strict_path_params {
+                    return Err(crate::errors::Error::invalid_schema(format!(
+                        "{service_name}: {}",
+                        mismatches.join("; ")
+                    )));
+                }
+                for mismatch in mismatches {
+                    result.warnings.push(format!("{service_name}: {mismatch}"));
+                }
+            }
+
+            // Items merged for this service, and its components renamed by the
+            // custom resolver, whose references need retargeting
+            let mut merged_paths = Vec::new();
+            let mut merged_webhooks = Vec::new();
+            let mut component_renames = Vec::new();
+            let mut scheme_renames = Vec::new();
+
+            let paths = apply_routing(&source_paths, &schema.manifest);
             for (mut path, mut path_item) in paths {
                 // Check for path conflicts
-                if let Some(existing_service) = seen_paths.get(&path) {
+                if let Some((existing_service, existing_path)) =
+                    seen_paths.get(&self.path_key(&path)).cloned()
+                {
                     let conflict = Conflict {
                         conflict_type: ConflictType::Path,
                         item: path.clone(),
@@ -211,110 +788,248 @@ impl Merger {
                         resolution: String::new(),
                         strategy,
                     };
+                    let existing_priority = priorities.get(&existing_service).copied();
+                    let outranked = existing_priority.unwrap_or(0) > priority.unwrap_or(0);
+                    let priority_note = if existing_priority.is_some() || priority.is_some() {
+                        format!(
+                            " (priority {} vs {})",
+                            existing_priority.unwrap_or(0),
+                            priority.unwrap_or(0)
+                        )
+                    } else {
+                        String::new()
+                    };
 
-                    match strategy {
-                        ConflictStrategy::Error => {
-                            return Err(crate::errors::Error::Custom(format!(
+                    if let Some(resolution) =
+                        self.custom_resolution(&conflict, &contributed, &schema)
+                    {
+                        match apply_resolution(resolution, conflict, &mut result.conflicts)? {
+                            Some(name) => path = name,
+                            None => continue,
+                        }
+                    } else {
+                        match strategy {
+                            ConflictStrategy::Error => {
+                                return Err(crate::errors::Error::Custom(format!(
                                 "path conflict: {path} exists in both {existing_service} and {service_name}"
                             )));
-                        }
-                        ConflictStrategy::Skip => {
-                            let mut c = conflict;
-                            c.resolution = format!("Skipped path from {service_name}");
-                            result.conflicts.push(c);
-                            continue;
-                        }
-                        ConflictStrategy::Overwrite => {
-                            let mut c = conflict;
-                            c.resolution = format!("Overwritten with {service_name} version");
-                            result.conflicts.push(c);
-                        }
-                        ConflictStrategy::Prefix => {
-                            let new_path = format!("/{service_name}{path}");
-                            let mut c = conflict;
-                            c.resolution = format!("Prefixed to {new_path}");
-                            result.conflicts.push(c);
-                            path = new_path;
-                        }
-                        ConflictStrategy::Merge => {
-                            let existing = result.spec.paths.get(&path).cloned();
-                            if let Some(existing) = existing {
-                                path_item = merge_path_items(existing, path_item);
                             }
-                            let mut c = conflict;
-                            c.resolution = "Merged operations".to_string();
-                            result.conflicts.push(c);
+                            ConflictStrategy::Skip => {
+                                let mut c = conflict;
+                                c.resolution = format!("Skipped path from {service_name}");
+                                result.conflicts.push(c);
+                                continue;
+                            }
+                            ConflictStrategy::Overwrite if outranked => {
+                                let mut c = conflict;
+                                c.resolution =
+                                    format!("Kept {existing_service} version{priority_note}");
+                                result.conflicts.push(c);
+                                continue;
+                            }
+                            ConflictStrategy::Overwrite => {
+                                let mut c = conflict;
+                                c.resolution = format!(
+                                    "Overwritten with {service_name} version{priority_note}"
+                                );
+                                result.conflicts.push(c);
+                            }
+                            ConflictStrategy::Prefix => {
+                                let new_path = format!("/{service_name}{path}");
+                                let mut c = conflict;
+                                c.resolution = format!("Prefixed to {new_path}");
+                                result.conflicts.push(c);
+                                path = new_path;
+                            }
+                            ConflictStrategy::Merge => {
+                                let existing = result.spec.paths.get(&existing_path).cloned();
+                                let mut method_conflicts = Vec::new();
+                                if let Some(existing) = existing {
+                                    let keep_existing = outranked
+                                        || self.config.method_conflict_strategy
+                                            == MethodConflictStrategy::Keep;
+                                    method_conflicts = self.method_conflicts(
+                                        &conflict,
+                                        &existing_path,
+                                        &existing,
+                                        &path_item,
+                                        keep_existing,
+                                    )?;
+                                    path_item = if keep_existing {
+                                        merge_path_items(path_item, existing)
+                                    } else {
+                                        merge_path_items(existing, path_item)
+                                    };
+                                }
+                                path = existing_path.clone();
+                                let mut c = conflict;
+                                c.resolution = format!("Merged operations{priority_note}");
+                                result.conflicts.push(c);
+                                result.conflicts.extend(method_conflicts);
+                            }
                         }
                     }
+
+                    // A differently spelled path replacing the existing one
+                    if path != existing_path
+                        && self.path_key(&path) == self.path_key(&existing_path)
+                    {
+                        result.spec.paths.remove(&existing_path);
+                    }
                 }
 
                 // Apply prefixes to operation IDs and tags
                 path_item = apply_operation_prefixes(
                     path_item,
                     &operation_id_prefix,
+                    self.config.operation_id_strategy,
                     &tag_prefix,
-                    &service_name,
+                    &schema.manifest,
                     &mut seen_operation_ids,
                     &mut result,
                 );
 
+                path_item = prefix_path_item_refs(path_item, &component_prefix, separator);
+
                 result.spec.paths.insert(path.clone(), path_item);
-                seen_paths.insert(path, service_name.clone());
+                seen_paths.insert(self.path_key(&path), (service_name.clone(), path.clone()));
+                merged_paths.push(path);
             }
 
-            // Merge components
-            if let Some(components) = &parsed.components {
-                let prefixed = prefix_component_names(components, &component_prefix);
-
-                for (name, schema_obj) in &prefixed.schemas {
-                    if let Some(existing_service) = seen_components.get(name) {
-                        let conflict = Conflict {
-                            conflict_type: ConflictType::Component,
-                            item: name.clone(),
-                            services: vec![existing_service.clone(), service_name.clone()],
-                            resolution: if strategy == ConflictStrategy::Skip {
-                                format!("Skipped component from {service_name}")
-                            } else {
-                                format!("Overwritten with {service_name} version")
-                            },
-                            strategy,
-                        };
-
-                        result.conflicts.push(conflict);
+            // Merge webhooks (keyed by name, not mounted under the routing prefix)
+            let mut webhooks: Vec<(String, PathItem)> = source_webhooks.into_iter().collect();
+            webhooks.sort_by(|a, b| a.0.cmp(&b.0));
+            for (mut name, mut webhook) in webhooks {
+                if let Some(existing_service) = seen_webhooks.get(&name) {
+                    let conflict = Conflict {
+                        conflict_type: ConflictType::Webhook,
+                        item: name.clone(),
+                        services: vec![existing_service.clone(), service_name.clone()],
+                        resolution: String::new(),
+                        strategy,
+                    };
 
-                        if strategy == ConflictStrategy::Skip {
-                            continue;
+                    if let Some(resolution) =
+                        self.custom_resolution(&conflict, &contributed, &schema)
+                    {
+                        match apply_resolution(resolution, conflict, &mut result.conflicts)? {
+                            Some(new_name) => name = new_name,
+                            None => continue,
+                        }
+                    } else {
+                        match strategy {
+                            ConflictStrategy::Error => {
+                                return Err(crate::errors::Error::Custom(format!(
+                                "webhook conflict: {name} exists in both {existing_service} and {service_name}"
+                            )));
+                            }
+                            ConflictStrategy::Skip => {
+                                let mut c = conflict;
+                                c.resolution = format!("Skipped webhook from {service_name}");
+                                result.conflicts.push(c);
+                                continue;
+                            }
+                            ConflictStrategy::Overwrite => {
+                                let mut c = conflict;
+                                c.resolution = format!("Overwritten with {service_name} version");
+                                result.conflicts.push(c);
+                            }
+                            ConflictStrategy::Prefix => {
+                                let new_name = format!(
+                                    "{}{separator}{name}",
+                                    self.config.prefix(&service_name)
+                                );
+                                let mut c = conflict;
+                                c.resolution = format!("Prefixed to {new_name}");
+                                result.conflicts.push(c);
+                                name = new_name;
+                            }
+                            ConflictStrategy::Merge => {
+                                let existing = result.spec.webhooks.get(&name).cloned();
+                                let mut method_conflicts = Vec::new();
+                                if let Some(existing) = existing {
+                                    let keep_existing = self.config.method_conflict_strategy
+                                        == MethodConflictStrategy::Keep;
+                                    method_conflicts = self.method_conflicts(
+                                        &conflict,
+                                        &name,
+                                        &existing,
+                                        &webhook,
+                                        keep_existing,
+                                    )?;
+                                    webhook = if keep_existing {
+                                        merge_path_items(webhook, existing)
+                                    } else {
+                                        merge_path_items(existing, webhook)
+                                    };
+                                }
+                                let mut c = conflict;
+                                c.resolution = "Merged operations".to_string();
+                                result.conflicts.push(c);
+                                result.conflicts.extend(method_conflicts);
+                            }
                         }
                     }
+                }
 
-                    if let Some(spec_components) = result.spec.components.as_mut() {
-                        spec_components
-                            .schemas
-                            .insert(name.clone(), schema_obj.clone());
+                webhook = apply_operation_prefixes(
+                    webhook,
+                    &operation_id_prefix,
+                    self.config.operation_id_strategy,
+                    &tag_prefix,
+                    &schema.manifest,
+                    &mut seen_operation_ids,
+                    &mut result,
+                );
+                webhook = prefix_path_item_refs(webhook, &component_prefix, separator);
+
+                result.spec.webhooks.insert(name.clone(), webhook);
+                seen_webhooks.insert(name.clone(), service_name.clone());
+                merged_webhooks.push(name);
+            }
+
+            // Merge components
+            if let Some(components) = &parsed.components {
+                let mut prefixed = prefix_component_names(components, &component_prefix, separator);
+                prefix_link_operation_ids(&mut prefixed, &|id| {
+                    self.config.operation_id_strategy.apply(
+                        id,
+                        &operation_id_prefix,
+                        &service_name,
+                        &schema.manifest.service_version,
+                    )
+                });
+
+                // Settle name conflicts of every kind first, so references
+                // can be retargeted at renamed components before merging
+                let mut merged_components = Vec::new();
+                for (kind, names) in component_names(&prefixed) {
+                    let seen = seen_components.entry(kind).or_default();
+                    for (original, name) in self.resolve_component_names(
+                        names,
+                        seen,
+                        &contributed,
+                        &schema,
+                        &mut result.conflicts,
+                    )? {
+                        if name != original {
+                            component_renames.push((kind, original.clone(), name.clone()));
+                        }
+                        merged_components.push((kind, original, name));
                     }
-                    seen_components.insert(name.clone(), service_name.clone());
+                }
+                for (kind, from, to) in &component_renames {
+                    prefixed = rename_component_refs(prefixed, kind, from, to);
                 }
 
-                // Merge other component types
                 if let Some(spec_components) = result.spec.components.as_mut() {
-                    for (name, response) in &prefixed.responses {
-                        spec_components
-                            .responses
-                            .insert(name.clone(), response.clone());
-                    }
-                    for (name, param) in &prefixed.parameters {
-                        spec_components
-                            .parameters
-                            .insert(name.clone(), param.clone());
-                    }
-                    for (name, body) in &prefixed.request_bodies {
-                        spec_components
-                            .request_bodies
-                            .insert(name.clone(), body.clone());
+                    for (kind, original, name) in merged_components {
+                        copy_component(&prefixed, spec_components, kind, &original, name);
                     }
                     // Merge security schemes (with conflict detection)
-                    for (name, scheme) in &prefixed.security_schemes {
-                        if let Some(existing_service) = seen_security_schemes.get(name) {
+                    for (original, scheme) in &prefixed.security_schemes {
+                        let mut name = original.clone();
+                        if let Some(existing_service) = seen_security_schemes.get(&name) {
                             let conflict = Conflict {
                                 conflict_type: ConflictType::SecurityScheme,
                                 item: name.clone(),
@@ -323,42 +1038,60 @@ impl Merger {
                                 strategy,
                             };
 
-                            match strategy {
-                                ConflictStrategy::Error => {
-                                    return Err(crate::errors::Error::Custom(format!(
-                                        "security scheme conflict: {name} exists in both {existing_service} and {service_name}"
-                                    )));
-                                }
-                                ConflictStrategy::Skip => {
-                                    let mut c = conflict;
-                                    c.resolution =
-                                        format!("Skipped security scheme from {service_name}");
-                                    result.conflicts.push(c);
-                                    continue;
+                            if let Some(resolution) =
+                                self.custom_resolution(&conflict, &contributed, &schema)
+                            {
+                                match apply_resolution(resolution, conflict, &mut result.conflicts)?
+                                {
+                                    Some(new_name) => name = new_name,
+                                    None => continue,
                                 }
-                                ConflictStrategy::Overwrite => {
-                                    let mut c = conflict;
-                                    c.resolution =
-                                        format!("Overwritten with {service_name} version");
-                                    result.conflicts.push(c);
+                                if &name != original {
+                                    scheme_renames.push((original.clone(), name.clone()));
                                 }
-                                ConflictStrategy::Prefix => {
-                                    let prefixed_name = format!("{service_name}_{name}");
-                                    let mut c = conflict;
-                                    c.resolution = format!("Prefixed to {prefixed_name}");
-                                    result.conflicts.push(c);
-                                    spec_components
-                                        .security_schemes
-                                        .insert(prefixed_name.clone(), scheme.clone());
-                                    seen_security_schemes
-                                        .insert(prefixed_name, service_name.clone());
-                                    continue;
-                                }
-                                ConflictStrategy::Merge => {
-                                    let mut c = conflict;
-                                    c.resolution =
-                                        format!("Merged (overwritten) with {service_name} version");
-                                    result.conflicts.push(c);
+                            } else {
+                                match strategy {
+                                    ConflictStrategy::Error => {
+                                        return Err(crate::errors::Error::Custom(format!(
+                                        "security scheme conflict: {name} exists in both {existing_service} and {service_name}"
+                                    )));
+                                    }
+                                    ConflictStrategy::Skip => {
+                                        let mut c = conflict;
+                                        c.resolution =
+                                            format!("Skipped security scheme from {service_name}");
+                                        result.conflicts.push(c);
+                                        continue;
+                                    }
+                                    ConflictStrategy::Overwrite => {
+                                        let mut c = conflict;
+                                        c.resolution =
+                                            format!("Overwritten with {service_name} version");
+                                        result.conflicts.push(c);
+                                    }
+                                    ConflictStrategy::Prefix => {
+                                        let prefixed_name = format!(
+                                            "{}{separator}{name}",
+                                            self.config.prefix(&service_name)
+                                        );
+                                        let mut c = conflict;
+                                        c.resolution = format!("Prefixed to {prefixed_name}");
+                                        result.conflicts.push(c);
+                                        spec_components
+                                            .security_schemes
+                                            .insert(prefixed_name.clone(), scheme.clone());
+                                        seen_security_schemes
+                                            .insert(prefixed_name.clone(), service_name.clone());
+                                        scheme_renames.push((original.clone(), prefixed_name));
+                                        continue;
+                                    }
+                                    ConflictStrategy::Merge => {
+                                        let mut c = conflict;
+                                        c.resolution = format!(
+                                            "Merged (overwritten) with {service_name} version"
+                                        );
+                                        result.conflicts.push(c);
+                                    }
                                 }
                             }
                         }
@@ -366,7 +1099,30 @@ impl Merger {
                         spec_components
                             .security_schemes
                             .insert(name.clone(), scheme.clone());
-                        seen_security_schemes.insert(name.clone(), service_name.clone());
+                        seen_security_schemes.insert(name, service_name.clone());
+                    }
+                }
+            }
+
+            // Retarget this service's operations at its renamed components
+            if !component_renames.is_empty() || !scheme_renames.is_empty() {
+                let spec = &mut result.spec;
+                let items = spec
+                    .paths
+                    .iter_mut()
+                    .filter(|(path, _)| merged_paths.contains(*path))
+                    .chain(
+                        spec.webhooks
+                            .iter_mut()
+                            .filter(|(name, _)| merged_webhooks.contains(*name)),
+                    )
+                    .map(|(_, item)| item);
+                for item in items {
+                    for (kind, from, to) in &component_renames {
+                        *item = rename_component_refs(item.clone(), kind, from, to);
+                    }
+                    for (from, to) in &scheme_renames {
+                        rename_security_requirements(item, from, to);
                     }
                 }
             }
@@ -374,26 +1130,47 @@ impl Merger {
             // Merge tags
             for mut tag in parsed.tags.clone() {
                 if !tag_prefix.is_empty() && self.config.include_service_tags {
-                    tag.name = format!("{}_{}", tag_prefix, tag.name);
+                    tag.name = prefix_tag(&tag_prefix, &tag.name);
                 }
 
-                if let Some(existing) = seen_tags.get(&tag.name) {
-                    // Merge descriptions
-                    if tag.description.is_some() && existing.description.is_none() {
-                        let mut updated = existing.clone();
-                        updated.description = tag.description;
-                        seen_tags.insert(tag.name.clone(), updated.clone());
-                        // Update in result as well
-                        if let Some(pos) = result.spec.tags.iter().position(|t| t.name == tag.name)
-                        {
-                            result.spec.tags[pos] = updated;
-                        }
-                    }
+                if let Some(&pos) = seen_tags.get(&tag.name) {
+                    merge_tag(&mut result.spec.tags[pos], tag);
                 } else {
-                    seen_tags.insert(tag.name.clone(), tag.clone());
+                    seen_tags.insert(tag.name.clone(), result.spec.tags.len());
                     result.spec.tags.push(tag);
                 }
             }
+
+            if self.config.custom_resolver.is_some() {
+                contributed.insert(service_name, schema);
+            }
+        }
+
+        if self.config.strip_examples {
+            strip_examples(&mut result.spec);
+        }
+        if self.config.strip_response_schemas {
+            strip_response_schemas(&mut result.spec);
+        }
+
+        if self.config.prune_unreferenced {
+            for name in result.unreferenced_components() {
+                if let Some(components) = result.spec.components.as_mut() {
+                    components.schemas.remove(&name);
+                }
+                result
+                    .warnings
+                    .push(format!("Pruned unreferenced component {name}"));
+            }
+        }
+
+        if self.config.gateway_lint {
+            for finding in lint_for_gateway(&result.spec) {
+                result.warnings.push(format!(
+                    "{} {}: {}",
+                    finding.code, finding.path, finding.message
+                ));
+            }
         }
 
         // Sort output if requested
@@ -404,6 +1181,133 @@ impl Merger {
         Ok(result)
     }
 
+    /// Key under which paths collide, per the path normalization settings
+    fn path_key(&self, path: &str) -> String {
+        let mut key = path;
+        if self.config.normalize_trailing_slash && key.len() > 1 {
+            key = key.trim_end_matches('/');
+        }
+        if self.config.case_insensitive_paths {
+            key.to_lowercase()
+        } else {
+            key.to_string()
+        }
+    }
+
+    /// Records a conflict for every method both path items define
+    ///
+    /// `conflict` is the path-level conflict being merged, naming the
+    /// existing service first. Fails under [`MethodConflictStrategy::Error`].
+    fn method_conflicts(
+        &self,
+        conflict: &Conflict,
+        item: &str,
+        existing: &PathItem,
+        new: &PathItem,
+        keep_existing: bool,
+    ) -> Result<Vec<Conflict>> {
+        let (existing_service, service_name) = (&conflict.services[0], &conflict.services[1]);
+        let methods = colliding_methods(existing, new);
+        if let (Some(method), MethodConflictStrategy::Error) =
+            (methods.first(), self.config.method_conflict_strategy)
+        {
+            return Err(crate::errors::Error::Custom(format!(
+                "method conflict: {} {item} is defined by both {existing_service} and {service_name}",
+                method.to_ascii_uppercase()
+            )));
+        }
+
+        let winner = if keep_existing {
+            existing_service
+        } else {
+            service_name
+        };
+        let loser = if keep_existing {
+            service_name
+        } else {
+            existing_service
+        };
+        Ok(methods
+            .into_iter()
+            .map(|method| Conflict {
+                conflict_type: conflict.conflict_type,
+                item: format!("{} {item}", method.to_ascii_uppercase()),
+                services: conflict.services.clone(),
+                resolution: format!("Kept {winner} operation, dropped {loser} operation"),
+                strategy: conflict.strategy,
+            })
+            .collect())
+    }
+
+    /// Settles name conflicts among one kind of `incoming`'s components
+    ///
+    /// The custom resolver decides when it can; otherwise the conflict is
+    /// recorded and the incoming component is dropped under
+    /// [`ConflictStrategy::Skip`] and overwrites the existing one under any
+    /// other strategy. Returns `(name, merged name)` for each component to
+    /// merge and records the merged names in `seen`.
+    fn resolve_component_names(
+        &self,
+        names: Vec<String>,
+        seen: &mut HashMap<String, String>,
+        contributed: &HashMap<String, ServiceSchema>,
+        incoming: &ServiceSchema,
+        conflicts: &mut Vec<Conflict>,
+    ) -> Result<Vec<(String, String)>> {
+        let service_name = &incoming.manifest.service_name;
+        let strategy =
+            self.get_conflict_strategy(get_composition_config(&incoming.manifest).as_ref());
+
+        let mut merged = Vec::new();
+        for original in names {
+            let mut name = original.clone();
+            if let Some(existing_service) = seen.get(&name) {
+                let conflict = Conflict {
+                    conflict_type: ConflictType::Component,
+                    item: name.clone(),
+                    services: vec![existing_service.clone(), service_name.clone()],
+                    resolution: if strategy == ConflictStrategy::Skip {
+                        format!("Skipped component from {service_name}")
+                    } else {
+                        format!("Overwritten with {service_name} version")
+                    },
+                    strategy,
+                };
+
+                if let Some(resolution) = self.custom_resolution(&conflict, contributed, incoming) {
+                    match apply_resolution(resolution, conflict, conflicts)? {
+                        Some(new_name) => name = new_name,
+                        None => continue,
+                    }
+                } else {
+                    conflicts.push(conflict);
+                    if strategy == ConflictStrategy::Skip {
+                        continue;
+                    }
+                }
+            }
+
+            seen.insert(name.clone(), service_name.clone());
+            merged.push((original, name));
+        }
+        Ok(merged)
+    }
+
+    /// Asks the custom resolver, if any, how to settle `conflict`
+    ///
+    /// Conflicts with items from the service being merged itself have no
+    /// earlier contributor and always use the strategy.
+    fn custom_resolution(
+        &self,
+        conflict: &Conflict,
+        contributed: &HashMap<String, ServiceSchema>,
+        incoming: &ServiceSchema,
+    ) -> Option<Resolution> {
+        let resolver = self.config.custom_resolver.as_ref()?;
+        let existing = contributed.get(conflict.services.first()?)?;
+        resolver.resolve(conflict, existing, incoming)
+    }
+
     fn get_conflict_strategy(
         &self,
         config: Option<&crate::types::CompositionConfig>,
@@ -416,34 +1320,134 @@ impl Merger {
 
 // Helper functions
 
-fn should_include_in_merge(schema: &ServiceSchema) -> bool {
-    for schema_desc in &schema.manifest.schemas {
-        if schema_desc.schema_type == SchemaType::OpenAPI {
-            if let Some(metadata) = &schema_desc.metadata {
-                if let Some(openapi_metadata) = &metadata.openapi {
-                    if let Some(composition) = &openapi_metadata.composition {
-                        return composition.include_in_merged;
+/// A service's `routing.priority`, 0 when unset
+fn service_priority(schema: &ServiceSchema) -> i32 {
+    schema.manifest.routing.priority.unwrap_or(0)
+}
+
+/// Collects the names of component schemas referenced anywhere in `value`
+fn collect_schema_refs(value: &serde_json::Value, names: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, child) in map {
+                match child.as_str() {
+                    Some(target) if key == "$ref" => {
+                        if let Some(name) = target.strip_prefix("#/components/schemas/") {
+                            names.push(name.replace("~1", "/").replace("~0", "~"));
+                        }
                     }
+                    _ => collect_schema_refs(child, names),
                 }
             }
-            // Default: include if OpenAPI schema is present
-            return true;
         }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_schema_refs(item, names);
+            }
+        }
+        _ => {}
     }
-    false
 }
 
-fn get_composition_config(manifest: &SchemaManifest) -> Option<crate::types::CompositionConfig> {
-    for schema_desc in &manifest.schemas {
-        if schema_desc.schema_type == SchemaType::OpenAPI {
-            if let Some(metadata) = &schema_desc.metadata {
-                if let Some(openapi_metadata) = &metadata.openapi {
-                    return openapi_metadata.composition.clone();
-                }
-            }
+/// Sorted names of each component kind merged with conflict detection
+///
+/// Security schemes are merged separately, as their conflicts also honor
+/// the Error and Prefix strategies.
+fn component_names(components: &Components) -> [(&'static str, Vec<String>); 7] {
+    fn sorted<T>(map: &HashMap<String, T>) -> Vec<String> {
+        let mut names: Vec<String> = map.keys().cloned().collect();
+        names.sort();
+        names
+    }
+    [
+        ("schemas", sorted(&components.schemas)),
+        ("responses", sorted(&components.responses)),
+        ("parameters", sorted(&components.parameters)),
+        ("requestBodies", sorted(&components.request_bodies)),
+        ("headers", sorted(&components.headers)),
+        ("examples", sorted(&components.examples)),
+        ("links", sorted(&components.links)),
+    ]
+}
+
+/// Copies the `kind` component `original` of `source` into `target` as `name`
+fn copy_component(
+    source: &Components,
+    target: &mut Components,
+    kind: &str,
+    original: &str,
+    name: String,
+) {
+    fn copy<T: Clone>(
+        from: &HashMap<String, T>,
+        to: &mut HashMap<String, T>,
+        key: &str,
+        name: String,
+    ) {
+        if let Some(component) = from.get(key) {
+            to.insert(name, component.clone());
         }
     }
-    None
+    match kind {
+        "schemas" => copy(&source.schemas, &mut target.schemas, original, name),
+        "responses" => copy(&source.responses, &mut target.responses, original, name),
+        "parameters" => copy(&source.parameters, &mut target.parameters, original, name),
+        "requestBodies" => copy(
+            &source.request_bodies,
+            &mut target.request_bodies,
+            original,
+            name,
+        ),
+        "headers" => copy(&source.headers, &mut target.headers, original, name),
+        "examples" => copy(&source.examples, &mut target.examples, original, name),
+        "links" => copy(&source.links, &mut target.links, original, name),
+        _ => {}
+    }
+}
+
+/// Records a custom resolution and returns the name to merge the incoming
+/// item under, or `None` to drop it
+fn apply_resolution(
+    resolution: Resolution,
+    mut conflict: Conflict,
+    conflicts: &mut Vec<Conflict>,
+) -> Result<Option<String>> {
+    let incoming = conflict.services.last().cloned().unwrap_or_default();
+    let name = match resolution {
+        Resolution::Error(message) => {
+            return Err(crate::errors::Error::Custom(format!(
+                "{:?} conflict: {} rejected by resolver: {message}",
+                conflict.conflict_type, conflict.item
+            )));
+        }
+        Resolution::KeepExisting => {
+            conflict.resolution = format!("Resolver kept existing version over {incoming}");
+            None
+        }
+        Resolution::TakeNew => {
+            conflict.resolution = format!("Resolver took {incoming} version");
+            Some(conflict.item.clone())
+        }
+        Resolution::RenameTo(new_name) => {
+            conflict.resolution = format!("Resolver renamed to {new_name}");
+            Some(new_name)
+        }
+    };
+    conflicts.push(conflict);
+    Ok(name)
+}
+
+fn should_include_in_merge(schema: &ServiceSchema) -> bool {
+    // Default: include if an OpenAPI schema is present
+    schema.manifest.get_schema(SchemaType::OpenAPI).is_some()
+        && schema
+            .manifest
+            .openapi_composition()
+            .map_or(true, |c| c.include_in_merged)
+}
+
+fn get_composition_config(manifest: &SchemaManifest) -> Option<crate::types::CompositionConfig> {
+    manifest.openapi_composition().cloned()
 }
 
 fn get_component_prefix(
@@ -498,4 +1502,36 @@ mod tests {
         assert_eq!(conflict.conflict_type, ConflictType::Path);
         assert_eq!(conflict.services.len(), 2);
     }
+
+    #[test]
+    fn test_conflict_severity() {
+        let conflict = |conflict_type, strategy| Conflict {
+            conflict_type,
+            item: "item".to_string(),
+            services: vec!["service-a".to_string(), "service-b".to_string()],
+            resolution: String::new(),
+            strategy,
+        };
+
+        assert_eq!(
+            conflict(ConflictType::Path, ConflictStrategy::Error).severity(),
+            ChangeSeverity::High
+        );
+        assert_eq!(
+            conflict(ConflictType::Path, ConflictStrategy::Overwrite).severity(),
+            ChangeSeverity::High
+        );
+        assert_eq!(
+            conflict(ConflictType::Component, ConflictStrategy::Skip).severity(),
+            ChangeSeverity::Medium
+        );
+        assert_eq!(
+            conflict(ConflictType::Tag, ConflictStrategy::Skip).severity(),
+            ChangeSeverity::Low
+        );
+        assert_eq!(
+            conflict(ConflictType::OperationID, ConflictStrategy::Prefix).severity(),
+            ChangeSeverity::Low
+        );
+    }
 }
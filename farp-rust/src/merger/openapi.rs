@@ -2,7 +2,7 @@
 
 use super::types::*;
 use super::*;
-use crate::types::{MountStrategy, SchemaManifest};
+use crate::types::{MountStrategy, RoutingConfig, SchemaManifest};
 use std::collections::HashMap;
 
 /// Parses a raw OpenAPI schema into structured format
@@ -31,6 +31,12 @@ pub fn parse_openapi_schema(raw: &serde_json::Value) -> Result<OpenAPISpec> {
         .map(parse_paths)
         .unwrap_or_default();
 
+    let webhooks = schema_map
+        .get("webhooks")
+        .and_then(|v| v.as_object())
+        .map(parse_paths)
+        .unwrap_or_default();
+
     let components = schema_map
         .get("components")
         .and_then(|v| v.as_object())
@@ -54,6 +60,7 @@ pub fn parse_openapi_schema(raw: &serde_json::Value) -> Result<OpenAPISpec> {
         info,
         servers,
         paths,
+        webhooks,
         components,
         security: Vec::new(),
         tags,
@@ -105,12 +112,108 @@ fn parse_servers(arr: &[serde_json::Value]) -> Vec<Server> {
                     .get("description")
                     .and_then(|v| v.as_str())
                     .map(String::from),
-                variables: None,
+                variables: obj
+                    .get("variables")
+                    .and_then(|v| v.as_object())
+                    .map(parse_server_variables),
             })
         })
         .collect()
 }
 
+/// Parses server variables, skipping any without a string `default`
+fn parse_server_variables(
+    obj: &serde_json::Map<String, serde_json::Value>,
+) -> HashMap<String, ServerVariable> {
+    obj.iter()
+        .filter_map(|(name, var)| {
+            let default = var.get("default")?.as_str()?.to_string();
+            let enum_values = var.get("enum").and_then(|v| v.as_array()).map(|values| {
+                values
+                    .iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            });
+            let description = var
+                .get("description")
+                .and_then(|v| v.as_str())
+                .map(String::from);
+            Some((
+                name.clone(),
+                ServerVariable {
+                    default,
+                    enum_values,
+                    description,
+                },
+            ))
+        })
+        .collect()
+}
+
+impl Server {
+    /// Names of the `{variables}` in the URL template, in order
+    pub fn template_variables(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        let mut rest = self.url.as_str();
+        while let Some(start) = rest.find('{') {
+            let Some(len) = rest[start..].find('}') else {
+                break;
+            };
+            names.push(&rest[start + 1..start + len]);
+            rest = &rest[start + len + 1..];
+        }
+        names
+    }
+
+    /// Checks every URL variable is defined and defaults are among their `enum`
+    pub fn validate_variables(&self) -> Result<()> {
+        let variables = self.variables.as_ref();
+        for name in self.template_variables() {
+            if variables.and_then(|v| v.get(name)).is_none() {
+                return Err(crate::errors::Error::validation(
+                    format!("servers.{}", self.url),
+                    format!("variable {{{name}}} has no definition"),
+                ));
+            }
+        }
+        for (name, variable) in variables.into_iter().flatten() {
+            if let Some(values) = &variable.enum_values {
+                if !values.contains(&variable.default) {
+                    return Err(crate::errors::Error::validation(
+                        format!("servers.{}", self.url),
+                        format!(
+                            "default {} of variable {name} is not in its enum",
+                            variable.default
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// URL with each defined variable replaced by its default
+    ///
+    /// Undefined variables are left as `{name}`.
+    pub fn resolved_url(&self) -> String {
+        let mut url = self.url.clone();
+        for (name, variable) in self.variables.iter().flatten() {
+            url = url.replace(&format!("{{{name}}}"), &variable.default);
+        }
+        url
+    }
+}
+
+impl OpenAPISpec {
+    /// Server URLs with variables substituted by their defaults
+    ///
+    /// `servers` keeps the templates and variables for consumers that need
+    /// them.
+    pub fn resolved_servers(&self) -> Vec<String> {
+        self.servers.iter().map(Server::resolved_url).collect()
+    }
+}
+
 fn parse_paths(obj: &serde_json::Map<String, serde_json::Value>) -> HashMap<String, PathItem> {
     obj.iter()
         .filter_map(|(path, item)| {
@@ -162,7 +265,7 @@ fn parse_path_item(obj: &serde_json::Map<String, serde_json::Value>) -> PathItem
             .get("trace")
             .and_then(|v| v.as_object())
             .map(parse_operation_public),
-        parameters: Vec::new(),
+        parameters: parse_parameters(obj.get("parameters")),
         extensions: obj
             .iter()
             .filter(|(k, _)| k.starts_with("x-"))
@@ -196,11 +299,30 @@ pub(crate) fn parse_operation_public(
                     .collect()
             })
             .unwrap_or_default(),
-        parameters: Vec::new(),
-        request_body: None,
-        responses: None,
-        security: Vec::new(),
+        parameters: parse_parameters(obj.get("parameters")),
+        request_body: obj
+            .get("requestBody")
+            .and_then(|v| v.as_object())
+            .map(parse_request_body),
+        responses: obj
+            .get("responses")
+            .and_then(|v| v.as_object())
+            .map(parse_responses),
+        security: obj
+            .get("security")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default(),
         deprecated: obj.get("deprecated").and_then(|v| v.as_bool()),
+        callbacks: obj
+            .get("callbacks")
+            .and_then(|v| v.as_object())
+            .map(|callbacks| {
+                callbacks
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect()
+            })
+            .unwrap_or_default(),
         extensions: obj
             .iter()
             .filter(|(k, _)| k.starts_with("x-"))
@@ -209,25 +331,186 @@ pub(crate) fn parse_operation_public(
     }
 }
 
+/// Parses a `parameters` array, keeping `$ref` entries as references
+fn parse_parameters(value: Option<&serde_json::Value>) -> Vec<Parameter> {
+    let Some(items) = value.and_then(|v| v.as_array()) else {
+        return Vec::new();
+    };
+
+    items.iter().filter_map(parse_parameter).collect()
+}
+
+/// Parses one parameter; `None` when a non-`$ref` entry lacks `name` or `in`
+fn parse_parameter(item: &serde_json::Value) -> Option<Parameter> {
+    let obj = item.as_object()?;
+    if let Some(reference) = obj.get("$ref").and_then(|v| v.as_str()) {
+        return Some(Parameter {
+            reference: Some(reference.to_string()),
+            name: String::new(),
+            in_: String::new(),
+            description: None,
+            required: None,
+            schema: None,
+            example: None,
+        });
+    }
+
+    Some(Parameter {
+        reference: None,
+        name: obj.get("name")?.as_str()?.to_string(),
+        in_: obj.get("in")?.as_str()?.to_string(),
+        description: obj
+            .get("description")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        required: obj.get("required").and_then(|v| v.as_bool()),
+        schema: obj.get("schema").cloned(),
+        example: obj.get("example").cloned(),
+    })
+}
+
+/// Parses a `requestBody`; a `$ref` body is kept in `extensions`
+fn parse_request_body(obj: &serde_json::Map<String, serde_json::Value>) -> RequestBody {
+    RequestBody {
+        description: obj
+            .get("description")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        content: obj
+            .get("content")
+            .and_then(|v| v.as_object())
+            .map(parse_media_types)
+            .unwrap_or_default(),
+        required: obj.get("required").and_then(|v| v.as_bool()),
+        extensions: obj
+            .iter()
+            .filter(|(k, _)| k.starts_with("x-") || *k == "$ref")
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+    }
+}
+
+/// Parses a `responses` map, keeping `$ref` entries as references
+fn parse_responses(obj: &serde_json::Map<String, serde_json::Value>) -> HashMap<String, Response> {
+    obj.iter()
+        .filter_map(|(status, response)| {
+            let response = response.as_object()?;
+            Some((
+                status.clone(),
+                Response {
+                    description: match response.get("description") {
+                        Some(description) => description.as_str().map(str::to_string),
+                        None if response.contains_key("$ref") => None,
+                        None => Some(String::new()),
+                    },
+                    content: response
+                        .get("content")
+                        .and_then(|v| v.as_object())
+                        .map(parse_media_types),
+                    headers: response
+                        .get("headers")
+                        .and_then(|v| serde_json::from_value(v.clone()).ok()),
+                    links: response
+                        .get("links")
+                        .and_then(|v| serde_json::from_value(v.clone()).ok()),
+                    extensions: response
+                        .iter()
+                        .filter(|(k, _)| k.starts_with("x-") || *k == "$ref")
+                        .map(|(k, v)| (k.clone(), v.clone()))
+                        .collect(),
+                },
+            ))
+        })
+        .collect()
+}
+
+fn parse_media_types(
+    obj: &serde_json::Map<String, serde_json::Value>,
+) -> HashMap<String, MediaType> {
+    obj.iter()
+        .filter_map(|(content_type, media)| {
+            let media = media.as_object()?;
+            Some((
+                content_type.clone(),
+                MediaType {
+                    schema: media.get("schema").cloned(),
+                    example: media.get("example").cloned(),
+                    examples: media
+                        .get("examples")
+                        .and_then(|v| serde_json::from_value(v.clone()).ok()),
+                },
+            ))
+        })
+        .collect()
+}
+
 fn parse_components(obj: &serde_json::Map<String, serde_json::Value>) -> Components {
-    let schemas = obj
-        .get("schemas")
+    Components {
+        schemas: parse_component_map(obj, "schemas", |v| Some(v.clone())),
+        responses: obj
+            .get("responses")
+            .and_then(|v| v.as_object())
+            .map(parse_responses)
+            .unwrap_or_default(),
+        parameters: parse_component_map(obj, "parameters", parse_parameter),
+        request_bodies: parse_component_map(obj, "requestBodies", |v| {
+            v.as_object().map(parse_request_body)
+        }),
+        headers: parse_component_map(obj, "headers", |v| serde_json::from_value(v.clone()).ok()),
+        security_schemes: parse_component_map(obj, "securitySchemes", |v| {
+            serde_json::from_value(v.clone()).ok()
+        }),
+        examples: parse_component_map(obj, "examples", |v| serde_json::from_value(v.clone()).ok()),
+        links: parse_component_map(obj, "links", |v| Some(v.clone())),
+    }
+}
+
+/// Parses the named entries of one `components` section, skipping malformed ones
+fn parse_component_map<T>(
+    obj: &serde_json::Map<String, serde_json::Value>,
+    kind: &str,
+    parse: impl Fn(&serde_json::Value) -> Option<T>,
+) -> HashMap<String, T> {
+    obj.get(kind)
         .and_then(|v| v.as_object())
-        .map(|schemas_obj| {
-            schemas_obj
+        .map(|entries| {
+            entries
                 .iter()
-                .map(|(k, v)| (k.clone(), v.clone()))
+                .filter_map(|(name, value)| Some((name.clone(), parse(value)?)))
                 .collect()
         })
-        .unwrap_or_default();
+        .unwrap_or_default()
+}
 
-    Components {
-        schemas,
-        responses: HashMap::new(),
-        parameters: HashMap::new(),
-        request_bodies: HashMap::new(),
-        headers: HashMap::new(),
-        security_schemes: HashMap::new(),
+/// Prefixes a tag name unless it already carries the prefix
+pub(crate) fn prefix_tag(prefix: &str, tag: &str) -> String {
+    match tag.strip_prefix(prefix) {
+        Some(rest) if rest.starts_with('_') => tag.to_string(),
+        _ => format!("{prefix}_{tag}"),
+    }
+}
+
+/// Folds a same-named tag into an existing one
+///
+/// Non-empty descriptions and external docs fill gaps in `existing`;
+/// extensions are unioned with `existing` winning on key collisions.
+pub(crate) fn merge_tag(existing: &mut Tag, other: Tag) {
+    if existing.description.as_deref().map_or(true, str::is_empty) {
+        if let Some(description) = other.description.filter(|d| !d.is_empty()) {
+            existing.description = Some(description);
+        }
+    }
+    if existing
+        .external_docs
+        .as_ref()
+        .map_or(true, |d| d.url.is_empty())
+    {
+        if let Some(docs) = other.external_docs.filter(|d| !d.url.is_empty()) {
+            existing.external_docs = Some(docs);
+        }
+    }
+    for (key, value) in other.extensions {
+        existing.extensions.entry(key).or_insert(value);
     }
 }
 
@@ -241,6 +524,15 @@ fn parse_tags(arr: &[serde_json::Value]) -> Vec<Tag> {
                     .get("description")
                     .and_then(|v| v.as_str())
                     .map(String::from),
+                external_docs: obj.get("externalDocs").and_then(|v| {
+                    Some(ExternalDocs {
+                        url: v.get("url")?.as_str()?.to_string(),
+                        description: v
+                            .get("description")
+                            .and_then(|d| d.as_str())
+                            .map(String::from),
+                    })
+                }),
                 extensions: obj
                     .iter()
                     .filter(|(k, _)| k.starts_with("x-"))
@@ -266,31 +558,55 @@ pub fn apply_routing(
 }
 
 fn apply_mount_strategy(path: &str, manifest: &SchemaManifest) -> String {
-    let routing = &manifest.routing;
+    format!("{}{}", mount_prefix(manifest), path)
+}
 
+/// Returns the path prefix a manifest's mount strategy puts before its paths
+///
+/// Empty for `Root` and `Subdomain` mounts (and `Custom` without a base path).
+pub fn mount_prefix(manifest: &SchemaManifest) -> String {
+    mount_prefix_with(manifest, &manifest.routing)
+}
+
+/// Like [`mount_prefix`], mounting with `routing` instead of the manifest's own
+pub fn mount_prefix_with(manifest: &SchemaManifest, routing: &RoutingConfig) -> String {
     match routing.strategy {
-        MountStrategy::Root => path.to_string(),
-        MountStrategy::Instance => format!("/{}{}", manifest.instance_id, path),
-        MountStrategy::Service => format!("/{}{}", manifest.service_name, path),
+        MountStrategy::Root | MountStrategy::Subdomain => String::new(),
+        MountStrategy::Instance => format!("/{}", manifest.instance_id),
+        MountStrategy::Service => format!("/{}", manifest.service_name),
         MountStrategy::Versioned => {
-            format!(
-                "/{}/{}{}",
-                manifest.service_name, manifest.service_version, path
-            )
+            format!("/{}/{}", manifest.service_name, manifest.service_version)
         }
-        MountStrategy::Custom => {
-            if let Some(base_path) = &routing.base_path {
-                format!("{base_path}{path}")
-            } else {
-                path.to_string()
-            }
-        }
-        MountStrategy::Subdomain => path.to_string(),
+        MountStrategy::Custom => routing.base_path.clone().unwrap_or_default(),
     }
 }
 
-/// Adds prefix to component schema names
-pub fn prefix_component_names(components: &Components, prefix: &str) -> Components {
+/// Splits an instance-mounted gateway path into the instance ID and backend path
+///
+/// Inverse of the `Instance` mount strategy: `/instance-abc/users/1` yields
+/// `("instance-abc", "/users/1")`. Returns `None` when the path has no
+/// leading segment.
+pub fn extract_instance_route(gateway_path: &str) -> Option<(String, String)> {
+    let rest = gateway_path.strip_prefix('/').unwrap_or(gateway_path);
+    let (instance_id, backend_path) = match rest.find('/') {
+        Some(end) => (&rest[..end], &rest[end..]),
+        None => (rest, "/"),
+    };
+    if instance_id.is_empty() {
+        return None;
+    }
+    Some((instance_id.to_string(), backend_path.to_string()))
+}
+
+/// Adds prefix to component names, joined by `separator`
+///
+/// Every kind but security schemes is renamed, and `$ref`s inside the
+/// components are rewritten to match.
+pub fn prefix_component_names(
+    components: &Components,
+    prefix: &str,
+    separator: &str,
+) -> Components {
     if prefix.is_empty() {
         return components.clone();
     }
@@ -299,46 +615,514 @@ pub fn prefix_component_names(components: &Components, prefix: &str) -> Componen
         schemas: components
             .schemas
             .iter()
-            .map(|(name, schema)| (format!("{prefix}_{name}"), schema.clone()))
+            .map(|(name, schema)| {
+                let mut schema = schema.clone();
+                rewrite_component_refs(&mut schema, prefix, separator);
+                (format!("{prefix}{separator}{name}"), schema)
+            })
             .collect(),
         responses: components
             .responses
             .iter()
-            .map(|(name, response)| (format!("{prefix}_{name}"), response.clone()))
+            .map(|(name, response)| {
+                (
+                    format!("{prefix}{separator}{name}"),
+                    with_prefixed_refs(response.clone(), prefix, separator),
+                )
+            })
             .collect(),
         parameters: components
             .parameters
             .iter()
-            .map(|(name, param)| (format!("{prefix}_{name}"), param.clone()))
+            .map(|(name, param)| {
+                (
+                    format!("{prefix}{separator}{name}"),
+                    with_prefixed_refs(param.clone(), prefix, separator),
+                )
+            })
             .collect(),
         request_bodies: components
             .request_bodies
             .iter()
-            .map(|(name, body)| (format!("{prefix}_{name}"), body.clone()))
+            .map(|(name, body)| {
+                (
+                    format!("{prefix}{separator}{name}"),
+                    with_prefixed_refs(body.clone(), prefix, separator),
+                )
+            })
+            .collect(),
+        headers: components
+            .headers
+            .iter()
+            .map(|(name, header)| {
+                (
+                    format!("{prefix}{separator}{name}"),
+                    with_prefixed_refs(header.clone(), prefix, separator),
+                )
+            })
             .collect(),
-        headers: HashMap::new(),
         security_schemes: components.security_schemes.clone(), // Don't prefix security schemes
+        examples: components
+            .examples
+            .iter()
+            .map(|(name, example)| {
+                (
+                    format!("{prefix}{separator}{name}"),
+                    with_prefixed_refs(example.clone(), prefix, separator),
+                )
+            })
+            .collect(),
+        links: components
+            .links
+            .iter()
+            .map(|(name, link)| {
+                let mut link = link.clone();
+                rewrite_component_refs(&mut link, prefix, separator);
+                (format!("{prefix}{separator}{name}"), link)
+            })
+            .collect(),
+    }
+}
+
+/// Rewrites the `operationId` of every link in `components` with `rename`
+///
+/// Links name their target operation by ID, so after
+/// [`prefix_component_names`] they must follow the operation ID rewriting of
+/// [`apply_operation_prefixes`]. Covers component links and the links of
+/// component responses.
+pub fn prefix_link_operation_ids(components: &mut Components, rename: &dyn Fn(&str) -> String) {
+    rename_link_operation_ids(&mut components.links, rename);
+    for response in components.responses.values_mut() {
+        if let Some(links) = response.links.as_mut() {
+            rename_link_operation_ids(links, rename);
+        }
     }
 }
 
-/// Applies prefixes to operation IDs and tags
+fn rename_link_operation_ids(
+    links: &mut HashMap<String, serde_json::Value>,
+    rename: &dyn Fn(&str) -> String,
+) {
+    for link in links.values_mut() {
+        if let Some(id) = link.get("operationId").and_then(|v| v.as_str()) {
+            link["operationId"] = rename(id).into();
+        }
+    }
+}
+
+/// Rewrites every `$ref` in a path item to the prefixed component names
+///
+/// Covers parameters, request bodies, responses and callbacks, so merged
+/// operations keep pointing at the components renamed by
+/// [`prefix_component_names`].
+pub fn prefix_path_item_refs(item: PathItem, component_prefix: &str, separator: &str) -> PathItem {
+    if component_prefix.is_empty() {
+        return item;
+    }
+    with_prefixed_refs(item, component_prefix, separator)
+}
+
+/// Round-trips a typed OpenAPI object through JSON to prefix its `$ref`s
+fn with_prefixed_refs<T>(item: T, prefix: &str, separator: &str) -> T
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    let Ok(mut value) = serde_json::to_value(&item) else {
+        return item;
+    };
+    rewrite_component_refs(&mut value, prefix, separator);
+    serde_json::from_value(value).unwrap_or(item)
+}
+
+/// Recursively prefixes `#/components/<kind>/<name>` references
+fn rewrite_component_refs(value: &mut serde_json::Value, prefix: &str, separator: &str) {
+    map_refs(value, &|reference| {
+        prefix_component_ref(reference, prefix, separator)
+    });
+}
+
+/// Recursively replaces every `$ref` with `map(reference)`
+fn map_refs(value: &mut serde_json::Value, map: &dyn Fn(&str) -> String) {
+    match value {
+        serde_json::Value::Object(obj) => {
+            for (key, child) in obj.iter_mut() {
+                if key == "$ref" {
+                    if let Some(reference) = child.as_str() {
+                        *child = map(reference).into();
+                    }
+                } else {
+                    map_refs(child, map);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for child in items {
+                map_refs(child, map);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Points `#/components/<kind>/<from>` references at `to`
+///
+/// Used when a conflict resolver renames a component after the service's
+/// operations were merged against its original name.
+pub fn rename_component_refs<T>(item: T, kind: &str, from: &str, to: &str) -> T
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    let Ok(mut value) = serde_json::to_value(&item) else {
+        return item;
+    };
+    let (from, to) = (
+        format!("#/components/{kind}/{from}"),
+        format!("#/components/{kind}/{to}"),
+    );
+    map_refs(&mut value, &|reference| {
+        if reference == from {
+            to.clone()
+        } else {
+            reference.to_string()
+        }
+    });
+    serde_json::from_value(value).unwrap_or(item)
+}
+
+/// Renames the `from` security scheme in a path item's security requirements
+pub fn rename_security_requirements(item: &mut PathItem, from: &str, to: &str) {
+    for operation in operations_mut(item) {
+        for requirement in &mut operation.security {
+            if let Some(scopes) = requirement.remove(from) {
+                requirement.insert(to.to_string(), scopes);
+            }
+        }
+    }
+}
+
+fn prefix_component_ref(reference: &str, prefix: &str, separator: &str) -> String {
+    for kind in [
+        "schemas",
+        "responses",
+        "parameters",
+        "requestBodies",
+        "headers",
+        "examples",
+        "links",
+    ] {
+        let base = format!("#/components/{kind}/");
+        if let Some(name) = reference.strip_prefix(&base) {
+            return format!("{base}{prefix}{separator}{name}");
+        }
+    }
+    reference.to_string()
+}
+
+/// Removes `example`/`examples` from a spec's parameters, bodies, responses
+/// and schemas, along with `components.examples`
+///
+/// Only the keywords are dropped: a schema property that happens to be named
+/// `example` is kept. Callbacks are carried verbatim and left untouched.
+pub fn strip_examples(spec: &mut OpenAPISpec) {
+    for item in spec.paths.values_mut().chain(spec.webhooks.values_mut()) {
+        item.parameters
+            .iter_mut()
+            .for_each(strip_parameter_examples);
+        for operation in operations_mut(item) {
+            operation
+                .parameters
+                .iter_mut()
+                .for_each(strip_parameter_examples);
+            if let Some(body) = operation.request_body.as_mut() {
+                body.content.values_mut().for_each(strip_media_examples);
+            }
+            for response in operation.responses.iter_mut().flat_map(|r| r.values_mut()) {
+                strip_response_examples(response);
+            }
+        }
+    }
+
+    if let Some(components) = spec.components.as_mut() {
+        components
+            .schemas
+            .values_mut()
+            .for_each(strip_schema_examples);
+        components
+            .responses
+            .values_mut()
+            .for_each(strip_response_examples);
+        components
+            .parameters
+            .values_mut()
+            .for_each(strip_parameter_examples);
+        for body in components.request_bodies.values_mut() {
+            body.content.values_mut().for_each(strip_media_examples);
+        }
+        for header in components.headers.values_mut() {
+            header.schema.iter_mut().for_each(strip_schema_examples);
+        }
+        // Only the stripped `examples` maps referenced these
+        components.examples.clear();
+    }
+}
+
+/// Drops the body schemas of every response
+///
+/// Status codes, descriptions, media types and headers stay, so routing and
+/// content negotiation still see the same structure.
+pub fn strip_response_schemas(spec: &mut OpenAPISpec) {
+    let strip = |response: &mut Response| {
+        for media in response.content.iter_mut().flat_map(|c| c.values_mut()) {
+            media.schema = None;
+        }
+    };
+    for item in spec.paths.values_mut().chain(spec.webhooks.values_mut()) {
+        for operation in operations_mut(item) {
+            operation
+                .responses
+                .iter_mut()
+                .flat_map(|r| r.values_mut())
+                .for_each(strip);
+        }
+    }
+    if let Some(components) = spec.components.as_mut() {
+        components.responses.values_mut().for_each(strip);
+    }
+}
+
+fn operations_mut(item: &mut PathItem) -> impl Iterator<Item = &mut Operation> {
+    [
+        &mut item.get,
+        &mut item.put,
+        &mut item.post,
+        &mut item.delete,
+        &mut item.options,
+        &mut item.head,
+        &mut item.patch,
+        &mut item.trace,
+    ]
+    .into_iter()
+    .flatten()
+}
+
+fn strip_parameter_examples(parameter: &mut Parameter) {
+    parameter.example = None;
+    parameter.schema.iter_mut().for_each(strip_schema_examples);
+}
+
+fn strip_media_examples(media: &mut MediaType) {
+    media.example = None;
+    media.examples = None;
+    media.schema.iter_mut().for_each(strip_schema_examples);
+}
+
+fn strip_response_examples(response: &mut Response) {
+    for media in response.content.iter_mut().flat_map(|c| c.values_mut()) {
+        strip_media_examples(media);
+    }
+    for header in response.headers.iter_mut().flat_map(|h| h.values_mut()) {
+        header.schema.iter_mut().for_each(strip_schema_examples);
+    }
+}
+
+/// Recursively removes `example`/`examples` keywords from a JSON Schema
+fn strip_schema_examples(schema: &mut serde_json::Value) {
+    match schema {
+        serde_json::Value::Object(obj) => {
+            obj.remove("example");
+            obj.remove("examples");
+            for (key, child) in obj.iter_mut() {
+                match key.as_str() {
+                    // Maps of names to subschemas: the names aren't keywords
+                    "properties" | "patternProperties" | "$defs" | "definitions"
+                    | "dependentSchemas" => {
+                        if let Some(children) = child.as_object_mut() {
+                            children.values_mut().for_each(strip_schema_examples);
+                        }
+                    }
+                    // Literal values, not subschemas
+                    "enum" | "const" | "default" => {}
+                    _ => strip_schema_examples(child),
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(strip_schema_examples),
+        _ => {}
+    }
+}
+
+/// Upgrades an OpenAPI 3.0.x document to 3.1 in place
+///
+/// Rewrites the 3.0-only schema constructs so 3.0 and 3.1 inputs can be
+/// merged into one valid 3.1 document:
+/// - `nullable: true` becomes a `"null"` entry in `type` (and in `enum`)
+/// - boolean `exclusiveMinimum`/`exclusiveMaximum` become the numeric bound
+///
+/// Documents that aren't 3.0.x are left untouched.
+pub fn normalize_to_3_1(spec: &mut serde_json::Value) {
+    let is_3_0 = spec
+        .get("openapi")
+        .and_then(|v| v.as_str())
+        .is_some_and(|v| v.starts_with("3.0"));
+    if !is_3_0 {
+        return;
+    }
+
+    spec["openapi"] = "3.1.0".into();
+    upgrade_schemas(spec);
+}
+
+/// Applies the 3.0 → 3.1 schema rewrites to every object in the tree
+fn upgrade_schemas(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(obj) => {
+            if let Some(nullable) = obj.get("nullable").and_then(|v| v.as_bool()) {
+                obj.remove("nullable");
+                if nullable {
+                    match obj.get_mut("type") {
+                        Some(serde_json::Value::String(ty)) => {
+                            let ty = std::mem::take(ty);
+                            obj.insert("type".to_string(), serde_json::json!([ty, "null"]));
+                        }
+                        Some(serde_json::Value::Array(types))
+                            if !types.iter().any(|t| t == "null") =>
+                        {
+                            types.push("null".into());
+                        }
+                        _ => {}
+                    }
+                    if let Some(values) = obj.get_mut("enum").and_then(|e| e.as_array_mut()) {
+                        if !values.contains(&serde_json::Value::Null) {
+                            values.push(serde_json::Value::Null);
+                        }
+                    }
+                }
+            }
+
+            for (exclusive, bound) in [
+                ("exclusiveMinimum", "minimum"),
+                ("exclusiveMaximum", "maximum"),
+            ] {
+                if let Some(flag) = obj.get(exclusive).and_then(|v| v.as_bool()) {
+                    obj.remove(exclusive);
+                    if flag {
+                        if let Some(limit) = obj.remove(bound) {
+                            obj.insert(exclusive.to_string(), limit);
+                        }
+                    }
+                }
+            }
+
+            for child in obj.values_mut() {
+                upgrade_schemas(child);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for child in items {
+                upgrade_schemas(child);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Fails if `value` nests objects and arrays more than `max_depth` levels deep
+///
+/// Reference rewriting, example stripping and the other schema walks of a
+/// merge recurse once per level, so checking their input up front keeps a
+/// pathological document from overflowing the stack. The check itself
+/// doesn't recurse.
+pub fn check_depth(value: &serde_json::Value, max_depth: usize) -> Result<()> {
+    let mut pending = vec![(value, 0)];
+    while let Some((value, depth)) = pending.pop() {
+        let children: Vec<&serde_json::Value> = match value {
+            serde_json::Value::Object(obj) => obj.values().collect(),
+            serde_json::Value::Array(items) => items.iter().collect(),
+            _ => continue,
+        };
+        if depth >= max_depth {
+            return Err(crate::errors::Error::invalid_schema(
+                "max schema depth exceeded",
+            ));
+        }
+        pending.extend(children.into_iter().map(|child| (child, depth + 1)));
+    }
+    Ok(())
+}
+
+/// Inlines every local (`#/...`) `$ref` in `raw`
+///
+/// External references are left untouched. Each followed reference counts as
+/// one hop; exceeding `max_depth` hops along any chain fails instead of
+/// recursing forever on cyclic schemas.
+pub fn dereference(raw: &serde_json::Value, max_depth: usize) -> Result<serde_json::Value> {
+    inline_refs(raw, raw, 0, max_depth)
+}
+
+fn inline_refs(
+    root: &serde_json::Value,
+    value: &serde_json::Value,
+    depth: usize,
+    max_depth: usize,
+) -> Result<serde_json::Value> {
+    match value {
+        serde_json::Value::Object(obj) => {
+            if let Some(pointer) = obj
+                .get("$ref")
+                .and_then(|r| r.as_str())
+                .and_then(|r| r.strip_prefix('#'))
+            {
+                if depth >= max_depth {
+                    return Err(crate::errors::Error::invalid_schema(
+                        "max ref depth exceeded",
+                    ));
+                }
+                let target = root.pointer(pointer).ok_or_else(|| {
+                    crate::errors::Error::invalid_schema(format!("unresolved $ref: #{pointer}"))
+                })?;
+                return inline_refs(root, target, depth + 1, max_depth);
+            }
+
+            obj.iter()
+                .map(|(key, child)| Ok((key.clone(), inline_refs(root, child, depth, max_depth)?)))
+                .collect::<Result<_>>()
+                .map(serde_json::Value::Object)
+        }
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(|child| inline_refs(root, child, depth, max_depth))
+            .collect::<Result<_>>()
+            .map(serde_json::Value::Array),
+        _ => Ok(value.clone()),
+    }
+}
+
+/// Applies prefixes to operation IDs (per `op_id_strategy`) and tags of
+/// `manifest`'s operations
 pub fn apply_operation_prefixes(
     mut item: PathItem,
     op_id_prefix: &str,
+    op_id_strategy: OperationIdStrategy,
     tag_prefix: &str,
-    service_name: &str,
+    manifest: &SchemaManifest,
     seen_operation_ids: &mut HashMap<String, String>,
     result: &mut MergeResult,
 ) -> PathItem {
+    let service_name = manifest.service_name.as_str();
+    let rename =
+        |id: &str| op_id_strategy.apply(id, op_id_prefix, service_name, &manifest.service_version);
     let mut apply_to_op = |op: &mut Option<Operation>| {
         if let Some(operation) = op {
+            // Links in responses target operations of the same service
+            for response in operation.responses.iter_mut().flat_map(|r| r.values_mut()) {
+                if let Some(links) = response.links.as_mut() {
+                    rename_link_operation_ids(links, &rename);
+                }
+            }
+
             // Prefix operation ID
             if let Some(original_id) = &operation.operation_id {
-                let new_id = if !op_id_prefix.is_empty() {
-                    format!("{op_id_prefix}_{original_id}")
-                } else {
-                    original_id.clone()
-                };
+                let new_id = rename(original_id);
 
                 // Check for conflicts
                 if let Some(existing_service) = seen_operation_ids.get(&new_id) {
@@ -346,7 +1130,10 @@ pub fn apply_operation_prefixes(
                         conflict_type: ConflictType::OperationID,
                         item: original_id.clone(),
                         services: vec![existing_service.clone(), service_name.to_string()],
-                        resolution: format!("Prefixed to {new_id}"),
+                        resolution: match op_id_strategy {
+                            OperationIdStrategy::Keep => format!("Kept as {new_id}"),
+                            _ => format!("Prefixed to {new_id}"),
+                        },
                         strategy: ConflictStrategy::Prefix,
                     });
                 }
@@ -359,7 +1146,7 @@ pub fn apply_operation_prefixes(
                 operation.tags = operation
                     .tags
                     .iter()
-                    .map(|tag| format!("{tag_prefix}_{tag}"))
+                    .map(|tag| prefix_tag(tag_prefix, tag))
                     .collect();
             }
         }
@@ -377,7 +1164,87 @@ pub fn apply_operation_prefixes(
     item
 }
 
+/// Derives a deterministic operation ID from a method and path
+///
+/// `GET /users/{id}` becomes `get_users_by_id`; path parameters turn into
+/// `by_<name>` and other punctuation into underscores. The root path maps to
+/// `<method>_root`.
+pub fn generate_operation_id(method: &str, path: &str) -> String {
+    let mut parts = vec![method.to_lowercase()];
+    for segment in path.split('/').filter(|s| !s.is_empty()) {
+        let segment = match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(param) => format!("by_{param}"),
+            None => segment.to_string(),
+        };
+        let cleaned: String = segment
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        parts.extend(
+            cleaned
+                .split('_')
+                .filter(|p| !p.is_empty())
+                .map(String::from),
+        );
+    }
+    if parts.len() == 1 {
+        parts.push("root".to_string());
+    }
+    parts.join("_")
+}
+
+/// Gives every operation of `item` without an `operationId` a generated one
+pub fn fill_missing_operation_ids(item: &mut PathItem, path: &str) {
+    for (method, operation) in [
+        ("get", &mut item.get),
+        ("put", &mut item.put),
+        ("post", &mut item.post),
+        ("delete", &mut item.delete),
+        ("options", &mut item.options),
+        ("head", &mut item.head),
+        ("patch", &mut item.patch),
+        ("trace", &mut item.trace),
+    ] {
+        if let Some(operation) = operation {
+            if operation.operation_id.is_none() {
+                operation.operation_id = Some(generate_operation_id(method, path));
+            }
+        }
+    }
+}
+
+/// HTTP methods (lower case) for which both path items define an operation
+///
+/// [`merge_path_items`] keeps only `new`'s operation for these.
+pub fn colliding_methods(existing: &PathItem, new: &PathItem) -> Vec<&'static str> {
+    [
+        ("get", existing.get.is_some() && new.get.is_some()),
+        ("put", existing.put.is_some() && new.put.is_some()),
+        ("post", existing.post.is_some() && new.post.is_some()),
+        ("delete", existing.delete.is_some() && new.delete.is_some()),
+        (
+            "options",
+            existing.options.is_some() && new.options.is_some(),
+        ),
+        ("head", existing.head.is_some() && new.head.is_some()),
+        ("patch", existing.patch.is_some() && new.patch.is_some()),
+        ("trace", existing.trace.is_some() && new.trace.is_some()),
+    ]
+    .into_iter()
+    .filter_map(|(method, both)| both.then_some(method))
+    .collect()
+}
+
 /// Merges two path items, preferring non-None operations
+///
+/// Where both define the same method, `new`'s operation wins; see
+/// [`colliding_methods`].
 pub fn merge_path_items(existing: PathItem, new: PathItem) -> PathItem {
     PathItem {
         summary: new.summary.or(existing.summary),
@@ -390,11 +1257,7 @@ pub fn merge_path_items(existing: PathItem, new: PathItem) -> PathItem {
         options: new.options.or(existing.options),
         head: new.head.or(existing.head),
         trace: new.trace.or(existing.trace),
-        parameters: {
-            let mut params = existing.parameters;
-            params.extend(new.parameters);
-            params
-        },
+        parameters: merge_parameters(existing.parameters, new.parameters),
         extensions: {
             let mut ext = existing.extensions;
             ext.extend(new.extensions);
@@ -403,6 +1266,20 @@ pub fn merge_path_items(existing: PathItem, new: PathItem) -> PathItem {
     }
 }
 
+/// Combines parameter lists; a new parameter replaces an existing one with
+/// the same `name`/`in` (or `$ref`)
+fn merge_parameters(existing: Vec<Parameter>, new: Vec<Parameter>) -> Vec<Parameter> {
+    let mut params: Vec<Parameter> = existing
+        .into_iter()
+        .filter(|p| {
+            !new.iter()
+                .any(|n| n.name == p.name && n.in_ == p.in_ && n.reference == p.reference)
+        })
+        .collect();
+    params.extend(new);
+    params
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -423,6 +1300,70 @@ mod tests {
         assert_eq!(parsed.info.title, "Test API");
     }
 
+    #[test]
+    fn test_resolved_servers() {
+        let schema = serde_json::json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Test API", "version": "1.0.0"},
+            "servers": [
+                {
+                    "url": "https://{env}.api.com/{version}",
+                    "variables": {
+                        "env": {"default": "prod", "enum": ["prod", "staging"]},
+                        "version": {"default": "v1", "description": "API version"}
+                    }
+                },
+                {"url": "https://{region}.api.com"}
+            ],
+            "paths": {}
+        });
+
+        let parsed = parse_openapi_schema(&schema).unwrap();
+        let variables = parsed.servers[0].variables.as_ref().unwrap();
+        assert_eq!(variables["env"].default, "prod");
+        assert_eq!(
+            variables["env"].enum_values,
+            Some(vec!["prod".to_string(), "staging".to_string()])
+        );
+        assert_eq!(
+            parsed.resolved_servers(),
+            vec!["https://prod.api.com/v1", "https://{region}.api.com"]
+        );
+
+        assert!(parsed.servers[0].validate_variables().is_ok());
+        assert!(matches!(
+            parsed.servers[1].validate_variables(),
+            Err(crate::errors::Error::Validation { .. })
+        ));
+
+        let mut server = parsed.servers[0].clone();
+        server
+            .variables
+            .as_mut()
+            .unwrap()
+            .get_mut("env")
+            .unwrap()
+            .default = "dev".to_string();
+        assert!(server.validate_variables().is_err());
+    }
+
+    #[test]
+    fn test_generate_operation_id() {
+        assert_eq!(
+            generate_operation_id("GET", "/users/{id}"),
+            "get_users_by_id"
+        );
+        assert_eq!(
+            generate_operation_id("post", "/users/{userId}/avatar.png"),
+            "post_users_by_userid_avatar_png"
+        );
+        assert_eq!(generate_operation_id("get", "/"), "get_root");
+        assert_eq!(
+            generate_operation_id("delete", "//api--v1/items/"),
+            "delete_api_v1_items"
+        );
+    }
+
     #[test]
     fn test_prefix_component_names() {
         let components = Components {
@@ -434,13 +1375,152 @@ mod tests {
             request_bodies: HashMap::new(),
             headers: HashMap::new(),
             security_schemes: HashMap::new(),
+            examples: HashMap::new(),
+            links: HashMap::new(),
         };
 
-        let prefixed = prefix_component_names(&components, "service");
+        let prefixed = prefix_component_names(&components, "service", "_");
         assert!(prefixed.schemas.contains_key("service_User"));
         assert!(!prefixed.schemas.contains_key("User"));
     }
 
+    #[test]
+    fn test_parse_webhooks_and_callbacks() {
+        let schema = serde_json::json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Pets", "version": "1.0.0"},
+            "paths": {
+                "/subscribe": {
+                    "post": {
+                        "operationId": "subscribe",
+                        "callbacks": {
+                            "onEvent": {
+                                "{$request.body#/callbackUrl}": {
+                                    "post": {
+                                        "requestBody": {
+                                            "content": {"application/json": {
+                                                "schema": {"$ref": "#/components/schemas/Event"}
+                                            }}
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "webhooks": {
+                "newPet": {"post": {"operationId": "newPet"}}
+            }
+        });
+
+        let parsed = parse_openapi_schema(&schema).unwrap();
+        assert!(parsed.webhooks.contains_key("newPet"));
+
+        let item = prefix_path_item_refs(parsed.paths["/subscribe"].clone(), "pets", "_");
+        let callback = &item.post.unwrap().callbacks["onEvent"];
+        assert_eq!(
+            callback["{$request.body#/callbackUrl}"]["post"]["requestBody"]["content"]
+                ["application/json"]["schema"]["$ref"],
+            "#/components/schemas/pets_Event"
+        );
+    }
+
+    #[test]
+    fn test_normalize_to_3_1() {
+        let mut spec = serde_json::json!({
+            "openapi": "3.0.3",
+            "components": {"schemas": {"Item": {
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string", "nullable": true},
+                    "kind": {"type": "string", "enum": ["a", "b"], "nullable": true},
+                    "count": {"type": "integer", "minimum": 0, "exclusiveMinimum": true},
+                    "ratio": {"type": "number", "maximum": 1, "exclusiveMaximum": false},
+                    "nullable": {"type": "boolean", "nullable": false}
+                }
+            }}}
+        });
+
+        normalize_to_3_1(&mut spec);
+
+        assert_eq!(spec["openapi"], "3.1.0");
+        let props = &spec["components"]["schemas"]["Item"]["properties"];
+        assert_eq!(
+            props["name"],
+            serde_json::json!({"type": ["string", "null"]})
+        );
+        assert_eq!(
+            props["kind"],
+            serde_json::json!({"type": ["string", "null"], "enum": ["a", "b", null]})
+        );
+        assert_eq!(
+            props["count"],
+            serde_json::json!({"type": "integer", "exclusiveMinimum": 0})
+        );
+        assert_eq!(
+            props["ratio"],
+            serde_json::json!({"type": "number", "maximum": 1})
+        );
+        assert_eq!(props["nullable"], serde_json::json!({"type": "boolean"}));
+
+        // 3.1 documents are left alone
+        let mut spec_3_1 = serde_json::json!({"openapi": "3.1.0", "x": {"nullable": true}});
+        normalize_to_3_1(&mut spec_3_1);
+        assert_eq!(spec_3_1["x"]["nullable"], true);
+    }
+
+    #[test]
+    fn test_dereference() {
+        let raw = serde_json::json!({
+            "paths": {"/users": {"get": {"responses": {"200": {"$ref": "#/components/responses/Users"}}}}},
+            "components": {
+                "responses": {"Users": {"content": {"application/json": {"schema": {"$ref": "#/components/schemas/User"}}}}},
+                "schemas": {"User": {"type": "object"}}
+            }
+        });
+
+        let resolved = dereference(&raw, 64).unwrap();
+        assert_eq!(
+            resolved["paths"]["/users"]["get"]["responses"]["200"]["content"]["application/json"]
+                ["schema"],
+            serde_json::json!({"type": "object"})
+        );
+    }
+
+    #[test]
+    fn test_dereference_ref_cycle() {
+        let raw = serde_json::json!({
+            "components": {
+                "schemas": {
+                    "Node": {
+                        "type": "object",
+                        "properties": {"next": {"$ref": "#/components/schemas/Node"}}
+                    },
+                    "Loop": {"$ref": "#/components/schemas/Loop"}
+                }
+            }
+        });
+
+        let err = dereference(&raw, 8).unwrap_err();
+        assert!(err.to_string().contains("max ref depth exceeded"));
+
+        let merger = Merger::new(MergerConfig::default());
+        assert!(merger.dereference(&raw).is_err());
+    }
+
+    #[test]
+    fn test_check_depth() {
+        let mut deep = serde_json::json!({"type": "string"});
+        for _ in 0..10 {
+            deep = serde_json::json!({"type": "array", "items": deep});
+        }
+
+        assert!(check_depth(&deep, 11).is_ok());
+        let err = check_depth(&deep, 10).unwrap_err();
+        assert!(err.to_string().contains("max schema depth exceeded"));
+    }
+
     #[test]
     fn test_merge_path_items() {
         let existing = PathItem {
@@ -454,6 +1534,7 @@ mod tests {
                 responses: None,
                 security: Vec::new(),
                 deprecated: None,
+                callbacks: HashMap::new(),
                 extensions: HashMap::new(),
             }),
             post: None,
@@ -481,6 +1562,7 @@ mod tests {
                 responses: None,
                 security: Vec::new(),
                 deprecated: None,
+                callbacks: HashMap::new(),
                 extensions: HashMap::new(),
             }),
             put: None,
@@ -499,4 +1581,95 @@ mod tests {
         assert!(merged.get.is_some());
         assert!(merged.post.is_some());
     }
+
+    #[test]
+    fn test_parse_parameters_and_request_body() {
+        let item = serde_json::json!({
+            "parameters": [{"$ref": "#/components/parameters/TraceId"}],
+            "put": {
+                "parameters": [
+                    {"name": "id", "in": "path", "required": true, "schema": {"type": "string"}},
+                    {"in": "query"}
+                ],
+                "requestBody": {
+                    "required": true,
+                    "content": {"application/json": {
+                        "schema": {"$ref": "#/components/schemas/User"},
+                        "examples": {"basic": {"value": {"name": "Ada"}}}
+                    }}
+                }
+            }
+        });
+
+        let item = parse_path_item(item.as_object().unwrap());
+        assert_eq!(
+            item.parameters[0].reference.as_deref(),
+            Some("#/components/parameters/TraceId")
+        );
+
+        let put = item.put.as_ref().unwrap();
+        assert_eq!(put.parameters.len(), 1);
+        assert_eq!(put.parameters[0].name, "id");
+        assert_eq!(put.parameters[0].in_, "path");
+        assert_eq!(put.parameters[0].required, Some(true));
+
+        let body = put.request_body.as_ref().unwrap();
+        assert_eq!(body.required, Some(true));
+        let media = &body.content["application/json"];
+        assert_eq!(
+            media.schema,
+            Some(serde_json::json!({"$ref": "#/components/schemas/User"}))
+        );
+        assert!(media.examples.as_ref().unwrap().contains_key("basic"));
+
+        let prefixed = serde_json::to_value(prefix_path_item_refs(item, "svc", "_")).unwrap();
+        assert_eq!(
+            prefixed["parameters"][0]["$ref"],
+            "#/components/parameters/svc_TraceId"
+        );
+        assert_eq!(
+            prefixed["put"]["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/svc_User"
+        );
+    }
+
+    #[test]
+    fn test_parse_responses() {
+        let responses = serde_json::json!({
+            "200": {"$ref": "#/components/responses/Users"},
+            "204": {},
+            "404": {"description": "Not found"}
+        });
+
+        let responses = parse_responses(responses.as_object().unwrap());
+        let value = serde_json::to_value(&responses).unwrap();
+        // References carry no description of their own
+        assert_eq!(
+            value["200"],
+            serde_json::json!({"$ref": "#/components/responses/Users"})
+        );
+        assert_eq!(value["204"], serde_json::json!({"description": ""}));
+        assert_eq!(value["404"]["description"], "Not found");
+    }
+
+    #[test]
+    fn test_extract_instance_route() {
+        assert_eq!(
+            extract_instance_route("/instance-abc/users/1"),
+            Some(("instance-abc".to_string(), "/users/1".to_string()))
+        );
+        assert_eq!(
+            extract_instance_route("/instance-abc"),
+            Some(("instance-abc".to_string(), "/".to_string()))
+        );
+        assert_eq!(extract_instance_route("/"), None);
+        assert_eq!(extract_instance_route(""), None);
+
+        let manifest = crate::manifest::new_manifest("users", "v1", "instance-abc");
+        let mounted = apply_mount_strategy("/users/1", &manifest);
+        assert_eq!(
+            extract_instance_route(&mounted),
+            Some(("instance-abc".to_string(), "/users/1".to_string()))
+        );
+    }
 }
@@ -100,6 +100,10 @@ impl ORPCMerger {
     }
 
     pub fn merge(&self, schemas: Vec<ORPCServiceSchema>) -> Result<ORPCMergeResult> {
+        self.config.validate_separators(false)?;
+        let separator = self.config.component_separator.as_str();
+        let namespace = self.config.namespace_separator.as_str();
+
         let mut result = ORPCMergeResult {
             spec: ORPCSpec {
                 orpc: "1.0.0".to_string(),
@@ -154,12 +158,13 @@ impl ORPCMerger {
             let parsed = schema.parsed.as_ref().unwrap();
             let strategy = self.config.default_conflict_strategy;
 
-            let procedure_prefix = &schema.manifest.service_name;
-            let schema_prefix = &schema.manifest.service_name;
+            let prefix = self.config.prefix(&service_name);
+            let procedure_prefix = &prefix;
+            let schema_prefix = &prefix;
 
             // Merge procedures
             for (proc_name, procedure) in &parsed.procedures {
-                let mut prefixed_name = format!("{procedure_prefix}.{proc_name}");
+                let mut prefixed_name = format!("{procedure_prefix}{namespace}{proc_name}");
 
                 if let Some(existing_service) = seen_procedures.get(&prefixed_name) {
                     let conflict = Conflict {
@@ -188,7 +193,7 @@ impl ORPCMerger {
                             result.conflicts.push(c);
                         }
                         ConflictStrategy::Prefix => {
-                            prefixed_name = format!("{service_name}.{proc_name}");
+                            prefixed_name = format!("{prefix}{namespace}{proc_name}");
                             let mut c = conflict;
                             c.resolution = format!("Prefixed to {prefixed_name}");
                             result.conflicts.push(c);
@@ -210,7 +215,7 @@ impl ORPCMerger {
 
             // Merge schemas
             for (schema_name, schema_obj) in &parsed.schemas {
-                let prefixed_name = format!("{schema_prefix}_{schema_name}");
+                let prefixed_name = format!("{schema_prefix}{separator}{schema_name}");
                 if let Some(existing_service) = seen_schemas.get(&prefixed_name) {
                     if strategy == ConflictStrategy::Skip {
                         result.conflicts.push(Conflict {
@@ -260,7 +265,7 @@ impl ORPCMerger {
                             result.conflicts.push(c);
                         }
                         ConflictStrategy::Prefix => {
-                            let prefixed_name = format!("{service_name}_{name}");
+                            let prefixed_name = format!("{prefix}{separator}{name}");
                             let mut c = conflict;
                             c.resolution = format!("Prefixed to {prefixed_name}");
                             result.conflicts.push(c);
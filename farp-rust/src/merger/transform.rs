@@ -0,0 +1,115 @@
+//! Pre-merge transformations applied to each parsed OpenAPI spec
+
+use super::types::{OpenAPISpec, Operation, PathItem};
+use std::collections::HashMap;
+
+/// Extension marking a path or operation as internal-only
+pub const INTERNAL_EXTENSION: &str = "x-internal";
+
+/// Mutates a service's parsed spec before it is merged
+///
+/// Transforms run in [`MergerConfig::transforms`](super::MergerConfig::transforms)
+/// order, after parsing and before conflict detection, so anything removed
+/// here never takes part in the merge.
+pub trait SchemaTransform: Send + Sync {
+    /// Applies the transformation in place
+    fn transform(&self, spec: &mut OpenAPISpec);
+}
+
+impl<F> SchemaTransform for F
+where
+    F: Fn(&mut OpenAPISpec) + Send + Sync,
+{
+    fn transform(&self, spec: &mut OpenAPISpec) {
+        self(spec)
+    }
+}
+
+/// Removes paths, webhooks and operations marked `x-internal: true`
+///
+/// Path items left without any operation are dropped as well.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StripInternal;
+
+impl SchemaTransform for StripInternal {
+    fn transform(&self, spec: &mut OpenAPISpec) {
+        strip_internal(&mut spec.paths);
+        strip_internal(&mut spec.webhooks);
+    }
+}
+
+/// Removes the spec's `servers` so only the merger's configured ones remain
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RemoveServers;
+
+impl SchemaTransform for RemoveServers {
+    fn transform(&self, spec: &mut OpenAPISpec) {
+        spec.servers.clear();
+    }
+}
+
+fn strip_internal(items: &mut HashMap<String, PathItem>) {
+    items.retain(|_, item| {
+        if is_internal(&item.extensions) {
+            return false;
+        }
+        let mut kept = 0;
+        for slot in operation_slots(item) {
+            if slot.as_ref().is_some_and(|op| is_internal(&op.extensions)) {
+                *slot = None;
+            }
+            kept += usize::from(slot.is_some());
+        }
+        kept > 0
+    });
+}
+
+fn is_internal(extensions: &HashMap<String, serde_json::Value>) -> bool {
+    extensions.get(INTERNAL_EXTENSION) == Some(&serde_json::Value::Bool(true))
+}
+
+fn operation_slots(item: &mut PathItem) -> [&mut Option<Operation>; 8] {
+    [
+        &mut item.get,
+        &mut item.put,
+        &mut item.post,
+        &mut item.delete,
+        &mut item.options,
+        &mut item.head,
+        &mut item.patch,
+        &mut item.trace,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merger::parse_openapi_schema;
+
+    #[test]
+    fn test_strip_internal() {
+        let mut spec = parse_openapi_schema(&serde_json::json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Users", "version": "1.0.0"},
+            "servers": [{"url": "http://users.internal"}],
+            "paths": {
+                "/users": {
+                    "get": {"operationId": "listUsers"},
+                    "delete": {"operationId": "purgeUsers", "x-internal": true}
+                },
+                "/debug": {"get": {"operationId": "debug", "x-internal": true}},
+                "/admin": {"x-internal": true, "get": {"operationId": "admin"}}
+            }
+        }))
+        .unwrap();
+
+        StripInternal.transform(&mut spec);
+        assert_eq!(spec.paths.keys().collect::<Vec<_>>(), vec!["/users"]);
+        let users = &spec.paths["/users"];
+        assert!(users.get.is_some());
+        assert!(users.delete.is_none());
+
+        RemoveServers.transform(&mut spec);
+        assert!(spec.servers.is_empty());
+    }
+}
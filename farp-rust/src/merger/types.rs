@@ -1,7 +1,7 @@
 //! Types for OpenAPI schema representation
 
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
 
 /// Simplified OpenAPI 3.x specification
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -10,7 +10,11 @@ pub struct OpenAPISpec {
     pub info: Info,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub servers: Vec<Server>,
+    #[serde(serialize_with = "sorted_map")]
     pub paths: HashMap<String, PathItem>,
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    #[serde(serialize_with = "sorted_map")]
+    pub webhooks: HashMap<String, PathItem>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub components: Option<Components>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
@@ -18,6 +22,7 @@ pub struct OpenAPISpec {
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub tags: Vec<Tag>,
     #[serde(flatten)]
+    #[serde(serialize_with = "sorted_map")]
     pub extensions: HashMap<String, serde_json::Value>,
 }
 
@@ -35,6 +40,7 @@ pub struct Info {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub license: Option<License>,
     #[serde(flatten)]
+    #[serde(serialize_with = "sorted_map")]
     pub extensions: HashMap<String, serde_json::Value>,
 }
 
@@ -64,6 +70,7 @@ pub struct Server {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "sorted_opt_map")]
     pub variables: Option<HashMap<String, ServerVariable>>,
 }
 
@@ -103,6 +110,7 @@ pub struct PathItem {
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub parameters: Vec<Parameter>,
     #[serde(flatten)]
+    #[serde(serialize_with = "sorted_map")]
     pub extensions: HashMap<String, serde_json::Value>,
 }
 
@@ -122,20 +130,30 @@ pub struct Operation {
     #[serde(skip_serializing_if = "Option::is_none", rename = "requestBody")]
     pub request_body: Option<RequestBody>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "sorted_opt_map")]
     pub responses: Option<HashMap<String, Response>>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub security: Vec<HashMap<String, Vec<String>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deprecated: Option<bool>,
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    #[serde(serialize_with = "sorted_map")]
+    pub callbacks: HashMap<String, serde_json::Value>,
     #[serde(flatten)]
+    #[serde(serialize_with = "sorted_map")]
     pub extensions: HashMap<String, serde_json::Value>,
 }
 
 /// OpenAPI parameter
+///
+/// A `$ref` parameter carries only `reference`; `name` and `in_` are empty.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Parameter {
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$ref", default)]
+    pub reference: Option<String>,
+    #[serde(skip_serializing_if = "String::is_empty", default)]
     pub name: String,
-    #[serde(rename = "in")]
+    #[serde(skip_serializing_if = "String::is_empty", rename = "in", default)]
     pub in_: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -152,22 +170,33 @@ pub struct Parameter {
 pub struct RequestBody {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    #[serde(serialize_with = "sorted_map")]
     pub content: HashMap<String, MediaType>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<bool>,
     #[serde(flatten)]
+    #[serde(serialize_with = "sorted_map")]
     pub extensions: HashMap<String, serde_json::Value>,
 }
 
 /// OpenAPI response
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Response {
-    pub description: String,
+    /// Required by OpenAPI, except on `$ref` responses where it's omitted
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "sorted_opt_map")]
     pub content: Option<HashMap<String, MediaType>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "sorted_opt_map")]
     pub headers: Option<HashMap<String, Header>>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    #[serde(serialize_with = "sorted_opt_map")]
+    pub links: Option<HashMap<String, serde_json::Value>>,
     #[serde(flatten)]
+    #[serde(serialize_with = "sorted_map")]
     pub extensions: HashMap<String, serde_json::Value>,
 }
 
@@ -179,12 +208,17 @@ pub struct MediaType {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub example: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "sorted_opt_map")]
     pub examples: Option<HashMap<String, Example>>,
 }
 
 /// Example object
+///
+/// A `$ref` example carries only `reference`.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Example {
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$ref", default)]
+    pub reference: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub summary: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -196,8 +230,12 @@ pub struct Example {
 }
 
 /// Header object
+///
+/// A `$ref` header carries only `reference`.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Header {
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$ref", default)]
+    pub reference: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -208,25 +246,38 @@ pub struct Header {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Components {
     #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    #[serde(serialize_with = "sorted_map")]
     pub schemas: HashMap<String, serde_json::Value>,
     #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    #[serde(serialize_with = "sorted_map")]
     pub responses: HashMap<String, Response>,
     #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    #[serde(serialize_with = "sorted_map")]
     pub parameters: HashMap<String, Parameter>,
     #[serde(
         skip_serializing_if = "HashMap::is_empty",
         default,
         rename = "requestBodies"
     )]
+    #[serde(serialize_with = "sorted_map")]
     pub request_bodies: HashMap<String, RequestBody>,
     #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    #[serde(serialize_with = "sorted_map")]
     pub headers: HashMap<String, Header>,
     #[serde(
         skip_serializing_if = "HashMap::is_empty",
         default,
         rename = "securitySchemes"
     )]
+    #[serde(serialize_with = "sorted_map")]
     pub security_schemes: HashMap<String, SecurityScheme>,
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    #[serde(serialize_with = "sorted_map")]
+    pub examples: HashMap<String, Example>,
+    /// Link objects, kept verbatim apart from `$ref` rewriting
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    #[serde(serialize_with = "sorted_map")]
+    pub links: HashMap<String, serde_json::Value>,
 }
 
 /// Security scheme
@@ -254,6 +305,37 @@ pub struct Tag {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
+    pub external_docs: Option<ExternalDocs>,
     #[serde(flatten)]
+    #[serde(serialize_with = "sorted_map")]
     pub extensions: HashMap<String, serde_json::Value>,
 }
+
+/// External documentation reference
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExternalDocs {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Serializes a map in key order so merged output is byte-stable
+fn sorted_map<S, V>(map: &HashMap<String, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    V: Serialize,
+{
+    serializer.collect_map(map.iter().collect::<BTreeMap<_, _>>())
+}
+
+fn sorted_opt_map<S, V>(map: &Option<HashMap<String, V>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    V: Serialize,
+{
+    match map {
+        Some(map) => sorted_map(map, serializer),
+        None => serializer.serialize_none(),
+    }
+}
@@ -0,0 +1,271 @@
+//! Minimal MessagePack encoding of JSON values, used by the binary manifest format.
+
+use crate::errors::{Error, Result};
+use serde_json::{Map, Number, Value};
+
+/// Maximum container nesting accepted when decoding
+const MAX_DEPTH: usize = 128;
+
+/// Encodes a JSON value as MessagePack
+pub(crate) fn encode(value: &Value, out: &mut Vec<u8>) {
+    match value {
+        Value::Null => out.push(0xc0),
+        Value::Bool(false) => out.push(0xc2),
+        Value::Bool(true) => out.push(0xc3),
+        Value::Number(n) => {
+            if let Some(u) = n.as_u64() {
+                encode_uint(u, out);
+            } else if let Some(i) = n.as_i64() {
+                encode_int(i, out);
+            } else {
+                out.push(0xcb);
+                out.extend_from_slice(&n.as_f64().unwrap_or_default().to_be_bytes());
+            }
+        }
+        Value::String(s) => {
+            encode_len(s.len(), [0xa0, 0xd9, 0xda, 0xdb], 32, out);
+            out.extend_from_slice(s.as_bytes());
+        }
+        Value::Array(items) => {
+            encode_len(items.len(), [0x90, 0x00, 0xdc, 0xdd], 16, out);
+            for item in items {
+                encode(item, out);
+            }
+        }
+        Value::Object(map) => {
+            encode_len(map.len(), [0x80, 0x00, 0xde, 0xdf], 16, out);
+            for (key, item) in map {
+                encode(&Value::String(key.clone()), out);
+                encode(item, out);
+            }
+        }
+    }
+}
+
+fn encode_uint(u: u64, out: &mut Vec<u8>) {
+    if u < 0x80 {
+        out.push(u as u8);
+    } else if let Ok(u) = u8::try_from(u) {
+        out.extend_from_slice(&[0xcc, u]);
+    } else if let Ok(u) = u16::try_from(u) {
+        out.push(0xcd);
+        out.extend_from_slice(&u.to_be_bytes());
+    } else if let Ok(u) = u32::try_from(u) {
+        out.push(0xce);
+        out.extend_from_slice(&u.to_be_bytes());
+    } else {
+        out.push(0xcf);
+        out.extend_from_slice(&u.to_be_bytes());
+    }
+}
+
+fn encode_int(i: i64, out: &mut Vec<u8>) {
+    if i >= -32 {
+        out.push(i as u8);
+    } else if let Ok(i) = i8::try_from(i) {
+        out.extend_from_slice(&[0xd0, i as u8]);
+    } else if let Ok(i) = i16::try_from(i) {
+        out.push(0xd1);
+        out.extend_from_slice(&i.to_be_bytes());
+    } else if let Ok(i) = i32::try_from(i) {
+        out.push(0xd2);
+        out.extend_from_slice(&i.to_be_bytes());
+    } else {
+        out.push(0xd3);
+        out.extend_from_slice(&i.to_be_bytes());
+    }
+}
+
+/// Writes a length header: fix form below `fix_limit`, then 8/16/32-bit forms
+///
+/// A zero `markers[1]` means the type has no 8-bit form.
+fn encode_len(len: usize, markers: [u8; 4], fix_limit: usize, out: &mut Vec<u8>) {
+    if len < fix_limit {
+        out.push(markers[0] | len as u8);
+    } else if markers[1] != 0 && len <= u8::MAX as usize {
+        out.extend_from_slice(&[markers[1], len as u8]);
+    } else if len <= u16::MAX as usize {
+        out.push(markers[2]);
+        out.extend_from_slice(&(len as u16).to_be_bytes());
+    } else {
+        out.push(markers[3]);
+        out.extend_from_slice(&(len as u32).to_be_bytes());
+    }
+}
+
+/// Decodes a single MessagePack value that must span all of `data`
+pub(crate) fn decode(data: &[u8]) -> Result<Value> {
+    let mut reader = Reader { data, pos: 0 };
+    let value = reader.value(0)?;
+    if reader.pos != data.len() {
+        return Err(Error::invalid_manifest("trailing bytes after msgpack body"));
+    }
+    Ok(value)
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|&end| end <= self.data.len())
+            .ok_or_else(|| Error::invalid_manifest("truncated msgpack body"))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.take(N)?.try_into().expect("take returns N bytes"))
+    }
+
+    fn len(&mut self, width: usize) -> Result<usize> {
+        Ok(match width {
+            1 => self.array::<1>()?[0] as usize,
+            2 => u16::from_be_bytes(self.array()?) as usize,
+            _ => u32::from_be_bytes(self.array()?) as usize,
+        })
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value> {
+        if depth > MAX_DEPTH {
+            return Err(Error::invalid_manifest("msgpack nesting too deep"));
+        }
+
+        let marker = self.array::<1>()?[0];
+        let value = match marker {
+            0x00..=0x7f => Value::from(marker),
+            0x80..=0x8f => self.map((marker & 0x0f) as usize, depth)?,
+            0x90..=0x9f => self.list((marker & 0x0f) as usize, depth)?,
+            0xa0..=0xbf => self.string((marker & 0x1f) as usize)?,
+            0xc0 => Value::Null,
+            0xc2 => Value::Bool(false),
+            0xc3 => Value::Bool(true),
+            0xca => float(f32::from_be_bytes(self.array()?) as f64)?,
+            0xcb => float(f64::from_be_bytes(self.array()?))?,
+            0xcc => Value::from(self.array::<1>()?[0]),
+            0xcd => Value::from(u16::from_be_bytes(self.array()?)),
+            0xce => Value::from(u32::from_be_bytes(self.array()?)),
+            0xcf => Value::from(u64::from_be_bytes(self.array()?)),
+            0xd0 => Value::from(self.array::<1>()?[0] as i8),
+            0xd1 => Value::from(i16::from_be_bytes(self.array()?)),
+            0xd2 => Value::from(i32::from_be_bytes(self.array()?)),
+            0xd3 => Value::from(i64::from_be_bytes(self.array()?)),
+            0xd9 => {
+                let len = self.len(1)?;
+                self.string(len)?
+            }
+            0xda => {
+                let len = self.len(2)?;
+                self.string(len)?
+            }
+            0xdb => {
+                let len = self.len(4)?;
+                self.string(len)?
+            }
+            0xdc => {
+                let len = self.len(2)?;
+                self.list(len, depth)?
+            }
+            0xdd => {
+                let len = self.len(4)?;
+                self.list(len, depth)?
+            }
+            0xde => {
+                let len = self.len(2)?;
+                self.map(len, depth)?
+            }
+            0xdf => {
+                let len = self.len(4)?;
+                self.map(len, depth)?
+            }
+            0xe0..=0xff => Value::from(marker as i8),
+            other => {
+                return Err(Error::invalid_manifest(format!(
+                    "unsupported msgpack type 0x{other:02x}"
+                )))
+            }
+        };
+        Ok(value)
+    }
+
+    fn string(&mut self, len: usize) -> Result<Value> {
+        let bytes = self.take(len)?;
+        let s = std::str::from_utf8(bytes)
+            .map_err(|_| Error::invalid_manifest("msgpack string is not UTF-8"))?;
+        Ok(Value::String(s.to_string()))
+    }
+
+    fn list(&mut self, len: usize, depth: usize) -> Result<Value> {
+        // Every element takes at least one byte; don't trust `len` for allocation
+        let mut items = Vec::with_capacity(len.min(self.data.len() - self.pos));
+        for _ in 0..len {
+            items.push(self.value(depth + 1)?);
+        }
+        Ok(Value::Array(items))
+    }
+
+    fn map(&mut self, len: usize, depth: usize) -> Result<Value> {
+        let mut map = Map::new();
+        for _ in 0..len {
+            let Value::String(key) = self.value(depth + 1)? else {
+                return Err(Error::invalid_manifest("msgpack map key is not a string"));
+            };
+            let value = self.value(depth + 1)?;
+            map.insert(key, value);
+        }
+        Ok(Value::Object(map))
+    }
+}
+
+fn float(f: f64) -> Result<Value> {
+    Number::from_f64(f)
+        .map(Value::Number)
+        .ok_or_else(|| Error::invalid_manifest("msgpack float is not finite"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_msgpack_round_trip() {
+        let value = json!({
+            "null": null,
+            "flags": [true, false],
+            "ints": [0, 127, 128, 255, 256, 65535, 65536, 4294967296u64, -1, -32, -33, -128, -129, -32769, -2147483649i64],
+            "float": 1.5,
+            "short": "farp",
+            "long": "x".repeat(300),
+            "many": (0..20).collect::<Vec<_>>(),
+            "nested": {"a": {"b": {"c": []}}}
+        });
+
+        let mut encoded = Vec::new();
+        encode(&value, &mut encoded);
+        assert_eq!(decode(&encoded).unwrap(), value);
+
+        // Known encodings from the spec
+        let mut out = Vec::new();
+        encode(&json!({"a": [1, -1]}), &mut out);
+        assert_eq!(out, [0x81, 0xa1, b'a', 0x92, 0x01, 0xff]);
+    }
+
+    #[test]
+    fn test_msgpack_rejects_malformed() {
+        let mut encoded = Vec::new();
+        encode(&json!({"key": "value"}), &mut encoded);
+
+        assert!(decode(&encoded[..encoded.len() - 1]).is_err());
+        assert!(decode(&[encoded.as_slice(), &[0xc0]].concat()).is_err());
+        assert!(decode(&[0xc1]).is_err());
+        assert!(decode(&[0xdd, 0xff, 0xff, 0xff, 0xff]).is_err());
+        assert!(decode(&vec![0x91; 1000]).is_err());
+    }
+}
@@ -0,0 +1,238 @@
+//! JSON Patch (RFC 6902) support for publishing schema deltas.
+
+use crate::errors::{Error, Result};
+use serde_json::Value;
+
+/// Applies a JSON Patch document to `doc`
+///
+/// Operations are applied in order; on error `doc` may be partially patched,
+/// so callers should patch a copy.
+///
+/// # Examples
+///
+/// ```
+/// use farp::patch::apply_patch;
+/// use serde_json::json;
+///
+/// let mut doc = json!({"info": {"version": "1.0.0"}});
+/// apply_patch(&mut doc, &[json!({"op": "replace", "path": "/info/version", "value": "1.1.0"})]).unwrap();
+/// assert_eq!(doc["info"]["version"], "1.1.0");
+/// ```
+pub fn apply_patch(doc: &mut Value, patch: &[Value]) -> Result<()> {
+    for (i, operation) in patch.iter().enumerate() {
+        apply_operation(doc, operation)
+            .map_err(|e| Error::validation("patch", format!("operation {i}: {e}")))?;
+    }
+    Ok(())
+}
+
+fn apply_operation(doc: &mut Value, operation: &Value) -> std::result::Result<(), String> {
+    let op = operation
+        .get("op")
+        .and_then(|v| v.as_str())
+        .ok_or("missing 'op'")?;
+    let path = parse_pointer(member(operation, "path")?)?;
+
+    match op {
+        "add" => add(doc, &path, value(operation)?.clone()),
+        "remove" => remove(doc, &path).map(|_| ()),
+        "replace" => {
+            remove(doc, &path)?;
+            add(doc, &path, value(operation)?.clone())
+        }
+        "move" => {
+            let from = parse_pointer(member(operation, "from")?)?;
+            if path.len() > from.len() && path[..from.len()] == from[..] {
+                return Err("cannot move a value into one of its children".to_string());
+            }
+            let moved = remove(doc, &from)?;
+            add(doc, &path, moved)
+        }
+        "copy" => {
+            let from = parse_pointer(member(operation, "from")?)?;
+            let copied = lookup(doc, &from)?.clone();
+            add(doc, &path, copied)
+        }
+        "test" => {
+            if lookup(doc, &path)? == value(operation)? {
+                Ok(())
+            } else {
+                Err("test failed".to_string())
+            }
+        }
+        other => Err(format!("unknown op '{other}'")),
+    }
+}
+
+fn member<'a>(operation: &'a Value, name: &str) -> std::result::Result<&'a str, String> {
+    operation
+        .get(name)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| format!("missing '{name}'"))
+}
+
+fn value(operation: &Value) -> std::result::Result<&Value, String> {
+    operation
+        .get("value")
+        .ok_or_else(|| "missing 'value'".to_string())
+}
+
+/// Splits an RFC 6901 pointer into unescaped reference tokens
+fn parse_pointer(pointer: &str) -> std::result::Result<Vec<String>, String> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    let rest = pointer
+        .strip_prefix('/')
+        .ok_or_else(|| format!("invalid JSON pointer: {pointer}"))?;
+    Ok(rest
+        .split('/')
+        .map(|t| t.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+fn array_index(token: &str, len: usize, allow_end: bool) -> std::result::Result<usize, String> {
+    if allow_end && token == "-" {
+        return Ok(len);
+    }
+    let index: usize = token
+        .parse()
+        .map_err(|_| format!("invalid array index: {token}"))?;
+    let max = if allow_end {
+        len
+    } else {
+        len.saturating_sub(1)
+    };
+    if index > max || (!allow_end && len == 0) {
+        return Err(format!("array index out of bounds: {index}"));
+    }
+    Ok(index)
+}
+
+fn lookup<'a>(doc: &'a Value, path: &[String]) -> std::result::Result<&'a Value, String> {
+    let mut current = doc;
+    for token in path {
+        current = match current {
+            Value::Object(obj) => obj.get(token),
+            Value::Array(arr) => arr.get(array_index(token, arr.len(), false)?),
+            _ => None,
+        }
+        .ok_or_else(|| format!("path not found: /{}", path.join("/")))?;
+    }
+    Ok(current)
+}
+
+fn lookup_mut<'a>(
+    doc: &'a mut Value,
+    path: &[String],
+) -> std::result::Result<&'a mut Value, String> {
+    let mut current = doc;
+    for token in path {
+        current = match current {
+            Value::Object(obj) => obj.get_mut(token),
+            Value::Array(arr) => {
+                let index = array_index(token, arr.len(), false)?;
+                arr.get_mut(index)
+            }
+            _ => None,
+        }
+        .ok_or_else(|| format!("path not found: /{}", path.join("/")))?;
+    }
+    Ok(current)
+}
+
+fn add(doc: &mut Value, path: &[String], value: Value) -> std::result::Result<(), String> {
+    let Some((last, parent)) = path.split_last() else {
+        *doc = value;
+        return Ok(());
+    };
+
+    match lookup_mut(doc, parent)? {
+        Value::Object(obj) => {
+            obj.insert(last.clone(), value);
+            Ok(())
+        }
+        Value::Array(arr) => {
+            let index = array_index(last, arr.len(), true)?;
+            arr.insert(index, value);
+            Ok(())
+        }
+        _ => Err(format!("parent of /{} is not a container", path.join("/"))),
+    }
+}
+
+fn remove(doc: &mut Value, path: &[String]) -> std::result::Result<Value, String> {
+    let (last, parent) = path.split_last().ok_or("cannot remove the document root")?;
+
+    match lookup_mut(doc, parent)? {
+        Value::Object(obj) => obj
+            .remove(last)
+            .ok_or_else(|| format!("path not found: /{}", path.join("/"))),
+        Value::Array(arr) => {
+            let index = array_index(last, arr.len(), false)?;
+            Ok(arr.remove(index))
+        }
+        _ => Err(format!("parent of /{} is not a container", path.join("/"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_apply_patch_operations() {
+        let mut doc = json!({
+            "info": {"title": "API", "version": "1.0.0"},
+            "tags": ["a", "c"],
+            "paths": {"/users": {"get": {}}}
+        });
+
+        apply_patch(
+            &mut doc,
+            &[
+                json!({"op": "test", "path": "/info/version", "value": "1.0.0"}),
+                json!({"op": "replace", "path": "/info/version", "value": "1.1.0"}),
+                json!({"op": "add", "path": "/tags/1", "value": "b"}),
+                json!({"op": "add", "path": "/tags/-", "value": "d"}),
+                json!({"op": "copy", "from": "/paths/~1users", "path": "/paths/~1admins"}),
+                json!({"op": "move", "from": "/info/title", "path": "/info/summary"}),
+                json!({"op": "remove", "path": "/tags/0"}),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            doc,
+            json!({
+                "info": {"summary": "API", "version": "1.1.0"},
+                "tags": ["b", "c", "d"],
+                "paths": {"/users": {"get": {}}, "/admins": {"get": {}}}
+            })
+        );
+    }
+
+    #[test]
+    fn test_apply_patch_errors() {
+        let mut doc = json!({"tags": []});
+
+        assert!(apply_patch(&mut doc, &[json!({"op": "remove", "path": "/missing"})]).is_err());
+        assert!(apply_patch(
+            &mut doc,
+            &[json!({"op": "add", "path": "/tags/1", "value": 1})]
+        )
+        .is_err());
+        assert!(apply_patch(
+            &mut doc,
+            &[json!({"op": "test", "path": "/tags", "value": [1]})]
+        )
+        .is_err());
+        assert!(apply_patch(&mut doc, &[json!({"op": "frobnicate", "path": "/tags"})]).is_err());
+        assert!(apply_patch(
+            &mut doc,
+            &[json!({"op": "add", "path": "tags", "value": 1})]
+        )
+        .is_err());
+    }
+}
@@ -2,15 +2,21 @@
 
 use crate::errors::{Error, Result};
 use crate::provider::{Application, SchemaProvider};
-use crate::types::SchemaType;
+use crate::types::{GraphQLMetadata, SchemaType};
+use async_graphql_parser::types::{ServiceDocument, TypeKind, TypeSystemDefinition};
 use async_trait::async_trait;
 
 /// GraphQL schema provider
 ///
-/// Generates GraphQL Schema Definition Language (SDL) from application
+/// Generates GraphQL Schema Definition Language (SDL) from application.
+/// The SDL is taken from `Application::routes()` when it holds a `String`
+/// (or `&'static str`), otherwise from [`with_sdl`](Self::with_sdl).
 pub struct GraphQLProvider {
     spec_version: String,
     endpoint: Option<String>,
+    sdl: Option<String>,
+    complexity_limit: Option<i32>,
+    depth_limit: Option<i32>,
 }
 
 impl GraphQLProvider {
@@ -19,8 +25,50 @@ impl GraphQLProvider {
         Self {
             spec_version: spec_version.into(),
             endpoint,
+            sdl: None,
+            complexity_limit: None,
+            depth_limit: None,
         }
     }
+
+    /// Sets the SDL used when the application doesn't provide one
+    pub fn with_sdl(mut self, sdl: impl Into<String>) -> Self {
+        self.sdl = Some(sdl.into());
+        self
+    }
+
+    /// Sets the query complexity limit reported in the metadata
+    pub fn with_complexity_limit(mut self, limit: i32) -> Self {
+        self.complexity_limit = Some(limit);
+        self
+    }
+
+    /// Sets the query depth limit reported in the metadata
+    pub fn with_depth_limit(mut self, limit: i32) -> Self {
+        self.depth_limit = Some(limit);
+        self
+    }
+
+    /// Resolves the SDL for an application
+    fn resolve_sdl(&self, app: &dyn Application) -> String {
+        let routes = app.routes();
+        if let Some(sdl) = routes.downcast_ref::<String>() {
+            return sdl.clone();
+        }
+        if let Some(sdl) = routes.downcast_ref::<&'static str>() {
+            return sdl.to_string();
+        }
+        if let Some(sdl) = &self.sdl {
+            return sdl.clone();
+        }
+
+        let field: String = app
+            .name()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        format!("type Query {{\n  {field}: String\n}}")
+    }
 }
 
 impl Default for GraphQLProvider {
@@ -36,10 +84,27 @@ impl SchemaProvider for GraphQLProvider {
     }
 
     async fn generate(&self, app: &dyn Application) -> Result<serde_json::Value> {
+        let sdl = self.resolve_sdl(app);
+        let document = parse_sdl(&sdl)?;
+        let counts = count_root_fields(&document);
+
+        let metadata = GraphQLMetadata {
+            federation: None,
+            subscriptions_enabled: counts.subscriptions > 0,
+            subscription_protocol: None,
+            complexity_limit: self.complexity_limit,
+            depth_limit: self.depth_limit,
+        };
+
         let schema = serde_json::json!({
-            "version": self.spec_version,
-            "schema": format!("type Query {{\n  {}: String\n}}", app.name()),
-            "types": []
+            "sdl": sdl,
+            "metadata": {
+                "version": self.spec_version,
+                "query_count": counts.queries,
+                "mutation_count": counts.mutations,
+                "subscription_count": counts.subscriptions,
+                "graphql": metadata
+            }
         });
 
         Ok(schema)
@@ -50,6 +115,13 @@ impl SchemaProvider for GraphQLProvider {
             return Err(Error::validation_failed("schema must be an object"));
         }
 
+        let sdl = schema
+            .get("sdl")
+            .and_then(|s| s.as_str())
+            .ok_or_else(|| Error::validation_failed("missing 'sdl' field"))?;
+
+        parse_sdl(sdl)?;
+
         Ok(())
     }
 
@@ -60,6 +132,68 @@ impl SchemaProvider for GraphQLProvider {
     fn endpoint(&self) -> Option<String> {
         self.endpoint.clone()
     }
+
+    fn content_type(&self) -> String {
+        "application/graphql".to_string()
+    }
+}
+
+/// Parses an SDL document, mapping syntax errors to validation failures
+fn parse_sdl(sdl: &str) -> Result<ServiceDocument> {
+    async_graphql_parser::parse_schema(sdl)
+        .map_err(|e| Error::validation_failed(format!("invalid GraphQL SDL: {e}")))
+}
+
+/// Number of fields on each root operation type
+#[derive(Debug, Default, PartialEq, Eq)]
+struct RootFieldCounts {
+    queries: usize,
+    mutations: usize,
+    subscriptions: usize,
+}
+
+/// Counts root fields, honoring a `schema { ... }` definition that renames the roots
+fn count_root_fields(document: &ServiceDocument) -> RootFieldCounts {
+    let mut query_root = "Query".to_string();
+    let mut mutation_root = "Mutation".to_string();
+    let mut subscription_root = "Subscription".to_string();
+
+    for definition in &document.definitions {
+        if let TypeSystemDefinition::Schema(schema) = definition {
+            if let Some(name) = &schema.node.query {
+                query_root = name.node.to_string();
+            }
+            if let Some(name) = &schema.node.mutation {
+                mutation_root = name.node.to_string();
+            }
+            if let Some(name) = &schema.node.subscription {
+                subscription_root = name.node.to_string();
+            }
+        }
+    }
+
+    let mut counts = RootFieldCounts::default();
+    for definition in &document.definitions {
+        let TypeSystemDefinition::Type(ty) = definition else {
+            continue;
+        };
+        let TypeKind::Object(object) = &ty.node.kind else {
+            continue;
+        };
+
+        // Extensions (`extend type Query`) add to the same root
+        let name = ty.node.name.node.as_str();
+        let fields = object.fields.len();
+        if name == query_root {
+            counts.queries += fields;
+        } else if name == mutation_root {
+            counts.mutations += fields;
+        } else if name == subscription_root {
+            counts.subscriptions += fields;
+        }
+    }
+
+    counts
 }
 
 #[cfg(test)]
@@ -82,6 +216,22 @@ mod tests {
         }
     }
 
+    struct SdlApp(&'static str);
+
+    impl Application for SdlApp {
+        fn name(&self) -> &str {
+            "sdl-app"
+        }
+
+        fn version(&self) -> &str {
+            "1.0.0"
+        }
+
+        fn routes(&self) -> Box<dyn std::any::Any + Send + Sync> {
+            Box::new(self.0.to_string())
+        }
+    }
+
     #[tokio::test]
     async fn test_graphql_provider() {
         let provider = GraphQLProvider::default();
@@ -89,5 +239,50 @@ mod tests {
 
         let schema = provider.generate(&app).await.unwrap();
         provider.validate(&schema).unwrap();
+        assert_eq!(provider.content_type(), "application/graphql");
+    }
+
+    #[tokio::test]
+    async fn test_generate_from_sdl() {
+        let provider = GraphQLProvider::default()
+            .with_complexity_limit(500)
+            .with_depth_limit(10);
+        let app = SdlApp(
+            r#"
+            type User { id: ID! name: String }
+            type Query { user(id: ID!): User users: [User!]! }
+            extend type Query { me: User }
+            type Mutation { createUser(name: String!): User }
+            type Subscription { userCreated: User }
+            "#,
+        );
+
+        let schema = provider.generate(&app).await.unwrap();
+        provider.validate(&schema).unwrap();
+
+        let metadata = &schema["metadata"];
+        assert_eq!(metadata["query_count"], 3);
+        assert_eq!(metadata["mutation_count"], 1);
+        assert_eq!(metadata["subscription_count"], 1);
+
+        let graphql: GraphQLMetadata = serde_json::from_value(metadata["graphql"].clone()).unwrap();
+        assert!(graphql.subscriptions_enabled);
+        assert_eq!(graphql.complexity_limit, Some(500));
+        assert_eq!(graphql.depth_limit, Some(10));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_malformed_sdl() {
+        let provider = GraphQLProvider::default();
+
+        let err = provider
+            .generate(&SdlApp("type Query { user(id: ID!: User }"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::ValidationFailed(_)));
+
+        let schema = serde_json::json!({"sdl": "type Query {"});
+        assert!(provider.validate(&schema).is_err());
+        assert!(provider.validate(&serde_json::json!({})).is_err());
     }
 }
@@ -1,4 +1,11 @@
 //! Schema provider implementations
+//!
+//! Each provider is gated behind its `providers-*` feature. Use
+//! [`register_all`] to register every enabled provider with its default
+//! configuration in one call.
+
+use crate::provider::{register_provider, ProviderRegistry, SchemaProvider};
+use std::sync::Arc;
 
 #[cfg(feature = "providers-openapi")]
 pub mod openapi;
@@ -20,3 +27,62 @@ pub mod avro;
 
 #[cfg(feature = "providers-thrift")]
 pub mod thrift;
+
+/// Returns a default-configured instance of every enabled built-in provider
+pub fn builtin_providers() -> Vec<Arc<dyn SchemaProvider>> {
+    vec![
+        #[cfg(feature = "providers-openapi")]
+        Arc::new(openapi::OpenAPIProvider::default()),
+        #[cfg(feature = "providers-asyncapi")]
+        Arc::new(asyncapi::AsyncAPIProvider::default()),
+        #[cfg(feature = "providers-grpc")]
+        Arc::new(grpc::GRPCProvider::default()),
+        #[cfg(feature = "providers-graphql")]
+        Arc::new(graphql::GraphQLProvider::default()),
+        #[cfg(feature = "providers-orpc")]
+        Arc::new(orpc::ORPCProvider::default()),
+        #[cfg(feature = "providers-avro")]
+        Arc::new(avro::AvroProvider::default()),
+        #[cfg(feature = "providers-thrift")]
+        Arc::new(thrift::ThriftProvider::default()),
+    ]
+}
+
+/// Registers every enabled built-in provider into `registry`
+///
+/// Existing providers for the same schema types are replaced.
+pub fn register_builtin(registry: &ProviderRegistry) {
+    for provider in builtin_providers() {
+        registry.register(provider);
+    }
+}
+
+/// Registers every enabled built-in provider into the global registry
+///
+/// Existing providers for the same schema types are replaced.
+pub fn register_all() {
+    for provider in builtin_providers() {
+        register_provider(provider);
+    }
+}
+
+#[cfg(all(test, feature = "providers-openapi", feature = "providers-asyncapi"))]
+mod tests {
+    use super::*;
+    use crate::provider::list_providers;
+    use crate::types::SchemaType;
+
+    #[test]
+    fn test_register_all() {
+        let registry = ProviderRegistry::new();
+        register_builtin(&registry);
+        assert_eq!(registry.list().len(), builtin_providers().len());
+        assert!(registry.has(SchemaType::OpenAPI));
+        assert!(registry.has(SchemaType::AsyncAPI));
+
+        register_all();
+        let registered = list_providers();
+        assert!(registered.contains(&SchemaType::OpenAPI));
+        assert!(registered.contains(&SchemaType::AsyncAPI));
+    }
+}
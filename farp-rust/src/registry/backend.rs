@@ -0,0 +1,207 @@
+//! Typed views of [`RegistryConfig::backend_config`](crate::registry::RegistryConfig)
+//!
+//! Backends read their settings from an untyped key/value map. Parsing it
+//! through a [`BackendConfig`] validates every key up front, so an unknown
+//! (e.g. misspelled) key or a wrongly typed value fails at startup with
+//! `Error::Validation` naming the offending `backend_config.<key>`.
+
+use crate::errors::{Error, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Settings a registry backend parses from `backend_config`
+pub trait BackendConfig: Sized {
+    /// Parses and validates the backend's entries in `map`
+    fn from_map(map: &HashMap<String, Value>) -> Result<Self>;
+}
+
+/// Redis backend settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedisConfig {
+    /// Connection URL (`redis://` or `rediss://`)
+    pub url: String,
+    /// Maximum pooled connections (default 10)
+    pub pool_size: u32,
+    /// Whether to connect over TLS (defaults to true for `rediss://` URLs)
+    pub tls: bool,
+}
+
+impl BackendConfig for RedisConfig {
+    fn from_map(map: &HashMap<String, Value>) -> Result<Self> {
+        reject_unknown(map, &["url", "pool_size", "tls"])?;
+        let url = required_str(map, "url")?;
+        let secure = url.starts_with("rediss://");
+        if !secure && !url.starts_with("redis://") {
+            return Err(Error::validation(
+                "backend_config.url",
+                format!("{url} is not a redis:// or rediss:// URL"),
+            ));
+        }
+
+        let pool_size = optional_u64(map, "pool_size")?.unwrap_or(10);
+        let pool_size = u32::try_from(pool_size)
+            .ok()
+            .filter(|size| *size > 0)
+            .ok_or_else(|| {
+                Error::validation(
+                    "backend_config.pool_size",
+                    format!("{pool_size} is not a valid pool size"),
+                )
+            })?;
+
+        Ok(Self {
+            url: url.to_string(),
+            pool_size,
+            tls: optional_bool(map, "tls")?.unwrap_or(secure),
+        })
+    }
+}
+
+/// DynamoDB backend settings, read by
+/// [`DynamoRegistry::from_config`](crate::registry::dynamo::DynamoRegistry::from_config)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynamoConfig {
+    /// Table holding manifests and schemas
+    pub table_name: String,
+    /// AWS region; the SDK's default chain applies when unset
+    pub region: Option<String>,
+    /// Endpoint override, e.g. a local DynamoDB
+    pub endpoint: Option<String>,
+}
+
+impl BackendConfig for DynamoConfig {
+    fn from_map(map: &HashMap<String, Value>) -> Result<Self> {
+        reject_unknown(map, &["table_name", "region", "endpoint"])?;
+        Ok(Self {
+            table_name: required_str(map, "table_name")?.to_string(),
+            region: optional_str(map, "region")?.map(str::to_string),
+            endpoint: optional_str(map, "endpoint")?.map(str::to_string),
+        })
+    }
+}
+
+/// Fails on the first key (in sorted order) not in `known`
+fn reject_unknown(map: &HashMap<String, Value>, known: &[&str]) -> Result<()> {
+    let mut keys: Vec<&String> = map.keys().collect();
+    keys.sort();
+    match keys.into_iter().find(|key| !known.contains(&key.as_str())) {
+        Some(key) => Err(Error::validation(
+            format!("backend_config.{key}"),
+            format!("unknown key; expected one of {}", known.join(", ")),
+        )),
+        None => Ok(()),
+    }
+}
+
+fn required_str<'a>(map: &'a HashMap<String, Value>, key: &str) -> Result<&'a str> {
+    optional_str(map, key)?
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| Error::validation(format!("backend_config.{key}"), "is required"))
+}
+
+fn optional_str<'a>(map: &'a HashMap<String, Value>, key: &str) -> Result<Option<&'a str>> {
+    typed(map, key, "a string", Value::as_str)
+}
+
+fn optional_u64(map: &HashMap<String, Value>, key: &str) -> Result<Option<u64>> {
+    typed(map, key, "a non-negative integer", Value::as_u64)
+}
+
+fn optional_bool(map: &HashMap<String, Value>, key: &str) -> Result<Option<bool>> {
+    typed(map, key, "a boolean", Value::as_bool)
+}
+
+/// Reads `key` with `extract`, treating a missing key or `null` as unset
+fn typed<'a, T>(
+    map: &'a HashMap<String, Value>,
+    key: &str,
+    expected: &str,
+    extract: impl Fn(&'a Value) -> Option<T>,
+) -> Result<Option<T>> {
+    match map.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => extract(value).map(Some).ok_or_else(|| {
+            Error::validation(
+                format!("backend_config.{key}"),
+                format!("must be {expected}, got {value}"),
+            )
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn map(value: Value) -> HashMap<String, Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_redis_config() {
+        let config = RedisConfig::from_map(&map(json!({
+            "url": "rediss://cache.internal:6380",
+            "pool_size": 32
+        })))
+        .unwrap();
+        assert_eq!(
+            config,
+            RedisConfig {
+                url: "rediss://cache.internal:6380".to_string(),
+                pool_size: 32,
+                tls: true,
+            }
+        );
+
+        let config = RedisConfig::from_map(&map(json!({"url": "redis://localhost"}))).unwrap();
+        assert_eq!(config.pool_size, 10);
+        assert!(!config.tls);
+    }
+
+    #[test]
+    fn test_redis_config_invalid() {
+        let field = |value: Value| match RedisConfig::from_map(&map(value)).unwrap_err() {
+            Error::Validation { field, .. } => field,
+            other => panic!("unexpected error {other}"),
+        };
+        assert_eq!(field(json!({"pool_size": 4})), "backend_config.url");
+        assert_eq!(field(json!({"url": "http://cache"})), "backend_config.url");
+        assert_eq!(
+            field(json!({"url": "redis://cache", "pool_size": "big"})),
+            "backend_config.pool_size"
+        );
+        assert_eq!(
+            field(json!({"url": "redis://cache", "pool_size": 0})),
+            "backend_config.pool_size"
+        );
+        assert_eq!(
+            field(json!({"url": "redis://cache", "tls": "yes"})),
+            "backend_config.tls"
+        );
+        assert_eq!(
+            field(json!({"url": "redis://cache", "pool_sise": 4})),
+            "backend_config.pool_sise"
+        );
+    }
+
+    #[test]
+    fn test_dynamo_config() {
+        let config = DynamoConfig::from_map(&map(json!({
+            "table_name": "farp",
+            "endpoint": "http://localhost:8000"
+        })))
+        .unwrap();
+        assert_eq!(
+            config,
+            DynamoConfig {
+                table_name: "farp".to_string(),
+                region: None,
+                endpoint: Some("http://localhost:8000".to_string()),
+            }
+        );
+
+        assert!(DynamoConfig::from_map(&map(json!({"region": "eu-west-1"}))).is_err());
+        assert!(DynamoConfig::from_map(&map(json!({"table_name": "farp", "table": "x"}))).is_err());
+    }
+}
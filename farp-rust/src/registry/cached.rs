@@ -0,0 +1,328 @@
+//! Read-through caching decorator for any registry.
+
+use crate::errors::Result;
+use crate::manifest::ManifestDiff;
+use crate::registry::{
+    ImportOptions, ImportReport, ManifestChangeHandler, ManifestEvent, RegistrySnapshot,
+    SchemaChangeHandler, SchemaPatch, SchemaRegistry,
+};
+use crate::types::SchemaManifest;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::io::AsyncRead;
+
+/// Registry decorator that caches manifest reads
+///
+/// `get_manifest` and `list_manifests` are served from cache for up to `ttl`;
+/// everything else goes straight to the wrapped registry. On the first read
+/// the cache subscribes to `watch_manifests` on the inner registry and drops
+/// entries as change events arrive, so other writers' updates are seen
+/// without waiting for the TTL. Registries that can't watch fall back to
+/// TTL-only expiry.
+pub struct CachedRegistry<R> {
+    inner: R,
+    ttl: Duration,
+    cache: Arc<ManifestCache>,
+    watching: tokio::sync::OnceCell<()>,
+}
+
+#[derive(Default)]
+struct ManifestCache {
+    manifests: RwLock<HashMap<String, (SchemaManifest, Instant)>>,
+    lists: RwLock<HashMap<String, (Vec<SchemaManifest>, Instant)>>,
+}
+
+impl ManifestCache {
+    /// Drops everything an event about `manifest` may have made stale
+    fn invalidate(&self, manifest: &SchemaManifest) {
+        self.manifests
+            .write()
+            .unwrap()
+            .remove(&manifest.instance_id);
+        let mut lists = self.lists.write().unwrap();
+        lists.remove(&manifest.service_name);
+        lists.remove("");
+    }
+
+    fn clear(&self) {
+        self.manifests.write().unwrap().clear();
+        self.lists.write().unwrap().clear();
+    }
+}
+
+impl<R: SchemaRegistry> CachedRegistry<R> {
+    /// Wraps `inner`, caching manifest reads for `ttl`
+    pub fn new(inner: R, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            cache: Arc::new(ManifestCache::default()),
+            watching: tokio::sync::OnceCell::new(),
+        }
+    }
+
+    /// Returns the wrapped registry
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// Drops all cached entries
+    pub fn invalidate_all(&self) {
+        self.cache.clear();
+    }
+
+    /// Subscribes to change events once, on first use
+    async fn ensure_watching(&self) {
+        self.watching
+            .get_or_init(|| async {
+                let cache = self.cache.clone();
+                let handler = Box::new(move |event: &ManifestEvent| {
+                    cache.invalidate(&event.manifest);
+                });
+                // Without a watch the TTL alone bounds staleness
+                let _ = self.inner.watch_manifests("", handler).await;
+            })
+            .await;
+    }
+
+    fn fresh(&self, cached_at: Instant) -> bool {
+        cached_at.elapsed() < self.ttl
+    }
+}
+
+#[async_trait]
+impl<R: SchemaRegistry> SchemaRegistry for CachedRegistry<R> {
+    async fn register_manifest(&self, manifest: &SchemaManifest) -> Result<()> {
+        self.inner.register_manifest(manifest).await?;
+        self.cache.invalidate(manifest);
+        Ok(())
+    }
+
+    async fn get_manifest(&self, instance_id: &str) -> Result<SchemaManifest> {
+        self.ensure_watching().await;
+
+        if let Some((manifest, cached_at)) = self.cache.manifests.read().unwrap().get(instance_id) {
+            if self.fresh(*cached_at) {
+                return Ok(manifest.clone());
+            }
+        }
+
+        let manifest = self.inner.get_manifest(instance_id).await?;
+        self.cache
+            .manifests
+            .write()
+            .unwrap()
+            .insert(instance_id.to_string(), (manifest.clone(), Instant::now()));
+        Ok(manifest)
+    }
+
+    async fn register_dry_run(&self, manifest: &SchemaManifest) -> Result<ManifestDiff> {
+        self.inner.register_dry_run(manifest).await
+    }
+
+    async fn update_manifest(&self, manifest: &SchemaManifest) -> Result<()> {
+        self.inner.update_manifest(manifest).await?;
+        self.cache.invalidate(manifest);
+        Ok(())
+    }
+
+    async fn delete_manifest(&self, instance_id: &str) -> Result<()> {
+        let cached = self
+            .cache
+            .manifests
+            .read()
+            .unwrap()
+            .get(instance_id)
+            .map(|(manifest, _)| manifest.clone());
+
+        self.inner.delete_manifest(instance_id).await?;
+        match cached {
+            Some(manifest) => self.cache.invalidate(&manifest),
+            // Unknown service: every cached list may contain the instance
+            None => self.cache.clear(),
+        }
+        Ok(())
+    }
+
+    async fn list_manifests(&self, service_name: &str) -> Result<Vec<SchemaManifest>> {
+        self.ensure_watching().await;
+
+        if let Some((manifests, cached_at)) = self.cache.lists.read().unwrap().get(service_name) {
+            if self.fresh(*cached_at) {
+                return Ok(manifests.clone());
+            }
+        }
+
+        let manifests = self.inner.list_manifests(service_name).await?;
+        self.cache.lists.write().unwrap().insert(
+            service_name.to_string(),
+            (manifests.clone(), Instant::now()),
+        );
+        Ok(manifests)
+    }
+
+    async fn publish_schema(&self, path: &str, schema: &serde_json::Value) -> Result<()> {
+        self.inner.publish_schema(path, schema).await
+    }
+
+    async fn fetch_schema(&self, path: &str) -> Result<serde_json::Value> {
+        self.inner.fetch_schema(path).await
+    }
+
+    async fn publish_schema_chunked(
+        &self,
+        path: &str,
+        reader: &mut (dyn AsyncRead + Send + Unpin),
+        total_size: u64,
+    ) -> Result<()> {
+        self.inner
+            .publish_schema_chunked(path, reader, total_size)
+            .await
+    }
+
+    async fn fetch_schema_streaming(
+        &self,
+        path: &str,
+    ) -> Result<Box<dyn AsyncRead + Send + Unpin>> {
+        self.inner.fetch_schema_streaming(path).await
+    }
+
+    async fn delete_schema(&self, path: &str) -> Result<()> {
+        self.inner.delete_schema(path).await
+    }
+
+    async fn list_schemas(&self, prefix: &str) -> Result<Vec<String>> {
+        self.inner.list_schemas(prefix).await
+    }
+
+    async fn publish_schema_delta(
+        &self,
+        path: &str,
+        base_hash: &str,
+        patch: &[serde_json::Value],
+    ) -> Result<String> {
+        self.inner
+            .publish_schema_delta(path, base_hash, patch)
+            .await
+    }
+
+    async fn schema_patches(&self, path: &str) -> Result<Vec<SchemaPatch>> {
+        self.inner.schema_patches(path).await
+    }
+
+    async fn export_snapshot(&self) -> Result<RegistrySnapshot> {
+        self.inner.export_snapshot().await
+    }
+
+    async fn import_snapshot(
+        &self,
+        snapshot: &RegistrySnapshot,
+        options: ImportOptions,
+    ) -> Result<ImportReport> {
+        let report = self.inner.import_snapshot(snapshot, options).await;
+        self.cache.clear();
+        report
+    }
+
+    async fn watch_manifests(
+        &self,
+        service_name: &str,
+        on_change: Box<dyn ManifestChangeHandler>,
+    ) -> Result<()> {
+        self.inner.watch_manifests(service_name, on_change).await
+    }
+
+    async fn watch_schemas(
+        &self,
+        path: &str,
+        on_change: Box<dyn SchemaChangeHandler>,
+    ) -> Result<()> {
+        self.inner.watch_schemas(path, on_change).await
+    }
+
+    async fn close(&self) -> Result<()> {
+        self.cache.clear();
+        self.inner.close().await
+    }
+
+    async fn health(&self) -> Result<()> {
+        self.inner.health().await
+    }
+}
+
+#[cfg(all(test, feature = "memory-registry"))]
+mod tests {
+    use super::*;
+    use crate::manifest::new_manifest;
+    use crate::registry::memory::MemoryRegistry;
+
+    fn manifest(version: &str) -> SchemaManifest {
+        let mut manifest = new_manifest("user-service", version, "instance-1");
+        manifest.endpoints.health = "/health".to_string();
+        manifest
+    }
+
+    #[tokio::test]
+    async fn test_get_manifest_served_from_cache() {
+        let registry = CachedRegistry::new(MemoryRegistry::new(), Duration::from_secs(60));
+        registry.register_manifest(&manifest("v1")).await.unwrap();
+
+        assert_eq!(
+            registry
+                .get_manifest("instance-1")
+                .await
+                .unwrap()
+                .service_version,
+            "v1"
+        );
+        assert_eq!(
+            registry.list_manifests("user-service").await.unwrap().len(),
+            1
+        );
+
+        // The backend forgets everything without emitting events
+        registry.inner().clear().await;
+        assert!(registry.get_manifest("instance-1").await.is_ok());
+        assert_eq!(
+            registry.list_manifests("user-service").await.unwrap().len(),
+            1
+        );
+
+        // Expired entries go back to the backend
+        let registry = CachedRegistry::new(MemoryRegistry::new(), Duration::ZERO);
+        registry.register_manifest(&manifest("v1")).await.unwrap();
+        registry.get_manifest("instance-1").await.unwrap();
+        registry.inner().clear().await;
+        assert!(registry.get_manifest("instance-1").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_watch_event_invalidates_cache() {
+        let registry = CachedRegistry::new(MemoryRegistry::new(), Duration::from_secs(60));
+        registry.register_manifest(&manifest("v1")).await.unwrap();
+        registry.get_manifest("instance-1").await.unwrap();
+
+        // Another writer updates the backend directly
+        registry
+            .inner()
+            .update_manifest(&manifest("v2"))
+            .await
+            .unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(2);
+        loop {
+            let version = registry
+                .get_manifest("instance-1")
+                .await
+                .unwrap()
+                .service_version;
+            if version == "v2" {
+                break;
+            }
+            assert!(Instant::now() < deadline, "cache was never invalidated");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+}
@@ -0,0 +1,837 @@
+//! DynamoDB registry implementation.
+//!
+//! Uses a single-table design:
+//!
+//! | attribute      | type | description                                  |
+//! |----------------|------|----------------------------------------------|
+//! | `service_name` | S    | partition key                                |
+//! | `instance_id`  | S    | sort key                                     |
+//! | `manifest`     | B    | gzip-compressed manifest JSON                |
+//! | `version`      | N    | optimistic-concurrency counter               |
+//! | `ttl`          | N    | expiry (epoch seconds), set when `ttl > 0`   |
+//!
+//! Published schemas live in the same table under a reserved partition
+//! (`__farp_schemas__`) keyed by their registry path, in a `schema` attribute.
+//! Schemas published with `publish_schema_chunked` are split into
+//! [`DEFAULT_CHUNK_SIZE`] items under a second partition
+//! (`__farp_schema_chunks__`, see [`StorageHelper::put_chunked`]) so they
+//! aren't bound by DynamoDB's 400KB item limit; their schema item carries a
+//! `chunked` flag instead of the body.
+//!
+//! `get_manifest` looks instances up by ID through a global secondary index
+//! whose partition key is `instance_id` (default name `instance_id-index`).
+//! Enable DynamoDB native TTL on the `ttl` attribute to expire stale instances.
+
+use crate::errors::{Error, Result};
+use crate::manifest::ManifestDiff;
+use crate::registry::backend::DynamoConfig;
+use crate::registry::{
+    check_protocol_version, diff_with_current, EventType, ManifestChangeHandler, ManifestEvent,
+    RegistryConfig, SchemaChangeHandler, SchemaRegistry,
+};
+use crate::storage::{
+    compress_data, decompress_data, StorageBackend, StorageEvent, StorageHelper, DEFAULT_CHUNK_SIZE,
+};
+use crate::types::SchemaManifest;
+use async_trait::async_trait;
+use aws_sdk_dynamodb::config::Region;
+use aws_sdk_dynamodb::primitives::Blob;
+use aws_sdk_dynamodb::types::{AttributeValue, Delete, Put, ReturnValue, TransactWriteItem};
+use aws_sdk_dynamodb::Client;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Partition used for published schemas
+const SCHEMA_PARTITION: &str = "__farp_schemas__";
+
+/// Partition holding the chunks of schemas published in chunks
+const SCHEMA_CHUNK_PARTITION: &str = "__farp_schema_chunks__";
+
+/// Prefix shared by the partitions that don't hold manifests
+const INTERNAL_PARTITION_PREFIX: &str = "__farp_";
+
+type Item = HashMap<String, AttributeValue>;
+
+/// DynamoDB registry implementation
+#[derive(Clone)]
+pub struct DynamoRegistry {
+    client: Client,
+    table_name: String,
+    instance_index: String,
+    poll_interval: Duration,
+    config: RegistryConfig,
+    closed: Arc<AtomicBool>,
+}
+
+impl DynamoRegistry {
+    /// Creates a new DynamoDB registry backed by `table_name`
+    pub fn new(client: Client, table_name: impl Into<String>, config: RegistryConfig) -> Self {
+        Self {
+            client,
+            table_name: table_name.into(),
+            instance_index: "instance_id-index".to_string(),
+            poll_interval: Duration::from_secs(5),
+            config,
+            closed: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Creates a DynamoDB registry from `config.backend_config`
+    ///
+    /// The entries are parsed as a [`DynamoConfig`]: `table_name` is
+    /// required, and `region` and `endpoint` override the ones `client` was
+    /// built with.
+    pub fn from_config(client: Client, config: RegistryConfig) -> Result<Self> {
+        let dynamo: DynamoConfig = config.typed_backend_config()?;
+        let mut client_config = client.config().to_builder();
+        if let Some(region) = dynamo.region {
+            client_config = client_config.region(Region::new(region));
+        }
+        if let Some(endpoint) = dynamo.endpoint {
+            client_config = client_config.endpoint_url(endpoint);
+        }
+        Ok(Self::new(
+            Client::from_conf(client_config.build()),
+            dynamo.table_name,
+            config,
+        ))
+    }
+
+    /// Sets the name of the `instance_id` global secondary index
+    pub fn with_instance_index(mut self, index_name: impl Into<String>) -> Self {
+        self.instance_index = index_name.into();
+        self
+    }
+
+    /// Sets how often `watch_manifests` polls the table for changes
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    fn ensure_open(&self) -> Result<()> {
+        if self.closed.load(Ordering::SeqCst) {
+            return Err(Error::backend_unavailable("registry is closed"));
+        }
+        Ok(())
+    }
+
+    /// Storage over the schema chunk partition
+    fn schema_chunks(&self) -> SchemaChunks {
+        SchemaChunks {
+            client: self.client.clone(),
+            table_name: self.table_name.clone(),
+        }
+    }
+
+    fn chunk_helper(&self) -> StorageHelper {
+        StorageHelper::new(0, self.config.max_schema_size)
+    }
+
+    /// Reads the schema item at `path`
+    async fn schema_item(&self, path: &str) -> Result<Item> {
+        let output = self
+            .client
+            .get_item()
+            .table_name(&self.table_name)
+            .key(
+                "service_name",
+                AttributeValue::S(SCHEMA_PARTITION.to_string()),
+            )
+            .key("instance_id", AttributeValue::S(path.to_string()))
+            .send()
+            .await
+            .map_err(|e| Error::backend_unavailable(format!("dynamodb get_item failed: {e}")))?;
+        output.item().cloned().ok_or(Error::SchemaNotFound)
+    }
+
+    fn encode_manifest(&self, manifest: &SchemaManifest) -> Result<Blob> {
+        let data = serde_json::to_vec(manifest)?;
+        Ok(Blob::new(compress_data(&data)?))
+    }
+
+    fn ttl_attribute(&self) -> Option<AttributeValue> {
+        (self.config.ttl > 0).then(|| {
+            let expires_at = chrono::Utc::now().timestamp() + self.config.ttl;
+            AttributeValue::N(expires_at.to_string())
+        })
+    }
+
+    /// Builds the stored item of a manifest
+    fn manifest_item(&self, manifest: &SchemaManifest, version: i64) -> Result<Item> {
+        let mut item = Item::from([
+            (
+                "service_name".to_string(),
+                AttributeValue::S(manifest.service_name.clone()),
+            ),
+            (
+                "instance_id".to_string(),
+                AttributeValue::S(manifest.instance_id.clone()),
+            ),
+            (
+                "manifest".to_string(),
+                AttributeValue::B(self.encode_manifest(manifest)?),
+            ),
+            (
+                "version".to_string(),
+                AttributeValue::N(version.to_string()),
+            ),
+        ]);
+        if let Some(ttl) = self.ttl_attribute() {
+            item.insert("ttl".to_string(), ttl);
+        }
+        Ok(item)
+    }
+
+    /// Writes a manifest item, guarded by `condition` when given
+    async fn put_manifest_item(
+        &self,
+        manifest: &SchemaManifest,
+        version: i64,
+        condition: Option<(&str, i64)>,
+    ) -> Result<()> {
+        let mut request = self
+            .client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(self.manifest_item(manifest, version)?));
+
+        if let Some((expression, expected)) = condition {
+            request = request
+                .condition_expression(expression)
+                .expression_attribute_values(":expected", AttributeValue::N(expected.to_string()));
+        }
+
+        request.send().await.map_err(|e| {
+            if e.as_service_error()
+                .is_some_and(|se| se.is_conditional_check_failed_exception())
+            {
+                Error::ConcurrentModification(manifest.instance_id.clone())
+            } else {
+                Error::backend_unavailable(format!("dynamodb put_item failed: {e}"))
+            }
+        })?;
+
+        Ok(())
+    }
+
+    /// Writes a manifest whose service changed and deletes its item in the
+    /// `previous` service partition, in one transaction
+    ///
+    /// Lookups by instance would otherwise find either copy. With
+    /// `expected`, the previous item must still have that version.
+    async fn move_manifest_item(
+        &self,
+        manifest: &SchemaManifest,
+        previous: &str,
+        version: i64,
+        expected: Option<i64>,
+    ) -> Result<()> {
+        let build_failed =
+            |e: aws_sdk_dynamodb::error::BuildError| Error::backend_unavailable(e.to_string());
+        let put = Put::builder()
+            .table_name(&self.table_name)
+            .set_item(Some(self.manifest_item(manifest, version)?))
+            .build()
+            .map_err(build_failed)?;
+        let mut delete = Delete::builder()
+            .table_name(&self.table_name)
+            .key("service_name", AttributeValue::S(previous.to_string()))
+            .key(
+                "instance_id",
+                AttributeValue::S(manifest.instance_id.clone()),
+            );
+        if let Some(expected) = expected {
+            delete = delete
+                .condition_expression("version = :expected")
+                .expression_attribute_values(":expected", AttributeValue::N(expected.to_string()));
+        }
+        let delete = delete.build().map_err(build_failed)?;
+
+        self.client
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().put(put).build())
+            .transact_items(TransactWriteItem::builder().delete(delete).build())
+            .send()
+            .await
+            .map_err(|e| {
+                if e.as_service_error()
+                    .is_some_and(|se| se.is_transaction_canceled_exception())
+                {
+                    Error::ConcurrentModification(manifest.instance_id.clone())
+                } else {
+                    Error::backend_unavailable(format!("dynamodb transact_write_items failed: {e}"))
+                }
+            })?;
+
+        Ok(())
+    }
+
+    /// Finds the raw item for an instance via the `instance_id` index
+    async fn find_instance_item(&self, instance_id: &str) -> Result<Option<Item>> {
+        let output = self
+            .client
+            .query()
+            .table_name(&self.table_name)
+            .index_name(&self.instance_index)
+            .key_condition_expression("instance_id = :id")
+            .expression_attribute_values(":id", AttributeValue::S(instance_id.to_string()))
+            .limit(1)
+            .send()
+            .await
+            .map_err(|e| Error::backend_unavailable(format!("dynamodb query failed: {e}")))?;
+
+        Ok(output.items().first().cloned())
+    }
+
+    /// Queries every manifest in a service partition, or scans the table when
+    /// `service_name` is empty
+    async fn load_manifests(&self, service_name: &str) -> Result<Vec<SchemaManifest>> {
+        let mut manifests = Vec::new();
+        let mut start_key: Option<Item> = None;
+
+        loop {
+            let (items, last_key) = if service_name.is_empty() {
+                let output = self
+                    .client
+                    .scan()
+                    .table_name(&self.table_name)
+                    .filter_expression("NOT begins_with(service_name, :internal)")
+                    .expression_attribute_values(
+                        ":internal",
+                        AttributeValue::S(INTERNAL_PARTITION_PREFIX.to_string()),
+                    )
+                    .set_exclusive_start_key(start_key.take())
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        Error::backend_unavailable(format!("dynamodb scan failed: {e}"))
+                    })?;
+                (
+                    output.items().to_vec(),
+                    output.last_evaluated_key().cloned(),
+                )
+            } else {
+                let output = self
+                    .client
+                    .query()
+                    .table_name(&self.table_name)
+                    .key_condition_expression("service_name = :svc")
+                    .expression_attribute_values(
+                        ":svc",
+                        AttributeValue::S(service_name.to_string()),
+                    )
+                    .set_exclusive_start_key(start_key.take())
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        Error::backend_unavailable(format!("dynamodb query failed: {e}"))
+                    })?;
+                (
+                    output.items().to_vec(),
+                    output.last_evaluated_key().cloned(),
+                )
+            };
+
+            for item in &items {
+                // Skip items that fail to decode rather than failing the whole listing
+                if let Ok(manifest) = decode_manifest(item) {
+                    manifests.push(manifest);
+                }
+            }
+
+            match last_key {
+                Some(key) if !key.is_empty() => start_key = Some(key),
+                _ => break,
+            }
+        }
+
+        Ok(manifests)
+    }
+}
+
+fn decode_manifest(item: &Item) -> Result<SchemaManifest> {
+    let blob = item
+        .get("manifest")
+        .and_then(|v| v.as_b().ok())
+        .ok_or_else(|| Error::invalid_manifest("item has no manifest attribute"))?;
+    let data = decompress_data(blob.as_ref())?;
+    SchemaManifest::from_json(&data)
+}
+
+/// Whether a schema item's body is stored in chunks
+fn is_chunked(item: &Item) -> bool {
+    item.get("chunked")
+        .and_then(|v| v.as_bool().ok())
+        .is_some_and(|chunked| *chunked)
+}
+
+/// Lists the sort keys in `partition` starting with `prefix`
+async fn partition_keys(
+    client: &Client,
+    table_name: &str,
+    partition: &str,
+    prefix: &str,
+) -> Result<Vec<String>> {
+    let mut keys = Vec::new();
+    let mut start_key: Option<Item> = None;
+
+    loop {
+        let output = client
+            .query()
+            .table_name(table_name)
+            .key_condition_expression(
+                "service_name = :partition AND begins_with(instance_id, :prefix)",
+            )
+            .expression_attribute_values(":partition", AttributeValue::S(partition.to_string()))
+            .expression_attribute_values(":prefix", AttributeValue::S(prefix.to_string()))
+            .projection_expression("instance_id")
+            .set_exclusive_start_key(start_key.take())
+            .send()
+            .await
+            .map_err(|e| Error::backend_unavailable(format!("dynamodb query failed: {e}")))?;
+
+        keys.extend(
+            output
+                .items()
+                .iter()
+                .filter_map(|item| item.get("instance_id"))
+                .filter_map(|v| v.as_s().ok())
+                .cloned(),
+        );
+
+        match output.last_evaluated_key() {
+            Some(key) if !key.is_empty() => start_key = Some(key.clone()),
+            _ => break,
+        }
+    }
+
+    Ok(keys)
+}
+
+/// Chunks of schemas published in chunks, one item per storage key
+#[derive(Clone)]
+struct SchemaChunks {
+    client: Client,
+    table_name: String,
+}
+
+impl SchemaChunks {
+    fn key(key: &str) -> [(&'static str, AttributeValue); 2] {
+        [
+            (
+                "service_name",
+                AttributeValue::S(SCHEMA_CHUNK_PARTITION.to_string()),
+            ),
+            ("instance_id", AttributeValue::S(key.to_string())),
+        ]
+    }
+}
+
+#[async_trait]
+impl StorageBackend for SchemaChunks {
+    async fn put(&self, key: &str, value: &[u8]) -> Result<()> {
+        let [partition, sort] = Self::key(key);
+        self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .item(partition.0, partition.1)
+            .item(sort.0, sort.1)
+            .item("data", AttributeValue::B(Blob::new(value)))
+            .send()
+            .await
+            .map_err(|e| Error::backend_unavailable(format!("dynamodb put_item failed: {e}")))?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        let [partition, sort] = Self::key(key);
+        let output = self
+            .client
+            .get_item()
+            .table_name(&self.table_name)
+            .key(partition.0, partition.1)
+            .key(sort.0, sort.1)
+            .send()
+            .await
+            .map_err(|e| Error::backend_unavailable(format!("dynamodb get_item failed: {e}")))?;
+        output
+            .item()
+            .and_then(|item| item.get("data"))
+            .and_then(|v| v.as_b().ok())
+            .map(|blob| blob.as_ref().to_vec())
+            .ok_or(Error::SchemaNotFound)
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let [partition, sort] = Self::key(key);
+        self.client
+            .delete_item()
+            .table_name(&self.table_name)
+            .key(partition.0, partition.1)
+            .key(sort.0, sort.1)
+            .send()
+            .await
+            .map_err(|e| Error::backend_unavailable(format!("dynamodb delete_item failed: {e}")))?;
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        partition_keys(
+            &self.client,
+            &self.table_name,
+            SCHEMA_CHUNK_PARTITION,
+            prefix,
+        )
+        .await
+    }
+
+    async fn watch(&self, _prefix: &str) -> Result<tokio::sync::mpsc::Receiver<StorageEvent>> {
+        Err(Error::unsupported("watching schema chunks"))
+    }
+
+    async fn close(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Service partition an item is stored in
+fn item_partition(item: &Item) -> Option<&str> {
+    item.get("service_name")
+        .and_then(|v| v.as_s().ok())
+        .map(String::as_str)
+}
+
+fn item_version(item: &Item) -> i64 {
+    item.get("version")
+        .and_then(|v| v.as_n().ok())
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(0)
+}
+
+#[async_trait]
+impl SchemaRegistry for DynamoRegistry {
+    async fn register_manifest(&self, manifest: &SchemaManifest) -> Result<()> {
+        self.ensure_open()?;
+        check_protocol_version(manifest)?;
+        self.config.check_manifest_checksum(manifest)?;
+        manifest.validate()?;
+        self.config.check_content_types(manifest)?;
+        self.config.check_registry_schemas(self, manifest).await?;
+
+        let current = self.find_instance_item(&manifest.instance_id).await?;
+        match current
+            .as_ref()
+            .and_then(item_partition)
+            .filter(|partition| *partition != manifest.service_name)
+        {
+            Some(previous) => self.move_manifest_item(manifest, previous, 1, None).await?,
+            None => self.put_manifest_item(manifest, 1, None).await?,
+        }
+        Ok(())
+    }
+
+    async fn get_manifest(&self, instance_id: &str) -> Result<SchemaManifest> {
+        self.ensure_open()?;
+        let item = self
+            .find_instance_item(instance_id)
+            .await?
+            .ok_or(Error::ManifestNotFound)?;
+        decode_manifest(&item)
+    }
+
+    async fn register_dry_run(&self, manifest: &SchemaManifest) -> Result<ManifestDiff> {
+        self.ensure_open()?;
+        check_protocol_version(manifest)?;
+        self.config.check_manifest_checksum(manifest)?;
+        manifest.validate()?;
+        self.config.check_content_types(manifest)?;
+        self.config.check_registry_schemas(self, manifest).await?;
+
+        diff_with_current(self, manifest).await
+    }
+
+    async fn update_manifest(&self, manifest: &SchemaManifest) -> Result<()> {
+        self.ensure_open()?;
+        check_protocol_version(manifest)?;
+        self.config.check_manifest_checksum(manifest)?;
+        manifest.validate()?;
+        self.config.check_content_types(manifest)?;
+
+        let current = self
+            .find_instance_item(&manifest.instance_id)
+            .await?
+            .ok_or(Error::ManifestNotFound)?;
+        let version = item_version(&current);
+
+        // Only overwrite the version we read; a concurrent writer fails the check
+        match item_partition(&current).filter(|partition| *partition != manifest.service_name) {
+            Some(previous) => {
+                self.move_manifest_item(manifest, previous, version + 1, Some(version))
+                    .await?
+            }
+            None => {
+                self.put_manifest_item(
+                    manifest,
+                    version + 1,
+                    Some(("version = :expected", version)),
+                )
+                .await?
+            }
+        }
+        Ok(())
+    }
+
+    async fn delete_manifest(&self, instance_id: &str) -> Result<()> {
+        self.ensure_open()?;
+        let item = self
+            .find_instance_item(instance_id)
+            .await?
+            .ok_or(Error::ManifestNotFound)?;
+        let manifest = decode_manifest(&item)?;
+
+        self.client
+            .delete_item()
+            .table_name(&self.table_name)
+            .key("service_name", AttributeValue::S(manifest.service_name))
+            .key("instance_id", AttributeValue::S(manifest.instance_id))
+            .send()
+            .await
+            .map_err(|e| Error::backend_unavailable(format!("dynamodb delete_item failed: {e}")))?;
+        Ok(())
+    }
+
+    async fn list_manifests(&self, service_name: &str) -> Result<Vec<SchemaManifest>> {
+        self.ensure_open()?;
+        self.load_manifests(service_name).await
+    }
+
+    async fn publish_schema(&self, path: &str, schema: &serde_json::Value) -> Result<()> {
+        self.ensure_open()?;
+
+        let data = serde_json::to_vec(schema)?;
+        if self.config.max_schema_size > 0 && data.len() as i64 > self.config.max_schema_size {
+            return Err(Error::schema_too_large(
+                data.len(),
+                self.config.max_schema_size as usize,
+            ));
+        }
+
+        let output = self
+            .client
+            .put_item()
+            .table_name(&self.table_name)
+            .item(
+                "service_name",
+                AttributeValue::S(SCHEMA_PARTITION.to_string()),
+            )
+            .item("instance_id", AttributeValue::S(path.to_string()))
+            .item(
+                "schema",
+                AttributeValue::B(Blob::new(compress_data(&data)?)),
+            )
+            .return_values(ReturnValue::AllOld)
+            .send()
+            .await
+            .map_err(|e| Error::backend_unavailable(format!("dynamodb put_item failed: {e}")))?;
+
+        // Replacing a chunked schema leaves its chunks unreferenced
+        if output.attributes().is_some_and(is_chunked) {
+            self.chunk_helper()
+                .delete_chunked(&self.schema_chunks(), path)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn publish_schema_chunked(
+        &self,
+        path: &str,
+        reader: &mut (dyn AsyncRead + Send + Unpin),
+        total_size: u64,
+    ) -> Result<()> {
+        self.ensure_open()?;
+
+        // Chunks first, then the flag pointing readers at them
+        self.chunk_helper()
+            .put_chunked(
+                &self.schema_chunks(),
+                path,
+                reader,
+                total_size,
+                DEFAULT_CHUNK_SIZE,
+            )
+            .await?;
+        self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .item(
+                "service_name",
+                AttributeValue::S(SCHEMA_PARTITION.to_string()),
+            )
+            .item("instance_id", AttributeValue::S(path.to_string()))
+            .item("chunked", AttributeValue::Bool(true))
+            .send()
+            .await
+            .map_err(|e| Error::backend_unavailable(format!("dynamodb put_item failed: {e}")))?;
+        Ok(())
+    }
+
+    async fn fetch_schema(&self, path: &str) -> Result<serde_json::Value> {
+        self.ensure_open()?;
+
+        let item = self.schema_item(path).await?;
+        let data = if is_chunked(&item) {
+            let mut data = Vec::new();
+            self.chunk_helper()
+                .get_chunked(Arc::new(self.schema_chunks()), path)
+                .await?
+                .read_to_end(&mut data)
+                .await?;
+            data
+        } else {
+            let blob = item
+                .get("schema")
+                .and_then(|v| v.as_b().ok())
+                .ok_or(Error::SchemaNotFound)?;
+            decompress_data(blob.as_ref())?
+        };
+        serde_json::from_slice(&data).map_err(|e| Error::invalid_schema(e.to_string()))
+    }
+
+    async fn fetch_schema_streaming(
+        &self,
+        path: &str,
+    ) -> Result<Box<dyn AsyncRead + Send + Unpin>> {
+        self.ensure_open()?;
+
+        if is_chunked(&self.schema_item(path).await?) {
+            let reader = self
+                .chunk_helper()
+                .get_chunked(Arc::new(self.schema_chunks()), path)
+                .await?;
+            return Ok(Box::new(reader));
+        }
+        let schema = self.fetch_schema(path).await?;
+        Ok(Box::new(std::io::Cursor::new(serde_json::to_vec(&schema)?)))
+    }
+
+    async fn delete_schema(&self, path: &str) -> Result<()> {
+        self.ensure_open()?;
+
+        let output = self
+            .client
+            .delete_item()
+            .table_name(&self.table_name)
+            .key(
+                "service_name",
+                AttributeValue::S(SCHEMA_PARTITION.to_string()),
+            )
+            .key("instance_id", AttributeValue::S(path.to_string()))
+            .return_values(ReturnValue::AllOld)
+            .send()
+            .await
+            .map_err(|e| Error::backend_unavailable(format!("dynamodb delete_item failed: {e}")))?;
+
+        if output.attributes().is_some_and(is_chunked) {
+            self.chunk_helper()
+                .delete_chunked(&self.schema_chunks(), path)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn list_schemas(&self, prefix: &str) -> Result<Vec<String>> {
+        self.ensure_open()?;
+        partition_keys(&self.client, &self.table_name, SCHEMA_PARTITION, prefix).await
+    }
+
+    /// Watches a service by polling the partition and diffing snapshots
+    ///
+    /// DynamoDB Streams are not consumed directly; polling keeps the backend
+    /// usable without a stream-enabled table.
+    async fn watch_manifests(
+        &self,
+        service_name: &str,
+        on_change: Box<dyn ManifestChangeHandler>,
+    ) -> Result<()> {
+        self.ensure_open()?;
+
+        let registry = self.clone();
+        let service_name = service_name.to_string();
+        let mut known: HashMap<String, SchemaManifest> = self
+            .load_manifests(&service_name)
+            .await?
+            .into_iter()
+            .map(|m| (m.instance_id.clone(), m))
+            .collect();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(registry.poll_interval);
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+                if registry.closed.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                let Ok(current) = registry.load_manifests(&service_name).await else {
+                    continue;
+                };
+                let mut next = HashMap::with_capacity(current.len());
+
+                for manifest in current {
+                    let event_type = match known.get(&manifest.instance_id) {
+                        None => Some(EventType::Added),
+                        Some(old) if old != &manifest => Some(EventType::Updated),
+                        Some(_) => None,
+                    };
+                    if let Some(event_type) = event_type {
+                        on_change.on_change(&ManifestEvent::new(event_type, manifest.clone()));
+                    }
+                    next.insert(manifest.instance_id.clone(), manifest);
+                }
+
+                for (instance_id, manifest) in known.drain() {
+                    if !next.contains_key(&instance_id) {
+                        on_change.on_change(&ManifestEvent::new(EventType::Removed, manifest));
+                    }
+                }
+
+                known = next;
+            }
+        });
+
+        Ok(())
+    }
+
+    async fn watch_schemas(
+        &self,
+        _path: &str,
+        _on_change: Box<dyn SchemaChangeHandler>,
+    ) -> Result<()> {
+        Err(Error::unsupported("watch_schemas"))
+    }
+
+    async fn close(&self) -> Result<()> {
+        self.closed.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn health(&self) -> Result<()> {
+        self.ensure_open()?;
+        self.client
+            .describe_table()
+            .table_name(&self.table_name)
+            .send()
+            .await
+            .map_err(|e| {
+                Error::backend_unavailable(format!("dynamodb describe_table failed: {e}"))
+            })?;
+        Ok(())
+    }
+}
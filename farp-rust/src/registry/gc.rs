@@ -0,0 +1,203 @@
+//! Garbage collection of published schemas no manifest references
+
+use crate::errors::Result;
+use crate::manifest::calculate_schema_checksum;
+use crate::registry::{SchemaPatch, SchemaRegistry};
+use std::collections::HashSet;
+
+/// Options for a garbage-collection run
+#[derive(Debug, Clone, Default)]
+pub struct GcOptions {
+    /// Report orphans without deleting them
+    pub dry_run: bool,
+}
+
+/// Deletes published schemas that no current manifest references
+///
+/// Returns the deleted paths. See [`collect_orphans_with_options`].
+pub async fn collect_orphans(
+    registry: &dyn SchemaRegistry,
+    service_name: &str,
+) -> Result<Vec<String>> {
+    collect_orphans_with_options(registry, service_name, &GcOptions::default()).await
+}
+
+/// Finds (and unless `dry_run` is set, deletes) orphaned schemas
+///
+/// A schema is referenced when a manifest's descriptor points at its path via
+/// `registry_path`, or when its content hash matches a descriptor `hash`.
+/// References are gathered from every registered manifest so schemas shared
+/// across services are never removed. When `service_name` is non-empty only
+/// paths containing it as a segment (e.g. `/schemas/<service>/openapi`) are
+/// candidates.
+pub async fn collect_orphans_with_options(
+    registry: &dyn SchemaRegistry,
+    service_name: &str,
+    options: &GcOptions,
+) -> Result<Vec<String>> {
+    let manifests = registry.list_manifests("").await?;
+
+    let mut referenced_paths = HashSet::new();
+    let mut referenced_hashes = HashSet::new();
+    for manifest in &manifests {
+        for schema in &manifest.schemas {
+            if let Some(path) = &schema.location.registry_path {
+                referenced_paths.insert(path.clone());
+            }
+            if !schema.hash.is_empty() {
+                referenced_hashes.insert(schema.hash.clone());
+            }
+        }
+    }
+
+    let paths = registry.list_schemas("").await?;
+    let listed: HashSet<&String> = paths.iter().collect();
+
+    let mut orphans = Vec::new();
+    for path in &paths {
+        // Patch chains live and die with the schema they audit
+        if path.ends_with("#patches") {
+            continue;
+        }
+        if !service_name.is_empty() && !path.split('/').any(|segment| segment == service_name) {
+            continue;
+        }
+        if referenced_paths.contains(path) {
+            continue;
+        }
+
+        // Same content published under another path is still in use
+        let schema = match registry.fetch_schema(path).await {
+            Ok(schema) => schema,
+            Err(_) => continue,
+        };
+        if referenced_hashes.contains(&calculate_schema_checksum(&schema)?) {
+            continue;
+        }
+
+        if !options.dry_run {
+            registry.delete_schema(path).await?;
+            let chain = SchemaPatch::chain_path(path);
+            if listed.contains(&chain) {
+                registry.delete_schema(&chain).await?;
+            }
+        }
+        orphans.push(path.clone());
+    }
+
+    Ok(orphans)
+}
+
+#[cfg(all(test, feature = "memory-registry"))]
+mod tests {
+    use super::*;
+    use crate::manifest::new_manifest;
+    use crate::registry::memory::MemoryRegistry;
+    use crate::types::{LocationType, SchemaDescriptor, SchemaLocation, SchemaType};
+
+    fn descriptor(
+        schema_type: SchemaType,
+        location: SchemaLocation,
+        hash: String,
+    ) -> SchemaDescriptor {
+        SchemaDescriptor {
+            schema_type,
+            schema_id: None,
+            spec_version: "3.1.0".to_string(),
+            location,
+            content_type: "application/json".to_string(),
+            inline_schema: None,
+            hash,
+            size: 0,
+            compatibility: None,
+            metadata: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_collect_orphans() {
+        let registry = MemoryRegistry::new();
+
+        let referenced = serde_json::json!({"openapi": "3.1.0", "paths": {}});
+        let by_hash = serde_json::json!({"asyncapi": "3.0.0", "channels": {}});
+        let orphan = serde_json::json!({"openapi": "3.0.0", "paths": {"/old": {}}});
+
+        registry
+            .publish_schema("/schemas/user-service/openapi", &referenced)
+            .await
+            .unwrap();
+        registry
+            .publish_schema("/schemas/user-service/asyncapi", &by_hash)
+            .await
+            .unwrap();
+        registry
+            .publish_schema("/schemas/user-service/openapi-v0", &orphan)
+            .await
+            .unwrap();
+        registry
+            .publish_schema("/schemas/order-service/openapi-v0", &orphan)
+            .await
+            .unwrap();
+
+        let mut manifest = new_manifest("user-service", "v1.0.0", "instance-1");
+        manifest.endpoints.health = "/health".to_string();
+        manifest.add_schema(descriptor(
+            SchemaType::OpenAPI,
+            SchemaLocation {
+                location_type: LocationType::Registry,
+                url: None,
+                registry_path: Some("/schemas/user-service/openapi".to_string()),
+                headers: None,
+            },
+            calculate_schema_checksum(&referenced).unwrap(),
+        ));
+        // Served over HTTP, but the same content is also published in the registry
+        manifest.add_schema(descriptor(
+            SchemaType::AsyncAPI,
+            SchemaLocation {
+                location_type: LocationType::HTTP,
+                url: Some("http://user-service/asyncapi.json".to_string()),
+                registry_path: None,
+                headers: None,
+            },
+            calculate_schema_checksum(&by_hash).unwrap(),
+        ));
+        registry.register_manifest(&manifest).await.unwrap();
+
+        // Dry run reports without deleting
+        let dry_run =
+            collect_orphans_with_options(&registry, "user-service", &GcOptions { dry_run: true })
+                .await
+                .unwrap();
+        assert_eq!(dry_run, vec!["/schemas/user-service/openapi-v0"]);
+        assert!(registry
+            .fetch_schema("/schemas/user-service/openapi-v0")
+            .await
+            .is_ok());
+
+        let deleted = collect_orphans(&registry, "user-service").await.unwrap();
+        assert_eq!(deleted, vec!["/schemas/user-service/openapi-v0"]);
+        assert!(registry
+            .fetch_schema("/schemas/user-service/openapi-v0")
+            .await
+            .is_err());
+
+        // Referenced schemas and other services' schemas are kept
+        assert!(registry
+            .fetch_schema("/schemas/user-service/openapi")
+            .await
+            .is_ok());
+        assert!(registry
+            .fetch_schema("/schemas/user-service/asyncapi")
+            .await
+            .is_ok());
+        assert!(registry
+            .fetch_schema("/schemas/order-service/openapi-v0")
+            .await
+            .is_ok());
+
+        // Without a service scope every orphan is collected
+        let deleted = collect_orphans(&registry, "").await.unwrap();
+        assert_eq!(deleted, vec!["/schemas/order-service/openapi-v0"]);
+    }
+}
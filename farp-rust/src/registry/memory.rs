@@ -1,13 +1,18 @@
 //! In-memory registry implementation for testing and development.
 
 use crate::errors::{Error, Result};
+use crate::manifest::ManifestDiff;
 use crate::registry::{
-    EventType, ManifestChangeHandler, ManifestEvent, SchemaChangeHandler, SchemaRegistry,
+    check_protocol_version, diff_with_current, import_snapshot_items, read_schema_body, EventType,
+    ImportOptions, ImportReport, ManifestChangeHandler, ManifestEvent, ManifestSelector,
+    RegistryConfig, RegistrySnapshot, SchemaChangeHandler, SchemaPatch, SchemaRegistry,
+    WatchConfig, WatchHandle, WebhookDispatcher,
 };
-use crate::types::SchemaManifest;
+use crate::types::{SchemaManifest, WebhookEvent, WebhookEventType};
 use async_trait::async_trait;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
+use tokio::io::AsyncRead;
 use tokio::sync::RwLock;
 
 /// In-memory registry implementation
@@ -20,20 +25,62 @@ pub struct MemoryRegistry {
 }
 
 struct RegistryInner {
+    config: RegistryConfig,
     manifests: RwLock<HashMap<String, SchemaManifest>>,
     schemas: RwLock<HashMap<String, serde_json::Value>>,
+    patches: RwLock<HashMap<String, Vec<SchemaPatch>>>,
     watchers: RwLock<HashMap<String, Vec<tokio::sync::mpsc::UnboundedSender<ManifestEvent>>>>,
+    history: RwLock<EventHistory>,
+    webhooks: RwLock<Vec<Box<dyn WebhookDispatcher>>>,
     closed: RwLock<bool>,
 }
 
+/// Number of manifest events kept for [`MemoryRegistry::watch_manifests_with_replay`]
+pub const EVENT_HISTORY_CAPACITY: usize = 1024;
+
+/// Recent manifest events, recorded while the manifest lock is held so
+/// their order matches the stored state
+#[derive(Default)]
+struct EventHistory {
+    events: VecDeque<ManifestEvent>,
+    /// Cursors below this can't catch up from `events`
+    evicted_through: u64,
+    /// Highest sequence recorded
+    latest: u64,
+}
+
+impl EventHistory {
+    /// Assigns the event the next sequence and records it
+    fn record(&mut self, mut event: ManifestEvent) -> ManifestEvent {
+        if self.events.len() == EVENT_HISTORY_CAPACITY {
+            if let Some(evicted) = self.events.pop_front() {
+                self.evicted_through = evicted.sequence;
+            }
+        }
+        self.latest += 1;
+        event.sequence = self.latest;
+        self.events.push_back(event.clone());
+        event
+    }
+}
+
 impl MemoryRegistry {
     /// Creates a new in-memory registry
     pub fn new() -> Self {
+        Self::with_config(RegistryConfig::default())
+    }
+
+    /// Creates a new in-memory registry with the given configuration
+    pub fn with_config(config: RegistryConfig) -> Self {
         Self {
             inner: Arc::new(RegistryInner {
+                config,
                 manifests: RwLock::new(HashMap::new()),
                 schemas: RwLock::new(HashMap::new()),
+                patches: RwLock::new(HashMap::new()),
                 watchers: RwLock::new(HashMap::new()),
+                history: RwLock::new(EventHistory::default()),
+                webhooks: RwLock::new(Vec::new()),
                 closed: RwLock::new(false),
             }),
         }
@@ -63,12 +110,255 @@ impl MemoryRegistry {
         }
     }
 
+    /// Registers a hook that receives the registry's webhook events
+    ///
+    /// `update_manifest` raises `TrafficShift` when an instance's deployment
+    /// traffic percentage or role changes; registering a new instance or
+    /// deleting one raises `InstanceScaling` with the service's old and new
+    /// instance counts.
+    pub async fn add_webhook_dispatcher(&self, dispatcher: Box<dyn WebhookDispatcher>) {
+        self.inner.webhooks.write().await.push(dispatcher);
+    }
+
+    /// Delivers a webhook event to every registered dispatcher
+    async fn dispatch_webhook(&self, event: WebhookEvent) {
+        for dispatcher in self.inner.webhooks.read().await.iter() {
+            dispatcher.dispatch(&event);
+        }
+    }
+
+    /// Counts the registered instances of a service
+    fn instance_count(manifests: &HashMap<String, SchemaManifest>, service_name: &str) -> usize {
+        manifests
+            .values()
+            .filter(|m| m.service_name == service_name)
+            .count()
+    }
+
+    /// Drops every watcher channel, simulating a backend restart (useful for testing)
+    ///
+    /// Watches started with [`watch_manifests_with`](Self::watch_manifests_with)
+    /// resubscribe and resync; nothing else is affected.
+    pub async fn disconnect_watchers(&self) {
+        self.inner.watchers.write().await.clear();
+    }
+
+    /// Subscribes a channel for `service_name` and returns its receiver
+    async fn subscribe(
+        &self,
+        service_name: &str,
+    ) -> tokio::sync::mpsc::UnboundedReceiver<ManifestEvent> {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut watchers = self.inner.watchers.write().await;
+        watchers
+            .entry(service_name.to_string())
+            .or_insert_with(Vec::new)
+            .push(tx);
+        rx
+    }
+
+    /// Snapshot of the manifests a watch on `service_name` covers, by instance ID
+    async fn snapshot(&self, service_name: &str) -> HashMap<String, SchemaManifest> {
+        let manifests = self.inner.manifests.read().await;
+        Self::covered(&manifests, service_name)
+    }
+
+    fn covered(
+        manifests: &HashMap<String, SchemaManifest>,
+        service_name: &str,
+    ) -> HashMap<String, SchemaManifest> {
+        manifests
+            .values()
+            .filter(|m| service_name.is_empty() || m.service_name == service_name)
+            .map(|m| (m.instance_id.clone(), m.clone()))
+            .collect()
+    }
+
+    /// Watches for manifest changes, resubscribing if the channel drops
+    ///
+    /// When the internal channel closes (e.g. backend restart) the watch waits
+    /// with exponential backoff, resubscribes, then re-lists the service and
+    /// emits synthetic `Added`/`Updated`/`Removed` events for anything that
+    /// changed while disconnected.
+    pub async fn watch_manifests_with(
+        &self,
+        service_name: &str,
+        on_change: Box<dyn ManifestChangeHandler>,
+        config: WatchConfig,
+    ) -> Result<WatchHandle> {
+        if self.is_closed().await {
+            return Err(Error::backend_unavailable("registry is closed"));
+        }
+
+        let rx = self.subscribe(service_name).await;
+        let known = self.snapshot(service_name).await;
+        Ok(self.spawn_watch(service_name, on_change, config, rx, known, 0))
+    }
+
+    /// Watches for manifest changes, first replaying the current state
+    ///
+    /// Without a cursor, every manifest the watch covers is replayed as an
+    /// `Added` event carrying the sequence of the latest change reflected in
+    /// the snapshot. With `since`, the recorded events after that sequence
+    /// are replayed as they happened, including `Removed` ones; if the
+    /// registry no longer holds all of them (see [`EVENT_HISTORY_CAPACITY`])
+    /// or never issued that sequence, the full snapshot is replayed instead.
+    ///
+    /// The replay is delivered before this returns. The snapshot and the
+    /// subscription are taken together, so live events continue exactly
+    /// where the replay ends: nothing is missed or delivered twice.
+    /// Reconnects behave as in [`watch_manifests_with`](Self::watch_manifests_with)
+    /// with the default [`WatchConfig`].
+    pub async fn watch_manifests_with_replay(
+        &self,
+        service_name: &str,
+        on_change: Box<dyn ManifestChangeHandler>,
+        since: Option<u64>,
+    ) -> Result<WatchHandle> {
+        if self.is_closed().await {
+            return Err(Error::backend_unavailable("registry is closed"));
+        }
+
+        let (rx, known, replay, replayed_through) = {
+            // Writers record and apply events under the manifest lock
+            let manifests = self.inner.manifests.read().await;
+            let history = self.inner.history.read().await;
+            let rx = self.subscribe(service_name).await;
+            let known = Self::covered(&manifests, service_name);
+            let replay: Vec<ManifestEvent> = match since {
+                Some(cursor) if (history.evicted_through..=history.latest).contains(&cursor) => {
+                    history
+                        .events
+                        .iter()
+                        .filter(|event| event.sequence > cursor)
+                        .filter(|event| {
+                            service_name.is_empty() || event.manifest.service_name == service_name
+                        })
+                        .cloned()
+                        .collect()
+                }
+                _ => {
+                    let mut current: Vec<&SchemaManifest> = known.values().collect();
+                    current.sort_by(|a, b| a.instance_id.cmp(&b.instance_id));
+                    current
+                        .into_iter()
+                        .map(|manifest| ManifestEvent {
+                            sequence: history.latest,
+                            ..ManifestEvent::new(EventType::Added, manifest.clone())
+                        })
+                        .collect()
+                }
+            };
+            (rx, known, replay, history.latest)
+        };
+
+        for event in &replay {
+            on_change.on_change(event);
+        }
+        Ok(self.spawn_watch(
+            service_name,
+            on_change,
+            WatchConfig::default(),
+            rx,
+            known,
+            replayed_through,
+        ))
+    }
+
+    /// Runs a watch over `rx`, resubscribing and resyncing if it closes
+    ///
+    /// `known` is the state the caller has already reported; events with a
+    /// sequence up to `skip_through` are already part of it and dropped.
+    fn spawn_watch(
+        &self,
+        service_name: &str,
+        on_change: Box<dyn ManifestChangeHandler>,
+        config: WatchConfig,
+        mut rx: tokio::sync::mpsc::UnboundedReceiver<ManifestEvent>,
+        mut known: HashMap<String, SchemaManifest>,
+        skip_through: u64,
+    ) -> WatchHandle {
+        let (handle, mut stopped) = WatchHandle::new();
+        let registry = self.clone();
+        let service_name = service_name.to_string();
+
+        tokio::spawn(async move {
+            // Grows with each disconnect, reset once events flow again
+            let mut backoff = config.initial_backoff;
+
+            loop {
+                // Deliver events until the channel closes or the watch is stopped
+                loop {
+                    tokio::select! {
+                        event = rx.recv() => match event {
+                            Some(event) if event.sequence <= skip_through => {}
+                            Some(event) => {
+                                backoff = config.initial_backoff;
+                                match event.event_type {
+                                    EventType::Removed => {
+                                        known.remove(&event.manifest.instance_id);
+                                    }
+                                    _ => {
+                                        known.insert(
+                                            event.manifest.instance_id.clone(),
+                                            event.manifest.clone(),
+                                        );
+                                    }
+                                }
+                                on_change.on_change(&event);
+                            }
+                            None => break,
+                        },
+                        Ok(()) = stopped.changed() => return,
+                    }
+                }
+
+                // Reconnect after backoff
+                tokio::select! {
+                    _ = tokio::time::sleep(backoff) => {}
+                    Ok(()) = stopped.changed() => return,
+                }
+                if registry.is_closed().await || *stopped.borrow() {
+                    return;
+                }
+                backoff = config.next_backoff(backoff);
+                rx = registry.subscribe(&service_name).await;
+
+                // Resync: report whatever changed while disconnected
+                let current = registry.snapshot(&service_name).await;
+                for (instance_id, manifest) in &current {
+                    let event_type = match known.get(instance_id) {
+                        None => EventType::Added,
+                        Some(previous) if previous != manifest => EventType::Updated,
+                        Some(_) => continue,
+                    };
+                    on_change.on_change(&ManifestEvent::new(event_type, manifest.clone()));
+                }
+                for (instance_id, manifest) in &known {
+                    if !current.contains_key(instance_id) {
+                        on_change
+                            .on_change(&ManifestEvent::new(EventType::Removed, manifest.clone()));
+                    }
+                }
+                known = current;
+            }
+        });
+
+        handle
+    }
+
     /// Clears all manifests and schemas (useful for testing)
     pub async fn clear(&self) {
         let mut manifests = self.inner.manifests.write().await;
         let mut schemas = self.inner.schemas.write().await;
+        let mut patches = self.inner.patches.write().await;
         manifests.clear();
         schemas.clear();
+        patches.clear();
+        // Clearing raises no events, so cursors from before it must resync
+        let mut history = self.inner.history.write().await;
+        history.events.clear();
+        history.evicted_through = history.latest + 1;
     }
 }
 
@@ -86,19 +376,36 @@ impl SchemaRegistry for MemoryRegistry {
         }
 
         // Validate manifest
+        check_protocol_version(manifest)?;
+        self.inner.config.check_manifest_checksum(manifest)?;
         manifest.validate()?;
+        self.inner.config.check_content_types(manifest)?;
+        self.inner
+            .config
+            .check_registry_schemas(self, manifest)
+            .await?;
 
         let mut manifests = self.inner.manifests.write().await;
-        manifests.insert(manifest.instance_id.clone(), manifest.clone());
+        let existed = manifests
+            .insert(manifest.instance_id.clone(), manifest.clone())
+            .is_some();
+        let instance_count = Self::instance_count(&manifests, &manifest.service_name);
 
         // Notify watchers
-        let event = ManifestEvent {
-            event_type: EventType::Added,
-            manifest: manifest.clone(),
-            timestamp: chrono::Utc::now().timestamp(),
-        };
+        let event = self.inner.history.write().await.record(ManifestEvent::new(
+            if existed {
+                EventType::Reregistered
+            } else {
+                EventType::Added
+            },
+            manifest.clone(),
+        ));
         drop(manifests); // Release lock before notifying
         self.notify_watchers(&manifest.service_name, event).await;
+        if !existed {
+            self.dispatch_webhook(scaling_event(manifest, instance_count - 1, instance_count))
+                .await;
+        }
 
         Ok(())
     }
@@ -111,29 +418,54 @@ impl SchemaRegistry for MemoryRegistry {
             .ok_or(Error::ManifestNotFound)
     }
 
+    async fn register_dry_run(&self, manifest: &SchemaManifest) -> Result<ManifestDiff> {
+        if self.is_closed().await {
+            return Err(Error::backend_unavailable("registry is closed"));
+        }
+
+        check_protocol_version(manifest)?;
+        self.inner.config.check_manifest_checksum(manifest)?;
+        manifest.validate()?;
+        self.inner.config.check_content_types(manifest)?;
+        self.inner
+            .config
+            .check_registry_schemas(self, manifest)
+            .await?;
+
+        diff_with_current(self, manifest).await
+    }
+
     async fn update_manifest(&self, manifest: &SchemaManifest) -> Result<()> {
         if self.is_closed().await {
             return Err(Error::backend_unavailable("registry is closed"));
         }
 
         // Validate manifest
+        check_protocol_version(manifest)?;
+        self.inner.config.check_manifest_checksum(manifest)?;
         manifest.validate()?;
+        self.inner.config.check_content_types(manifest)?;
 
         let mut manifests = self.inner.manifests.write().await;
         if !manifests.contains_key(&manifest.instance_id) {
             return Err(Error::ManifestNotFound);
         }
 
-        manifests.insert(manifest.instance_id.clone(), manifest.clone());
+        let previous = manifests.insert(manifest.instance_id.clone(), manifest.clone());
 
         // Notify watchers
-        let event = ManifestEvent {
-            event_type: EventType::Updated,
-            manifest: manifest.clone(),
-            timestamp: chrono::Utc::now().timestamp(),
-        };
+        let event = self
+            .inner
+            .history
+            .write()
+            .await
+            .record(ManifestEvent::new(EventType::Updated, manifest.clone()));
         drop(manifests); // Release lock before notifying
         self.notify_watchers(&manifest.service_name, event).await;
+        if let Some(shift) = previous.and_then(|previous| traffic_shift_event(&previous, manifest))
+        {
+            self.dispatch_webhook(shift).await;
+        }
 
         Ok(())
     }
@@ -147,15 +479,19 @@ impl SchemaRegistry for MemoryRegistry {
         let manifest = manifests
             .remove(instance_id)
             .ok_or(Error::ManifestNotFound)?;
+        let instance_count = Self::instance_count(&manifests, &manifest.service_name);
 
         // Notify watchers
-        let event = ManifestEvent {
-            event_type: EventType::Removed,
-            manifest: manifest.clone(),
-            timestamp: chrono::Utc::now().timestamp(),
-        };
+        let event = self
+            .inner
+            .history
+            .write()
+            .await
+            .record(ManifestEvent::new(EventType::Removed, manifest.clone()));
         drop(manifests); // Release lock before notifying
         self.notify_watchers(&manifest.service_name, event).await;
+        self.dispatch_webhook(scaling_event(&manifest, instance_count + 1, instance_count))
+            .await;
 
         Ok(())
     }
@@ -170,6 +506,20 @@ impl SchemaRegistry for MemoryRegistry {
         Ok(results)
     }
 
+    async fn list_manifests_filtered(
+        &self,
+        selector: &ManifestSelector,
+    ) -> Result<Vec<SchemaManifest>> {
+        let manifests = self.inner.manifests.read().await;
+        let mut results: Vec<SchemaManifest> = manifests
+            .values()
+            .filter(|m| selector.matches(m))
+            .cloned()
+            .collect();
+        results.sort_by(|a, b| a.instance_id.cmp(&b.instance_id));
+        Ok(results)
+    }
+
     async fn publish_schema(&self, path: &str, schema: &serde_json::Value) -> Result<()> {
         if self.is_closed().await {
             return Err(Error::backend_unavailable("registry is closed"));
@@ -180,6 +530,21 @@ impl SchemaRegistry for MemoryRegistry {
         Ok(())
     }
 
+    async fn publish_schema_chunked(
+        &self,
+        path: &str,
+        reader: &mut (dyn AsyncRead + Send + Unpin),
+        total_size: u64,
+    ) -> Result<()> {
+        if self.is_closed().await {
+            return Err(Error::backend_unavailable("registry is closed"));
+        }
+
+        let body = read_schema_body(reader, total_size, self.inner.config.max_schema_size).await?;
+        let schema = serde_json::from_slice(&body)?;
+        self.publish_schema(path, &schema).await
+    }
+
     async fn fetch_schema(&self, path: &str) -> Result<serde_json::Value> {
         let schemas = self.inner.schemas.read().await;
         schemas.get(path).cloned().ok_or(Error::SchemaNotFound)
@@ -192,36 +557,117 @@ impl SchemaRegistry for MemoryRegistry {
 
         let mut schemas = self.inner.schemas.write().await;
         schemas.remove(path);
+        self.inner.patches.write().await.remove(path);
         Ok(())
     }
 
-    async fn watch_manifests(
+    async fn publish_schema_delta(
         &self,
-        service_name: &str,
-        on_change: Box<dyn ManifestChangeHandler>,
-    ) -> Result<()> {
+        path: &str,
+        base_hash: &str,
+        patch: &[serde_json::Value],
+    ) -> Result<String> {
         if self.is_closed().await {
             return Err(Error::backend_unavailable("registry is closed"));
         }
 
-        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        // Hold the write lock so the base can't change between check and store
+        let mut schemas = self.inner.schemas.write().await;
+        let base = schemas.get(path).ok_or(Error::SchemaNotFound)?;
+        let (schema, entry) = SchemaPatch::apply(base, base_hash, patch)?;
+        let result_hash = entry.result_hash.clone();
+
+        schemas.insert(path.to_string(), schema);
+        self.inner
+            .patches
+            .write()
+            .await
+            .entry(path.to_string())
+            .or_default()
+            .push(entry);
 
-        // Register watcher
-        {
-            let mut watchers = self.inner.watchers.write().await;
-            watchers
-                .entry(service_name.to_string())
-                .or_insert_with(Vec::new)
-                .push(tx);
+        Ok(result_hash)
+    }
+
+    async fn schema_patches(&self, path: &str) -> Result<Vec<SchemaPatch>> {
+        let patches = self.inner.patches.read().await;
+        Ok(patches.get(path).cloned().unwrap_or_default())
+    }
+
+    /// Takes a consistent snapshot; patch chains are stored at their chain paths
+    async fn export_snapshot(&self) -> Result<RegistrySnapshot> {
+        let manifests = self.inner.manifests.read().await;
+        let schemas = self.inner.schemas.read().await;
+        let patches = self.inner.patches.read().await;
+
+        let mut manifests: Vec<SchemaManifest> = manifests.values().cloned().collect();
+        manifests.sort_by(|a, b| a.instance_id.cmp(&b.instance_id));
+
+        let mut exported = schemas.clone();
+        for (path, chain) in patches.iter() {
+            exported.insert(SchemaPatch::chain_path(path), serde_json::to_value(chain)?);
         }
 
-        // Start watching
-        tokio::spawn(async move {
-            while let Some(event) = rx.recv().await {
-                on_change.on_change(&event);
+        Ok(RegistrySnapshot::new(manifests, exported))
+    }
+
+    async fn import_snapshot(
+        &self,
+        snapshot: &RegistrySnapshot,
+        options: ImportOptions,
+    ) -> Result<ImportReport> {
+        let (chains, schemas): (HashMap<_, _>, HashMap<_, _>) = snapshot
+            .schemas
+            .clone()
+            .into_iter()
+            .partition(|(path, _)| path.ends_with("#patches"));
+        let plain = RegistrySnapshot {
+            schemas,
+            ..snapshot.clone()
+        };
+        let mut report = import_snapshot_items(self, &plain, options).await?;
+
+        let mut chains: Vec<(String, serde_json::Value)> = chains.into_iter().collect();
+        chains.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut patches = self.inner.patches.write().await;
+        for (chain_path, chain) in chains {
+            let item = format!("schema:{chain_path}");
+            let path = chain_path.trim_end_matches("#patches").to_string();
+            if options.skip_existing && patches.contains_key(&path) {
+                report.skipped.push(item);
+                continue;
             }
-        });
+            match serde_json::from_value::<Vec<SchemaPatch>>(chain) {
+                Ok(chain) => {
+                    patches.insert(path, chain);
+                    report.imported.push(item);
+                }
+                Err(e) => report.failed.push((item, e.to_string())),
+            }
+        }
+
+        Ok(report)
+    }
 
+    async fn list_schemas(&self, prefix: &str) -> Result<Vec<String>> {
+        let schemas = self.inner.schemas.read().await;
+        let mut paths: Vec<String> = schemas
+            .keys()
+            .filter(|path| path.starts_with(prefix))
+            .cloned()
+            .collect();
+        paths.sort();
+        Ok(paths)
+    }
+
+    async fn watch_manifests(
+        &self,
+        service_name: &str,
+        on_change: Box<dyn ManifestChangeHandler>,
+    ) -> Result<()> {
+        // Runs until the registry is closed
+        self.watch_manifests_with(service_name, on_change, WatchConfig::default())
+            .await?;
         Ok(())
     }
 
@@ -231,9 +677,7 @@ impl SchemaRegistry for MemoryRegistry {
         _on_change: Box<dyn SchemaChangeHandler>,
     ) -> Result<()> {
         // Schema watching not implemented in memory registry
-        Err(Error::Custom(
-            "schema watching not supported in memory registry".to_string(),
-        ))
+        Err(Error::unsupported("watch_schemas"))
     }
 
     async fn close(&self) -> Result<()> {
@@ -259,6 +703,57 @@ impl SchemaRegistry for MemoryRegistry {
     }
 }
 
+/// Builds a `TrafficShift` event if the deployment traffic or role changed
+fn traffic_shift_event(
+    previous: &SchemaManifest,
+    current: &SchemaManifest,
+) -> Option<WebhookEvent> {
+    let traffic = |m: &SchemaManifest| {
+        m.instance
+            .as_ref()
+            .and_then(|i| i.deployment.as_ref())
+            .and_then(|d| d.traffic_percent)
+    };
+    let role = |m: &SchemaManifest| m.instance.as_ref().and_then(|i| i.role);
+
+    if traffic(previous) == traffic(current) && role(previous) == role(current) {
+        return None;
+    }
+
+    Some(WebhookEvent {
+        event_type: WebhookEventType::TrafficShift,
+        service_name: current.service_name.clone(),
+        instance_id: current.instance_id.clone(),
+        timestamp: chrono::Utc::now().timestamp(),
+        data: HashMap::from([
+            ("old_traffic_percent".to_string(), traffic(previous).into()),
+            ("new_traffic_percent".to_string(), traffic(current).into()),
+            (
+                "old_role".to_string(),
+                serde_json::to_value(role(previous)).unwrap_or_default(),
+            ),
+            (
+                "new_role".to_string(),
+                serde_json::to_value(role(current)).unwrap_or_default(),
+            ),
+        ]),
+    })
+}
+
+/// Builds an `InstanceScaling` event for a change in a service's instance count
+fn scaling_event(manifest: &SchemaManifest, previous: usize, current: usize) -> WebhookEvent {
+    WebhookEvent {
+        event_type: WebhookEventType::InstanceScaling,
+        service_name: manifest.service_name.clone(),
+        instance_id: manifest.instance_id.clone(),
+        timestamp: chrono::Utc::now().timestamp(),
+        data: HashMap::from([
+            ("previous_count".to_string(), previous.into()),
+            ("instance_count".to_string(), current.into()),
+        ]),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -278,6 +773,57 @@ mod tests {
         assert_eq!(retrieved.instance_id, "instance-123");
     }
 
+    #[tokio::test]
+    async fn test_protocol_versions() {
+        use crate::registry::protocol_versions;
+
+        let registry = MemoryRegistry::new();
+        let instance = |service: &str, id: &str, version: &str| {
+            let mut manifest = new_manifest(service, "v1.0.0", id);
+            manifest.version = version.to_string();
+            manifest.endpoints.health = "/health".to_string();
+            manifest.update_checksum().unwrap();
+            manifest
+        };
+        for (service, id, version) in [
+            ("user-service", "a", "1.0.0"),
+            ("user-service", "b", "1.0.0"),
+            ("user-service", "c", "1.0.1"),
+            ("order-service", "d", "1.0.1"),
+        ] {
+            registry
+                .register_manifest(&instance(service, id, version))
+                .await
+                .unwrap();
+        }
+
+        let versions = protocol_versions(&registry, "user-service").await.unwrap();
+        assert_eq!(
+            versions,
+            HashMap::from([("1.0.0".to_string(), 2), ("1.0.1".to_string(), 1)])
+        );
+        let versions = protocol_versions(&registry, "").await.unwrap();
+        assert_eq!(versions["1.0.1"], 2);
+
+        // Incompatible versions are rejected at the registry boundary
+        for version in ["2.0.0", "1.99.0"] {
+            let err = registry
+                .register_manifest(&instance("user-service", "e", version))
+                .await
+                .unwrap_err();
+            assert!(matches!(err, Error::IncompatibleVersion { .. }));
+        }
+        let err = registry
+            .update_manifest(&instance("user-service", "a", "2.0.0"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::IncompatibleVersion { .. }));
+        assert_eq!(
+            protocol_versions(&registry, "user-service").await.unwrap()["1.0.0"],
+            2
+        );
+    }
+
     #[tokio::test]
     async fn test_update_manifest() {
         let registry = MemoryRegistry::new();
@@ -295,6 +841,42 @@ mod tests {
         assert_eq!(retrieved.service_version, "v2.0.0");
     }
 
+    #[tokio::test]
+    async fn test_register_dry_run() {
+        let registry = MemoryRegistry::new();
+        let mut manifest = new_manifest("test-service", "v1.0.0", "instance-123");
+        manifest.endpoints.health = "/health".to_string();
+        manifest.add_capability("rest");
+        manifest.update_checksum().unwrap();
+
+        // A new instance reports everything as added
+        let diff = registry.register_dry_run(&manifest).await.unwrap();
+        assert_eq!(diff.capabilities_added, vec!["rest".to_string()]);
+        assert!(!diff.endpoints_changed);
+        assert!(matches!(
+            registry.get_manifest("instance-123").await,
+            Err(Error::ManifestNotFound)
+        ));
+
+        registry.register_manifest(&manifest).await.unwrap();
+        let mut updated = manifest.clone();
+        updated.capabilities = vec!["grpc".to_string()];
+        updated.endpoints.metrics = Some("/metrics".to_string());
+        updated.update_checksum().unwrap();
+
+        let diff = registry.register_dry_run(&updated).await.unwrap();
+        assert_eq!(diff.capabilities_added, vec!["grpc".to_string()]);
+        assert_eq!(diff.capabilities_removed, vec!["rest".to_string()]);
+        assert!(diff.endpoints_changed);
+        assert_eq!(
+            registry.get_manifest("instance-123").await.unwrap(),
+            manifest
+        );
+
+        updated.endpoints.health = String::new();
+        assert!(registry.register_dry_run(&updated).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_delete_manifest() {
         let registry = MemoryRegistry::new();
@@ -353,6 +935,57 @@ mod tests {
         assert_eq!(fetched, schema);
     }
 
+    #[tokio::test]
+    async fn test_chunked_publish_and_streaming_fetch() {
+        use tokio::io::AsyncReadExt;
+
+        let registry = MemoryRegistry::new();
+        let paths: serde_json::Map<String, serde_json::Value> = (0..2000)
+            .map(|i| (format!("/items/{i}"), serde_json::json!({"get": {}})))
+            .collect();
+        let schema = serde_json::json!({"openapi": "3.1.0", "paths": paths});
+        let body = serde_json::to_vec(&schema).unwrap();
+
+        let mut reader = body.as_slice();
+        registry
+            .publish_schema_chunked("/schemas/big", &mut reader, body.len() as u64)
+            .await
+            .unwrap();
+
+        let mut stream = registry
+            .fetch_schema_streaming("/schemas/big")
+            .await
+            .unwrap();
+        let mut read_back = Vec::new();
+        stream.read_to_end(&mut read_back).await.unwrap();
+        let fetched: serde_json::Value = serde_json::from_slice(&read_back).unwrap();
+        assert_eq!(fetched, schema);
+
+        // The limit applies to the declared total before anything is read
+        let registry = MemoryRegistry::with_config(RegistryConfig {
+            max_schema_size: 1024,
+            ..Default::default()
+        });
+        let mut reader = body.as_slice();
+        let err = registry
+            .publish_schema_chunked("/schemas/big", &mut reader, body.len() as u64)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::SchemaToLarge { .. }));
+
+        // Buffering is bounded even without a configured limit
+        let registry = MemoryRegistry::with_config(RegistryConfig {
+            max_schema_size: 0,
+            ..Default::default()
+        });
+        let mut reader = body.as_slice();
+        let err = registry
+            .publish_schema_chunked("/schemas/big", &mut reader, u64::MAX)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::SchemaToLarge { .. }));
+    }
+
     #[tokio::test]
     async fn test_delete_schema() {
         let registry = MemoryRegistry::new();
@@ -368,6 +1001,695 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_publish_schema_delta() {
+        let registry = MemoryRegistry::new();
+        let path = "/schemas/user-service/openapi";
+        let base = serde_json::json!({"openapi": "3.0.0", "info": {"version": "1.0.0"}});
+        registry.publish_schema(path, &base).await.unwrap();
+        let base_hash = crate::manifest::calculate_schema_checksum(&base).unwrap();
+
+        let patch = vec![
+            serde_json::json!({"op": "replace", "path": "/info/version", "value": "1.1.0"}),
+            serde_json::json!({"op": "add", "path": "/paths", "value": {}}),
+        ];
+        let new_hash = registry
+            .publish_schema_delta(path, &base_hash, &patch)
+            .await
+            .unwrap();
+
+        let expected =
+            serde_json::json!({"openapi": "3.0.0", "info": {"version": "1.1.0"}, "paths": {}});
+        let fetched = registry.fetch_schema(path).await.unwrap();
+        assert_eq!(fetched, expected);
+        assert_eq!(
+            new_hash,
+            crate::manifest::calculate_schema_checksum(&expected).unwrap()
+        );
+
+        let chain = registry.schema_patches(path).await.unwrap();
+        assert_eq!(chain.len(), 1);
+        assert_eq!(chain[0].base_hash, base_hash);
+        assert_eq!(chain[0].result_hash, new_hash);
+        assert_eq!(chain[0].patch, patch);
+
+        // The base has moved on, so patching it again is rejected
+        let err = registry
+            .publish_schema_delta(path, &base_hash, &patch)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::ChecksumMismatch { .. }));
+        assert_eq!(registry.fetch_schema(path).await.unwrap(), expected);
+        assert_eq!(registry.schema_patches(path).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_traffic_shift_and_scaling() {
+        use crate::types::{
+            DeploymentMetadata, DeploymentStrategy, InstanceMetadata, InstanceRole, InstanceStatus,
+        };
+
+        let registry = MemoryRegistry::new();
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = events.clone();
+        registry
+            .add_webhook_dispatcher(Box::new(move |event: &WebhookEvent| {
+                sink.lock().unwrap().push(event.clone());
+            }))
+            .await;
+
+        let with_traffic = |percent: i32| {
+            let mut manifest = new_manifest("checkout", "v2.0.0", "canary-1");
+            manifest.endpoints.health = "/health".to_string();
+            manifest.instance = Some(InstanceMetadata {
+                address: "10.0.0.5:8080".to_string(),
+                region: None,
+                zone: None,
+                labels: None,
+                weight: None,
+                status: InstanceStatus::Healthy,
+                role: Some(InstanceRole::Canary),
+                deployment: Some(DeploymentMetadata {
+                    deployment_id: "deploy-42".to_string(),
+                    strategy: DeploymentStrategy::Canary,
+                    traffic_percent: Some(percent),
+                    stage: None,
+                    deployed_at: 0,
+                }),
+                started_at: 0,
+                expected_schema_checksum: None,
+            });
+            manifest
+        };
+
+        registry.register_manifest(&with_traffic(10)).await.unwrap();
+        registry.update_manifest(&with_traffic(10)).await.unwrap();
+        registry.update_manifest(&with_traffic(50)).await.unwrap();
+        registry.delete_manifest("canary-1").await.unwrap();
+
+        let events = events.lock().unwrap();
+        let types: Vec<WebhookEventType> = events.iter().map(|e| e.event_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                WebhookEventType::InstanceScaling,
+                WebhookEventType::TrafficShift,
+                WebhookEventType::InstanceScaling,
+            ]
+        );
+
+        let shift = &events[1];
+        assert_eq!(shift.service_name, "checkout");
+        assert_eq!(shift.instance_id, "canary-1");
+        assert_eq!(shift.data["old_traffic_percent"], 10);
+        assert_eq!(shift.data["new_traffic_percent"], 50);
+        assert_eq!(shift.data["new_role"], "canary");
+
+        assert_eq!(events[0].data["instance_count"], 1);
+        assert_eq!(events[2].data["previous_count"], 1);
+        assert_eq!(events[2].data["instance_count"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_round_trip() {
+        let source = MemoryRegistry::new();
+        for (service, instance_id) in [("user-service", "user-1"), ("order-service", "order-1")] {
+            let mut manifest = new_manifest(service, "v1.0.0", instance_id);
+            manifest.endpoints.health = "/health".to_string();
+            source.register_manifest(&manifest).await.unwrap();
+        }
+        let base = serde_json::json!({"openapi": "3.1.0", "paths": {}});
+        source
+            .publish_schema("/schemas/user-service/openapi", &base)
+            .await
+            .unwrap();
+        source
+            .publish_schema_delta(
+                "/schemas/user-service/openapi",
+                &crate::manifest::calculate_schema_checksum(&base).unwrap(),
+                &[serde_json::json!({"op": "add", "path": "/info", "value": {}})],
+            )
+            .await
+            .unwrap();
+
+        let snapshot = source.export_snapshot().await.unwrap();
+        assert_eq!(
+            snapshot.format_version,
+            crate::registry::SNAPSHOT_FORMAT_VERSION
+        );
+        assert_eq!(snapshot.manifests.len(), 2);
+
+        // Snapshots survive serialization
+        let snapshot: RegistrySnapshot =
+            serde_json::from_slice(&serde_json::to_vec(&snapshot).unwrap()).unwrap();
+
+        let target = MemoryRegistry::new();
+        let report = target
+            .import_snapshot(&snapshot, ImportOptions::default())
+            .await
+            .unwrap();
+        assert!(report.is_success());
+        assert_eq!(report.imported.len(), 4);
+
+        assert_eq!(target.list_manifests("").await.unwrap().len(), 2);
+        assert_eq!(
+            target
+                .fetch_schema("/schemas/user-service/openapi")
+                .await
+                .unwrap(),
+            source
+                .fetch_schema("/schemas/user-service/openapi")
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            target
+                .schema_patches("/schemas/user-service/openapi")
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            target.list_schemas("").await.unwrap(),
+            vec!["/schemas/user-service/openapi"]
+        );
+
+        // Re-importing skips what exists and reports invalid manifests
+        let mut with_invalid = snapshot.clone();
+        with_invalid
+            .manifests
+            .push(new_manifest("broken-service", "v1.0.0", "broken-1"));
+        let report = target
+            .import_snapshot(
+                &with_invalid,
+                ImportOptions {
+                    skip_existing: true,
+                },
+            )
+            .await
+            .unwrap();
+        assert!(report.imported.is_empty());
+        assert_eq!(report.skipped.len(), 4);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, "manifest:broken-1");
+
+        let mut future = snapshot;
+        future.format_version = 99;
+        assert!(matches!(
+            target
+                .import_snapshot(&future, ImportOptions::default())
+                .await,
+            Err(Error::IncompatibleVersion { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_manifests_filtered() {
+        use crate::types::{InstanceMetadata, InstanceRole, InstanceStatus};
+
+        let registry = MemoryRegistry::new();
+        let instances = [
+            ("payment-api", "pay-1", "eu", InstanceStatus::Healthy),
+            ("payment-worker", "pay-2", "us", InstanceStatus::Draining),
+            ("user-service", "user-1", "eu", InstanceStatus::Healthy),
+        ];
+        for (service, instance_id, region, status) in instances {
+            let mut manifest = new_manifest(service, "v1.0.0", instance_id);
+            manifest.endpoints.health = "/health".to_string();
+            manifest.instance = Some(InstanceMetadata {
+                address: "10.0.0.1:8080".to_string(),
+                region: None,
+                zone: None,
+                labels: Some(HashMap::from([("region".to_string(), region.to_string())])),
+                weight: None,
+                status,
+                role: Some(InstanceRole::Primary),
+                deployment: None,
+                started_at: 0,
+                expected_schema_checksum: None,
+            });
+            registry.register_manifest(&manifest).await.unwrap();
+        }
+        // No instance metadata: only matched by name-only selectors
+        let mut bare = new_manifest("payment-legacy", "v1.0.0", "pay-3");
+        bare.endpoints.health = "/health".to_string();
+        registry.register_manifest(&bare).await.unwrap();
+
+        let ids = |manifests: Vec<SchemaManifest>| -> Vec<String> {
+            manifests.into_iter().map(|m| m.instance_id).collect()
+        };
+
+        let by_name = ManifestSelector {
+            service_name: Some("payment-*".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            ids(registry.list_manifests_filtered(&by_name).await.unwrap()),
+            vec!["pay-1", "pay-2", "pay-3"]
+        );
+
+        let by_label = ManifestSelector {
+            labels: HashMap::from([("region".to_string(), "eu".to_string())]),
+            ..Default::default()
+        };
+        assert_eq!(
+            ids(registry.list_manifests_filtered(&by_label).await.unwrap()),
+            vec!["pay-1", "user-1"]
+        );
+
+        let by_status = ManifestSelector {
+            service_name: Some("payment-*".to_string()),
+            status: Some(InstanceStatus::Draining),
+            ..Default::default()
+        };
+        assert_eq!(
+            ids(registry.list_manifests_filtered(&by_status).await.unwrap()),
+            vec!["pay-2"]
+        );
+
+        let by_role = ManifestSelector {
+            role: Some(InstanceRole::Canary),
+            ..Default::default()
+        };
+        assert!(registry
+            .list_manifests_filtered(&by_role)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_register_emits_added_then_reregistered() {
+        let registry = MemoryRegistry::new();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        registry
+            .watch_manifests(
+                "test-service",
+                Box::new(move |event: &ManifestEvent| {
+                    let _ = tx.send(event.event_type);
+                }),
+            )
+            .await
+            .unwrap();
+
+        let mut manifest = new_manifest("test-service", "v1.0.0", "instance-123");
+        manifest.endpoints.health = "/health".to_string();
+        manifest.update_checksum().unwrap();
+
+        registry.register_manifest(&manifest).await.unwrap();
+        assert_eq!(rx.recv().await, Some(EventType::Added));
+
+        registry.register_manifest(&manifest).await.unwrap();
+        assert_eq!(rx.recv().await, Some(EventType::Reregistered));
+
+        registry.update_manifest(&manifest).await.unwrap();
+        assert_eq!(rx.recv().await, Some(EventType::Updated));
+    }
+
+    #[tokio::test]
+    async fn test_watch_with_replay() {
+        let registry = MemoryRegistry::new();
+        let manifest = |instance_id: &str| {
+            let mut manifest = new_manifest("test-service", "v1.0.0", instance_id);
+            manifest.endpoints.health = "/health".to_string();
+            manifest
+        };
+        registry
+            .register_manifest(&manifest("instance-2"))
+            .await
+            .unwrap();
+        registry
+            .register_manifest(&manifest("instance-1"))
+            .await
+            .unwrap();
+        let mut other = manifest("other-1");
+        other.service_name = "other-service".to_string();
+        registry.register_manifest(&other).await.unwrap();
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let watch = |tx: tokio::sync::mpsc::UnboundedSender<(EventType, String)>,
+                     sequences: Arc<std::sync::Mutex<Vec<u64>>>| {
+            Box::new(move |event: &ManifestEvent| {
+                sequences.lock().unwrap().push(event.sequence);
+                let _ = tx.send((event.event_type, event.manifest.instance_id.clone()));
+            })
+        };
+        let sequences = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let handle = registry
+            .watch_manifests_with_replay("test-service", watch(tx, sequences.clone()), None)
+            .await
+            .unwrap();
+
+        // The snapshot replays first, then live changes follow
+        assert_eq!(
+            next_event(&mut rx).await,
+            (EventType::Added, "instance-1".to_string())
+        );
+        assert_eq!(
+            next_event(&mut rx).await,
+            (EventType::Added, "instance-2".to_string())
+        );
+        registry
+            .update_manifest(&manifest("instance-1"))
+            .await
+            .unwrap();
+        assert_eq!(
+            next_event(&mut rx).await,
+            (EventType::Updated, "instance-1".to_string())
+        );
+        assert!(rx.try_recv().is_err());
+        handle.stop();
+
+        // Resuming from the replay's cursor yields only what happened since
+        let cursor = sequences.lock().unwrap()[0];
+        registry.delete_manifest("instance-2").await.unwrap();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        registry
+            .watch_manifests_with_replay(
+                "test-service",
+                watch(tx, Arc::new(std::sync::Mutex::new(Vec::new()))),
+                Some(cursor),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            next_event(&mut rx).await,
+            (EventType::Updated, "instance-1".to_string())
+        );
+        assert_eq!(
+            next_event(&mut rx).await,
+            (EventType::Removed, "instance-2".to_string())
+        );
+        assert!(rx.try_recv().is_err());
+
+        // A cursor from before a clear falls back to the snapshot
+        registry.clear().await;
+        registry
+            .register_manifest(&manifest("instance-3"))
+            .await
+            .unwrap();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        registry
+            .watch_manifests_with_replay(
+                "test-service",
+                watch(tx, Arc::new(std::sync::Mutex::new(Vec::new()))),
+                Some(cursor),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            next_event(&mut rx).await,
+            (EventType::Added, "instance-3".to_string())
+        );
+        assert!(rx.try_recv().is_err());
+
+        // So does a cursor the registry never issued
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        registry
+            .watch_manifests_with_replay(
+                "test-service",
+                watch(tx, Arc::new(std::sync::Mutex::new(Vec::new()))),
+                Some(u64::MAX),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            next_event(&mut rx).await,
+            (EventType::Added, "instance-3".to_string())
+        );
+        assert!(rx.try_recv().is_err());
+    }
+
+    async fn next_event(
+        rx: &mut tokio::sync::mpsc::UnboundedReceiver<(EventType, String)>,
+    ) -> (EventType, String) {
+        tokio::time::timeout(std::time::Duration::from_secs(2), rx.recv())
+            .await
+            .expect("timed out waiting for event")
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_watch_resubscribes_after_disconnect() {
+        let registry = MemoryRegistry::new();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+        let config = WatchConfig {
+            initial_backoff: std::time::Duration::from_millis(50),
+            ..Default::default()
+        };
+        let handle = registry
+            .watch_manifests_with(
+                "test-service",
+                Box::new(move |event: &ManifestEvent| {
+                    let _ = tx.send((event.event_type, event.manifest.instance_id.clone()));
+                }),
+                config,
+            )
+            .await
+            .unwrap();
+
+        let mut manifest = new_manifest("test-service", "v1.0.0", "instance-1");
+        manifest.endpoints.health = "/health".to_string();
+
+        // Change while disconnected is delivered by the resync
+        registry.disconnect_watchers().await;
+        registry.register_manifest(&manifest).await.unwrap();
+        assert_eq!(
+            next_event(&mut rx).await,
+            (EventType::Added, "instance-1".to_string())
+        );
+
+        // Subsequent changes arrive over the new subscription
+        manifest.instance_id = "instance-2".to_string();
+        registry.register_manifest(&manifest).await.unwrap();
+        assert_eq!(
+            next_event(&mut rx).await,
+            (EventType::Added, "instance-2".to_string())
+        );
+
+        handle.stop();
+        assert!(handle.is_stopped());
+    }
+
+    #[tokio::test]
+    async fn test_verify_registry_schemas() {
+        let registry = MemoryRegistry::with_config(RegistryConfig {
+            verify_registry_schemas: true,
+            ..Default::default()
+        });
+        let schema = serde_json::json!({"openapi": "3.1.0", "paths": {}});
+        let hash = crate::manifest::calculate_schema_checksum(&schema).unwrap();
+
+        let mut manifest = new_manifest("test-service", "v1.0.0", "instance-123");
+        manifest.endpoints.health = "/health".to_string();
+        manifest.add_schema(crate::types::SchemaDescriptor {
+            schema_type: crate::types::SchemaType::OpenAPI,
+            schema_id: None,
+            spec_version: "3.1.0".to_string(),
+            location: crate::types::SchemaLocation {
+                location_type: crate::types::LocationType::Registry,
+                url: None,
+                registry_path: Some("/schemas/test-service/openapi".to_string()),
+                headers: None,
+            },
+            content_type: "application/json".to_string(),
+            inline_schema: None,
+            hash: hash.clone(),
+            size: 1024,
+            compatibility: None,
+            metadata: None,
+        });
+
+        let err = registry.register_manifest(&manifest).await.unwrap_err();
+        assert!(
+            matches!(err, Error::SchemaFetchFailed(ref msg) if msg.contains("/schemas/test-service/openapi (missing)"))
+        );
+
+        registry
+            .publish_schema(
+                "/schemas/test-service/openapi",
+                &serde_json::json!({"openapi": "3.0.0"}),
+            )
+            .await
+            .unwrap();
+        let err = registry.register_manifest(&manifest).await.unwrap_err();
+        assert!(matches!(err, Error::SchemaFetchFailed(ref msg) if msg.contains("hash mismatch")));
+
+        registry
+            .publish_schema("/schemas/test-service/openapi", &schema)
+            .await
+            .unwrap();
+        registry.register_manifest(&manifest).await.unwrap();
+        assert!(registry.get_manifest("instance-123").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_latest_schema() {
+        use crate::types::{InstanceMetadata, InstanceStatus, LocationType, SchemaType};
+
+        let registry = MemoryRegistry::new();
+        let instances = [
+            ("v1.0.0", InstanceStatus::Healthy),
+            ("v1.4.2", InstanceStatus::Healthy),
+            ("v2.0.0", InstanceStatus::Healthy),
+            ("v3.0.0", InstanceStatus::Unhealthy),
+        ];
+        for (version, status) in instances {
+            let schema = serde_json::json!({"openapi": "3.1.0", "info": {"version": version}});
+            let mut manifest = new_manifest("user-service", version, format!("user-{version}"));
+            manifest.endpoints.health = "/health".to_string();
+            manifest.instance = Some(InstanceMetadata {
+                address: "10.0.0.1:8080".to_string(),
+                region: None,
+                zone: None,
+                labels: None,
+                weight: None,
+                status,
+                role: None,
+                deployment: None,
+                started_at: 0,
+                expected_schema_checksum: None,
+            });
+            manifest.add_schema(crate::types::SchemaDescriptor {
+                schema_type: SchemaType::OpenAPI,
+                schema_id: None,
+                spec_version: "3.1.0".to_string(),
+                location: crate::types::SchemaLocation {
+                    location_type: LocationType::Inline,
+                    url: None,
+                    registry_path: None,
+                    headers: None,
+                },
+                content_type: "application/json".to_string(),
+                hash: crate::manifest::calculate_schema_checksum(&schema).unwrap(),
+                inline_schema: Some(schema),
+                size: 0,
+                compatibility: None,
+                metadata: None,
+            });
+            registry.register_manifest(&manifest).await.unwrap();
+        }
+
+        let (_, schema) = registry
+            .resolve_latest_schema("user-service", SchemaType::OpenAPI)
+            .await
+            .unwrap();
+        assert_eq!(schema["info"]["version"], "v2.0.0");
+
+        let (descriptor, schema) = registry
+            .resolve_schema_version("user-service", SchemaType::OpenAPI, "^1.0")
+            .await
+            .unwrap();
+        assert_eq!(schema["info"]["version"], "v1.4.2");
+        assert_eq!(descriptor.schema_type, SchemaType::OpenAPI);
+
+        let err = registry
+            .resolve_schema_version("user-service", SchemaType::OpenAPI, ">=4")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::SchemaNotFound));
+        let err = registry
+            .resolve_latest_schema("user-service", SchemaType::AsyncAPI)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::SchemaNotFound));
+        let err = registry
+            .resolve_latest_schema("billing", SchemaType::OpenAPI)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::ManifestNotFound));
+        let err = registry
+            .resolve_schema_version("user-service", SchemaType::OpenAPI, "not a range")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Validation { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_require_checksum() {
+        let registry = MemoryRegistry::with_config(RegistryConfig {
+            require_checksum: true,
+            ..Default::default()
+        });
+
+        let mut manifest = new_manifest("test-service", "v1.0.0", "instance-123");
+        manifest.endpoints.health = "/health".to_string();
+        manifest.add_schema(crate::types::SchemaDescriptor {
+            schema_type: crate::types::SchemaType::OpenAPI,
+            schema_id: None,
+            spec_version: "3.1.0".to_string(),
+            location: crate::types::SchemaLocation {
+                location_type: crate::types::LocationType::HTTP,
+                url: Some("http://test-service/openapi.json".to_string()),
+                registry_path: None,
+                headers: None,
+            },
+            content_type: "application/json".to_string(),
+            inline_schema: None,
+            hash: "a".repeat(64),
+            size: 1024,
+            compatibility: None,
+            metadata: None,
+        });
+
+        let err = registry.register_manifest(&manifest).await.unwrap_err();
+        assert!(matches!(err, Error::Validation { ref field, .. } if field == "checksum"));
+        let err = registry.register_dry_run(&manifest).await.unwrap_err();
+        assert!(matches!(err, Error::Validation { ref field, .. } if field == "checksum"));
+
+        manifest.checksum = "b".repeat(64);
+        let err = registry.register_manifest(&manifest).await.unwrap_err();
+        assert!(matches!(err, Error::Validation { ref field, .. } if field == "checksum"));
+
+        manifest.update_checksum().unwrap();
+        registry.register_manifest(&manifest).await.unwrap();
+        assert!(registry.get_manifest("instance-123").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_strict_content_types() {
+        let mut manifest = new_manifest("test-service", "v1.0.0", "instance-123");
+        manifest.endpoints.health = "/health".to_string();
+        manifest.add_schema(crate::types::SchemaDescriptor {
+            schema_type: crate::types::SchemaType::GRPC,
+            schema_id: None,
+            spec_version: "proto3".to_string(),
+            location: crate::types::SchemaLocation {
+                location_type: crate::types::LocationType::HTTP,
+                url: Some("http://test-service/service.proto".to_string()),
+                registry_path: None,
+                headers: None,
+            },
+            content_type: "text/csv".to_string(),
+            inline_schema: None,
+            hash: "a".repeat(64),
+            size: 1024,
+            compatibility: None,
+            metadata: None,
+        });
+
+        // Lenient by default
+        MemoryRegistry::new()
+            .register_manifest(&manifest)
+            .await
+            .unwrap();
+
+        let registry = MemoryRegistry::with_config(RegistryConfig {
+            strict_content_types: true,
+            ..Default::default()
+        });
+        let err = registry.register_manifest(&manifest).await.unwrap_err();
+        assert!(matches!(err, Error::Validation { ref field, .. } if field == "content_type"));
+        let err = registry.register_dry_run(&manifest).await.unwrap_err();
+        assert!(matches!(err, Error::Validation { ref field, .. } if field == "content_type"));
+
+        manifest.schemas[0].content_type = "application/x-protobuf".to_string();
+        registry.register_dry_run(&manifest).await.unwrap();
+        registry.register_manifest(&manifest).await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_close_registry() {
         let registry = MemoryRegistry::new();
@@ -376,4 +1698,19 @@ mod tests {
         let result = registry.health().await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_watch_schemas_unsupported() {
+        let registry = MemoryRegistry::new();
+        let err = registry
+            .watch_schemas(
+                "/schemas/test",
+                Box::new(|_: &crate::registry::SchemaEvent| {}),
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::Unsupported { ref operation } if operation == "watch_schemas")
+        );
+    }
 }
@@ -1,5 +1,10 @@
 //! Registry implementations
 
+pub mod cached;
+pub mod gc;
+
 #[cfg(feature = "memory-registry")]
 pub mod memory;
 
+#[cfg(feature = "dynamo-registry")]
+pub mod dynamo;
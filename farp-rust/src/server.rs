@@ -0,0 +1,388 @@
+//! HTTP pull server for manifests and schemas
+//!
+//! Serves any [`SchemaRegistry`] over HTTP so gateways can pull without a
+//! FARP client library:
+//!
+//! | Route | Response |
+//! |-------|----------|
+//! | `GET /farp/services/{service}/instances` | Manifests of every instance of the service |
+//! | `GET /farp/manifests/{instance_id}` | One instance's manifest |
+//! | `GET /farp/schemas/{path}` | The schema published at `/{path}` (may contain `/`) |
+//! | `GET /farp/merged/openapi?service=...` | OpenAPI specs of the listed services, merged live |
+//! | `GET /farp/events?service=...&since=...` | Long-poll for manifest events after cursor `since` |
+//!
+//! The events endpoint answers immediately when buffered [`ManifestEvent`]s
+//! newer than `since` exist, and otherwise blocks until one arrives or the
+//! long-poll timeout elapses. The timeout is the service's
+//! [`PollingConfig::long_polling_timeout`](crate::types::PollingConfig) when
+//! a manifest enables long polling, or [`DEFAULT_LONG_POLL_TIMEOUT`]. The
+//! response is `{"events": [...], "cursor": n}`; pass `cursor` back as
+//! `since` to resume. The server starts watching a service on its first
+//! poll and keeps the last [`EVENT_BUFFER_SIZE`] events. Only services with
+//! a registered manifest can be watched (404 otherwise), and at most
+//! [`MAX_WATCHED_SERVICES`] are watched at once; polls for further services
+//! answer 503.
+//!
+//! Sequences are assigned as events enter a service's buffer, starting from
+//! the time the buffer was created, so cursors from before a server restart
+//! fall below every new one. A `since` older than the buffered events or
+//! newer than the latest one answers 410 Gone; the client should re-list
+//! the service and poll without `since`. At most
+//! [`MAX_WATCHERS_PER_SERVICE`] long polls wait on one service at a time;
+//! further ones answer 503.
+//!
+//! Errors are returned as `{"error": "..."}` with a status derived from the
+//! [`Error`] variant (404 for missing manifests and schemas, 400 for bad
+//! requests, 410 for expired cursors, 501 for unsupported operations, 503
+//! for unavailable backends).
+//!
+//! ```no_run
+//! use farp::registry::memory::MemoryRegistry;
+//! use std::sync::Arc;
+//!
+//! # async fn run() -> std::io::Result<()> {
+//! let app = farp::server::router(Arc::new(MemoryRegistry::new()));
+//! let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
+//! axum::serve(listener, app).await
+//! # }
+//! ```
+
+use crate::errors::Error;
+use crate::merger::{Merger, ServiceSchema};
+use crate::registry::{descriptor_schema, ManifestEvent, SchemaRegistry};
+use crate::types::{SchemaManifest, SchemaType};
+use axum::extract::{Path, RawQuery, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::Notify;
+
+/// Long-poll timeout when no manifest of the service configures one
+pub const DEFAULT_LONG_POLL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Number of recent events kept per service for long-poll consumers
+pub const EVENT_BUFFER_SIZE: usize = 1024;
+
+/// Number of long polls that may wait on one service at a time
+pub const MAX_WATCHERS_PER_SERVICE: usize = 256;
+
+/// Number of services the events endpoint watches at once
+pub const MAX_WATCHED_SERVICES: usize = 1024;
+
+#[derive(Clone)]
+struct AppState {
+    registry: Arc<dyn SchemaRegistry>,
+    merger: Arc<Merger>,
+    events: Arc<tokio::sync::Mutex<HashMap<String, Arc<EventBuffer>>>>,
+}
+
+/// Recent events of one watched service
+struct EventBuffer {
+    log: Mutex<EventLog>,
+    notify: Notify,
+    watchers: AtomicUsize,
+}
+
+/// Buffered events with the sequence range cursors may resume from
+struct EventLog {
+    events: VecDeque<ManifestEvent>,
+    /// Cursors below this can't catch up from `events`
+    evicted_through: u64,
+    /// Sequence of the latest event (the starting sequence before any)
+    latest: u64,
+}
+
+impl EventBuffer {
+    fn new() -> Self {
+        // Starting from the clock keeps sequences increasing across restarts
+        let start = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_micros() as u64);
+        Self {
+            log: Mutex::new(EventLog {
+                events: VecDeque::new(),
+                evicted_through: start,
+                latest: start,
+            }),
+            notify: Notify::new(),
+            watchers: AtomicUsize::new(0),
+        }
+    }
+
+    /// Assigns the event the next sequence and buffers it
+    fn push(&self, event: &ManifestEvent) {
+        let mut log = self.log.lock().unwrap_or_else(|e| e.into_inner());
+        if log.events.len() == EVENT_BUFFER_SIZE {
+            if let Some(evicted) = log.events.pop_front() {
+                log.evicted_through = evicted.sequence;
+            }
+        }
+        log.latest += 1;
+        let sequence = log.latest;
+        log.events.push_back(ManifestEvent {
+            sequence,
+            ..event.clone()
+        });
+        drop(log);
+        self.notify.notify_waiters();
+    }
+
+    /// Returns the buffered events after `cursor` (all without one) and the
+    /// latest sequence
+    ///
+    /// Fails with [`Error::CursorExpired`] for cursors outside the buffered range.
+    fn since(&self, cursor: Option<u64>) -> crate::errors::Result<(Vec<ManifestEvent>, u64)> {
+        let log = self.log.lock().unwrap_or_else(|e| e.into_inner());
+        let cursor = match cursor {
+            Some(cursor) if cursor < log.evicted_through || cursor > log.latest => {
+                return Err(Error::CursorExpired(cursor));
+            }
+            Some(cursor) => cursor,
+            None => 0,
+        };
+        let events = log
+            .events
+            .iter()
+            .filter(|e| e.sequence > cursor)
+            .cloned()
+            .collect();
+        Ok((events, log.latest))
+    }
+
+    /// Claims a long-poll slot, released when the guard drops
+    fn watch(self: &Arc<Self>) -> Option<WatcherSlot> {
+        let slot = WatcherSlot(self.clone());
+        (self.watchers.fetch_add(1, Ordering::AcqRel) < MAX_WATCHERS_PER_SERVICE).then_some(slot)
+    }
+}
+
+/// A long poll waiting on an [`EventBuffer`]
+struct WatcherSlot(Arc<EventBuffer>);
+
+impl Drop for WatcherSlot {
+    fn drop(&mut self) {
+        self.0.watchers.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Builds the pull server routes, merging with the default [`Merger`]
+pub fn router(registry: Arc<dyn SchemaRegistry>) -> Router {
+    router_with_merger(registry, Merger::default())
+}
+
+/// Builds the pull server routes, merging OpenAPI specs with `merger`
+pub fn router_with_merger(registry: Arc<dyn SchemaRegistry>, merger: Merger) -> Router {
+    Router::new()
+        .route("/farp/services/{service}/instances", get(service_instances))
+        .route("/farp/manifests/{instance_id}", get(manifest))
+        .route("/farp/schemas/{*path}", get(schema))
+        .route("/farp/merged/openapi", get(merged_openapi))
+        .route("/farp/events", get(events))
+        .with_state(AppState {
+            registry,
+            merger: Arc::new(merger),
+            events: Arc::default(),
+        })
+}
+
+async fn service_instances(State(state): State<AppState>, Path(service): Path<String>) -> Response {
+    match state.registry.list_manifests(&service).await {
+        Ok(manifests) => json(StatusCode::OK, &manifests),
+        Err(e) => error_response(&e),
+    }
+}
+
+async fn manifest(State(state): State<AppState>, Path(instance_id): Path<String>) -> Response {
+    match state.registry.get_manifest(&instance_id).await {
+        Ok(manifest) => json(StatusCode::OK, &manifest),
+        Err(e) => error_response(&e),
+    }
+}
+
+async fn schema(State(state): State<AppState>, Path(path): Path<String>) -> Response {
+    // The wildcard doesn't capture the `/` that separates it from the route
+    match state.registry.fetch_schema(&format!("/{path}")).await {
+        Ok(schema) => json(StatusCode::OK, &schema),
+        Err(e) => error_response(&e),
+    }
+}
+
+async fn merged_openapi(State(state): State<AppState>, RawQuery(query): RawQuery) -> Response {
+    let services = query_services(query.as_deref().unwrap_or_default());
+    if services.is_empty() {
+        return error_response(&Error::validation(
+            "service",
+            "at least one service is required",
+        ));
+    }
+
+    let mut schemas = Vec::new();
+    for service in &services {
+        let manifests = match state.registry.list_manifests(service).await {
+            Ok(manifests) => manifests,
+            Err(e) => return error_response(&e),
+        };
+        let Some(manifest) = latest_with_openapi(manifests) else {
+            continue;
+        };
+        let descriptor = manifest
+            .get_schema(SchemaType::OpenAPI)
+            .expect("filtered to manifests with an OpenAPI schema");
+        match descriptor_schema(state.registry.as_ref(), descriptor).await {
+            Ok(schema) => schemas.push(ServiceSchema {
+                manifest,
+                schema,
+                parsed: None,
+            }),
+            Err(e) => return error_response(&e),
+        }
+    }
+
+    match state.merger.merge(schemas) {
+        Ok(result) => json(StatusCode::OK, &result.spec),
+        Err(e) => error_response(&e),
+    }
+}
+
+async fn events(State(state): State<AppState>, RawQuery(query): RawQuery) -> Response {
+    let query = query.unwrap_or_default();
+    let services = query_services(&query);
+    let [service] = services.as_slice() else {
+        return error_response(&Error::validation(
+            "service",
+            "exactly one service is required",
+        ));
+    };
+    let since = form_urlencoded::parse(query.as_bytes())
+        .find(|(key, _)| key == "since")
+        .map(|(_, value)| value.parse::<u64>())
+        .transpose();
+    let Ok(since) = since else {
+        return error_response(&Error::validation("since", "must be an event sequence"));
+    };
+
+    let buffer = match event_buffer(&state, service).await {
+        Ok(buffer) => buffer,
+        Err(e) => return error_response(&e),
+    };
+    let Some(_slot) = buffer.watch() else {
+        return error_response(&Error::backend_unavailable(format!(
+            "too many watchers for service {service}"
+        )));
+    };
+    let timeout = long_poll_timeout(state.registry.as_ref(), service).await;
+    let deadline = tokio::time::Instant::now() + timeout;
+
+    let (events, latest) = loop {
+        let notified = buffer.notify.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+
+        let (events, latest) = match buffer.since(since) {
+            Ok(found) => found,
+            Err(e) => return error_response(&e),
+        };
+        if !events.is_empty() || tokio::time::timeout_at(deadline, notified).await.is_err() {
+            break (events, latest);
+        }
+    };
+
+    let cursor = events.last().map_or(latest, |e| e.sequence);
+    json(
+        StatusCode::OK,
+        &serde_json::json!({ "events": events, "cursor": cursor }),
+    )
+}
+
+/// Returns the service's event buffer, watching the service on first use
+///
+/// Fails with `Error::ManifestNotFound` for services without a registered
+/// manifest and with `Error::BackendUnavailable` once
+/// [`MAX_WATCHED_SERVICES`] are watched, so clients can't grow the buffers
+/// and watch tasks without bound.
+async fn event_buffer(state: &AppState, service: &str) -> crate::errors::Result<Arc<EventBuffer>> {
+    let mut buffers = state.events.lock().await;
+    if let Some(buffer) = buffers.get(service) {
+        return Ok(buffer.clone());
+    }
+    if state.registry.list_manifests(service).await?.is_empty() {
+        return Err(Error::ManifestNotFound);
+    }
+    if buffers.len() >= MAX_WATCHED_SERVICES {
+        return Err(Error::backend_unavailable("too many watched services"));
+    }
+
+    let buffer = Arc::new(EventBuffer::new());
+    let sink = buffer.clone();
+    state
+        .registry
+        .watch_manifests(
+            service,
+            Box::new(move |event: &ManifestEvent| sink.push(event)),
+        )
+        .await?;
+    buffers.insert(service.to_string(), buffer.clone());
+    Ok(buffer)
+}
+
+/// Long-poll timeout declared by the service's manifests, if any enables long polling
+async fn long_poll_timeout(registry: &dyn SchemaRegistry, service: &str) -> Duration {
+    let manifests = registry.list_manifests(service).await.unwrap_or_default();
+    manifests
+        .iter()
+        .filter_map(|m| m.webhook.as_ref()?.http_routes.as_ref()?.polling.as_ref())
+        .filter(|polling| polling.long_polling)
+        .find_map(|polling| polling.long_polling_timeout_duration().ok().flatten())
+        .unwrap_or(DEFAULT_LONG_POLL_TIMEOUT)
+}
+
+/// Collects `service` query values; each may also be a comma-separated list
+fn query_services(query: &str) -> Vec<String> {
+    let mut services: Vec<String> = Vec::new();
+    for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+        if key != "service" {
+            continue;
+        }
+        for name in value.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            if !services.iter().any(|s| s == name) {
+                services.push(name.to_string());
+            }
+        }
+    }
+    services
+}
+
+/// Picks the most recently updated instance that publishes an OpenAPI schema
+fn latest_with_openapi(manifests: Vec<SchemaManifest>) -> Option<SchemaManifest> {
+    manifests
+        .into_iter()
+        .filter(|m| m.get_schema(SchemaType::OpenAPI).is_some())
+        .max_by_key(|m| m.updated_at)
+}
+
+fn json<T: Serialize + ?Sized>(status: StatusCode, value: &T) -> Response {
+    match serde_json::to_vec(value) {
+        Ok(body) => (status, [(header::CONTENT_TYPE, "application/json")], body).into_response(),
+        Err(e) => error_response(&Error::from(e)),
+    }
+}
+
+fn error_response(err: &Error) -> Response {
+    let status = match err {
+        Error::ManifestNotFound | Error::SchemaNotFound => StatusCode::NOT_FOUND,
+        Error::Validation { .. } => StatusCode::BAD_REQUEST,
+        Error::CursorExpired(_) => StatusCode::GONE,
+        Error::Unsupported { .. } => StatusCode::NOT_IMPLEMENTED,
+        Error::BackendUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    let body = HashMap::from([("error", err.to_string())]);
+    let body = serde_json::to_vec(&body).unwrap_or_default();
+    (status, [(header::CONTENT_TYPE, "application/json")], body).into_response()
+}
@@ -7,7 +7,19 @@ use async_trait::async_trait;
 use flate2::read::GzDecoder;
 use flate2::write::GzEncoder;
 use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::future::Future;
 use std::io::{Read, Write};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, ReadBuf};
+
+#[cfg(feature = "backend-s3")]
+pub mod s3;
 
 /// Storage backend trait for low-level key-value operations
 ///
@@ -48,6 +60,110 @@ pub struct StorageEvent {
     pub value: Option<Vec<u8>>,
 }
 
+/// Emulates [`StorageBackend::watch`] for stores without change notifications
+///
+/// Every `interval` the watcher lists the prefix, reads each key and diffs
+/// the values against the previous snapshot, emitting `Added`, `Updated` and
+/// `Removed` events. Keys present when watching starts are not reported.
+/// Each poll reads every matching value, so keep prefixes narrow on stores
+/// that charge per request. Polling stops once the receiver is dropped.
+#[derive(Debug, Clone)]
+pub struct PollingWatcher {
+    interval: Duration,
+    buffer: usize,
+}
+
+impl PollingWatcher {
+    /// Creates a watcher polling every `interval`
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            buffer: 100,
+        }
+    }
+
+    /// Sets the capacity of the event channel
+    pub fn with_buffer(mut self, buffer: usize) -> Self {
+        self.buffer = buffer.max(1);
+        self
+    }
+
+    /// Starts polling `prefix` on `backend`
+    pub async fn watch<B: StorageBackend + 'static>(
+        &self,
+        backend: Arc<B>,
+        prefix: &str,
+    ) -> Result<tokio::sync::mpsc::Receiver<StorageEvent>> {
+        let prefix = prefix.to_string();
+        let mut known = snapshot(backend.as_ref(), &prefix).await?;
+        let (tx, rx) = tokio::sync::mpsc::channel(self.buffer);
+        let period = self.interval;
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+                if tx.is_closed() {
+                    break;
+                }
+
+                let Ok(mut current) = snapshot(backend.as_ref(), &prefix).await else {
+                    continue;
+                };
+
+                let mut events = Vec::new();
+                for (key, value) in &current {
+                    let event_type = match known.remove(key) {
+                        None => EventType::Added,
+                        Some(old) if &old != value => EventType::Updated,
+                        Some(_) => continue,
+                    };
+                    events.push(StorageEvent {
+                        event_type,
+                        key: key.clone(),
+                        value: Some(value.clone()),
+                    });
+                }
+                events.extend(known.drain().map(|(key, _)| StorageEvent {
+                    event_type: EventType::Removed,
+                    key,
+                    value: None,
+                }));
+                events.sort_by(|a, b| a.key.cmp(&b.key));
+
+                for event in events {
+                    if tx.send(event).await.is_err() {
+                        return;
+                    }
+                }
+                known = std::mem::take(&mut current);
+            }
+        });
+
+        Ok(rx)
+    }
+}
+
+/// Reads every key under `prefix`, skipping keys deleted mid-scan
+async fn snapshot<B: StorageBackend + ?Sized>(
+    backend: &B,
+    prefix: &str,
+) -> Result<HashMap<String, Vec<u8>>> {
+    let mut values = HashMap::new();
+    for key in backend.list(prefix).await? {
+        match backend.get(&key).await {
+            Ok(value) => {
+                values.insert(key, value);
+            }
+            Err(Error::SchemaNotFound) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(values)
+}
+
 /// Storage helper for JSON serialization and compression
 pub struct StorageHelper {
     compression_threshold: i64,
@@ -114,15 +230,335 @@ impl StorageHelper {
     }
 }
 
+/// Default chunk size for [`StorageHelper::put_chunked`] (256 KiB)
+pub const DEFAULT_CHUNK_SIZE: usize = 256 * 1024;
+
+/// Index stored alongside a chunked value, listing how to reassemble it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkIndex {
+    /// Total size of the value in bytes
+    pub total_size: u64,
+    /// Size of every chunk but the last
+    pub chunk_size: usize,
+    /// Number of chunks
+    pub chunks: usize,
+    /// SHA256 of the reassembled value (hex)
+    pub sha256: String,
+    /// Write the chunks belong to, so a rewrite never touches chunks a
+    /// reader of the previous value may still be fetching
+    #[serde(default)]
+    pub generation: String,
+}
+
+impl ChunkIndex {
+    /// Checks that the index describes a value of at most `max_size` bytes
+    /// (`0` for no limit) split consistently into its chunks
+    fn validate(&self, max_size: i64) -> Result<()> {
+        if max_size > 0 && self.total_size > max_size as u64 {
+            return Err(Error::schema_too_large(
+                usize::try_from(self.total_size).unwrap_or(usize::MAX),
+                max_size as usize,
+            ));
+        }
+        let expected = match self.chunk_size {
+            0 => None,
+            size => usize::try_from(self.total_size.div_ceil(size as u64)).ok(),
+        };
+        if expected != Some(self.chunks) {
+            return Err(Error::invalid_schema(format!(
+                "chunk index lists {} chunks of {} bytes for {} bytes",
+                self.chunks, self.chunk_size, self.total_size
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl StorageHelper {
+    /// Key holding the [`ChunkIndex`] of a chunked value
+    pub fn chunk_index_key(key: &str) -> String {
+        format!("{key}/chunks")
+    }
+
+    fn chunk_key(key: &str, generation: &str, index: usize) -> String {
+        if generation.is_empty() {
+            format!("{key}/chunks/{index:06}")
+        } else {
+            format!("{key}/chunks/{generation}/{index:06}")
+        }
+    }
+
+    /// Deletes a value stored by [`put_chunked`](Self::put_chunked), index first
+    pub async fn delete_chunked<B>(&self, backend: &B, key: &str) -> Result<()>
+    where
+        B: StorageBackend + ?Sized,
+    {
+        let index_key = Self::chunk_index_key(key);
+        backend.delete(&index_key).await?;
+        for chunk in backend.list(&format!("{index_key}/")).await? {
+            backend.delete(&chunk).await?;
+        }
+        Ok(())
+    }
+
+    /// Streams a value into `chunk_size` pieces under `key` without buffering it whole
+    ///
+    /// `total_size` is the declared length: it is checked against `max_size`
+    /// before anything is read, and streams that end early or run past it
+    /// are rejected with `Error::Validation`. Chunks are written under a new
+    /// generation and the index last, so readers never see a partial value
+    /// or a mix of two; the previous value's chunks are removed afterwards,
+    /// and the new ones if the write fails.
+    pub async fn put_chunked<B, R>(
+        &self,
+        backend: &B,
+        key: &str,
+        mut reader: R,
+        total_size: u64,
+        chunk_size: usize,
+    ) -> Result<ChunkIndex>
+    where
+        B: StorageBackend + ?Sized,
+        R: AsyncRead + Unpin + Send,
+    {
+        if self.max_size > 0 && total_size > self.max_size as u64 {
+            return Err(Error::schema_too_large(
+                total_size as usize,
+                self.max_size as usize,
+            ));
+        }
+
+        let chunk_size = chunk_size.max(1);
+        let generation = format!(
+            "{:x}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_nanos())
+        );
+        let chunk_prefix = format!("{}/", Self::chunk_index_key(key));
+        let current = format!("{chunk_prefix}{generation}/");
+        let written = Self::write_chunks(
+            backend,
+            key,
+            &generation,
+            &mut reader,
+            total_size,
+            chunk_size,
+        )
+        .await;
+        let index = match written {
+            Ok((chunks, sha256)) => ChunkIndex {
+                total_size,
+                chunk_size,
+                chunks,
+                sha256,
+                generation,
+            },
+            Err(e) => {
+                for partial in backend.list(&current).await.unwrap_or_default() {
+                    let _ = backend.delete(&partial).await;
+                }
+                return Err(e);
+            }
+        };
+        backend
+            .put(&Self::chunk_index_key(key), &serde_json::to_vec(&index)?)
+            .await?;
+
+        for stale in backend.list(&chunk_prefix).await? {
+            if !stale.starts_with(&current) {
+                backend.delete(&stale).await?;
+            }
+        }
+
+        Ok(index)
+    }
+
+    /// Writes `reader` as chunks of one generation, returning the chunk
+    /// count and SHA256 of the value
+    async fn write_chunks<B, R>(
+        backend: &B,
+        key: &str,
+        generation: &str,
+        reader: &mut R,
+        total_size: u64,
+        chunk_size: usize,
+    ) -> Result<(usize, String)>
+    where
+        B: StorageBackend + ?Sized,
+        R: AsyncRead + Unpin + Send,
+    {
+        let mut hasher = Sha256::new();
+        let mut chunks = 0;
+        let mut written: u64 = 0;
+        let mut buffer = vec![0u8; chunk_size];
+
+        loop {
+            let mut filled = 0;
+            while filled < chunk_size {
+                let n = reader.read(&mut buffer[filled..]).await?;
+                if n == 0 {
+                    break;
+                }
+                filled += n;
+            }
+            if filled == 0 {
+                break;
+            }
+
+            written += filled as u64;
+            if written > total_size {
+                return Err(Error::validation(
+                    "total_size",
+                    format!("stream is longer than the declared {total_size} bytes"),
+                ));
+            }
+
+            hasher.update(&buffer[..filled]);
+            backend
+                .put(&Self::chunk_key(key, generation, chunks), &buffer[..filled])
+                .await?;
+            chunks += 1;
+        }
+
+        if written != total_size {
+            return Err(Error::validation(
+                "total_size",
+                format!("stream ended after {written} of {total_size} bytes"),
+            ));
+        }
+        Ok((chunks, hex::encode(hasher.finalize())))
+    }
+
+    /// Opens a reader over a value stored by [`put_chunked`](Self::put_chunked)
+    ///
+    /// The stored index is checked against `max_size` and for consistent
+    /// chunking before anything is read. Chunks are fetched one at a time as
+    /// the reader is drained. A missing chunk, one longer or shorter than
+    /// the index allows, or a checksum mismatch surfaces as an I/O error
+    /// from the reader.
+    pub async fn get_chunked<B>(&self, backend: Arc<B>, key: &str) -> Result<ChunkReader>
+    where
+        B: StorageBackend + ?Sized + 'static,
+    {
+        let data = backend.get(&Self::chunk_index_key(key)).await?;
+        let index: ChunkIndex =
+            serde_json::from_slice(&data).map_err(|e| Error::invalid_schema(e.to_string()))?;
+        index.validate(self.max_size)?;
+
+        let key = key.to_string();
+        let generation = index.generation.clone();
+        Ok(ChunkReader {
+            fetch: Box::new(move |i| {
+                let backend = backend.clone();
+                let chunk_key = Self::chunk_key(&key, &generation, i);
+                Box::pin(async move { backend.get(&chunk_key).await })
+            }),
+            index,
+            next: 0,
+            current: Vec::new(),
+            offset: 0,
+            pending: None,
+            hasher: Sha256::new(),
+            verified: false,
+        })
+    }
+}
+
+type ChunkFuture = Pin<Box<dyn Future<Output = Result<Vec<u8>>> + Send>>;
+
+/// Reader returned by [`StorageHelper::get_chunked`]
+///
+/// Fetches one chunk at a time and verifies the recorded checksum once the
+/// last chunk has been consumed.
+pub struct ChunkReader {
+    fetch: Box<dyn Fn(usize) -> ChunkFuture + Send + Sync>,
+    index: ChunkIndex,
+    next: usize,
+    current: Vec<u8>,
+    offset: usize,
+    pending: Option<ChunkFuture>,
+    hasher: Sha256,
+    verified: bool,
+}
+
+impl ChunkReader {
+    /// Index describing the value being read
+    pub fn index(&self) -> &ChunkIndex {
+        &self.index
+    }
+}
+
+impl AsyncRead for ChunkReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+
+        loop {
+            if this.offset < this.current.len() {
+                let n = buf.remaining().min(this.current.len() - this.offset);
+                buf.put_slice(&this.current[this.offset..this.offset + n]);
+                this.offset += n;
+                return Poll::Ready(Ok(()));
+            }
+
+            if this.next >= this.index.chunks {
+                if !this.verified {
+                    let digest = hex::encode(std::mem::take(&mut this.hasher).finalize());
+                    if digest != this.index.sha256 {
+                        return Poll::Ready(Err(std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            "chunked value checksum mismatch",
+                        )));
+                    }
+                    this.verified = true;
+                }
+                return Poll::Ready(Ok(()));
+            }
+
+            let next = this.next;
+            let fetch = &this.fetch;
+            let pending = this.pending.get_or_insert_with(|| fetch(next));
+            match pending.as_mut().poll(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(result) => {
+                    this.pending = None;
+                    let chunk = result.map_err(|e| std::io::Error::other(e.to_string()))?;
+                    let start = this.next as u64 * this.index.chunk_size as u64;
+                    let expected =
+                        (this.index.total_size - start).min(this.index.chunk_size as u64);
+                    if chunk.len() as u64 != expected {
+                        return Poll::Ready(Err(std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            format!(
+                                "chunk {} is {} bytes, expected {expected}",
+                                this.next,
+                                chunk.len()
+                            ),
+                        )));
+                    }
+                    this.hasher.update(&chunk);
+                    this.current = chunk;
+                    this.offset = 0;
+                    this.next += 1;
+                }
+            }
+        }
+    }
+}
+
 /// Compresses data using gzip
-fn compress_data(data: &[u8]) -> Result<Vec<u8>> {
+pub(crate) fn compress_data(data: &[u8]) -> Result<Vec<u8>> {
     let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
     encoder.write_all(data)?;
     Ok(encoder.finish()?)
 }
 
 /// Decompresses gzip data
-fn decompress_data(data: &[u8]) -> Result<Vec<u8>> {
+pub(crate) fn decompress_data(data: &[u8]) -> Result<Vec<u8>> {
     let mut decoder = GzDecoder::new(data);
     let mut decompressed = Vec::new();
     decoder.read_to_end(&mut decompressed)?;
@@ -228,6 +664,30 @@ impl<B: StorageBackend> ManifestStorage<B> {
         let key = self.schema_key(path);
         self.backend.delete(&key).await
     }
+
+    /// Stores a schema body from a stream as [`DEFAULT_CHUNK_SIZE`] chunks
+    pub async fn put_schema_chunked<R: AsyncRead + Unpin + Send>(
+        &self,
+        path: &str,
+        reader: R,
+        total_size: u64,
+    ) -> Result<ChunkIndex> {
+        let key = self.schema_key(path);
+        self.helper
+            .put_chunked(&self.backend, &key, reader, total_size, DEFAULT_CHUNK_SIZE)
+            .await
+    }
+
+    /// Opens a reader over a schema stored with [`put_schema_chunked`](Self::put_schema_chunked)
+    pub async fn get_schema_streaming(&self, path: &str) -> Result<ChunkReader>
+    where
+        B: Clone + 'static,
+    {
+        let key = self.schema_key(path);
+        self.helper
+            .get_chunked(Arc::new(self.backend.clone()), &key)
+            .await
+    }
 }
 
 #[cfg(test)]
@@ -247,10 +707,277 @@ mod tests {
         assert_eq!(&decompressed[..], data_slice);
     }
 
+    /// In-process backend for exercising the storage utilities
+    #[derive(Default)]
+    struct MapBackend {
+        data: tokio::sync::RwLock<HashMap<String, Vec<u8>>>,
+    }
+
+    #[async_trait]
+    impl StorageBackend for MapBackend {
+        async fn put(&self, key: &str, value: &[u8]) -> Result<()> {
+            self.data
+                .write()
+                .await
+                .insert(key.to_string(), value.to_vec());
+            Ok(())
+        }
+
+        async fn get(&self, key: &str) -> Result<Vec<u8>> {
+            self.data
+                .read()
+                .await
+                .get(key)
+                .cloned()
+                .ok_or(Error::SchemaNotFound)
+        }
+
+        async fn delete(&self, key: &str) -> Result<()> {
+            self.data.write().await.remove(key);
+            Ok(())
+        }
+
+        async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+            let data = self.data.read().await;
+            Ok(data
+                .keys()
+                .filter(|k| k.starts_with(prefix))
+                .cloned()
+                .collect())
+        }
+
+        async fn watch(&self, _prefix: &str) -> Result<tokio::sync::mpsc::Receiver<StorageEvent>> {
+            Err(Error::unsupported("watch"))
+        }
+
+        async fn close(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_polling_watcher() {
+        let backend = Arc::new(MapBackend::default());
+        backend.put("schemas/existing", b"v1").await.unwrap();
+        backend.put("other/ignored", b"v1").await.unwrap();
+
+        let mut events = PollingWatcher::new(Duration::from_millis(10))
+            .watch(backend.clone(), "schemas/")
+            .await
+            .unwrap();
+
+        backend.put("schemas/existing", b"v2").await.unwrap();
+        backend.put("schemas/new", b"v1").await.unwrap();
+        backend.put("other/ignored", b"v2").await.unwrap();
+
+        let mut seen = Vec::new();
+        while seen.len() < 2 {
+            let event = events.recv().await.unwrap();
+            seen.push((event.event_type, event.key, event.value));
+        }
+        assert_eq!(
+            seen,
+            vec![
+                (
+                    EventType::Updated,
+                    "schemas/existing".to_string(),
+                    Some(b"v2".to_vec())
+                ),
+                (
+                    EventType::Added,
+                    "schemas/new".to_string(),
+                    Some(b"v1".to_vec())
+                ),
+            ]
+        );
+
+        backend.delete("schemas/new").await.unwrap();
+        let event = events.recv().await.unwrap();
+        assert_eq!(event.event_type, EventType::Removed);
+        assert_eq!(event.key, "schemas/new");
+        assert_eq!(event.value, None);
+    }
+
     #[test]
     fn test_storage_helper() {
         let helper = StorageHelper::new(100, 1024 * 1024);
         assert_eq!(helper.compression_threshold, 100);
         assert_eq!(helper.max_size, 1024 * 1024);
     }
+
+    fn large_schema() -> Vec<u8> {
+        let paths: serde_json::Map<String, serde_json::Value> = (0..5000)
+            .map(|i| {
+                (
+                    format!("/resources/{i}"),
+                    serde_json::json!({"get": {"operationId": format!("getResource{i}")}}),
+                )
+            })
+            .collect();
+        serde_json::to_vec(&serde_json::json!({"openapi": "3.1.0", "paths": paths})).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_chunked_round_trip() {
+        let backend = Arc::new(MapBackend::default());
+        let helper = StorageHelper::new(0, 4 * 1024 * 1024);
+        let body = large_schema();
+
+        let index = helper
+            .put_chunked(
+                backend.as_ref(),
+                "schemas/big",
+                body.as_slice(),
+                body.len() as u64,
+                64 * 1024,
+            )
+            .await
+            .unwrap();
+        assert_eq!(index.total_size, body.len() as u64);
+        assert_eq!(index.chunks, body.len().div_ceil(64 * 1024));
+        assert_eq!(
+            backend.list("schemas/big/chunks/").await.unwrap().len(),
+            index.chunks
+        );
+
+        let mut reader = helper
+            .get_chunked(backend.clone(), "schemas/big")
+            .await
+            .unwrap();
+        let mut read_back = Vec::new();
+        reader.read_to_end(&mut read_back).await.unwrap();
+        assert_eq!(read_back, body);
+
+        // Overwriting with a shorter value drops the chunks it no longer uses
+        helper
+            .put_chunked(backend.as_ref(), "schemas/big", &b"{}"[..], 2, 64 * 1024)
+            .await
+            .unwrap();
+        assert_eq!(backend.list("schemas/big/chunks/").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_chunked_enforces_sizes() {
+        let backend = MapBackend::default();
+        let helper = StorageHelper::new(0, 1024);
+        let body = vec![b' '; 2048];
+
+        let err = helper
+            .put_chunked(&backend, "too-big", body.as_slice(), 2048, 256)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::SchemaToLarge { .. }));
+
+        // The declared size must match what the stream actually yields
+        let err = helper
+            .put_chunked(&backend, "lying", body.as_slice(), 512, 256)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Validation { .. }));
+        let err = helper
+            .put_chunked(&backend, "short", &body[..100], 512, 256)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Validation { .. }));
+        assert!(backend.get("short/chunks").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_chunked_detects_corruption() {
+        let backend = Arc::new(MapBackend::default());
+        let helper = StorageHelper::new(0, 0);
+        let index = helper
+            .put_chunked(backend.as_ref(), "k", &b"0123456789"[..], 10, 4)
+            .await
+            .unwrap();
+        let chunk = format!("k/chunks/{}/000001", index.generation);
+        backend.put(&chunk, b"XXXX").await.unwrap();
+
+        let mut reader = helper.get_chunked(backend.clone(), "k").await.unwrap();
+        let mut out = Vec::new();
+        assert!(reader.read_to_end(&mut out).await.is_err());
+
+        // Chunks longer than the index allows are rejected, not buffered
+        backend.put(&chunk, &[b'X'; 4096]).await.unwrap();
+        let mut reader = helper.get_chunked(backend.clone(), "k").await.unwrap();
+        let err = reader.read_to_end(&mut Vec::new()).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn test_chunked_validates_stored_index() {
+        let backend = Arc::new(MapBackend::default());
+        let helper = StorageHelper::new(0, 1024);
+        let mut index = helper
+            .put_chunked(backend.as_ref(), "k", &b"0123456789"[..], 10, 4)
+            .await
+            .unwrap();
+
+        index.total_size = u64::MAX;
+        backend
+            .put("k/chunks", &serde_json::to_vec(&index).unwrap())
+            .await
+            .unwrap();
+        let err = helper
+            .get_chunked(backend.clone(), "k")
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(err, Error::SchemaToLarge { .. }));
+
+        index.total_size = 10;
+        index.chunks = 1;
+        backend
+            .put("k/chunks", &serde_json::to_vec(&index).unwrap())
+            .await
+            .unwrap();
+        let err = helper.get_chunked(backend, "k").await.err().unwrap();
+        assert!(matches!(err, Error::InvalidSchema(_)));
+    }
+
+    #[tokio::test]
+    async fn test_chunked_rewrite_uses_new_chunks() {
+        let backend = Arc::new(MapBackend::default());
+        let helper = StorageHelper::new(0, 0);
+        let first = helper
+            .put_chunked(backend.as_ref(), "k", &b"0123456789"[..], 10, 4)
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        let second = helper
+            .put_chunked(backend.as_ref(), "k", &b"abcdef"[..], 6, 4)
+            .await
+            .unwrap();
+        assert_ne!(first.generation, second.generation);
+
+        let mut keys = backend.list("k/chunks/").await.unwrap();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec![
+                format!("k/chunks/{}/000000", second.generation),
+                format!("k/chunks/{}/000001", second.generation),
+            ]
+        );
+
+        // A failed rewrite leaves the stored value and no chunks of its own
+        let err = helper
+            .put_chunked(backend.as_ref(), "k", &b"xyz"[..], 8, 2)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Validation { .. }));
+        assert_eq!(backend.list("k/chunks/").await.unwrap().len(), 2);
+        let mut out = Vec::new();
+        helper
+            .get_chunked(backend.clone(), "k")
+            .await
+            .unwrap()
+            .read_to_end(&mut out)
+            .await
+            .unwrap();
+        assert_eq!(out, b"abcdef");
+
+        helper.delete_chunked(backend.as_ref(), "k").await.unwrap();
+        assert!(backend.list("k/chunks").await.unwrap().is_empty());
+    }
 }
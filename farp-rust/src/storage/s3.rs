@@ -0,0 +1,187 @@
+//! S3-backed storage backend.
+//!
+//! Keys map to object keys under a configurable prefix (`<prefix>/<key>`),
+//! so one bucket can hold several namespaces. S3 has no change feed, so
+//! [`StorageBackend::watch`] is served by a [`PollingWatcher`].
+//!
+//! Works with any S3-compatible store (MinIO, R2, ...) via the client's
+//! endpoint configuration; enable path-style addressing for MinIO.
+
+use super::{PollingWatcher, StorageBackend, StorageEvent};
+use crate::errors::{Error, Result};
+use async_trait::async_trait;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::Client;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// S3 storage backend
+#[derive(Clone)]
+pub struct S3Backend {
+    client: Client,
+    bucket: String,
+    prefix: String,
+    poll_interval: Duration,
+    closed: Arc<AtomicBool>,
+}
+
+impl S3Backend {
+    /// Creates a backend storing objects in `bucket`
+    pub fn new(client: Client, bucket: impl Into<String>) -> Self {
+        Self {
+            client,
+            bucket: bucket.into(),
+            prefix: String::new(),
+            poll_interval: Duration::from_secs(10),
+            closed: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Stores every key under `prefix` within the bucket
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sets how often `watch` polls the bucket for changes
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    fn ensure_open(&self) -> Result<()> {
+        if self.closed.load(Ordering::SeqCst) {
+            return Err(Error::backend_unavailable("backend is closed"));
+        }
+        Ok(())
+    }
+
+    fn object_key(&self, key: &str) -> String {
+        if self.prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}/{}", self.prefix, key.trim_start_matches('/'))
+        }
+    }
+
+    /// Maps an object key back to the backend key, if it lies under the prefix
+    fn backend_key(&self, object_key: &str) -> Option<String> {
+        if self.prefix.is_empty() {
+            return Some(object_key.to_string());
+        }
+        object_key
+            .strip_prefix(&self.prefix)?
+            .strip_prefix('/')
+            .map(String::from)
+    }
+}
+
+#[async_trait]
+impl StorageBackend for S3Backend {
+    async fn put(&self, key: &str, value: &[u8]) -> Result<()> {
+        self.ensure_open()?;
+
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(self.object_key(key))
+            .body(ByteStream::from(value.to_vec()))
+            .send()
+            .await
+            .map_err(|e| Error::backend_unavailable(format!("s3 put_object failed: {e}")))?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        self.ensure_open()?;
+
+        let output = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(self.object_key(key))
+            .send()
+            .await
+            .map_err(|e| {
+                let not_found = e.as_service_error().is_some_and(|e| e.is_no_such_key())
+                    || e.raw_response().map(|r| r.status().as_u16()) == Some(404);
+                if not_found {
+                    Error::SchemaNotFound
+                } else {
+                    Error::backend_unavailable(format!("s3 get_object failed: {e}"))
+                }
+            })?;
+
+        let body = output
+            .body
+            .collect()
+            .await
+            .map_err(|e| Error::backend_unavailable(format!("s3 body read failed: {e}")))?;
+        Ok(body.into_bytes().to_vec())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.ensure_open()?;
+
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(self.object_key(key))
+            .send()
+            .await
+            .map_err(|e| Error::backend_unavailable(format!("s3 delete_object failed: {e}")))?;
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        self.ensure_open()?;
+
+        let mut keys = Vec::new();
+        let mut continuation_token: Option<String> = None;
+
+        loop {
+            let output = self
+                .client
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .prefix(self.object_key(prefix))
+                .set_continuation_token(continuation_token.take())
+                .send()
+                .await
+                .map_err(|e| {
+                    Error::backend_unavailable(format!("s3 list_objects_v2 failed: {e}"))
+                })?;
+
+            keys.extend(
+                output
+                    .contents()
+                    .iter()
+                    .filter_map(|object| object.key())
+                    .filter_map(|key| self.backend_key(key)),
+            );
+
+            match output.next_continuation_token() {
+                Some(token) if output.is_truncated().unwrap_or(false) => {
+                    continuation_token = Some(token.to_string());
+                }
+                _ => break,
+            }
+        }
+
+        Ok(keys)
+    }
+
+    async fn watch(&self, prefix: &str) -> Result<tokio::sync::mpsc::Receiver<StorageEvent>> {
+        self.ensure_open()?;
+
+        PollingWatcher::new(self.poll_interval)
+            .watch(Arc::new(self.clone()), prefix)
+            .await
+    }
+
+    async fn close(&self) -> Result<()> {
+        self.closed.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+}
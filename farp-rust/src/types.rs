@@ -3,8 +3,11 @@
 //! This module contains all the data structures used in the FARP protocol,
 //! including schemas, manifests, routing configurations, and metadata types.
 
+use crate::errors::Result;
+use crate::util::parse_duration;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 
 /// Schema type enumeration
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -65,6 +68,71 @@ impl SchemaType {
             SchemaType::Custom => "custom",
         }
     }
+
+    /// Media types a descriptor of this type may declare as `content_type`
+    ///
+    /// Every type but gRPC, GraphQL SDL and Thrift IDL is a JSON or YAML
+    /// document; those three also accept JSON (descriptor sets,
+    /// introspection results, IDL summaries). Empty for `Custom`, which
+    /// accepts anything.
+    pub fn allowed_content_types(&self) -> Vec<&'static str> {
+        const JSON_YAML: &[&str] = &[
+            "application/json",
+            "application/yaml",
+            "application/x-yaml",
+            "text/yaml",
+        ];
+        match self {
+            SchemaType::OpenAPI => [
+                JSON_YAML,
+                &[
+                    "application/vnd.oai.openapi",
+                    "application/vnd.oai.openapi+json",
+                    "application/vnd.oai.openapi+yaml",
+                ],
+            ]
+            .concat(),
+            SchemaType::AsyncAPI => [
+                JSON_YAML,
+                &[
+                    "application/vnd.aai.asyncapi",
+                    "application/vnd.aai.asyncapi+json",
+                    "application/vnd.aai.asyncapi+yaml",
+                ],
+            ]
+            .concat(),
+            SchemaType::ORPC => JSON_YAML.to_vec(),
+            SchemaType::GRPC => vec![
+                "application/x-protobuf",
+                "application/protobuf",
+                "text/x-protobuf",
+                "text/plain",
+                "application/json",
+            ],
+            SchemaType::GraphQL => vec!["application/graphql", "text/plain", "application/json"],
+            SchemaType::Thrift => vec!["application/x-thrift", "text/plain", "application/json"],
+            SchemaType::Avro => vec![
+                "application/vnd.apache.avro+json",
+                "application/json",
+                "avro/binary",
+            ],
+            SchemaType::Custom => Vec::new(),
+        }
+    }
+
+    /// Checks `content_type` against [`allowed_content_types`](Self::allowed_content_types)
+    ///
+    /// Matching ignores case and parameters such as `; charset=utf-8`.
+    pub fn accepts_content_type(&self, content_type: &str) -> bool {
+        let allowed = self.allowed_content_types();
+        let essence = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        allowed.is_empty() || allowed.contains(&essence.as_str())
+    }
 }
 
 impl std::fmt::Display for SchemaType {
@@ -153,6 +221,17 @@ impl Capability {
             Capability::AMQP => "amqp",
         }
     }
+
+    /// Maps an AsyncAPI server protocol (`mqtt`, `amqps`, `wss`, ...) to the
+    /// capability it provides, if any
+    pub fn from_protocol(protocol: &str) -> Option<Capability> {
+        match protocol.to_ascii_lowercase().as_str() {
+            "mqtt" | "mqtts" | "secure-mqtt" | "mqtt5" => Some(Capability::MQTT),
+            "amqp" | "amqps" | "amqp1" => Some(Capability::AMQP),
+            "ws" | "wss" => Some(Capability::WebSocket),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for Capability {
@@ -611,7 +690,9 @@ impl std::fmt::Display for ChangeSeverity {
 }
 
 /// Data sensitivity level
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Levels are ordered from least (`Public`) to most (`PCI`) sensitive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum DataSensitivity {
     /// Public data
@@ -634,6 +715,13 @@ pub enum DataSensitivity {
     PCI,
 }
 
+impl DataSensitivity {
+    /// Returns whether access to this data must be audited (PII, PHI and PCI)
+    pub fn requires_audit(&self) -> bool {
+        *self >= DataSensitivity::PII
+    }
+}
+
 impl std::fmt::Display for DataSensitivity {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
@@ -694,7 +782,8 @@ pub struct SchemaManifest {
     pub instance: Option<InstanceMetadata>,
     /// Schemas exposed by this instance
     pub schemas: Vec<SchemaDescriptor>,
-    /// Capabilities/protocols supported
+    /// Capabilities/protocols supported (duplicates are dropped on deserialization)
+    #[serde(deserialize_with = "deserialize_capabilities")]
     pub capabilities: Vec<String>,
     /// Endpoints for introspection and health
     pub endpoints: SchemaEndpoints,
@@ -710,10 +799,96 @@ pub struct SchemaManifest {
     /// Service operational hints
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hints: Option<ServiceHints>,
+    /// Service-wide rate limit the gateway should enforce
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub quota: Option<QuotaConfig>,
     /// Timestamp of last update (Unix timestamp)
     pub updated_at: i64,
     /// SHA256 checksum of all schemas
     pub checksum: String,
+    /// Detached signature over the manifest (excluded from the signed payload)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub signature: Option<String>,
+    /// Memoized checksum (not serialized, ignored by equality)
+    #[serde(skip)]
+    pub(crate) checksum_memo: ChecksumMemo,
+}
+
+/// Deserializes a capability list, keeping the first occurrence of each entry
+fn deserialize_capabilities<'de, D>(deserializer: D) -> std::result::Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let mut capabilities = Vec::<String>::deserialize(deserializer)?;
+    let mut seen = std::collections::HashSet::new();
+    capabilities.retain(|cap| seen.insert(cap.clone()));
+    Ok(capabilities)
+}
+
+/// Memoized manifest checksum, keyed by the schema hashes it was computed from
+///
+/// Lets `calculate_manifest_checksum` skip re-sorting and re-hashing when no
+/// schema hash has changed since the last computation.
+#[derive(Debug, Default)]
+pub(crate) struct ChecksumMemo(std::sync::Mutex<Option<MemoEntry>>);
+
+/// Schema checksum keys in manifest order, and the checksum computed from them
+type MemoEntry = (Vec<ChecksumKey>, String);
+
+/// The descriptor fields that order a schema in the manifest checksum
+///
+/// `(type, spec_version, location, hash)`, where the location is the
+/// registry path or URL (empty for inline schemas).
+pub(crate) type ChecksumKey = (String, String, String, String);
+
+impl SchemaDescriptor {
+    /// Returns the key that orders this schema in the manifest checksum
+    pub(crate) fn checksum_key(&self) -> ChecksumKey {
+        let location = self
+            .location
+            .registry_path
+            .as_ref()
+            .or(self.location.url.as_ref())
+            .cloned()
+            .unwrap_or_default();
+        (
+            self.schema_type.as_str().to_string(),
+            self.spec_version.clone(),
+            location,
+            self.hash.clone(),
+        )
+    }
+}
+
+impl ChecksumMemo {
+    /// Returns the memoized checksum if it was computed from `schemas`
+    pub(crate) fn get(&self, schemas: &[SchemaDescriptor]) -> Option<String> {
+        let memo = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let (key, checksum) = memo.as_ref()?;
+        let unchanged = key.len() == schemas.len()
+            && key.iter().zip(schemas).all(|(k, s)| *k == s.checksum_key());
+        unchanged.then(|| checksum.clone())
+    }
+
+    /// Records the checksum computed from `schemas`
+    pub(crate) fn set(&self, schemas: &[SchemaDescriptor], checksum: &str) {
+        let key = schemas.iter().map(SchemaDescriptor::checksum_key).collect();
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = Some((key, checksum.to_string()));
+    }
+}
+
+impl Clone for ChecksumMemo {
+    fn clone(&self) -> Self {
+        let memo = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        Self(std::sync::Mutex::new(memo.clone()))
+    }
+}
+
+impl PartialEq for ChecksumMemo {
+    fn eq(&self, _other: &Self) -> bool {
+        // A cache never affects manifest identity
+        true
+    }
 }
 
 /// Schema descriptor describing a single API schema/contract
@@ -722,6 +897,9 @@ pub struct SchemaDescriptor {
     /// Type of schema
     #[serde(rename = "type")]
     pub schema_type: SchemaType,
+    /// Distinguishes multiple schemas of the same type (e.g. "public", "internal")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_id: Option<String>,
     /// Specification version
     pub spec_version: String,
     /// How to retrieve the schema
@@ -765,6 +943,9 @@ pub struct SchemaLocation {
 pub struct SchemaEndpoints {
     /// Health check endpoint (required)
     pub health: String,
+    /// Health probe configuration (gateway defaults apply when omitted)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<HealthCheckConfig>,
     /// Prometheus metrics endpoint
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metrics: Option<String>,
@@ -782,6 +963,58 @@ pub struct SchemaEndpoints {
     pub graphql: Option<String>,
 }
 
+/// Health probe configuration for gateways
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthCheckConfig {
+    /// Time between probes (e.g. "10s")
+    #[serde(default = "default_health_interval")]
+    pub interval: String,
+    /// Probe timeout (e.g. "2s")
+    #[serde(default = "default_health_timeout")]
+    pub timeout: String,
+    /// Consecutive successes before marking healthy
+    #[serde(default = "default_healthy_threshold")]
+    pub healthy_threshold: u32,
+    /// Consecutive failures before marking unhealthy
+    #[serde(default = "default_unhealthy_threshold")]
+    pub unhealthy_threshold: u32,
+    /// HTTP status codes treated as healthy
+    #[serde(default = "default_expected_statuses")]
+    pub expected_statuses: Vec<u16>,
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            interval: default_health_interval(),
+            timeout: default_health_timeout(),
+            healthy_threshold: default_healthy_threshold(),
+            unhealthy_threshold: default_unhealthy_threshold(),
+            expected_statuses: default_expected_statuses(),
+        }
+    }
+}
+
+fn default_health_interval() -> String {
+    "10s".to_string()
+}
+
+fn default_health_timeout() -> String {
+    "2s".to_string()
+}
+
+fn default_healthy_threshold() -> u32 {
+    2
+}
+
+fn default_unhealthy_threshold() -> u32 {
+    3
+}
+
+fn default_expected_statuses() -> Vec<u16> {
+    vec![200]
+}
+
 /// Instance metadata
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct InstanceMetadata {
@@ -814,6 +1047,104 @@ pub struct InstanceMetadata {
     pub expected_schema_checksum: Option<String>,
 }
 
+impl InstanceMetadata {
+    /// Parses `address` into its host and port
+    pub fn parsed_address(&self) -> Result<Address> {
+        Address::parse(&self.address)
+    }
+}
+
+/// A validated `host:port` instance address
+///
+/// IPv6 hosts use bracket syntax (`[::1]:8080`); [`host`](Self::host)
+/// returns them without the brackets and `Display` puts them back.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Address {
+    host: String,
+    port: u16,
+}
+
+impl Address {
+    /// Parses `host:port`, `[ipv6]:port` or a bare IP/hostname with a port
+    ///
+    /// Fails with `Error::Validation` when the port is missing, zero or out
+    /// of range, when the host is empty or contains characters other than
+    /// letters, digits, `-`, `.` and `_`, or when an IPv6 host isn't bracketed.
+    pub fn parse(address: &str) -> Result<Self> {
+        let invalid = |message: &str| {
+            crate::errors::Error::validation("instance.address", format!("{address:?}: {message}"))
+        };
+
+        let (host, port) = if let Some(rest) = address.strip_prefix('[') {
+            let (host, rest) = rest
+                .split_once(']')
+                .ok_or_else(|| invalid("unterminated '['"))?;
+            host.parse::<std::net::Ipv6Addr>()
+                .map_err(|_| invalid("bracketed host is not an IPv6 address"))?;
+            let port = rest
+                .strip_prefix(':')
+                .ok_or_else(|| invalid("missing port"))?;
+            (host, port)
+        } else {
+            let (host, port) = address
+                .rsplit_once(':')
+                .ok_or_else(|| invalid("missing port"))?;
+            if host.contains(':') {
+                return Err(invalid("IPv6 hosts must be bracketed"));
+            }
+            if host.is_empty() {
+                return Err(invalid("missing host"));
+            }
+            if !host
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_'))
+            {
+                return Err(invalid("host contains invalid characters"));
+            }
+            (host, port)
+        };
+
+        let port = port
+            .parse::<u16>()
+            .ok()
+            .filter(|&port| port != 0)
+            .ok_or_else(|| invalid("port must be 1-65535"))?;
+
+        Ok(Self {
+            host: host.to_string(),
+            port,
+        })
+    }
+
+    /// Host name or IP, without IPv6 brackets
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Port number
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+impl std::fmt::Display for Address {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.host.contains(':') {
+            write!(f, "[{}]:{}", self.host, self.port)
+        } else {
+            write!(f, "{}:{}", self.host, self.port)
+        }
+    }
+}
+
+impl std::str::FromStr for Address {
+    type Err = crate::errors::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
 /// Deployment metadata
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DeploymentMetadata {
@@ -844,7 +1175,7 @@ pub struct RoutingConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub subdomain: Option<String>,
     /// Path rewriting rules
-    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub rewrite: Vec<PathRewrite>,
     /// Strip prefix before forwarding
     #[serde(default)]
@@ -853,14 +1184,97 @@ pub struct RoutingConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub priority: Option<i32>,
     /// Tags for route grouping
-    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub tags: Vec<String>,
+    /// Session affinity; WebSocket routes default to sticky when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sticky: Option<StickyConfig>,
 }
 
 fn default_mount_strategy() -> MountStrategy {
     MountStrategy::Instance
 }
 
+/// Session affinity (sticky session) configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StickyConfig {
+    /// Set to false to opt out of the WebSocket default
+    #[serde(default = "default_sticky_enabled")]
+    pub enabled: bool,
+    /// Affinity cookie name
+    #[serde(default = "default_sticky_cookie_name")]
+    pub cookie_name: String,
+    /// Affinity lifetime (e.g. "1h")
+    #[serde(default = "default_sticky_ttl")]
+    pub ttl: String,
+    /// Request header to hash on instead of the cookie
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash_key: Option<String>,
+}
+
+impl Default for StickyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_sticky_enabled(),
+            cookie_name: default_sticky_cookie_name(),
+            ttl: default_sticky_ttl(),
+            hash_key: None,
+        }
+    }
+}
+
+fn default_sticky_enabled() -> bool {
+    true
+}
+
+fn default_sticky_cookie_name() -> String {
+    "farp_affinity".to_string()
+}
+
+fn default_sticky_ttl() -> String {
+    "1h".to_string()
+}
+
+/// Service-wide rate limit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuotaConfig {
+    /// Requests allowed per window
+    pub requests: u64,
+    /// Window length (e.g. "1m")
+    pub window: String,
+    /// Extra requests tolerated above the steady rate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub burst: Option<u64>,
+    /// Whether each consumer gets its own allowance or all share one
+    #[serde(default)]
+    pub scope: QuotaScope,
+}
+
+impl QuotaConfig {
+    /// Parses `window`, which must be a positive duration
+    pub fn window_duration(&self) -> Result<Duration> {
+        let window = parse_duration(&self.window)?;
+        if window.is_zero() {
+            return Err(crate::errors::Error::validation(
+                "quota.window",
+                "window must be positive",
+            ));
+        }
+        Ok(window)
+    }
+}
+
+/// Who a quota's allowance is counted against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuotaScope {
+    /// Each consumer has its own allowance
+    #[default]
+    Consumer,
+    /// One allowance shared by all callers
+    Global,
+}
+
 /// Path rewrite rule
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PathRewrite {
@@ -944,6 +1358,23 @@ pub struct WebhookConfig {
     pub http_routes: Option<HTTPCommunicationRoutes>,
 }
 
+/// Event delivered to service or gateway webhooks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    /// Event type
+    #[serde(rename = "type")]
+    pub event_type: WebhookEventType,
+    /// Service the event concerns
+    pub service_name: String,
+    /// Instance the event concerns
+    pub instance_id: String,
+    /// Event time (Unix timestamp)
+    pub timestamp: i64,
+    /// Event-specific payload
+    #[serde(default)]
+    pub data: HashMap<String, serde_json::Value>,
+}
+
 /// HTTP communication routes
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct HTTPCommunicationRoutes {
@@ -988,6 +1419,13 @@ pub struct CommunicationRoute {
     pub timeout: Option<String>,
 }
 
+impl CommunicationRoute {
+    /// Parses `timeout`, if set
+    pub fn timeout_duration(&self) -> Result<Option<Duration>> {
+        self.timeout.as_deref().map(parse_duration).transpose()
+    }
+}
+
 /// Polling configuration
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PollingConfig {
@@ -1004,6 +1442,26 @@ pub struct PollingConfig {
     pub long_polling_timeout: Option<String>,
 }
 
+impl PollingConfig {
+    /// Parses `interval`
+    pub fn interval_duration(&self) -> Result<Duration> {
+        parse_duration(&self.interval)
+    }
+
+    /// Parses `timeout`, if set
+    pub fn timeout_duration(&self) -> Result<Option<Duration>> {
+        self.timeout.as_deref().map(parse_duration).transpose()
+    }
+
+    /// Parses `long_polling_timeout`, if set
+    pub fn long_polling_timeout_duration(&self) -> Result<Option<Duration>> {
+        self.long_polling_timeout
+            .as_deref()
+            .map(parse_duration)
+            .transpose()
+    }
+}
+
 /// Retry configuration
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RetryConfig {
@@ -1017,6 +1475,29 @@ pub struct RetryConfig {
     pub multiplier: f64,
 }
 
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay: "100ms".to_string(),
+            max_delay: "5s".to_string(),
+            multiplier: 2.0,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Parses `initial_delay`
+    pub fn initial_delay_duration(&self) -> Result<Duration> {
+        parse_duration(&self.initial_delay)
+    }
+
+    /// Parses `max_delay`
+    pub fn max_delay_duration(&self) -> Result<Duration> {
+        parse_duration(&self.max_delay)
+    }
+}
+
 /// Schema compatibility metadata
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SchemaCompatibility {
@@ -1086,6 +1567,107 @@ pub struct ServiceHints {
     /// Service dependencies
     #[serde(default)]
     pub dependencies: Vec<ServiceDependency>,
+    /// Retry and circuit-breaking policy for gateway routes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resiliency: Option<ResiliencyConfig>,
+    /// Default data sensitivity for routes whose operations don't declare one
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub sensitivity: Option<DataSensitivity>,
+}
+
+impl ServiceHints {
+    /// Parses `recommended_timeout`, if set
+    pub fn recommended_timeout_duration(&self) -> Result<Option<Duration>> {
+        self.recommended_timeout
+            .as_deref()
+            .map(parse_duration)
+            .transpose()
+    }
+
+    /// The resiliency policy routes should carry, if one is configured
+    ///
+    /// A policy without a `per_try_timeout` defaults it to the expected
+    /// p99 latency (or p95 when p99 is unknown).
+    pub fn resiliency_policy(&self) -> Option<ResiliencyConfig> {
+        let mut policy = self.resiliency.clone()?;
+        if policy.per_try_timeout.is_none() {
+            policy.per_try_timeout = self
+                .expected_latency
+                .as_ref()
+                .and_then(|latency| latency.p99.clone().or_else(|| latency.p95.clone()));
+        }
+        Some(policy)
+    }
+}
+
+/// Retry and circuit-breaking policy for a service's routes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResiliencyConfig {
+    /// Retries after the first attempt (0 disables retries)
+    #[serde(default = "default_retry_attempts")]
+    pub retry_attempts: u32,
+    /// Timeout of each attempt (e.g. "500ms")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_try_timeout: Option<String>,
+    /// Circuit breaker guarding the upstream
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+}
+
+fn default_retry_attempts() -> u32 {
+    2
+}
+
+impl Default for ResiliencyConfig {
+    fn default() -> Self {
+        Self {
+            retry_attempts: default_retry_attempts(),
+            per_try_timeout: None,
+            circuit_breaker: None,
+        }
+    }
+}
+
+impl ResiliencyConfig {
+    /// Parses `per_try_timeout`, if set
+    pub fn per_try_timeout_duration(&self) -> Result<Option<Duration>> {
+        self.per_try_timeout
+            .as_deref()
+            .map(parse_duration)
+            .transpose()
+    }
+}
+
+/// Circuit breaker thresholds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures (5xx or connection errors) that open the circuit
+    #[serde(default = "default_consecutive_failures")]
+    pub consecutive_failures: u32,
+    /// How long an open circuit rejects traffic before probing again (e.g. "30s")
+    #[serde(default = "default_open_duration")]
+    pub open_duration: String,
+    /// Cap on concurrent requests to the upstream
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_requests: Option<u32>,
+}
+
+fn default_consecutive_failures() -> u32 {
+    5
+}
+
+fn default_open_duration() -> String {
+    "30s".to_string()
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            consecutive_failures: default_consecutive_failures(),
+            open_duration: default_open_duration(),
+            max_requests: None,
+        }
+    }
 }
 
 /// Latency profile
@@ -1452,6 +2034,91 @@ mod tests {
         assert_eq!(strategy, MountStrategy::Instance);
     }
 
+    #[test]
+    fn test_health_check_config_serde() {
+        let config = HealthCheckConfig {
+            interval: "30s".to_string(),
+            timeout: "5s".to_string(),
+            healthy_threshold: 1,
+            unhealthy_threshold: 5,
+            expected_statuses: vec![200, 204],
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        let deserialized: HealthCheckConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, config);
+
+        // Missing fields fall back to defaults
+        let partial: HealthCheckConfig = serde_json::from_str(r#"{"interval": "1m"}"#).unwrap();
+        assert_eq!(partial.interval, "1m");
+        assert_eq!(partial.timeout, "2s");
+        assert_eq!(partial.healthy_threshold, 2);
+        assert_eq!(partial.unhealthy_threshold, 3);
+        assert_eq!(partial.expected_statuses, vec![200]);
+
+        // Absent on the endpoints entirely
+        let endpoints: SchemaEndpoints = serde_json::from_str(r#"{"health": "/health"}"#).unwrap();
+        assert!(endpoints.health_check.is_none());
+    }
+
+    #[test]
+    fn test_duration_accessors() {
+        let retry = RetryConfig {
+            initial_delay: "500ms".to_string(),
+            max_delay: "1h30m".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            retry.initial_delay_duration().unwrap(),
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            retry.max_delay_duration().unwrap(),
+            Duration::from_secs(5400)
+        );
+
+        let polling = PollingConfig {
+            interval: "30x".to_string(),
+            timeout: None,
+            long_polling: true,
+            long_polling_timeout: Some("1m".to_string()),
+        };
+        assert!(matches!(
+            polling.interval_duration(),
+            Err(crate::errors::Error::Validation { .. })
+        ));
+        assert_eq!(polling.timeout_duration().unwrap(), None);
+        assert_eq!(
+            polling.long_polling_timeout_duration().unwrap(),
+            Some(Duration::from_secs(60))
+        );
+    }
+
+    #[test]
+    fn test_quota_config_serde() {
+        let quota: QuotaConfig =
+            serde_json::from_value(serde_json::json!({"requests": 100, "window": "1m"})).unwrap();
+        assert_eq!(quota.scope, QuotaScope::Consumer);
+        assert_eq!(quota.burst, None);
+        assert_eq!(quota.window_duration().unwrap(), Duration::from_secs(60));
+        for window in ["0s", "soon"] {
+            let invalid = QuotaConfig {
+                window: window.to_string(),
+                ..quota.clone()
+            };
+            assert!(invalid.window_duration().is_err());
+        }
+
+        let quota = QuotaConfig {
+            burst: Some(20),
+            scope: QuotaScope::Global,
+            ..quota
+        };
+        assert_eq!(
+            serde_json::to_value(&quota).unwrap(),
+            serde_json::json!({"requests": 100, "window": "1m", "burst": 20, "scope": "global"})
+        );
+    }
+
     #[test]
     fn test_schema_manifest_serde() {
         let manifest = SchemaManifest {
@@ -1470,12 +2137,97 @@ mod tests {
             auth: None,
             webhook: None,
             hints: None,
+            quota: None,
             updated_at: 1234567890,
             checksum: "abc123".to_string(),
+            signature: None,
+            checksum_memo: ChecksumMemo::default(),
         };
 
         let json = serde_json::to_string(&manifest).unwrap();
         let deserialized: SchemaManifest = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized.service_name, "test-service");
     }
+
+    #[test]
+    fn test_address_parse() {
+        let address = Address::parse("example.com:443").unwrap();
+        assert_eq!(address.host(), "example.com");
+        assert_eq!(address.port(), 443);
+        assert_eq!(address.to_string(), "example.com:443");
+
+        let address: Address = "[::1]:8080".parse().unwrap();
+        assert_eq!(address.host(), "::1");
+        assert_eq!(address.port(), 8080);
+        assert_eq!(address.to_string(), "[::1]:8080");
+
+        for malformed in [
+            "",
+            "example.com",
+            ":8080",
+            "example.com:",
+            "example.com:0",
+            "example.com:70000",
+            "exa mple.com:80",
+            "::1:8080",
+            "[::1]8080",
+            "[::1:8080",
+            "[example.com]:80",
+        ] {
+            assert!(
+                matches!(
+                    Address::parse(malformed),
+                    Err(crate::errors::Error::Validation { .. })
+                ),
+                "{malformed:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_content_types_per_schema_type() {
+        let cases = [
+            (SchemaType::OpenAPI, "application/yaml", "text/csv"),
+            (
+                SchemaType::AsyncAPI,
+                "application/json",
+                "application/graphql",
+            ),
+            (SchemaType::GRPC, "application/x-protobuf", "text/html"),
+            (
+                SchemaType::GraphQL,
+                "application/graphql",
+                "application/yaml",
+            ),
+            (
+                SchemaType::ORPC,
+                "application/json",
+                "application/x-protobuf",
+            ),
+            (
+                SchemaType::Thrift,
+                "application/x-thrift",
+                "application/yaml",
+            ),
+            (
+                SchemaType::Avro,
+                "application/vnd.apache.avro+json",
+                "text/plain",
+            ),
+        ];
+        for (schema_type, valid, invalid) in cases {
+            assert!(
+                schema_type.accepts_content_type(valid),
+                "{schema_type}: {valid}"
+            );
+            assert!(
+                !schema_type.accepts_content_type(invalid),
+                "{schema_type}: {invalid}"
+            );
+        }
+
+        assert!(SchemaType::OpenAPI.accepts_content_type("Application/JSON; charset=utf-8"));
+        assert!(SchemaType::OpenAPI.accepts_content_type("application/vnd.oai.openapi+yaml"));
+        assert!(SchemaType::Custom.accepts_content_type("text/csv"));
+    }
 }
@@ -0,0 +1,181 @@
+//! Small helpers shared across modules.
+
+use crate::errors::{Error, Result};
+use std::time::Duration;
+
+/// Matches a `/`-separated name (route path, service name) against a glob pattern
+///
+/// `*` matches within a single path segment, `**` matches across segments.
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    fn matches(pattern: &[u8], path: &[u8]) -> bool {
+        match pattern.split_first() {
+            None => path.is_empty(),
+            Some((b'*', rest)) if rest.first() == Some(&b'*') => {
+                let rest = &rest[1..];
+                (0..=path.len()).any(|i| matches(rest, &path[i..]))
+            }
+            Some((b'*', rest)) => {
+                let segment_end = path.iter().position(|&c| c == b'/').unwrap_or(path.len());
+                (0..=segment_end).any(|i| matches(rest, &path[i..]))
+            }
+            Some((c, rest)) => path.first() == Some(c) && matches(rest, &path[1..]),
+        }
+    }
+
+    matches(pattern.as_bytes(), path.as_bytes())
+}
+
+/// Parses a Go-style duration string such as `30s`, `1m500ms` or `1.5h`
+///
+/// Accepts one or more `<number><unit>` terms with units `ns`, `us` (or `µs`),
+/// `ms`, `s`, `m` and `h`; fractional numbers are allowed. A bare `0` is the
+/// only unitless value. Negative and overflowing durations are rejected.
+pub fn parse_duration(s: &str) -> Result<Duration> {
+    let invalid = |reason: &str| Error::validation("duration", format!("{reason}: {s:?}"));
+
+    let input = s.trim();
+    if input == "0" {
+        return Ok(Duration::ZERO);
+    }
+    if input.is_empty() {
+        return Err(invalid("empty duration"));
+    }
+
+    let mut total_nanos: u128 = 0;
+    let mut rest = input;
+    while !rest.is_empty() {
+        let number_end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .ok_or_else(|| invalid("missing unit"))?;
+        let (number, tail) = rest.split_at(number_end);
+        if number.is_empty() || number == "." {
+            return Err(invalid("expected a number"));
+        }
+        let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+        if fraction.contains('.') {
+            return Err(invalid("invalid number"));
+        }
+
+        let unit_end = tail
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_end);
+        let unit_nanos: u128 = match unit {
+            "ns" => 1,
+            "us" | "µs" => 1_000,
+            "ms" => 1_000_000,
+            "s" => 1_000_000_000,
+            "m" => 60_000_000_000,
+            "h" => 3_600_000_000_000,
+            _ => return Err(invalid("unknown unit")),
+        };
+
+        // Whole units are exact; the fraction adds less than one unit
+        let whole: u128 = match whole {
+            "" => 0,
+            digits => digits.parse().map_err(|_| invalid("overflow"))?,
+        };
+        let fraction = match fraction {
+            "" => 0,
+            digits => {
+                let value: f64 = format!("0.{digits}")
+                    .parse()
+                    .map_err(|_| invalid("invalid number"))?;
+                (value * unit_nanos as f64).round() as u128
+            }
+        };
+        total_nanos = whole
+            .checked_mul(unit_nanos)
+            .and_then(|nanos| nanos.checked_add(fraction))
+            .and_then(|nanos| total_nanos.checked_add(nanos))
+            .ok_or_else(|| invalid("overflow"))?;
+        rest = tail;
+    }
+
+    let secs = u64::try_from(total_nanos / 1_000_000_000).map_err(|_| invalid("overflow"))?;
+    Ok(Duration::new(secs, (total_nanos % 1_000_000_000) as u32))
+}
+
+/// Turns a name into an identifier safe for schema prefixes
+///
+/// Lowercases `name` and replaces every character outside `[a-z0-9_]`
+/// (including non-ASCII letters) with `_`, collapsing runs of `_` into one:
+/// `order.service v2` becomes `order_service_v2`.
+pub fn sanitize_identifier(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for c in name.chars() {
+        let c = if c.is_ascii_alphanumeric() {
+            c.to_ascii_lowercase()
+        } else {
+            '_'
+        };
+        if c != '_' || !out.ends_with('_') {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("/health", "/health"));
+        assert!(glob_match("/public/*", "/public/docs"));
+        assert!(!glob_match("/public/*", "/public/docs/v1"));
+        assert!(glob_match("/public/**", "/public/docs/v1"));
+        assert!(glob_match("/users/*/avatar", "/users/{id}/avatar"));
+        assert!(!glob_match("/public/*", "/private/docs"));
+        assert!(glob_match("payment-*", "payment-gateway"));
+        assert!(!glob_match("payment-*", "user-service"));
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(
+            parse_duration("1m500ms").unwrap(),
+            Duration::from_millis(60_500)
+        );
+        assert_eq!(parse_duration("1.5h").unwrap(), Duration::from_secs(5400));
+        assert_eq!(parse_duration("250us").unwrap(), Duration::from_micros(250));
+        assert_eq!(parse_duration("1µs").unwrap(), Duration::from_micros(1));
+        assert_eq!(parse_duration("10ns").unwrap(), Duration::from_nanos(10));
+        assert_eq!(parse_duration(" 2s ").unwrap(), Duration::from_secs(2));
+        assert_eq!(parse_duration("0").unwrap(), Duration::ZERO);
+
+        assert_eq!(parse_duration(".5s").unwrap(), Duration::from_millis(500));
+
+        for invalid in ["", "30", "s", "-1s", "1.2.3s", "10d", "1m30", "."] {
+            assert!(
+                matches!(parse_duration(invalid), Err(Error::Validation { .. })),
+                "{invalid:?} should be rejected"
+            );
+        }
+
+        // Overflow is an error, not a panic or a wrapped value
+        for overflowing in [
+            "99999999999999999999h",
+            "9999999999999999999999999999999999999999h",
+            "5124095576030432h",
+            "5124095576030431h1h",
+        ] {
+            assert!(
+                matches!(parse_duration(overflowing), Err(Error::Validation { .. })),
+                "{overflowing:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sanitize_identifier() {
+        assert_eq!(sanitize_identifier("order.service v2"), "order_service_v2");
+        assert_eq!(sanitize_identifier("User-Service"), "user_service");
+        assert_eq!(sanitize_identifier("a..b  c__d"), "a_b_c_d");
+        assert_eq!(sanitize_identifier("café-bär"), "caf_b_r");
+        assert_eq!(sanitize_identifier("日本 api"), "_api");
+        assert_eq!(sanitize_identifier("already_fine_1"), "already_fine_1");
+    }
+}
@@ -82,6 +82,117 @@ pub fn is_compatible(manifest_version: &str) -> bool {
     }
 }
 
+/// Picks the highest supported version satisfying a semver range
+///
+/// `requested_range` uses Cargo-style requirements (`^1.0`, `>=1.1, <2`,
+/// `~1.2.0`). Unparseable versions in `supported` are ignored; an
+/// unparseable range or no overlap yields `None`.
+///
+/// # Examples
+///
+/// ```
+/// use farp::version::negotiate;
+///
+/// assert_eq!(negotiate(&["1.0.0", "1.2.0", "2.0.0"], "^1.0"), Some("1.2.0".to_string()));
+/// assert_eq!(negotiate(&["1.0.0"], ">=2"), None);
+/// ```
+pub fn negotiate(supported: &[&str], requested_range: &str) -> Option<String> {
+    let range = semver::VersionReq::parse(requested_range).ok()?;
+    supported
+        .iter()
+        .filter_map(|v| semver::Version::parse(v).ok().map(|parsed| (parsed, *v)))
+        .filter(|(parsed, _)| range.matches(parsed))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, v)| v.to_string())
+}
+
+/// How a manifest's protocol version relates to [`PROTOCOL_VERSION`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionRelation {
+    /// Older than this protocol
+    Older,
+    /// Exactly this protocol version
+    Same,
+    /// Newer than this protocol
+    Newer,
+    /// Not a valid semver string
+    Invalid,
+}
+
+/// Result of [`compatibility_report`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionCompat {
+    /// The version that was checked
+    pub manifest_version: String,
+    /// This library's protocol version
+    pub protocol_version: String,
+    /// Ordering relative to the protocol version
+    pub relation: VersionRelation,
+    /// Whether manifests of this version can be processed (see [`is_compatible`])
+    pub compatible: bool,
+    /// Human-readable explanation
+    pub details: String,
+}
+
+/// Describes how a manifest version compares to this protocol version
+///
+/// # Examples
+///
+/// ```
+/// use farp::version::{compatibility_report, VersionRelation};
+///
+/// let report = compatibility_report("1.1.0");
+/// assert_eq!(report.relation, VersionRelation::Newer);
+/// assert!(!report.compatible);
+/// ```
+pub fn compatibility_report(manifest_version: &str) -> VersionCompat {
+    let compatible = is_compatible(manifest_version);
+    let protocol = semver::Version::new(
+        PROTOCOL_MAJOR as u64,
+        PROTOCOL_MINOR as u64,
+        PROTOCOL_PATCH as u64,
+    );
+
+    let (relation, details) = match semver::Version::parse(manifest_version) {
+        Err(e) => (
+            VersionRelation::Invalid,
+            format!("{manifest_version:?} is not a valid version: {e}"),
+        ),
+        Ok(version) => {
+            let relation = match version.cmp(&protocol) {
+                std::cmp::Ordering::Less => VersionRelation::Older,
+                std::cmp::Ordering::Equal => VersionRelation::Same,
+                std::cmp::Ordering::Greater => VersionRelation::Newer,
+            };
+            let details = if version.major != protocol.major {
+                format!(
+                    "major version {} differs from protocol {PROTOCOL_VERSION}",
+                    version.major
+                )
+            } else if version.minor > protocol.minor {
+                format!(
+                    "minor version {} is newer than protocol {PROTOCOL_VERSION}; upgrade farp to read it",
+                    version.minor
+                )
+            } else if relation == VersionRelation::Same {
+                format!("matches protocol {PROTOCOL_VERSION}")
+            } else {
+                format!("compatible with protocol {PROTOCOL_VERSION}")
+            };
+            (relation, details)
+        }
+    };
+
+    VersionCompat {
+        manifest_version: manifest_version.to_string(),
+        protocol_version: PROTOCOL_VERSION.to_string(),
+        relation,
+        compatible,
+        details,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -117,4 +228,49 @@ mod tests {
         assert!(!is_compatible("invalid"));
         assert!(!is_compatible(""));
     }
+
+    #[test]
+    fn test_negotiate() {
+        let supported = ["1.0.0", "1.1.0", "1.2.3", "2.0.0", "not-a-version"];
+
+        // Overlapping ranges pick the highest match
+        assert_eq!(negotiate(&supported, "^1.0"), Some("1.2.3".to_string()));
+        assert_eq!(
+            negotiate(&supported, ">=1.0, <1.2"),
+            Some("1.1.0".to_string())
+        );
+        assert_eq!(negotiate(&supported, "~1.1"), Some("1.1.0".to_string()));
+        assert_eq!(negotiate(&supported, ">=1"), Some("2.0.0".to_string()));
+
+        // Non-overlapping and invalid ranges
+        assert_eq!(negotiate(&supported, "^3.0"), None);
+        assert_eq!(negotiate(&supported, ">1.2.3, <2.0.0"), None);
+        assert_eq!(negotiate(&supported, "latest"), None);
+        assert_eq!(negotiate(&[], "*"), None);
+    }
+
+    #[test]
+    fn test_compatibility_report() {
+        let same = compatibility_report("1.0.0");
+        assert_eq!(same.relation, VersionRelation::Same);
+        assert!(same.compatible);
+
+        let patch = compatibility_report("1.0.4");
+        assert_eq!(patch.relation, VersionRelation::Newer);
+        assert!(patch.compatible);
+
+        let minor = compatibility_report("1.3.0");
+        assert_eq!(minor.relation, VersionRelation::Newer);
+        assert!(!minor.compatible);
+        assert!(minor.details.contains("minor version 3"));
+
+        let old = compatibility_report("0.9.0");
+        assert_eq!(old.relation, VersionRelation::Older);
+        assert!(!old.compatible);
+        assert!(old.details.contains("major version 0"));
+
+        let invalid = compatibility_report("v1");
+        assert_eq!(invalid.relation, VersionRelation::Invalid);
+        assert!(!invalid.compatible);
+    }
 }
@@ -0,0 +1,269 @@
+//! Integration tests for the DynamoDB registry
+//!
+//! Requires DynamoDB Local, e.g.:
+//!
+//! ```text
+//! docker run -p 8000:8000 amazon/dynamodb-local
+//! DYNAMODB_ENDPOINT=http://localhost:8000 cargo test --features dynamo-registry
+//! ```
+//!
+//! Tests are skipped when `DYNAMODB_ENDPOINT` is not set.
+
+#![cfg(feature = "dynamo-registry")]
+
+use aws_sdk_dynamodb::config::{BehaviorVersion, Credentials, Region};
+use aws_sdk_dynamodb::types::{
+    AttributeDefinition, BillingMode, GlobalSecondaryIndex, KeySchemaElement, KeyType, Projection,
+    ProjectionType, ScalarAttributeType,
+};
+use farp::prelude::*;
+use farp::registry::dynamo::DynamoRegistry;
+use farp::registry::RegistryConfig;
+
+async fn setup(table: &str) -> Option<DynamoRegistry> {
+    let endpoint = std::env::var("DYNAMODB_ENDPOINT").ok()?;
+
+    let config = aws_sdk_dynamodb::Config::builder()
+        .behavior_version(BehaviorVersion::latest())
+        .endpoint_url(endpoint)
+        .region(Region::new("us-east-1"))
+        .credentials_provider(Credentials::new("local", "local", None, None, "farp-tests"))
+        .build();
+    let client = aws_sdk_dynamodb::Client::from_conf(config);
+
+    let _ = client.delete_table().table_name(table).send().await;
+    client
+        .create_table()
+        .table_name(table)
+        .billing_mode(BillingMode::PayPerRequest)
+        .attribute_definitions(attribute("service_name"))
+        .attribute_definitions(attribute("instance_id"))
+        .key_schema(key("service_name", KeyType::Hash))
+        .key_schema(key("instance_id", KeyType::Range))
+        .global_secondary_indexes(
+            GlobalSecondaryIndex::builder()
+                .index_name("instance_id-index")
+                .key_schema(key("instance_id", KeyType::Hash))
+                .projection(
+                    Projection::builder()
+                        .projection_type(ProjectionType::All)
+                        .build(),
+                )
+                .build()
+                .unwrap(),
+        )
+        .send()
+        .await
+        .unwrap();
+
+    let registry_config = RegistryConfig {
+        backend: "dynamodb".to_string(),
+        ttl: 3600,
+        backend_config: [("table_name".to_string(), table.into())].into(),
+        ..Default::default()
+    };
+    Some(DynamoRegistry::from_config(client, registry_config).unwrap())
+}
+
+fn attribute(name: &str) -> AttributeDefinition {
+    AttributeDefinition::builder()
+        .attribute_name(name)
+        .attribute_type(ScalarAttributeType::S)
+        .build()
+        .unwrap()
+}
+
+fn key(name: &str, key_type: KeyType) -> KeySchemaElement {
+    KeySchemaElement::builder()
+        .attribute_name(name)
+        .key_type(key_type)
+        .build()
+        .unwrap()
+}
+
+fn manifest(service: &str, instance: &str) -> SchemaManifest {
+    let mut manifest = new_manifest(service, "v1.0.0", instance);
+    manifest.endpoints.health = "/health".to_string();
+    manifest.update_checksum().unwrap();
+    manifest
+}
+
+#[tokio::test]
+async fn test_dynamo_register_get_list_delete() {
+    let Some(registry) = setup("farp-crud").await else {
+        return;
+    };
+
+    registry
+        .register_manifest(&manifest("service-a", "instance-1"))
+        .await
+        .unwrap();
+    registry
+        .register_manifest(&manifest("service-a", "instance-2"))
+        .await
+        .unwrap();
+    registry
+        .register_manifest(&manifest("service-b", "instance-3"))
+        .await
+        .unwrap();
+
+    let fetched = registry.get_manifest("instance-2").await.unwrap();
+    assert_eq!(fetched.service_name, "service-a");
+
+    assert_eq!(registry.list_manifests("service-a").await.unwrap().len(), 2);
+    assert_eq!(registry.list_manifests("").await.unwrap().len(), 3);
+
+    registry.delete_manifest("instance-2").await.unwrap();
+    assert!(matches!(
+        registry.get_manifest("instance-2").await,
+        Err(Error::ManifestNotFound)
+    ));
+}
+
+#[tokio::test]
+async fn test_dynamo_service_change_moves_manifest() {
+    let Some(registry) = setup("farp-move").await else {
+        return;
+    };
+
+    registry
+        .register_manifest(&manifest("service-a", "instance-1"))
+        .await
+        .unwrap();
+    registry
+        .register_manifest(&manifest("service-b", "instance-1"))
+        .await
+        .unwrap();
+    assert!(registry
+        .list_manifests("service-a")
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        registry
+            .get_manifest("instance-1")
+            .await
+            .unwrap()
+            .service_name,
+        "service-b"
+    );
+
+    registry
+        .update_manifest(&manifest("service-c", "instance-1"))
+        .await
+        .unwrap();
+    assert!(registry
+        .list_manifests("service-b")
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(registry.list_manifests("").await.unwrap().len(), 1);
+    assert_eq!(
+        registry
+            .get_manifest("instance-1")
+            .await
+            .unwrap()
+            .service_name,
+        "service-c"
+    );
+}
+
+#[tokio::test]
+async fn test_dynamo_update_and_schemas() {
+    let Some(registry) = setup("farp-update").await else {
+        return;
+    };
+
+    let mut m = manifest("service-a", "instance-1");
+    registry.register_manifest(&m).await.unwrap();
+
+    m.service_version = "v2.0.0".to_string();
+    m.update_checksum().unwrap();
+    registry.update_manifest(&m).await.unwrap();
+    assert_eq!(
+        registry
+            .get_manifest("instance-1")
+            .await
+            .unwrap()
+            .service_version,
+        "v2.0.0"
+    );
+
+    let missing = manifest("service-a", "instance-404");
+    assert!(matches!(
+        registry.update_manifest(&missing).await,
+        Err(Error::ManifestNotFound)
+    ));
+
+    let schema = serde_json::json!({"openapi": "3.1.0", "paths": {}});
+    registry
+        .publish_schema("/schemas/service-a/openapi", &schema)
+        .await
+        .unwrap();
+    assert_eq!(
+        registry
+            .fetch_schema("/schemas/service-a/openapi")
+            .await
+            .unwrap(),
+        schema
+    );
+
+    // Schemas live in a reserved partition and never show up as manifests
+    assert_eq!(registry.list_manifests("").await.unwrap().len(), 1);
+
+    registry
+        .delete_schema("/schemas/service-a/openapi")
+        .await
+        .unwrap();
+    assert!(matches!(
+        registry.fetch_schema("/schemas/service-a/openapi").await,
+        Err(Error::SchemaNotFound)
+    ));
+}
+
+#[tokio::test]
+async fn test_dynamo_chunked_schema() {
+    let Some(registry) = setup("farp-chunked").await else {
+        return;
+    };
+
+    // Well past the 400KB item limit
+    let description = "x".repeat(1024 * 1024);
+    let schema = serde_json::json!({"openapi": "3.1.0", "info": {"description": description}});
+    let body = serde_json::to_vec(&schema).unwrap();
+    registry
+        .publish_schema_chunked(
+            "/schemas/service-a/openapi",
+            &mut std::io::Cursor::new(body.as_slice()),
+            body.len() as u64,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        registry
+            .fetch_schema("/schemas/service-a/openapi")
+            .await
+            .unwrap(),
+        schema
+    );
+
+    // Chunks live in their own partition and never show up as schemas
+    assert_eq!(
+        registry.list_schemas("/schemas/").await.unwrap(),
+        vec!["/schemas/service-a/openapi".to_string()]
+    );
+
+    // Replacing the chunked schema with a plain one drops its chunks
+    let small = serde_json::json!({"openapi": "3.1.0", "paths": {}});
+    registry
+        .publish_schema("/schemas/service-a/openapi", &small)
+        .await
+        .unwrap();
+    assert_eq!(
+        registry
+            .fetch_schema("/schemas/service-a/openapi")
+            .await
+            .unwrap(),
+        small
+    );
+}
@@ -17,6 +17,7 @@ async fn test_full_workflow() {
 
     manifest.add_schema(SchemaDescriptor {
         schema_type: SchemaType::OpenAPI,
+        schema_id: None,
         spec_version: "3.1.0".to_string(),
         location: SchemaLocation {
             location_type: LocationType::Inline,
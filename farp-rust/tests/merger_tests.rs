@@ -1,10 +1,10 @@
 //! Integration tests for OpenAPI merger
 
 use farp::manifest::new_manifest;
-use farp::merger::{Merger, MergerConfig, ServiceSchema};
+use farp::merger::{Conflict, Merger, MergerConfig, ReportFormat, ServiceSchema};
 use farp::types::{
-    CompositionConfig, ConflictStrategy, LocationType, OpenAPIMetadata, ProtocolMetadata,
-    SchemaDescriptor, SchemaType,
+    ChangeSeverity, CompositionConfig, ConflictStrategy, LocationType, OpenAPIMetadata,
+    ProtocolMetadata, SchemaDescriptor, SchemaType,
 };
 
 #[test]
@@ -18,6 +18,7 @@ fn test_basic_merge() {
     // Add OpenAPI schema descriptor
     manifest1.add_schema(SchemaDescriptor {
         schema_type: SchemaType::OpenAPI,
+        schema_id: None,
         spec_version: "3.1.0".to_string(),
         location: farp::types::SchemaLocation {
             location_type: LocationType::Inline,
@@ -68,6 +69,7 @@ fn test_basic_merge() {
     // Add OpenAPI schema descriptor
     manifest2.add_schema(SchemaDescriptor {
         schema_type: SchemaType::OpenAPI,
+        schema_id: None,
         spec_version: "3.1.0".to_string(),
         location: farp::types::SchemaLocation {
             location_type: LocationType::Inline,
@@ -141,8 +143,8 @@ fn test_basic_merge() {
 
     // Check components were prefixed and merged
     if let Some(components) = &result.spec.components {
-        assert!(components.schemas.contains_key("user-service_User"));
-        assert!(components.schemas.contains_key("product-service_Product"));
+        assert!(components.schemas.contains_key("user_service_User"));
+        assert!(components.schemas.contains_key("product_service_Product"));
     }
 }
 
@@ -162,6 +164,7 @@ fn test_conflict_resolution_prefix() {
     // Add OpenAPI schema descriptor
     manifest1.add_schema(SchemaDescriptor {
         schema_type: SchemaType::OpenAPI,
+        schema_id: None,
         spec_version: "3.1.0".to_string(),
         location: farp::types::SchemaLocation {
             location_type: LocationType::Inline,
@@ -192,6 +195,7 @@ fn test_conflict_resolution_prefix() {
     // Add OpenAPI schema descriptor
     manifest2.add_schema(SchemaDescriptor {
         schema_type: SchemaType::OpenAPI,
+        schema_id: None,
         spec_version: "3.1.0".to_string(),
         location: farp::types::SchemaLocation {
             location_type: LocationType::Inline,
@@ -248,6 +252,7 @@ fn test_composition_config() {
     // Add composition config
     let descriptor = SchemaDescriptor {
         schema_type: SchemaType::OpenAPI,
+        schema_id: None,
         spec_version: "3.1.0".to_string(),
         location: farp::types::SchemaLocation {
             location_type: LocationType::Inline,
@@ -327,6 +332,7 @@ fn test_exclude_from_merge() {
     // Add composition config that excludes from merge
     let descriptor = SchemaDescriptor {
         schema_type: SchemaType::OpenAPI,
+        schema_id: None,
         spec_version: "3.1.0".to_string(),
         location: farp::types::SchemaLocation {
             location_type: LocationType::Inline,
@@ -384,3 +390,1503 @@ fn test_exclude_from_merge() {
         .excluded_services
         .contains(&"excluded-service".to_string()));
 }
+
+/// Builds a root-mounted service sharing component/operation prefixes with its peers
+fn shared_prefix_service(name: &str, strategy: ConflictStrategy) -> ServiceSchema {
+    let mut manifest = new_manifest(name, "v1.0.0", format!("{name}-1"));
+    manifest.endpoints.health = "/health".to_string();
+    manifest.routing.strategy = farp::types::MountStrategy::Root;
+    manifest.add_schema(SchemaDescriptor {
+        schema_type: SchemaType::OpenAPI,
+        schema_id: None,
+        spec_version: "3.1.0".to_string(),
+        location: farp::types::SchemaLocation {
+            location_type: LocationType::Inline,
+            url: None,
+            registry_path: None,
+            headers: None,
+        },
+        content_type: "application/json".to_string(),
+        inline_schema: None,
+        hash: "a".repeat(64),
+        size: 1024,
+        compatibility: None,
+        metadata: Some(ProtocolMetadata {
+            openapi: Some(OpenAPIMetadata {
+                extensions: None,
+                server_variables: None,
+                default_security: Vec::new(),
+                composition: Some(CompositionConfig {
+                    include_in_merged: true,
+                    component_prefix: Some("shared".to_string()),
+                    tag_prefix: None,
+                    operation_id_prefix: Some("shared".to_string()),
+                    conflict_strategy: strategy,
+                    preserve_extensions: false,
+                    custom_servers: Vec::new(),
+                }),
+            }),
+            graphql: None,
+            grpc: None,
+            asyncapi: None,
+            orpc: None,
+        }),
+    });
+
+    let schema = serde_json::json!({
+        "openapi": "3.1.0",
+        "info": {"title": name, "version": "1.0.0"},
+        "paths": {
+            "/data": {"get": {"operationId": "getData"}}
+        },
+        "components": {
+            "schemas": {"Model": {"type": "object"}}
+        }
+    });
+
+    ServiceSchema {
+        manifest,
+        schema,
+        parsed: None,
+    }
+}
+
+#[test]
+fn test_conflict_summary() {
+    let merger = Merger::default();
+
+    let schemas = vec![
+        shared_prefix_service("service-a", ConflictStrategy::Overwrite),
+        // Path overwritten (high), component overwritten (medium),
+        // operation ID prefixed (low)
+        shared_prefix_service("service-b", ConflictStrategy::Overwrite),
+        // Path and component skipped (medium)
+        shared_prefix_service("service-c", ConflictStrategy::Skip),
+    ];
+
+    let result = merger.merge(schemas).unwrap();
+    let summary = result.conflict_summary();
+
+    assert_eq!(summary.get(&ChangeSeverity::High), Some(&1));
+    assert_eq!(summary.get(&ChangeSeverity::Medium), Some(&3));
+    assert_eq!(summary.get(&ChangeSeverity::Low), Some(&1));
+    assert_eq!(summary.get(&ChangeSeverity::Critical), None);
+    assert_eq!(summary.values().sum::<usize>(), result.conflicts.len());
+}
+
+#[test]
+fn test_merge_webhooks_prefix_conflict() {
+    let merger = Merger::default();
+
+    let service = |name: &str, instance: &str| {
+        let mut manifest = new_manifest(name, "v1.0.0", instance);
+        manifest.endpoints.health = "/health".to_string();
+        manifest.add_schema(SchemaDescriptor {
+            schema_type: SchemaType::OpenAPI,
+            schema_id: None,
+            spec_version: "3.1.0".to_string(),
+            location: farp::types::SchemaLocation {
+                location_type: LocationType::Inline,
+                url: None,
+                registry_path: None,
+                headers: None,
+            },
+            content_type: "application/json".to_string(),
+            inline_schema: None,
+            hash: "a".repeat(64),
+            size: 1024,
+            compatibility: None,
+            metadata: None,
+        });
+
+        ServiceSchema {
+            manifest,
+            schema: serde_json::json!({
+                "openapi": "3.1.0",
+                "info": {"title": name, "version": "1.0.0"},
+                "paths": {},
+                "webhooks": {
+                    "newPet": {"post": {"operationId": "newPet"}}
+                }
+            }),
+            parsed: None,
+        }
+    };
+
+    let result = merger
+        .merge(vec![
+            service("pet-store", "instance-1"),
+            service("pet-shelter", "instance-2"),
+        ])
+        .unwrap();
+
+    assert!(result.spec.webhooks.contains_key("newPet"));
+    assert!(result.spec.webhooks.contains_key("pet_shelter_newPet"));
+
+    let conflict = result
+        .conflicts
+        .iter()
+        .find(|c| c.conflict_type == farp::merger::ConflictType::Webhook)
+        .unwrap();
+    assert_eq!(conflict.item, "newPet");
+    assert_eq!(conflict.resolution, "Prefixed to pet_shelter_newPet");
+
+    // Webhooks survive serialization of the merged spec
+    let json = serde_json::to_value(&result.spec).unwrap();
+    assert!(json["webhooks"]["pet_shelter_newPet"]["post"].is_object());
+
+    // The prefix follows the configured component separator
+    let merger = Merger::new(MergerConfig {
+        component_separator: "__".to_string(),
+        ..Default::default()
+    });
+    let result = merger
+        .merge(vec![
+            service("pet-store", "instance-1"),
+            service("pet-shelter", "instance-2"),
+        ])
+        .unwrap();
+    assert!(result.spec.webhooks.contains_key("pet_shelter__newPet"));
+}
+
+#[test]
+fn test_merge_normalizes_openapi_3_0() {
+    let merger = Merger::default();
+
+    let mut legacy = shared_prefix_service("legacy-service", ConflictStrategy::Prefix);
+    legacy.schema = serde_json::json!({
+        "openapi": "3.0.3",
+        "info": {"title": "Legacy", "version": "1.0.0"},
+        "paths": {},
+        "components": {
+            "schemas": {
+                "Account": {
+                    "type": "object",
+                    "properties": {"nickname": {"type": "string", "nullable": true}}
+                }
+            }
+        }
+    });
+    let modern = shared_prefix_service("modern-service", ConflictStrategy::Prefix);
+
+    let result = merger.merge(vec![legacy, modern]).unwrap();
+
+    assert_eq!(result.spec.openapi, "3.1.0");
+    let components = result.spec.components.unwrap();
+    assert_eq!(
+        components.schemas["shared_Account"]["properties"]["nickname"],
+        serde_json::json!({"type": ["string", "null"]})
+    );
+    assert_eq!(
+        result
+            .warnings
+            .iter()
+            .filter(|w| w.contains("Upgraded"))
+            .collect::<Vec<_>>(),
+        vec!["Upgraded legacy-service from OpenAPI 3.0.3 to 3.1.0"]
+    );
+}
+
+#[test]
+fn test_custom_resolver_prefers_primary() {
+    use farp::merger::{Conflict, ConflictType, Resolution};
+    use farp::types::{InstanceMetadata, InstanceRole, InstanceStatus};
+    use std::sync::Arc;
+
+    let service = |name: &str, role: InstanceRole| {
+        let mut service = shared_prefix_service(name, ConflictStrategy::Error);
+        service.manifest.instance = Some(InstanceMetadata {
+            address: "10.0.0.1:8080".to_string(),
+            region: None,
+            zone: None,
+            labels: None,
+            weight: None,
+            status: InstanceStatus::Healthy,
+            role: Some(role),
+            deployment: None,
+            started_at: 0,
+            expected_schema_checksum: None,
+        });
+        service.schema["paths"]["/data"]["get"]["summary"] = serde_json::json!(name);
+        service
+    };
+    let is_primary = |s: &ServiceSchema| {
+        s.manifest.instance.as_ref().and_then(|i| i.role) == Some(InstanceRole::Primary)
+    };
+
+    let merger = Merger::new(MergerConfig {
+        custom_resolver: Some(Arc::new(
+            move |_: &Conflict, existing: &ServiceSchema, incoming: &ServiceSchema| {
+                if is_primary(incoming) {
+                    Some(Resolution::TakeNew)
+                } else if is_primary(existing) {
+                    Some(Resolution::KeepExisting)
+                } else {
+                    None
+                }
+            },
+        )),
+        ..Default::default()
+    });
+
+    for order in [
+        vec![
+            service("canary", InstanceRole::Canary),
+            service("primary", InstanceRole::Primary),
+        ],
+        vec![
+            service("primary", InstanceRole::Primary),
+            service("canary", InstanceRole::Canary),
+        ],
+    ] {
+        // The Error strategy would abort; the resolver settles every conflict
+        let result = merger.merge(order).unwrap();
+
+        let summary = result.spec.paths["/data"]
+            .get
+            .as_ref()
+            .unwrap()
+            .summary
+            .clone();
+        assert_eq!(summary.as_deref(), Some("primary"));
+
+        let path_conflict = result
+            .conflicts
+            .iter()
+            .find(|c| c.conflict_type == ConflictType::Path)
+            .unwrap();
+        assert!(path_conflict.resolution.starts_with("Resolver"));
+        assert!(result
+            .conflicts
+            .iter()
+            .any(|c| c.conflict_type == ConflictType::Component && c.item == "shared_Model"));
+    }
+
+    // Without a primary the resolver defers to the strategy
+    let result = merger.merge(vec![
+        service("canary-a", InstanceRole::Canary),
+        service("canary-b", InstanceRole::Canary),
+    ]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_custom_resolver_rename_retargets_references() {
+    use farp::merger::{ConflictType, Resolution};
+    use std::sync::Arc;
+
+    let service = |name: &str| {
+        let mut service = shared_prefix_service(name, ConflictStrategy::Skip);
+        service.schema["paths"][format!("/{name}")] = serde_json::json!({
+            "get": {
+                "operationId": format!("get-{name}"),
+                "responses": {"200": {
+                    "description": "ok",
+                    "content": {"application/json": {
+                        "schema": {"$ref": "#/components/schemas/Model"}
+                    }}
+                }},
+                "security": [{"auth": []}]
+            }
+        });
+        service.schema["components"]["securitySchemes"] =
+            serde_json::json!({"auth": {"type": "http", "scheme": "bearer"}});
+        service
+    };
+
+    let merger = Merger::new(MergerConfig {
+        custom_resolver: Some(Arc::new(
+            |conflict: &Conflict, _: &ServiceSchema, incoming: &ServiceSchema| match conflict
+                .conflict_type
+            {
+                ConflictType::Component | ConflictType::SecurityScheme => {
+                    Some(Resolution::RenameTo(format!(
+                        "{}_{}",
+                        conflict.item, incoming.manifest.service_name
+                    )))
+                }
+                _ => None,
+            },
+        )),
+        ..Default::default()
+    });
+
+    let result = merger
+        .merge(vec![service("service-a"), service("service-b")])
+        .unwrap();
+    let components = result.spec.components.as_ref().unwrap();
+    assert!(components.schemas.contains_key("shared_Model"));
+    assert!(components.schemas.contains_key("shared_Model_service-b"));
+    assert!(components.security_schemes.contains_key("auth_service-b"));
+
+    let target = |path: &str| {
+        let get = result.spec.paths[path].get.as_ref().unwrap();
+        let response = &get.responses.as_ref().unwrap()["200"];
+        let schema = response.content.as_ref().unwrap()["application/json"]
+            .schema
+            .clone()
+            .unwrap();
+        (
+            schema["$ref"].clone(),
+            get.security[0].keys().cloned().collect::<Vec<_>>(),
+        )
+    };
+    assert_eq!(
+        target("/service-a"),
+        (
+            serde_json::json!("#/components/schemas/shared_Model"),
+            vec!["auth".to_string()]
+        )
+    );
+    assert_eq!(
+        target("/service-b"),
+        (
+            serde_json::json!("#/components/schemas/shared_Model_service-b"),
+            vec!["auth_service-b".to_string()]
+        )
+    );
+}
+
+#[test]
+fn test_prefixed_security_scheme_retargets_operation_security() {
+    let service = |name: &str| {
+        let mut service = shared_prefix_service(name, ConflictStrategy::Prefix);
+        service.schema["paths"] = serde_json::json!({
+            format!("/{name}"): {"get": {
+                "operationId": format!("get-{name}"),
+                "security": [{"auth": ["read"]}]
+            }}
+        });
+        service.schema["components"]["securitySchemes"] =
+            serde_json::json!({"auth": {"type": "apiKey", "name": name, "in": "header"}});
+        service
+    };
+
+    let result = Merger::default()
+        .merge(vec![service("service-a"), service("service-b")])
+        .unwrap();
+    let schemes = &result.spec.components.as_ref().unwrap().security_schemes;
+    assert_eq!(schemes["auth"].name.as_deref(), Some("service-a"));
+    assert_eq!(schemes["service_b_auth"].name.as_deref(), Some("service-b"));
+
+    let security = |path: &str| {
+        result.spec.paths[path]
+            .get
+            .as_ref()
+            .unwrap()
+            .security
+            .clone()
+    };
+    assert_eq!(
+        security("/service-a"),
+        vec![[("auth".to_string(), vec!["read".to_string()])].into()]
+    );
+    assert_eq!(
+        security("/service-b"),
+        vec![[("service_b_auth".to_string(), vec!["read".to_string()])].into()]
+    );
+}
+
+#[test]
+fn test_component_conflicts_cover_every_kind() {
+    use farp::merger::{ConflictType, Resolution};
+    use std::sync::Arc;
+
+    let service = |name: &str| {
+        let mut service = shared_prefix_service(name, ConflictStrategy::Skip);
+        service.schema["paths"] = serde_json::json!({
+            format!("/{name}"): {"get": {
+                "operationId": format!("get-{name}"),
+                "responses": {"404": {"$ref": "#/components/responses/NotFound"}}
+            }}
+        });
+        service.schema["components"] = serde_json::json!({
+            "responses": {"NotFound": {"description": name}},
+            "parameters": {"Id": {"name": "id", "in": "query", "description": name}},
+            "headers": {"RateLimit": {"description": name}}
+        });
+        service
+    };
+    let description = |value: serde_json::Value| value["description"].clone();
+
+    // Skip keeps the first service's components and reports each conflict
+    let result = Merger::default()
+        .merge(vec![service("service-a"), service("service-b")])
+        .unwrap();
+    for item in ["shared_NotFound", "shared_Id", "shared_RateLimit"] {
+        assert!(
+            result
+                .conflicts
+                .iter()
+                .any(|c| c.conflict_type == ConflictType::Component && c.item == item),
+            "{item}"
+        );
+    }
+    let components = result.spec.components.as_ref().unwrap();
+    assert_eq!(
+        description(serde_json::to_value(&components.responses["shared_NotFound"]).unwrap()),
+        "service-a"
+    );
+    assert_eq!(
+        description(serde_json::to_value(&components.parameters["shared_Id"]).unwrap()),
+        "service-a"
+    );
+    assert_eq!(
+        description(serde_json::to_value(&components.headers["shared_RateLimit"]).unwrap()),
+        "service-a"
+    );
+
+    // A resolver rename keeps both and retargets the renamed one's references
+    let merger = Merger::new(MergerConfig {
+        custom_resolver: Some(Arc::new(
+            |conflict: &Conflict, _: &ServiceSchema, incoming: &ServiceSchema| {
+                Some(Resolution::RenameTo(format!(
+                    "{}_{}",
+                    conflict.item, incoming.manifest.service_name
+                )))
+            },
+        )),
+        ..Default::default()
+    });
+    let result = merger
+        .merge(vec![service("service-a"), service("service-b")])
+        .unwrap();
+    let components = result.spec.components.as_ref().unwrap();
+    assert_eq!(
+        description(
+            serde_json::to_value(&components.responses["shared_NotFound_service-b"]).unwrap()
+        ),
+        "service-b"
+    );
+    let not_found = |path: &str| {
+        let get = result.spec.paths[path].get.as_ref().unwrap();
+        serde_json::to_value(&get.responses.as_ref().unwrap()["404"]).unwrap()["$ref"].clone()
+    };
+    assert_eq!(
+        not_found("/service-a"),
+        "#/components/responses/shared_NotFound"
+    );
+    assert_eq!(
+        not_found("/service-b"),
+        "#/components/responses/shared_NotFound_service-b"
+    );
+}
+
+#[test]
+fn test_unreferenced_components_after_skipped_path() {
+    let service = |name: &str| {
+        let mut service = shared_prefix_service(name, ConflictStrategy::Skip);
+        let metadata = service.manifest.schemas[0].metadata.as_mut().unwrap();
+        let composition = metadata
+            .openapi
+            .as_mut()
+            .unwrap()
+            .composition
+            .as_mut()
+            .unwrap();
+        composition.component_prefix = None;
+        service.schema = serde_json::json!({
+            "openapi": "3.1.0",
+            "info": {"title": name, "version": "1.0.0"},
+            "paths": {
+                "/data": {"get": {"callbacks": {"onChange": {"{$request.body#/url}": {"post": {
+                    "requestBody": {"content": {"application/json": {"schema": {"$ref": "#/components/schemas/Widget"}}}}
+                }}}}}}
+            },
+            "components": {"schemas": {
+                "Widget": {"type": "object", "properties": {"part": {"$ref": "#/components/schemas/Part"}}},
+                "Part": {"type": "object"}
+            }}
+        });
+        service
+    };
+
+    // service-b's /data is skipped, orphaning its components
+    let result = Merger::default()
+        .merge(vec![service("service-a"), service("service-b")])
+        .unwrap();
+    assert_eq!(
+        result.unreferenced_components(),
+        vec!["service_b_Part", "service_b_Widget"]
+    );
+    assert!(result
+        .spec
+        .components
+        .as_ref()
+        .unwrap()
+        .schemas
+        .contains_key("service_b_Widget"));
+
+    let merger = Merger::new(MergerConfig {
+        prune_unreferenced: true,
+        ..Default::default()
+    });
+    let result = merger
+        .merge(vec![service("service-a"), service("service-b")])
+        .unwrap();
+    assert!(result.unreferenced_components().is_empty());
+    let mut names: Vec<&String> = result
+        .spec
+        .components
+        .as_ref()
+        .unwrap()
+        .schemas
+        .keys()
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["service_a_Part", "service_a_Widget"]);
+    assert!(result
+        .warnings
+        .contains(&"Pruned unreferenced component service_b_Widget".to_string()));
+}
+
+#[test]
+fn test_generated_operation_ids_participate_in_conflicts() {
+    let service = |name: &str| {
+        let mut service = shared_prefix_service(name, ConflictStrategy::Prefix);
+        service.schema = serde_json::json!({
+            "openapi": "3.1.0",
+            "info": {"title": name, "version": "1.0.0"},
+            "paths": {"/users/{id}": {"get": {"summary": "Fetch a user"}}}
+        });
+        service
+    };
+
+    // Without generation the operations carry no ID and never collide
+    let result = Merger::default()
+        .merge(vec![service("service-a"), service("service-b")])
+        .unwrap();
+    assert!(result.spec.paths["/users/{id}"]
+        .get
+        .as_ref()
+        .unwrap()
+        .operation_id
+        .is_none());
+    assert!(!result
+        .conflicts
+        .iter()
+        .any(|c| c.conflict_type == farp::merger::ConflictType::OperationID));
+
+    let merger = Merger::new(MergerConfig {
+        generate_missing_operation_ids: true,
+        ..Default::default()
+    });
+    let result = merger
+        .merge(vec![service("service-a"), service("service-b")])
+        .unwrap();
+
+    // IDs come from the service's own path, so they're identical across runs
+    // and unaffected by the path being re-mounted under /service-b
+    for path in ["/users/{id}", "/service-b/users/{id}"] {
+        assert_eq!(
+            result.spec.paths[path]
+                .get
+                .as_ref()
+                .unwrap()
+                .operation_id
+                .as_deref(),
+            Some("shared_get_users_by_id")
+        );
+    }
+    let conflict = result
+        .conflicts
+        .iter()
+        .find(|c| c.conflict_type == farp::merger::ConflictType::OperationID)
+        .unwrap();
+    assert_eq!(conflict.item, "get_users_by_id");
+    assert_eq!(conflict.services, vec!["service-a", "service-b"]);
+}
+
+#[test]
+fn test_parameters_and_request_body_survive_merge() {
+    let mut service = shared_prefix_service("user-service", ConflictStrategy::Prefix);
+    service.schema = serde_json::json!({
+        "openapi": "3.1.0",
+        "info": {"title": "users", "version": "1.0.0"},
+        "paths": {
+            "/users/{id}": {"put": {
+                "operationId": "updateUser",
+                "parameters": [
+                    {"name": "id", "in": "path", "required": true, "schema": {"type": "string"}}
+                ],
+                "requestBody": {
+                    "required": true,
+                    "content": {"application/json": {"schema": {"$ref": "#/components/schemas/User"}}}
+                }
+            }}
+        },
+        "components": {"schemas": {"User": {"type": "object"}}}
+    });
+
+    let result = Merger::default().merge(vec![service]).unwrap();
+    let put = result.spec.paths["/users/{id}"].put.as_ref().unwrap();
+
+    assert_eq!(put.parameters.len(), 1);
+    assert_eq!(put.parameters[0].name, "id");
+    assert_eq!(put.parameters[0].in_, "path");
+    assert_eq!(put.parameters[0].required, Some(true));
+
+    let body = put.request_body.as_ref().unwrap();
+    assert_eq!(body.required, Some(true));
+    assert_eq!(
+        body.content["application/json"].schema,
+        Some(serde_json::json!({"$ref": "#/components/schemas/shared_User"}))
+    );
+    assert!(result.unreferenced_components().is_empty());
+}
+
+#[test]
+fn test_merge_output_is_deterministic() {
+    let service = |name: &str| {
+        let mut service = shared_prefix_service(name, ConflictStrategy::Prefix);
+        let paths: serde_json::Map<String, serde_json::Value> = (0..20)
+            .map(|i| {
+                (
+                    format!("/{name}/r{i}"),
+                    serde_json::json!({"get": {
+                        "x-a": 1, "x-b": 2, "x-c": 3,
+                        "responses": {"200": {"description": "ok"}, "404": {"description": "missing"}}
+                    }}),
+                )
+            })
+            .collect();
+        let schemas: serde_json::Map<String, serde_json::Value> = (0..20)
+            .map(|i| (format!("Model{i}"), serde_json::json!({"type": "object"})))
+            .collect();
+        service.schema = serde_json::json!({
+            "openapi": "3.1.0",
+            "info": {"title": name, "version": "1.0.0"},
+            "paths": paths,
+            "components": {"schemas": schemas}
+        });
+        service
+    };
+
+    let merge = || {
+        let result = Merger::default()
+            .merge(vec![service("alpha"), service("beta")])
+            .unwrap();
+        serde_json::to_string(&result.spec).unwrap()
+    };
+
+    let first = merge();
+    for _ in 0..5 {
+        assert_eq!(merge(), first);
+    }
+    assert!(first.find("\"/alpha/r0\"").unwrap() < first.find("\"/beta/r0\"").unwrap());
+}
+
+#[test]
+fn test_conflict_report() {
+    let result = Merger::default()
+        .merge(vec![
+            shared_prefix_service("service-a", ConflictStrategy::Overwrite),
+            shared_prefix_service("service-b", ConflictStrategy::Overwrite),
+            shared_prefix_service("service-c", ConflictStrategy::Skip),
+        ])
+        .unwrap();
+    assert!(!result.conflicts.is_empty());
+
+    let markdown = result.conflict_report(ReportFormat::Markdown);
+    let rows: Vec<&str> = markdown
+        .lines()
+        .filter(|line| line.starts_with("| ") && !line.starts_with("| Type"))
+        .collect();
+    assert_eq!(rows.len(), result.conflicts.len());
+    for conflict in &result.conflicts {
+        assert!(rows.iter().any(|row| row.contains(&conflict.item)
+            && row.contains(&conflict.resolution)
+            && row.contains(&conflict.services.join(", "))));
+    }
+    // Path conflicts are grouped before component conflicts
+    assert!(markdown.find("### path").unwrap() < markdown.find("### component").unwrap());
+
+    let json = result.conflict_report(ReportFormat::Json);
+    let parsed: Vec<Conflict> = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, result.conflicts);
+
+    let empty = Merger::default()
+        .merge(vec![shared_prefix_service(
+            "solo",
+            ConflictStrategy::Prefix,
+        )])
+        .unwrap();
+    assert_eq!(empty.conflict_report(ReportFormat::Json), "[]");
+}
+
+#[test]
+fn test_transforms_strip_internal_operations() {
+    use farp::merger::{RemoveServers, StripInternal};
+    use std::sync::Arc;
+
+    let mut service = shared_prefix_service("service-a", ConflictStrategy::Prefix);
+    service.schema = serde_json::json!({
+        "openapi": "3.1.0",
+        "info": {"title": "service-a", "version": "1.0.0"},
+        "servers": [{"url": "http://service-a.internal"}],
+        "paths": {
+            "/users": {
+                "get": {"operationId": "listUsers"},
+                "delete": {"operationId": "purgeUsers", "x-internal": true}
+            },
+            "/debug/vars": {"get": {"operationId": "debugVars", "x-internal": true}}
+        }
+    });
+
+    let result = Merger::default().merge(vec![service.clone()]).unwrap();
+    assert!(result.spec.paths.contains_key("/debug/vars"));
+    assert!(result.spec.paths["/users"].delete.is_some());
+
+    let merger = Merger::new(MergerConfig {
+        transforms: vec![Arc::new(StripInternal), Arc::new(RemoveServers)],
+        ..Default::default()
+    });
+    let result = merger.merge(vec![service]).unwrap();
+    assert!(!result.spec.paths.contains_key("/debug/vars"));
+    assert!(result.spec.paths["/users"].get.is_some());
+    assert!(result.spec.paths["/users"].delete.is_none());
+    let merged = serde_json::to_string(&result.spec).unwrap();
+    assert!(!merged.contains("purgeUsers"));
+    assert!(!merged.contains("service-a.internal"));
+}
+
+#[test]
+fn test_service_hash_operation_ids_never_collide() {
+    use farp::merger::{ConflictType, OperationIdStrategy};
+
+    let services = || {
+        vec![
+            shared_prefix_service("service-a", ConflictStrategy::Prefix),
+            shared_prefix_service("service-b", ConflictStrategy::Prefix),
+        ]
+    };
+    let operation_ids = |result: &farp::merger::MergeResult| {
+        let mut ids: Vec<String> = result
+            .spec
+            .paths
+            .values()
+            .filter_map(|item| item.get.as_ref()?.operation_id.clone())
+            .collect();
+        ids.sort();
+        ids
+    };
+    let has_op_conflict = |result: &farp::merger::MergeResult| {
+        result
+            .conflicts
+            .iter()
+            .any(|c| c.conflict_type == ConflictType::OperationID)
+    };
+
+    // A shared prefix makes plain prefixing collide
+    let result = Merger::default().merge(services()).unwrap();
+    assert_eq!(operation_ids(&result), vec!["shared_getData"; 2]);
+    assert!(has_op_conflict(&result));
+
+    let merger = Merger::new(MergerConfig {
+        operation_id_strategy: OperationIdStrategy::ServiceHash,
+        ..Default::default()
+    });
+    let result = merger.merge(services()).unwrap();
+    let ids = operation_ids(&result);
+    assert_eq!(ids.len(), 2);
+    assert_ne!(ids[0], ids[1]);
+    assert!(ids.iter().all(|id| id.starts_with("shared_getData_")));
+    assert!(!has_op_conflict(&result));
+
+    // Two versions of one service get different hashes too
+    let mut v2 = shared_prefix_service("service-a", ConflictStrategy::Prefix);
+    v2.manifest.service_version = "v2.0.0".to_string();
+    v2.manifest.instance_id = "service-a-2".to_string();
+    let result = merger
+        .merge(vec![
+            shared_prefix_service("service-a", ConflictStrategy::Prefix),
+            v2,
+        ])
+        .unwrap();
+    let ids = operation_ids(&result);
+    assert_eq!(ids.len(), 2);
+    assert_ne!(ids[0], ids[1]);
+    assert!(!has_op_conflict(&result));
+
+    let merger = Merger::new(MergerConfig {
+        operation_id_strategy: OperationIdStrategy::Keep,
+        ..Default::default()
+    });
+    let result = merger.merge(services()).unwrap();
+    assert_eq!(operation_ids(&result), vec!["getData"; 2]);
+}
+
+#[test]
+fn test_gateway_lint_during_merge() {
+    let mut service = shared_prefix_service("service-a", ConflictStrategy::Prefix);
+    service.schema = serde_json::json!({
+        "openapi": "3.1.0",
+        "info": {"title": "service-a", "version": "1.0.0"},
+        "paths": {
+            "/users/me": {"get": {"operationId": "me"}},
+            "/users/{id}": {"get": {}}
+        }
+    });
+
+    let result = Merger::default().merge(vec![service.clone()]).unwrap();
+    assert!(result.warnings.is_empty());
+
+    let merger = Merger::new(MergerConfig {
+        gateway_lint: true,
+        ..Default::default()
+    });
+    let result = merger.merge(vec![service]).unwrap();
+    assert!(result
+        .warnings
+        .iter()
+        .any(|w| w.starts_with("FARP302 /users/me:")));
+    assert!(result
+        .warnings
+        .iter()
+        .any(|w| w == "FARP303 /users/{id}: GET has no operationId"));
+}
+
+#[test]
+fn test_merged_info_contact_and_license() {
+    use farp::merger::{Contact, License};
+
+    let merger = Merger::new(MergerConfig {
+        merged_contact: Some(Contact {
+            name: Some("API Team".to_string()),
+            url: None,
+            email: Some("api@example.com".to_string()),
+        }),
+        merged_license: Some(License {
+            name: "Apache-2.0".to_string(),
+            url: Some("https://www.apache.org/licenses/LICENSE-2.0".to_string()),
+        }),
+        merged_terms_of_service: Some("https://example.com/terms".to_string()),
+        ..Default::default()
+    });
+    let result = merger
+        .merge(vec![shared_prefix_service(
+            "service-a",
+            ConflictStrategy::Prefix,
+        )])
+        .unwrap();
+
+    let info = serde_json::to_value(&result.spec.info).unwrap();
+    assert_eq!(info["contact"]["email"], "api@example.com");
+    assert_eq!(info["license"]["name"], "Apache-2.0");
+    assert_eq!(info["termsOfService"], "https://example.com/terms");
+}
+
+#[test]
+fn test_shared_tags_merge_metadata() {
+    let service = |name: &str, tags: serde_json::Value| {
+        let mut service = shared_prefix_service(name, ConflictStrategy::Prefix);
+        service.schema["tags"] = tags;
+        service
+    };
+    let services = || {
+        vec![
+            service(
+                "service-a",
+                serde_json::json!([
+                    {"name": "accounts", "description": "Account management", "x-owner": "team-a"}
+                ]),
+            ),
+            service(
+                "service-b",
+                serde_json::json!([{
+                    "name": "accounts",
+                    "description": "",
+                    "externalDocs": {"url": "https://docs.example.com/accounts"},
+                    "x-owner": "team-b",
+                    "x-audience": "public"
+                }]),
+            ),
+        ]
+    };
+
+    let merger = Merger::new(MergerConfig {
+        include_service_tags: false,
+        ..Default::default()
+    });
+    let result = merger.merge(services()).unwrap();
+    assert_eq!(result.spec.tags.len(), 1);
+    let tag = &result.spec.tags[0];
+    assert_eq!(tag.description.as_deref(), Some("Account management"));
+    assert_eq!(
+        tag.external_docs.as_ref().map(|d| d.url.as_str()),
+        Some("https://docs.example.com/accounts")
+    );
+    assert_eq!(tag.extensions["x-owner"], "team-a");
+    assert_eq!(tag.extensions["x-audience"], "public");
+
+    let tags = serde_json::to_value(&result.spec.tags).unwrap();
+    assert_eq!(
+        tags[0]["externalDocs"]["url"],
+        "https://docs.example.com/accounts"
+    );
+
+    // Tags already carrying the service prefix are not prefixed twice
+    let result = Merger::default()
+        .merge(vec![service(
+            "service-a",
+            serde_json::json!([{"name": "service_a_accounts"}, {"name": "accounts"}]),
+        )])
+        .unwrap();
+    let names: Vec<&str> = result.spec.tags.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["service_a_accounts"]);
+}
+
+#[test]
+fn test_custom_component_separator() {
+    let mut service = shared_prefix_service("service-a", ConflictStrategy::Prefix);
+    service.schema["paths"]["/data"]["get"]["requestBody"] = serde_json::json!({
+        "content": {
+            "application/json": {"schema": {"$ref": "#/components/schemas/Model"}}
+        }
+    });
+
+    let merger = Merger::new(MergerConfig {
+        component_separator: "__".to_string(),
+        ..Default::default()
+    });
+    let result = merger.merge(vec![service]).unwrap();
+    let spec = serde_json::to_value(&result.spec).unwrap();
+    assert!(spec["components"]["schemas"].get("shared__Model").is_some());
+    assert_eq!(
+        spec["paths"]["/data"]["get"]["requestBody"]["content"]["application/json"]["schema"]
+            ["$ref"],
+        "#/components/schemas/shared__Model"
+    );
+
+    let invalid = |component: &str, namespace: &str| MergerConfig {
+        component_separator: component.to_string(),
+        namespace_separator: namespace.to_string(),
+        ..Default::default()
+    };
+    for config in [invalid("/", "."), invalid("", "."), invalid("_", "#")] {
+        let err = Merger::new(config)
+            .merge(vec![shared_prefix_service(
+                "service-a",
+                ConflictStrategy::Prefix,
+            )])
+            .unwrap_err();
+        assert!(matches!(err, farp::errors::Error::Validation { .. }));
+    }
+    assert!(invalid("-", ".").validate_separators(false).is_ok());
+    assert!(invalid("-", ".").validate_separators(true).is_err());
+}
+
+#[test]
+fn test_merge_stream_large_fleet() {
+    let services = (0..100).map(|i| {
+        let mut service = shared_prefix_service(&format!("service-{i}"), ConflictStrategy::Prefix);
+        service.schema["paths"] = serde_json::json!({
+            format!("/service-{i}/items"): {"get": {"operationId": "listItems"}},
+            format!("/service-{i}/items/{{id}}"): {"get": {"operationId": "getItem"}}
+        });
+        Ok(service)
+    });
+
+    let result = Merger::default().merge_stream(services).unwrap();
+    assert_eq!(result.included_services.len(), 100);
+    assert_eq!(result.spec.paths.len(), 200);
+
+    // A failing source aborts the merge
+    let failing = vec![
+        Ok(shared_prefix_service("service-a", ConflictStrategy::Prefix)),
+        Err(farp::errors::Error::SchemaNotFound),
+    ];
+    assert!(matches!(
+        Merger::default().merge_stream(failing.into_iter()),
+        Err(farp::errors::Error::SchemaNotFound)
+    ));
+}
+
+#[test]
+fn test_normalized_path_conflicts() {
+    let service = |name: &str, path: &str, strategy: ConflictStrategy| {
+        let mut service = shared_prefix_service(name, strategy);
+        service.schema["paths"] = serde_json::json!({path: {"get": {"operationId": "list"}}});
+        service
+    };
+    let path_conflicts = |result: &farp::merger::MergeResult| {
+        result
+            .conflicts
+            .iter()
+            .filter(|c| c.conflict_type == farp::merger::ConflictType::Path)
+            .count()
+    };
+    let paths = |result: &farp::merger::MergeResult| {
+        let mut paths: Vec<String> = result.spec.paths.keys().cloned().collect();
+        paths.sort();
+        paths
+    };
+
+    // Case-only difference
+    let case_only = || {
+        vec![
+            service("service-a", "/Users", ConflictStrategy::Skip),
+            service("service-b", "/users", ConflictStrategy::Skip),
+        ]
+    };
+    let result = Merger::default().merge(case_only()).unwrap();
+    assert_eq!(path_conflicts(&result), 0);
+    assert_eq!(paths(&result), vec!["/Users", "/users"]);
+
+    let merger = Merger::new(MergerConfig {
+        case_insensitive_paths: true,
+        ..Default::default()
+    });
+    let result = merger.merge(case_only()).unwrap();
+    assert_eq!(path_conflicts(&result), 1);
+    assert_eq!(paths(&result), vec!["/Users"]);
+
+    // Trailing-slash-only difference; the overwriting spelling replaces the old one
+    let trailing_slash = || {
+        vec![
+            service("service-a", "/users", ConflictStrategy::Overwrite),
+            service("service-b", "/users/", ConflictStrategy::Overwrite),
+        ]
+    };
+    let result = Merger::default().merge(trailing_slash()).unwrap();
+    assert_eq!(path_conflicts(&result), 0);
+
+    let merger = Merger::new(MergerConfig {
+        normalize_trailing_slash: true,
+        ..Default::default()
+    });
+    let result = merger.merge(trailing_slash()).unwrap();
+    assert_eq!(path_conflicts(&result), 1);
+    assert_eq!(paths(&result), vec!["/users/"]);
+}
+
+#[test]
+fn test_path_param_mismatches() {
+    let service = |paths: serde_json::Value| {
+        let mut service = shared_prefix_service("service-a", ConflictStrategy::Prefix);
+        service.schema["paths"] = paths;
+        service.schema["components"]["parameters"] = serde_json::json!({
+            "UserId": {"name": "userId", "in": "path", "required": true}
+        });
+        service
+    };
+    let param = |name: &str| serde_json::json!({"name": name, "in": "path", "required": true});
+
+    let matched = service(serde_json::json!({
+        "/users/{userId}": {
+            "parameters": [param("userId")],
+            "get": {"operationId": "getUser"}
+        },
+        "/users/{userId}/posts/{postId}": {
+            "get": {
+                "operationId": "getPost",
+                "parameters": [{"$ref": "#/components/parameters/UserId"}, param("postId")]
+            }
+        }
+    }));
+    let result = Merger::default().merge(vec![matched]).unwrap();
+    assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+
+    let mismatched = || {
+        service(serde_json::json!({
+            "/users/{userId}": {
+                "get": {"operationId": "getUser", "parameters": [param("id")]}
+            }
+        }))
+    };
+    let result = Merger::default().merge(vec![mismatched()]).unwrap();
+    assert_eq!(
+        result.warnings,
+        vec![
+            "service-a: GET /users/{userId}: placeholder {userId} has no path parameter",
+            "service-a: GET /users/{userId}: path parameter id is not in the template",
+        ]
+    );
+
+    let strict = Merger::new(MergerConfig {
+        strict_path_params: true,
+        ..Default::default()
+    });
+    assert!(matches!(
+        strict.merge(vec![mismatched()]),
+        Err(farp::errors::Error::InvalidSchema(_))
+    ));
+}
+
+#[test]
+fn test_routing_priority_wins_overwrite() {
+    let service = |name: &str, priority: Option<i32>| {
+        let mut service = shared_prefix_service(name, ConflictStrategy::Overwrite);
+        service.manifest.routing.priority = priority;
+        service.schema["paths"]["/data"]["get"]["summary"] = name.into();
+        service
+    };
+    let winner = |result: &farp::merger::MergeResult| {
+        result.spec.paths["/data"]
+            .get
+            .as_ref()
+            .and_then(|op| op.summary.clone())
+            .unwrap()
+    };
+
+    // Without priorities the later service overwrites
+    let result = Merger::default()
+        .merge(vec![service("service-a", None), service("service-b", None)])
+        .unwrap();
+    assert_eq!(winner(&result), "service-b");
+
+    // The higher-priority service wins regardless of input order
+    let result = Merger::default()
+        .merge(vec![
+            service("service-a", Some(10)),
+            service("service-b", Some(1)),
+        ])
+        .unwrap();
+    assert_eq!(winner(&result), "service-a");
+    assert_eq!(result.included_services, vec!["service-a", "service-b"]);
+    let conflict = result
+        .conflicts
+        .iter()
+        .find(|c| c.conflict_type == farp::merger::ConflictType::Path)
+        .unwrap();
+    assert_eq!(
+        conflict.resolution,
+        "Kept service-a version (priority 10 vs 1)"
+    );
+
+    // Ties fall back to service name order, so the later name overwrites
+    let result = Merger::default()
+        .merge(vec![
+            service("service-b", Some(5)),
+            service("service-a", Some(5)),
+        ])
+        .unwrap();
+    assert_eq!(result.included_services, vec!["service-a", "service-b"]);
+    assert_eq!(winner(&result), "service-b");
+}
+
+#[test]
+fn test_merge_records_method_collisions() {
+    let service = |name: &str| {
+        let mut service = shared_prefix_service(name, ConflictStrategy::Merge);
+        service.schema["paths"]["/data"]["get"]["summary"] = name.into();
+        service
+    };
+    let services = || {
+        let mut b = service("service-b");
+        b.schema["paths"]["/data"]["post"] = serde_json::json!({"operationId": "createData"});
+        vec![service("service-a"), b]
+    };
+    let get_summary = |result: &farp::merger::MergeResult| {
+        result.spec.paths["/data"]
+            .get
+            .as_ref()
+            .and_then(|op| op.summary.clone())
+            .unwrap()
+    };
+
+    let result = Merger::default().merge(services()).unwrap();
+    assert_eq!(get_summary(&result), "service-b");
+    assert!(result.spec.paths["/data"].post.is_some());
+    let methods: Vec<(&str, &str)> = result
+        .conflicts
+        .iter()
+        .filter(|c| c.item.starts_with("GET "))
+        .map(|c| (c.item.as_str(), c.resolution.as_str()))
+        .collect();
+    // POST only exists in service-b, so only GET collides
+    assert_eq!(
+        methods,
+        vec![(
+            "GET /data",
+            "Kept service-b operation, dropped service-a operation"
+        )]
+    );
+    assert!(!result.conflicts.iter().any(|c| c.item.starts_with("POST ")));
+
+    let keep = Merger::new(MergerConfig {
+        method_conflict_strategy: farp::merger::MethodConflictStrategy::Keep,
+        ..Default::default()
+    });
+    let result = keep.merge(services()).unwrap();
+    assert_eq!(get_summary(&result), "service-a");
+    assert!(result.spec.paths["/data"].post.is_some());
+
+    let strict = Merger::new(MergerConfig {
+        method_conflict_strategy: farp::merger::MethodConflictStrategy::Error,
+        ..Default::default()
+    });
+    assert!(matches!(
+        strict.merge(services()),
+        Err(farp::errors::Error::Custom(message)) if message.contains("GET /data")
+    ));
+}
+
+#[test]
+fn test_prefixes_are_sanitized() {
+    let service = |name: &str| {
+        let mut service = shared_prefix_service(name, ConflictStrategy::Prefix);
+        let metadata = service.manifest.schemas[0].metadata.as_mut().unwrap();
+        let composition = metadata
+            .openapi
+            .as_mut()
+            .unwrap()
+            .composition
+            .as_mut()
+            .unwrap();
+        composition.component_prefix = None;
+        composition.operation_id_prefix = None;
+        service
+    };
+    let services = || vec![service("order.service v2"), service("Über api")];
+    let names = |result: &farp::merger::MergeResult| {
+        let mut names: Vec<String> = result
+            .spec
+            .components
+            .as_ref()
+            .unwrap()
+            .schemas
+            .keys()
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    };
+
+    let result = Merger::default().merge(services()).unwrap();
+    assert_eq!(
+        names(&result),
+        vec!["_ber_api_Model", "order_service_v2_Model"]
+    );
+    let get = result.spec.paths["/data"].get.as_ref().unwrap();
+    assert!(get
+        .operation_id
+        .as_deref()
+        .is_some_and(|id| id.ends_with("_getData") && !id.contains(' ')));
+
+    let raw = Merger::new(MergerConfig {
+        sanitize_prefixes: false,
+        ..Default::default()
+    });
+    let result = raw.merge(services()).unwrap();
+    assert_eq!(
+        names(&result),
+        vec!["order.service v2_Model", "Über api_Model"]
+    );
+}
+
+#[test]
+fn test_strip_examples_and_response_schemas() {
+    let mut service = shared_prefix_service("user-service", ConflictStrategy::Prefix);
+    service.schema = serde_json::json!({
+        "openapi": "3.1.0",
+        "info": {"title": "users", "version": "1.0.0"},
+        "paths": {
+            "/users/{id}": {"put": {
+                "operationId": "updateUser",
+                "parameters": [
+                    {"name": "id", "in": "path", "required": true,
+                     "schema": {"type": "string"}, "example": "42"}
+                ],
+                "requestBody": {
+                    "content": {"application/json": {
+                        "schema": {"$ref": "#/components/schemas/User"},
+                        "examples": {"ada": {"$ref": "#/components/examples/Ada"}}
+                    }}
+                },
+                "responses": {
+                    "200": {
+                        "description": "Updated user",
+                        "content": {"application/json": {
+                            "schema": {"$ref": "#/components/schemas/User"},
+                            "example": {"name": "Ada"}
+                        }}
+                    }
+                }
+            }}
+        },
+        "components": {
+            "schemas": {"User": {
+                "type": "object",
+                "example": {"name": "Ada"},
+                "properties": {
+                    "name": {"type": "string", "examples": ["Ada"]},
+                    "example": {"type": "boolean"}
+                }
+            }},
+            "examples": {"Ada": {"value": {"name": "Ada"}}}
+        }
+    });
+
+    let lean = Merger::new(MergerConfig {
+        strip_examples: true,
+        strip_response_schemas: true,
+        ..Default::default()
+    });
+    let result = lean.merge(vec![service.clone()]).unwrap();
+    let put = result.spec.paths["/users/{id}"].put.as_ref().unwrap();
+    assert_eq!(put.operation_id.as_deref(), Some("shared_updateUser"));
+    assert_eq!(put.parameters[0].name, "id");
+    assert!(put.parameters[0].example.is_none());
+
+    let body = &put.request_body.as_ref().unwrap().content["application/json"];
+    assert!(body.examples.is_none());
+    assert!(body.schema.is_some());
+
+    let response = &put.responses.as_ref().unwrap()["200"];
+    assert_eq!(response.description.as_deref(), Some("Updated user"));
+    let media = &response.content.as_ref().unwrap()["application/json"];
+    assert!(media.example.is_none());
+    assert!(media.schema.is_none());
+
+    // Shared examples lose their only references and go too
+    let components = result.spec.components.as_ref().unwrap();
+    assert!(components.examples.is_empty());
+
+    // Keywords go, a property named `example` stays
+    let user = &components.schemas["shared_User"];
+    assert_eq!(
+        user,
+        &serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string"},
+                "example": {"type": "boolean"}
+            }
+        })
+    );
+
+    // Off by default
+    let result = Merger::default().merge(vec![service]).unwrap();
+    assert!(
+        result.spec.components.as_ref().unwrap().examples["shared_Ada"]
+            .value
+            .is_some()
+    );
+    let put = result.spec.paths["/users/{id}"].put.as_ref().unwrap();
+    assert!(put.parameters[0].example.is_some());
+    let response = &put.responses.as_ref().unwrap()["200"];
+    assert!(response.content.as_ref().unwrap()["application/json"]
+        .schema
+        .is_some());
+}
+
+#[test]
+fn test_shared_examples_and_headers_survive_merge() {
+    let mut service = shared_prefix_service("user-service", ConflictStrategy::Prefix);
+    service.schema = serde_json::json!({
+        "openapi": "3.1.0",
+        "info": {"title": "users", "version": "1.0.0"},
+        "paths": {
+            "/users": {"get": {
+                "operationId": "listUsers",
+                "responses": {
+                    "200": {
+                        "description": "Users",
+                        "headers": {"X-Rate-Limit": {"$ref": "#/components/headers/RateLimit"}},
+                        "content": {"application/json": {
+                            "schema": {"type": "array"},
+                            "examples": {"ada": {"$ref": "#/components/examples/Ada"}}
+                        }},
+                        "links": {
+                            "first": {"$ref": "#/components/links/FirstUser"},
+                            "self": {"operationId": "listUsers"}
+                        }
+                    },
+                    "404": {"$ref": "#/components/responses/NotFound"}
+                }
+            }}
+        },
+        "components": {
+            "responses": {"NotFound": {
+                "description": "Missing",
+                "headers": {"X-Rate-Limit": {"$ref": "#/components/headers/RateLimit"}}
+            }},
+            "headers": {"RateLimit": {"description": "Remaining calls", "schema": {"type": "integer"}}},
+            "examples": {"Ada": {"summary": "A user", "value": [{"name": "Ada"}]}},
+            "links": {"FirstUser": {"operationId": "getUser"}}
+        }
+    });
+
+    let result = Merger::default().merge(vec![service]).unwrap();
+    let components = result.spec.components.as_ref().unwrap();
+    assert_eq!(
+        components.headers["shared_RateLimit"]
+            .description
+            .as_deref(),
+        Some("Remaining calls")
+    );
+    assert_eq!(
+        components.examples["shared_Ada"].value,
+        Some(serde_json::json!([{"name": "Ada"}]))
+    );
+    // Link targets follow the operation ID prefixing
+    assert_eq!(
+        components.links["shared_FirstUser"]["operationId"],
+        "shared_getUser"
+    );
+    assert_eq!(
+        components.responses["shared_NotFound"]
+            .headers
+            .as_ref()
+            .unwrap()["X-Rate-Limit"]
+            .reference
+            .as_deref(),
+        Some("#/components/headers/shared_RateLimit")
+    );
+
+    let get = result.spec.paths["/users"].get.as_ref().unwrap();
+    let responses = get.responses.as_ref().unwrap();
+    let ok = &responses["200"];
+    assert_eq!(
+        ok.headers.as_ref().unwrap()["X-Rate-Limit"]
+            .reference
+            .as_deref(),
+        Some("#/components/headers/shared_RateLimit")
+    );
+    let media = &ok.content.as_ref().unwrap()["application/json"];
+    assert_eq!(
+        media.examples.as_ref().unwrap()["ada"].reference.as_deref(),
+        Some("#/components/examples/shared_Ada")
+    );
+    assert_eq!(
+        ok.links.as_ref().unwrap()["first"],
+        serde_json::json!({"$ref": "#/components/links/shared_FirstUser"})
+    );
+    assert_eq!(get.operation_id.as_deref(), Some("shared_listUsers"));
+    assert_eq!(
+        ok.links.as_ref().unwrap()["self"]["operationId"],
+        "shared_listUsers"
+    );
+    assert_eq!(
+        responses["404"].extensions["$ref"],
+        "#/components/responses/shared_NotFound"
+    );
+}
+
+#[test]
+fn test_merge_rejects_overly_nested_schema() {
+    let mut nested = serde_json::json!({"type": "string"});
+    for _ in 0..100 {
+        nested = serde_json::json!({"type": "object", "properties": {"child": nested}});
+    }
+    let mut service = shared_prefix_service("deep-service", ConflictStrategy::Prefix);
+    service.schema["components"]["schemas"]["Deep"] = nested;
+
+    let err = Merger::default().merge(vec![service.clone()]).unwrap_err();
+    assert!(err.to_string().contains("max schema depth exceeded"));
+
+    // The ref depth limit doesn't apply to plain nesting
+    let merger = Merger::new(MergerConfig {
+        max_ref_depth: 512,
+        ..Default::default()
+    });
+    assert!(merger.merge(vec![service.clone()]).is_err());
+
+    // A larger schema depth limit admits it
+    let merger = Merger::new(MergerConfig {
+        max_schema_depth: 512,
+        ..Default::default()
+    });
+    assert!(merger.merge(vec![service]).is_ok());
+}
@@ -0,0 +1,118 @@
+//! Integration tests for the S3 storage backend
+//!
+//! Requires an S3-compatible endpoint such as MinIO, e.g.:
+//!
+//! ```text
+//! docker run -p 9000:9000 minio/minio server /data
+//! S3_ENDPOINT=http://localhost:9000 cargo test --features backend-s3
+//! ```
+//!
+//! Credentials default to MinIO's `minioadmin`/`minioadmin`. Tests are
+//! skipped when `S3_ENDPOINT` is not set.
+
+#![cfg(feature = "backend-s3")]
+
+use aws_sdk_s3::config::{BehaviorVersion, Credentials, Region};
+use farp::errors::Error;
+use farp::registry::EventType;
+use farp::storage::s3::S3Backend;
+use farp::storage::{ManifestStorage, StorageBackend};
+use std::time::Duration;
+
+async fn setup(bucket: &str, prefix: &str) -> Option<S3Backend> {
+    let endpoint = std::env::var("S3_ENDPOINT").ok()?;
+    let access_key = std::env::var("S3_ACCESS_KEY").unwrap_or_else(|_| "minioadmin".to_string());
+    let secret_key = std::env::var("S3_SECRET_KEY").unwrap_or_else(|_| "minioadmin".to_string());
+
+    let config = aws_sdk_s3::Config::builder()
+        .behavior_version(BehaviorVersion::latest())
+        .endpoint_url(endpoint)
+        .region(Region::new("us-east-1"))
+        .credentials_provider(Credentials::new(
+            access_key,
+            secret_key,
+            None,
+            None,
+            "farp-tests",
+        ))
+        .force_path_style(true)
+        .build();
+    let client = aws_sdk_s3::Client::from_conf(config);
+
+    let _ = client.create_bucket().bucket(bucket).send().await;
+    let backend = S3Backend::new(client, bucket)
+        .with_prefix(prefix)
+        .with_poll_interval(Duration::from_millis(200));
+    for key in backend.list("").await.unwrap() {
+        backend.delete(&key).await.unwrap();
+    }
+    Some(backend)
+}
+
+#[tokio::test]
+async fn test_s3_put_get_list_delete() {
+    let Some(backend) = setup("farp-tests", "crud").await else {
+        return;
+    };
+
+    backend.put("schemas/a", b"alpha").await.unwrap();
+    backend.put("schemas/b", b"beta").await.unwrap();
+    backend.put("manifests/x", b"manifest").await.unwrap();
+
+    assert_eq!(backend.get("schemas/a").await.unwrap(), b"alpha");
+
+    let mut keys = backend.list("schemas/").await.unwrap();
+    keys.sort();
+    assert_eq!(keys, vec!["schemas/a", "schemas/b"]);
+
+    backend.delete("schemas/a").await.unwrap();
+    assert!(matches!(
+        backend.get("schemas/a").await,
+        Err(Error::SchemaNotFound)
+    ));
+}
+
+#[tokio::test]
+async fn test_s3_schema_storage() {
+    let Some(backend) = setup("farp-tests", "schemas").await else {
+        return;
+    };
+
+    let storage = ManifestStorage::new(backend, "farp", 1024, 10 * 1024 * 1024);
+    let schema = serde_json::json!({
+        "openapi": "3.1.0",
+        "paths": {},
+        "x-padding": "x".repeat(4096)
+    });
+
+    storage
+        .put_schema("/schemas/user-service/v1/openapi", &schema)
+        .await
+        .unwrap();
+    assert_eq!(
+        storage
+            .get_schema("/schemas/user-service/v1/openapi")
+            .await
+            .unwrap(),
+        schema
+    );
+}
+
+#[tokio::test]
+async fn test_s3_watch_polls_for_changes() {
+    let Some(backend) = setup("farp-tests", "watch").await else {
+        return;
+    };
+
+    backend.put("schemas/existing", b"v1").await.unwrap();
+    let mut events = backend.watch("schemas/").await.unwrap();
+
+    backend.put("schemas/existing", b"v2").await.unwrap();
+    let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.event_type, EventType::Updated);
+    assert_eq!(event.key, "schemas/existing");
+    assert_eq!(event.value.as_deref(), Some(&b"v2"[..]));
+}
@@ -0,0 +1,233 @@
+//! Integration tests for the HTTP pull server
+
+#![cfg(all(feature = "server", feature = "memory-registry"))]
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use farp::manifest::new_manifest;
+use farp::registry::memory::MemoryRegistry;
+use farp::registry::SchemaRegistry;
+use farp::types::{LocationType, SchemaDescriptor, SchemaLocation, SchemaManifest, SchemaType};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+async fn get(app: &Router, uri: &str) -> (StatusCode, serde_json::Value) {
+    let response = app
+        .clone()
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+fn openapi(title: &str, path: &str) -> serde_json::Value {
+    serde_json::json!({
+        "openapi": "3.1.0",
+        "info": {"title": title, "version": "1.0.0"},
+        "paths": {path: {"get": {"operationId": format!("get_{title}")}}}
+    })
+}
+
+fn manifest(service: &str, instance: &str, location: SchemaLocation) -> SchemaManifest {
+    let mut manifest = new_manifest(service, "v1.0.0", instance);
+    manifest.endpoints.health = "/health".to_string();
+    manifest.routing.strategy = farp::types::MountStrategy::Root;
+    manifest.add_schema(SchemaDescriptor {
+        schema_type: SchemaType::OpenAPI,
+        schema_id: None,
+        spec_version: "3.1.0".to_string(),
+        inline_schema: (location.location_type == LocationType::Inline)
+            .then(|| openapi(service, &format!("/{service}"))),
+        location,
+        content_type: "application/json".to_string(),
+        hash: "a".repeat(64),
+        size: 0,
+        compatibility: None,
+        metadata: None,
+    });
+    manifest.update_checksum().unwrap();
+    manifest
+}
+
+async fn app() -> Router {
+    setup().await.1
+}
+
+async fn setup() -> (Arc<MemoryRegistry>, Router) {
+    let registry = Arc::new(MemoryRegistry::new());
+    registry
+        .publish_schema("/schemas/orders/v1/openapi", &openapi("orders", "/orders"))
+        .await
+        .unwrap();
+    let inline = SchemaLocation {
+        location_type: LocationType::Inline,
+        url: None,
+        registry_path: None,
+        headers: None,
+    };
+    let stored = SchemaLocation {
+        location_type: LocationType::Registry,
+        url: None,
+        registry_path: Some("/schemas/orders/v1/openapi".to_string()),
+        headers: None,
+    };
+    for manifest in [
+        manifest("users", "users-1", inline.clone()),
+        manifest("users", "users-2", inline),
+        manifest("orders", "orders-1", stored),
+    ] {
+        registry.register_manifest(&manifest).await.unwrap();
+    }
+    (registry.clone(), farp::server::router(registry))
+}
+
+#[tokio::test]
+async fn test_service_instances_and_manifests() {
+    let app = app().await;
+
+    let (status, body) = get(&app, "/farp/services/users/instances").await;
+    assert_eq!(status, StatusCode::OK);
+    let mut instances: Vec<&str> = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["instance_id"].as_str().unwrap())
+        .collect();
+    instances.sort();
+    assert_eq!(instances, vec!["users-1", "users-2"]);
+
+    let (status, body) = get(&app, "/farp/services/billing/instances").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, serde_json::json!([]));
+
+    let (status, body) = get(&app, "/farp/manifests/orders-1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["service_name"], "orders");
+
+    let (status, body) = get(&app, "/farp/manifests/missing").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(body["error"].is_string());
+}
+
+#[tokio::test]
+async fn test_schemas() {
+    let app = app().await;
+
+    let (status, body) = get(&app, "/farp/schemas/schemas/orders/v1/openapi").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["info"]["title"], "orders");
+
+    let (status, _) = get(&app, "/farp/schemas/schemas/orders/v2/openapi").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_merged_openapi() {
+    let app = app().await;
+
+    let (status, body) = get(&app, "/farp/merged/openapi?service=users&service=orders").await;
+    assert_eq!(status, StatusCode::OK);
+    let paths = body["paths"].as_object().unwrap();
+    assert!(paths.contains_key("/users"));
+    assert!(paths.contains_key("/orders"));
+
+    let (status, body) = get(&app, "/farp/merged/openapi?service=users,orders").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["paths"].as_object().unwrap().len(), 2);
+
+    let (status, _) = get(&app, "/farp/merged/openapi").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_long_poll_delivers_update() {
+    let (registry, app) = setup().await;
+
+    let poll = tokio::spawn({
+        let app = app.clone();
+        async move { get(&app, "/farp/events?service=users").await }
+    });
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    let mut updated = registry.get_manifest("users-1").await.unwrap();
+    updated.service_version = "v1.1.0".to_string();
+    registry.update_manifest(&updated).await.unwrap();
+
+    let (status, body) = tokio::time::timeout(std::time::Duration::from_secs(5), poll)
+        .await
+        .expect("long poll should return before its timeout")
+        .unwrap();
+    assert_eq!(status, StatusCode::OK);
+    let events = body["events"].as_array().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["event_type"], "updated");
+    assert_eq!(events[0]["manifest"]["service_version"], "v1.1.0");
+    let cursor = body["cursor"].as_u64().unwrap();
+    assert_eq!(events[0]["sequence"].as_u64(), Some(cursor));
+
+    // Events already buffered after an older cursor are returned immediately
+    let (_, body) = get(
+        &app,
+        &format!("/farp/events?service=users&since={}", cursor - 1),
+    )
+    .await;
+    assert_eq!(body["events"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_long_poll_times_out() {
+    let (registry, app) = setup().await;
+    let mut manifest = registry.get_manifest("orders-1").await.unwrap();
+    manifest.webhook = Some(farp::types::WebhookConfig {
+        service_webhook: None,
+        gateway_webhook: None,
+        secret: None,
+        subscribe_events: Vec::new(),
+        publish_events: Vec::new(),
+        retry: None,
+        http_routes: Some(farp::types::HTTPCommunicationRoutes {
+            service_routes: Vec::new(),
+            gateway_routes: Vec::new(),
+            polling: Some(farp::types::PollingConfig {
+                interval: "5s".to_string(),
+                timeout: None,
+                long_polling: true,
+                long_polling_timeout: Some("100ms".to_string()),
+            }),
+        }),
+    });
+    registry.update_manifest(&manifest).await.unwrap();
+
+    let (status, body) = get(&app, "/farp/events?service=orders").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["events"], serde_json::json!([]));
+    let cursor = body["cursor"].as_u64().unwrap();
+
+    // Resuming from the returned cursor waits again
+    let (status, body) = get(&app, &format!("/farp/events?service=orders&since={cursor}")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, serde_json::json!({"events": [], "cursor": cursor}));
+
+    // Cursors the buffer never issued (e.g. from before a restart) are rejected
+    for stale in [7, cursor + 1] {
+        let (status, body) = get(&app, &format!("/farp/events?service=orders&since={stale}")).await;
+        assert_eq!(status, StatusCode::GONE);
+        assert_eq!(
+            body["error"],
+            format!("event cursor {stale} is expired or unknown")
+        );
+    }
+
+    // Unregistered services aren't watched
+    let (status, _) = get(&app, "/farp/events?service=unknown").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = get(&app, "/farp/events").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = get(&app, "/farp/events?service=orders&since=latest").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}